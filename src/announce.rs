@@ -0,0 +1,271 @@
+use crate::schedule::TaskKind;
+use crate::server::{load_server_config, ServerConfig};
+use crate::version::compare_versions;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Map, Value};
+
+// The chat colors every version knows, with the RGB the game draws them in
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("black", 0x000000),
+    ("dark_blue", 0x0000AA),
+    ("dark_green", 0x00AA00),
+    ("dark_aqua", 0x00AAAA),
+    ("dark_red", 0xAA0000),
+    ("dark_purple", 0xAA00AA),
+    ("gold", 0xFFAA00),
+    ("gray", 0xAAAAAA),
+    ("dark_gray", 0x555555),
+    ("blue", 0x5555FF),
+    ("green", 0x55FF55),
+    ("aqua", 0x55FFFF),
+    ("red", 0xFF5555),
+    ("light_purple", 0xFF55FF),
+    ("yellow", 0xFFFF55),
+    ("white", 0xFFFFFF)
+];
+const DECORATIONS: &[(&[&str], &str)] = &[
+    (&["bold", "b"], "bold"),
+    (&["italic", "i", "em"], "italic"),
+    (&["underlined", "u"], "underlined"),
+    (&["strikethrough", "st"], "strikethrough"),
+    (&["obfuscated", "obf"], "obfuscated")
+];
+const CLICK_ACTIONS: &[&str] = &["open_url", "run_command", "suggest_command", "copy_to_clipboard"];
+
+pub async fn handle_announce(server_name: &str, message: &str, every: Option<String>, cron: Option<String>) -> Result<()> {
+    let config = load_server_config(server_name)?;
+
+    let cron = match (every, cron) {
+        (Some(every), _) => interval_cron(&every)?,
+        (None, Some(cron)) => cron,
+        (None, None) => {
+            broadcast(&config, message).await?;
+            println!("📢 Announced on '{}': {}", server_name, plain_text(message));
+            return Ok(());
+        }
+    };
+
+    crate::schedule::schedule_task(server_name, &cron, TaskKind::Announce { message: message.to_string() })?;
+    println!("💡 Announcements are sent while 'anvil daemon' is running, remove it with: anvil schedule -s {} remove <id>", server_name);
+    Ok(())
+}
+
+pub async fn broadcast(config: &ServerConfig, message: &str) -> Result<()> {
+    crate::rcon::send_command(config, &announcement_command(config, message)).await?;
+    Ok(())
+}
+
+// Intervals the cron scheduler can express: minutes dividing an hour, hours dividing a day, or a day
+fn interval_cron(every: &str) -> Result<String> {
+    let every = every.trim().to_lowercase();
+    let split = every.find(|c: char| !c.is_ascii_digit()).unwrap_or(every.len());
+    let (amount, unit) = every.split_at(split);
+    let amount: u32 = amount.parse().map_err(|_| anyhow!("Invalid interval '{}', expected e.g. 30m, 2h or 1d", every))?;
+
+    let cron = match unit {
+        "m" | "min" if amount > 0 && 60 % amount == 0 => format!("*/{} * * * *", amount),
+        "h" if amount > 0 && 24 % amount == 0 => format!("0 */{} * * *", amount),
+        "d" if amount == 1 => "0 0 * * *".to_string(),
+        _ => {
+            return Err(anyhow!(
+                "Cannot repeat every {}: use minutes that divide an hour, hours that divide a day or 1d, or pass --cron",
+                every
+            ));
+        }
+    };
+    Ok(cron)
+}
+
+fn announcement_command(config: &ServerConfig, message: &str) -> String {
+    // tellraw arrived in 1.7.2, older servers can only broadcast plain text
+    if compare_versions(&config.version, "1.7.2").is_lt() {
+        return format!("say {}", plain_text(message));
+    }
+    format!("tellraw @a {}", to_component(message, &config.version))
+}
+
+#[derive(Clone, Default)]
+struct Style {
+    color: Option<String>,
+    decorations: Vec<&'static str>,
+    click: Option<(String, String)>
+}
+
+enum Tag {
+    Open(String, Style),
+    Close(String),
+    Reset,
+    Newline
+}
+
+// Converts MiniMessage tags such as <gold>, <#ff8800>, <bold>, <click:open_url:...> and </gold> into a
+// tellraw text component; anything that is not a known tag stays in the text as typed
+fn to_component(message: &str, version: &str) -> Value {
+    let mut parts = vec![Value::String(String::new())];
+    let mut stack: Vec<(String, Style)> = Vec::new();
+    let mut text = String::new();
+    let mut rest = message;
+
+    while let Some(start) = rest.find(['<', '\\']) {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        // "\<" types a literal bracket
+        if let Some(escaped) = rest.strip_prefix("\\<") {
+            text.push('<');
+            rest = escaped;
+            continue;
+        }
+        if let Some(other) = rest.strip_prefix('\\') {
+            text.push('\\');
+            rest = other;
+            continue;
+        }
+
+        let current = stack.last().map(|(_, style)| style.clone()).unwrap_or_default();
+        let tag = rest[1..].find('>').and_then(|end| Some((parse_tag(&rest[1..end + 1], &current, version)?, end + 2)));
+        let Some((tag, length)) = tag else {
+            text.push('<');
+            rest = &rest[1..];
+            continue;
+        };
+        rest = &rest[length..];
+
+        push_text(&mut parts, &mut text, &current, version);
+        match tag {
+            Tag::Open(name, style) => stack.push((name, style)),
+            Tag::Close(name) => {
+                if let Some(index) = stack.iter().rposition(|(open, _)| *open == name) {
+                    stack.truncate(index);
+                }
+            }
+            Tag::Reset => stack.clear(),
+            Tag::Newline => text.push('\n')
+        }
+    }
+    text.push_str(rest);
+    let current = stack.last().map(|(_, style)| style.clone()).unwrap_or_default();
+    push_text(&mut parts, &mut text, &current, version);
+
+    Value::Array(parts)
+}
+
+fn parse_tag(tag: &str, current: &Style, version: &str) -> Option<Tag> {
+    if let Some(name) = tag.strip_prefix('/') {
+        let name = name.split(':').next().unwrap_or_default().to_lowercase();
+        return canonical_name(&name).map(Tag::Close);
+    }
+
+    let mut style = current.clone();
+    let lower = tag.to_lowercase();
+    match lower.as_str() {
+        "reset" | "r" => return Some(Tag::Reset),
+        "newline" | "br" => return Some(Tag::Newline),
+        _ => {}
+    }
+
+    // <color:red> and <c:#ff0000> are the long forms of <red> and <#ff0000>
+    let color = lower.strip_prefix("color:").or_else(|| lower.strip_prefix("c:")).unwrap_or(&lower);
+    if let Some(color) = parse_color(color, version) {
+        style.color = Some(color);
+        return Some(Tag::Open("color".to_string(), style));
+    }
+
+    if let Some((_, decoration)) = DECORATIONS.iter().find(|(names, _)| names.contains(&lower.as_str())) {
+        style.decorations.push(decoration);
+        return Some(Tag::Open(decoration.to_string(), style));
+    }
+
+    // The click value keeps its case, a URL or command may need it
+    let (action, value) = tag.strip_prefix("click:")?.split_once(':')?;
+    let action = action.to_lowercase();
+    if !CLICK_ACTIONS.contains(&action.as_str()) {
+        return None;
+    }
+    style.click = Some((action, value.to_string()));
+    Some(Tag::Open("click".to_string(), style))
+}
+
+// Closing tags name the same thing the opening tag styled, </red> closes the color like </color> does
+fn canonical_name(name: &str) -> Option<String> {
+    if parse_color(name, "").is_some() || name == "color" || name == "c" {
+        return Some("color".to_string());
+    }
+    if name == "click" {
+        return Some(name.to_string());
+    }
+    DECORATIONS
+        .iter()
+        .find(|(names, _)| names.contains(&name))
+        .map(|(_, decoration)| decoration.to_string())
+}
+
+fn parse_color(color: &str, version: &str) -> Option<String> {
+    let color = match color {
+        "grey" => "gray",
+        "dark_grey" => "dark_gray",
+        other => other
+    };
+    if NAMED_COLORS.iter().any(|(name, _)| *name == color) {
+        return Some(color.to_string());
+    }
+
+    let hex = color.strip_prefix('#').filter(|hex| hex.len() == 6)?;
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    // Before 1.16 chat only had the named colors, so hex becomes the closest of them
+    if !version.is_empty() && compare_versions(version, "1.16").is_lt() {
+        return Some(nearest_named_color(rgb).to_string());
+    }
+    Some(format!("#{}", hex))
+}
+
+fn nearest_named_color(rgb: u32) -> &'static str {
+    let channels = |rgb: u32| [(rgb >> 16) as i64 & 0xFF, (rgb >> 8) as i64 & 0xFF, rgb as i64 & 0xFF];
+    let target = channels(rgb);
+    NAMED_COLORS
+        .iter()
+        .min_by_key(|(_, named)| {
+            channels(*named).iter().zip(target).map(|(a, b)| (a - b) * (a - b)).sum::<i64>()
+        })
+        .map_or("white", |(name, _)| *name)
+}
+
+fn push_text(parts: &mut Vec<Value>, text: &mut String, style: &Style, version: &str) {
+    if text.is_empty() {
+        return;
+    }
+
+    let mut component = Map::new();
+    component.insert("text".to_string(), Value::String(std::mem::take(text)));
+    if let Some(color) = &style.color {
+        component.insert("color".to_string(), Value::String(color.clone()));
+    }
+    for decoration in &style.decorations {
+        component.insert(decoration.to_string(), Value::Bool(true));
+    }
+    if let Some((action, value)) = &style.click {
+        // 1.21.5 renamed clickEvent and gave each action its own field instead of "value"
+        if compare_versions(version, "1.21.5").is_ge() {
+            let field = match action.as_str() {
+                "open_url" => "url",
+                "run_command" | "suggest_command" => "command",
+                _ => "value"
+            };
+            component.insert("click_event".to_string(), json!({ "action": action, field: value }));
+        } else {
+            component.insert("clickEvent".to_string(), json!({ "action": action, "value": value }));
+        }
+    }
+    parts.push(Value::Object(component));
+}
+
+// The message with its tags taken out, for servers without tellraw and for the terminal
+fn plain_text(message: &str) -> String {
+    let component = to_component(message, "");
+    component
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|part| part.get("text").and_then(Value::as_str))
+        .collect()
+}
@@ -0,0 +1,54 @@
+use crate::server::get_anvil_dir;
+use super::http::SendWithBackoff;
+use anyhow::Result;
+use sha1::{Digest, Sha1};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+fn cache_path(namespace: &str, url: &str) -> PathBuf {
+    let mut hasher = Sha1::new();
+    hasher.update(url.as_bytes());
+
+    get_anvil_dir()
+        .join("cache")
+        .join(namespace)
+        .join(format!("{}.json", hex::encode(hasher.finalize())))
+}
+
+fn read(path: &Path, ttl: Duration) -> Option<String> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let age = SystemTime::now().duration_since(modified).ok()?;
+    if age > ttl {
+        return None;
+    }
+    fs::read_to_string(path).ok()
+}
+
+fn write(path: &Path, body: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, body)?;
+    Ok(())
+}
+
+pub async fn get_text(namespace: &str, url: &str, ttl: Duration) -> Result<String> {
+    let path = cache_path(namespace, url);
+    if let Some(body) = read(&path, ttl) {
+        return Ok(body);
+    }
+
+    let body = reqwest::Client::new()
+        .get(url)
+        .header("User-Agent", "anvil-cli/0.1.0")
+        .send_with_backoff()
+        .await?
+        .text()
+        .await?;
+
+    // A cache that can't be written only costs an extra request next time
+    let _ = write(&path, &body);
+
+    Ok(body)
+}
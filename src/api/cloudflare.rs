@@ -0,0 +1,82 @@
+use super::http::SendWithBackoff;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+const API_BASE: &str = "https://api.cloudflare.com/client/v4";
+
+#[derive(Deserialize)]
+struct CloudflareResponse<T> {
+    result: T
+}
+
+#[derive(Deserialize)]
+struct CloudflareZone {
+    id: String
+}
+
+#[derive(Deserialize)]
+struct CloudflareRecord {
+    id: String
+}
+
+pub async fn find_zone_id(token: &str, hostname: &str) -> Result<String> {
+    let client = reqwest::Client::new();
+    let labels: Vec<&str> = hostname.split('.').collect();
+
+    // The zone is the longest suffix of the hostname that Cloudflare knows about
+    for start in 0..labels.len().saturating_sub(1) {
+        let candidate = labels[start..].join(".");
+        let url = format!("{}/zones?name={}", API_BASE, urlencoding::encode(&candidate));
+
+        let response: CloudflareResponse<Vec<CloudflareZone>> = client
+            .get(&url)
+            .bearer_auth(token)
+            .send_with_backoff()
+            .await?
+            .json()
+            .await?;
+
+        if let Some(zone) = response.result.into_iter().next() {
+            return Ok(zone.id);
+        }
+    }
+
+    Err(anyhow!("No Cloudflare zone found for {} (does the token have Zone:Read access?)", hostname))
+}
+
+async fn find_record(token: &str, zone_id: &str, record_type: &str, name: &str) -> Result<Option<CloudflareRecord>> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "{}/zones/{}/dns_records?type={}&name={}",
+        API_BASE,
+        zone_id,
+        record_type,
+        urlencoding::encode(name)
+    );
+
+    let response: CloudflareResponse<Vec<CloudflareRecord>> = client
+        .get(&url)
+        .bearer_auth(token)
+        .send_with_backoff()
+        .await?
+        .json()
+        .await?;
+    Ok(response.result.into_iter().next())
+}
+
+pub async fn upsert_record(token: &str, zone_id: &str, record_type: &str, name: &str, body: Value) -> Result<()> {
+    let client = reqwest::Client::new();
+
+    let request = match find_record(token, zone_id, record_type, name).await? {
+        Some(record) => client.put(format!("{}/zones/{}/dns_records/{}", API_BASE, zone_id, record.id)),
+        None => client.post(format!("{}/zones/{}/dns_records", API_BASE, zone_id))
+    };
+
+    request
+        .bearer_auth(token)
+        .json(&body)
+        .send_with_backoff()
+        .await?;
+    Ok(())
+}
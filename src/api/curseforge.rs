@@ -0,0 +1,68 @@
+use super::http::SendWithBackoff;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+const MINECRAFT_GAME_ID: u32 = 432;
+const BUKKIT_PLUGINS_CLASS_ID: u32 = 5;
+
+#[derive(Deserialize)]
+struct CurseForgeResponse<T> {
+    data: T
+}
+
+#[derive(Deserialize)]
+pub struct CurseForgeMod {
+    pub id: u64,
+    pub name: String
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeFile {
+    pub id: u64,
+    pub display_name: String,
+    pub file_name: String,
+    pub download_url: Option<String>
+}
+
+fn api_key() -> Result<String> {
+    std::env::var("CURSEFORGE_API_KEY")
+        .map_err(|_| anyhow!("CurseForge needs an API key in the CURSEFORGE_API_KEY environment variable"))
+}
+
+pub async fn search_mod(query: &str) -> Result<Option<CurseForgeMod>> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "https://api.curseforge.com/v1/mods/search?gameId={}&classId={}&searchFilter={}&sortField=2&sortOrder=desc&pageSize=1",
+        MINECRAFT_GAME_ID,
+        BUKKIT_PLUGINS_CLASS_ID,
+        urlencoding::encode(query)
+    );
+
+    let response: CurseForgeResponse<Vec<CurseForgeMod>> = client
+        .get(&url)
+        .header("x-api-key", api_key()?)
+        .send_with_backoff()
+        .await?
+        .json()
+        .await?;
+    Ok(response.data.into_iter().next())
+}
+
+pub async fn get_mod_files(mod_id: &str, game_version: &str) -> Result<Vec<CurseForgeFile>> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "https://api.curseforge.com/v1/mods/{}/files?gameVersion={}",
+        mod_id,
+        urlencoding::encode(game_version)
+    );
+
+    let response: CurseForgeResponse<Vec<CurseForgeFile>> = client
+        .get(&url)
+        .header("x-api-key", api_key()?)
+        .send_with_backoff()
+        .await?
+        .json()
+        .await?;
+    Ok(response.data)
+}
@@ -0,0 +1,65 @@
+use super::http::SendWithBackoff;
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::json;
+
+const API_URL: &str = "https://discord.com/api/v10";
+
+#[derive(Deserialize)]
+pub struct Message {
+    pub id: String,
+    pub content: String,
+    pub author: Author,
+    // Set on messages posted through a webhook, including the ones relayed from the game
+    #[serde(default)]
+    pub webhook_id: Option<String>
+}
+
+#[derive(Deserialize)]
+pub struct Author {
+    pub username: String,
+    #[serde(default)]
+    pub global_name: Option<String>,
+    #[serde(default)]
+    pub bot: bool
+}
+
+impl Author {
+    pub fn display_name(&self) -> &str {
+        self.global_name.as_deref().unwrap_or(&self.username)
+    }
+}
+
+pub async fn post_webhook(webhook: &str, username: &str, avatar_url: Option<&str>, content: &str) -> Result<()> {
+    reqwest::Client::new()
+        .post(webhook)
+        .json(&json!({
+            "username": username,
+            "avatar_url": avatar_url,
+            "content": content,
+            // Players typing @everyone must not ping the whole Discord server
+            "allowed_mentions": { "parse": [] }
+        }))
+        .send_with_backoff()
+        .await?;
+    Ok(())
+}
+
+// Messages in the channel newer than `after`, oldest first; without `after` only the latest one
+pub async fn channel_messages(bot_token: &str, channel_id: &str, after: Option<&str>) -> Result<Vec<Message>> {
+    let url = match after {
+        Some(after) => format!("{}/channels/{}/messages?after={}&limit=50", API_URL, channel_id, after),
+        None => format!("{}/channels/{}/messages?limit=1", API_URL, channel_id)
+    };
+
+    let mut messages: Vec<Message> = reqwest::Client::new()
+        .get(&url)
+        .header("Authorization", format!("Bot {}", bot_token))
+        .send_with_backoff()
+        .await?
+        .json()
+        .await?;
+    // Discord returns the newest message first
+    messages.reverse();
+    Ok(messages)
+}
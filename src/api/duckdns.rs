@@ -0,0 +1,21 @@
+use super::http::SendWithBackoff;
+use anyhow::{anyhow, Result};
+
+pub const DOMAIN_SUFFIX: &str = ".duckdns.org";
+
+pub async fn update(subdomain: &str, token: &str, ip: &str) -> Result<()> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "https://www.duckdns.org/update?domains={}&token={}&ip={}",
+        urlencoding::encode(subdomain),
+        urlencoding::encode(token),
+        ip
+    );
+
+    let response = client.get(&url).send_with_backoff().await?.text().await?;
+    if response.trim() != "OK" {
+        return Err(anyhow!("DuckDNS rejected the update for {} (check the token)", subdomain));
+    }
+
+    Ok(())
+}
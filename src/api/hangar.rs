@@ -0,0 +1,84 @@
+use super::http::SendWithBackoff;
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct HangarProjects {
+    result: Vec<HangarProject>
+}
+
+#[derive(Deserialize)]
+pub struct HangarProject {
+    pub name: String,
+    pub namespace: HangarNamespace
+}
+
+#[derive(Deserialize)]
+pub struct HangarNamespace {
+    pub slug: String
+}
+
+#[derive(Deserialize)]
+struct HangarVersions {
+    result: Vec<HangarVersion>
+}
+
+#[derive(Deserialize)]
+pub struct HangarVersion {
+    #[serde(default)]
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub downloads: HashMap<String, HangarDownload>
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HangarDownload {
+    pub file_info: Option<HangarFileInfo>,
+    pub download_url: Option<String>,
+    pub external_url: Option<String>
+}
+
+#[derive(Deserialize)]
+pub struct HangarFileInfo {
+    pub name: String
+}
+
+pub async fn search_project(query: &str) -> Result<Option<HangarProject>> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "https://hangar.papermc.io/api/v1/projects?limit=1&q={}",
+        urlencoding::encode(query)
+    );
+
+    let response: HangarProjects = client
+        .get(&url)
+        .header("User-Agent", "anvil-cli/0.1.0")
+        .send_with_backoff()
+        .await?
+        .json()
+        .await?;
+
+    Ok(response.result.into_iter().next())
+}
+
+pub async fn get_compatible_versions(slug: &str, game_version: &str) -> Result<Vec<HangarVersion>> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "https://hangar.papermc.io/api/v1/projects/{}/versions?limit=5&platform=PAPER&platformVersion={}",
+        urlencoding::encode(slug),
+        urlencoding::encode(game_version)
+    );
+
+    let response: HangarVersions = client
+        .get(&url)
+        .header("User-Agent", "anvil-cli/0.1.0")
+        .send_with_backoff()
+        .await?
+        .json()
+        .await?;
+
+    Ok(response.result)
+}
@@ -0,0 +1,92 @@
+use anyhow::{anyhow, Result};
+use futures_util::TryStreamExt;
+use indicatif::ProgressBar;
+use reqwest::Response;
+use sha1::Digest;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+pub async fn download_with_hash<D: Digest>(
+    response: Response,
+    output_path: &Path,
+    expected: Option<&str>,
+    pb: &ProgressBar,
+) -> Result<String> {
+    if let Some(size) = response.content_length() {
+        let name = output_path.file_name().unwrap_or_default().to_string_lossy();
+        crate::disk::ensure_free_space(output_path, size, &format!("downloading {}", name))?;
+    }
+
+    let name = output_path.file_name().unwrap_or_default().to_string_lossy();
+    let events = crate::progress::DownloadEvents::new(&name, response.content_length(), pb);
+
+    // Write next to the target so the final rename stays on one filesystem and is atomic
+    let partial_path = partial_path(output_path);
+    let result = tokio::select! {
+        result = write_stream::<D>(response, &partial_path, pb, events) => result,
+        _ = tokio::signal::ctrl_c() => Err(anyhow!("Interrupted"))
+    };
+
+    let hash = match result {
+        Ok(hash) if expected.is_none_or(|expected| expected.eq_ignore_ascii_case(&hash)) => hash,
+        Ok(_) => {
+            let _ = tokio::fs::remove_file(&partial_path).await;
+            return Err(anyhow!("Downloaded file hash doesn't match expected hash"));
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&partial_path).await;
+            return Err(e);
+        }
+    };
+
+    tokio::fs::rename(&partial_path, output_path).await?;
+    Ok(hash)
+}
+
+async fn write_stream<D: Digest>(
+    response: Response,
+    path: &Path,
+    pb: &ProgressBar,
+    mut events: crate::progress::DownloadEvents,
+) -> Result<String> {
+    let mut file = tokio::fs::File::create(path).await?;
+    let mut hasher = D::new();
+    let mut downloaded = 0u64;
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.try_next().await? {
+        hasher.update(&chunk);
+        file.write_all(&chunk).await?;
+        downloaded += chunk.len() as u64;
+        pb.set_position(downloaded);
+        events.update(downloaded);
+    }
+    file.sync_all().await?;
+    events.finish(downloaded);
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+pub async fn file_hash<D: Digest>(file_path: &Path) -> Result<String> {
+    let mut file = tokio::fs::File::open(file_path).await?;
+    let mut hasher = D::new();
+    let mut buffer = vec![0; READ_BUFFER_SIZE];
+
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
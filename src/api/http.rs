@@ -0,0 +1,65 @@
+use anyhow::{anyhow, Result};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::future::Future;
+use std::time::Duration;
+
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+const DEFAULT_RETRY_SECS: u64 = 10;
+const MAX_RETRY_SECS: u64 = 120;
+
+pub trait SendWithBackoff {
+    fn send_with_backoff(self) -> impl Future<Output = Result<Response>>;
+}
+
+impl SendWithBackoff for RequestBuilder {
+    fn send_with_backoff(self) -> impl Future<Output = Result<Response>> {
+        send(self)
+    }
+}
+
+async fn send(request: RequestBuilder) -> Result<Response> {
+    let mut attempt = 0;
+
+    loop {
+        let response = request
+            .try_clone()
+            .ok_or_else(|| anyhow!("Streaming requests cannot be retried"))?
+            .send()
+            .await?;
+
+        if response.status() != StatusCode::TOO_MANY_REQUESTS {
+            return Ok(response.error_for_status()?);
+        }
+
+        let host = response.url().host_str().unwrap_or("the API").to_string();
+        if attempt >= MAX_RATE_LIMIT_RETRIES {
+            return Err(anyhow!(
+                "{} is still rate limiting requests after {} retries, try again later",
+                host, MAX_RATE_LIMIT_RETRIES
+            ));
+        }
+
+        let wait = retry_after(&response).min(MAX_RETRY_SECS);
+        println!("⏳ Rate limited by {}, waiting {}s before retrying...", host, wait);
+        tokio::time::sleep(Duration::from_secs(wait)).await;
+        attempt += 1;
+    }
+}
+
+fn retry_after(response: &Response) -> u64 {
+    // Modrinth reports the window reset in X-Ratelimit-Reset, everyone else uses Retry-After
+    ["retry-after", "x-ratelimit-reset"]
+        .iter()
+        .find_map(|header| {
+            response
+                .headers()
+                .get(*header)?
+                .to_str()
+                .ok()?
+                .trim()
+                .parse::<u64>()
+                .ok()
+        })
+        .map(|secs| secs.max(1))
+        .unwrap_or(DEFAULT_RETRY_SECS)
+}
@@ -0,0 +1,30 @@
+use super::http::SendWithBackoff;
+use anyhow::Result;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct ServerStatus {
+    pub online: bool,
+    #[serde(default)]
+    pub debug: Option<StatusDebug>
+}
+
+#[derive(Deserialize)]
+pub struct StatusDebug {
+    #[serde(default)]
+    pub error: Option<serde_json::Value>
+}
+
+pub async fn get_status(address: &str) -> Result<ServerStatus> {
+    let client = reqwest::Client::new();
+    let url = format!("https://api.mcsrvstat.us/3/{}", address);
+
+    let status = client
+        .get(&url)
+        .header("User-Agent", "anvil-cli/0.1.0")
+        .send_with_backoff()
+        .await?
+        .json()
+        .await?;
+    Ok(status)
+}
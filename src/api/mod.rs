@@ -1,3 +1,14 @@
 pub mod paper;
 pub mod modrinth;
-pub mod vanilla;
\ No newline at end of file
+pub mod vanilla;
+pub mod hangar;
+pub mod cache;
+pub mod http;
+pub mod hash;
+pub mod spiget;
+pub mod curseforge;
+pub mod cloudflare;
+pub mod duckdns;
+pub mod mcsrvstat;
+pub mod mojang;
+pub mod discord;
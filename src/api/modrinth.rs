@@ -1,12 +1,24 @@
+use super::hash::download_with_hash;
+use super::http::SendWithBackoff;
 use anyhow::Result;
-use futures_util::TryStreamExt;
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::ProgressBar;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+const SEARCH_TTL: Duration = Duration::from_secs(60 * 60);
+const VERSIONS_TTL: Duration = Duration::from_secs(10 * 60);
+const VERSION_TTL: Duration = Duration::from_secs(24 * 60 * 60);
 
 #[derive(Deserialize)]
 pub struct ModrinthVersion {
+    pub id: String,
+    #[serde(default)]
+    pub project_id: String,
     pub version_number: String,
-    pub files: Vec<ModrinthFile>
+    pub files: Vec<ModrinthFile>,
+    #[serde(default)]
+    pub game_versions: Vec<String>
 }
 
 #[derive(Deserialize)]
@@ -14,11 +26,24 @@ pub struct ModrinthFile {
     pub url: String,
     pub filename: String,
     pub primary: bool,
+    #[serde(default)]
+    pub hashes: HashMap<String, String>,
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Deserialize)]
+pub struct ModrinthProject {
+    pub id: String,
+    #[serde(default)]
+    pub client_side: String,
 }
 
 #[derive(Deserialize)]
 pub struct ModrinthSearchResponse {
     pub hits: Vec<ModrinthSearchHit>,
+    #[serde(default)]
+    pub total_hits: u32,
 }
 
 #[derive(Deserialize)]
@@ -26,77 +51,129 @@ pub struct ModrinthSearchHit {
     pub project_id: String,
     pub title: String,
     pub description: String,
+    #[serde(default)]
+    pub downloads: u64,
 }
 
-pub async fn search_project(query: &str) -> Result<ModrinthSearchHit> {
-    let client = reqwest::Client::new();
+pub async fn search_project(query: &str) -> Result<Option<ModrinthSearchHit>> {
     let url = format!(
         "https://api.modrinth.com/v2/search?query={}&facets=[[\"project_type:mod\"]]",
         urlencoding::encode(query)
     );
 
-    let response = client
-        .get(&url)
-        .header("User-Agent", "anvil-cli/0.1.0")
-        .send()
-        .await?;
-
-    let response_text = response.text().await?;
+    let response_text = super::cache::get_text("modrinth", &url, SEARCH_TTL).await?;
     let search_response: ModrinthSearchResponse = serde_json::from_str(&response_text)?;
 
-    if search_response.hits.is_empty() {
-        return Err(anyhow::anyhow!("No plugins found for query: {}", query));
+    Ok(search_response.hits.into_iter().next())
+}
+
+pub async fn browse_projects(
+    query: Option<&str>,
+    category: Option<&str>,
+    game_version: &str,
+    offset: u32,
+    limit: u32,
+) -> Result<ModrinthSearchResponse> {
+    let mut facets = vec![
+        "[\"project_type:mod\"]".to_string(),
+        "[\"categories:paper\",\"categories:spigot\",\"categories:bukkit\"]".to_string(),
+        format!("[\"versions:{}\"]", game_version),
+    ];
+    if let Some(category) = category {
+        facets.push(format!("[\"categories:{}\"]", category));
     }
 
-    Ok(search_response.hits.into_iter().next().unwrap())
+    let url = format!(
+        "https://api.modrinth.com/v2/search?query={}&facets={}&index=downloads&offset={}&limit={}",
+        urlencoding::encode(query.unwrap_or_default()),
+        urlencoding::encode(&format!("[{}]", facets.join(","))),
+        offset,
+        limit
+    );
+
+    let body = super::cache::get_text("modrinth", &url, SEARCH_TTL).await?;
+    Ok(serde_json::from_str(&body)?)
 }
 
 pub async fn get_project_versions(
     project_id: &str,
     game_version: &str,
 ) -> Result<Vec<ModrinthVersion>> {
-    let client = reqwest::Client::new();
     let url = format!(
         "https://api.modrinth.com/v2/project/{}/version?game_versions=[\"{}\"]",
         project_id, game_version
     );
 
-    let versions: Vec<ModrinthVersion> = client.get(&url).send().await?.json().await?;
+    let body = super::cache::get_text("modrinth", &url, VERSIONS_TTL).await?;
+    let versions: Vec<ModrinthVersion> = serde_json::from_str(&body)?;
+    Ok(versions)
+}
+
+pub async fn get_datapack_versions(project_id: &str, game_version: &str) -> Result<Vec<ModrinthVersion>> {
+    let url = format!(
+        "https://api.modrinth.com/v2/project/{}/version?game_versions=[\"{}\"]&loaders=[\"datapack\"]",
+        project_id, game_version
+    );
+
+    let body = super::cache::get_text("modrinth", &url, VERSIONS_TTL).await?;
+    Ok(serde_json::from_str(&body)?)
+}
+
+pub async fn get_version(version_id: &str) -> Result<ModrinthVersion> {
+    let url = format!("https://api.modrinth.com/v2/version/{}", version_id);
+
+    let body = super::cache::get_text("modrinth", &url, VERSION_TTL).await?;
+    let version: ModrinthVersion = serde_json::from_str(&body)?;
+    Ok(version)
+}
+
+pub async fn get_projects(project_ids: &[String]) -> Result<Vec<ModrinthProject>> {
+    let url = format!(
+        "https://api.modrinth.com/v2/projects?ids={}",
+        urlencoding::encode(&serde_json::to_string(project_ids)?)
+    );
+
+    let body = super::cache::get_text("modrinth", &url, VERSION_TTL).await?;
+    Ok(serde_json::from_str(&body)?)
+}
+
+pub async fn get_versions_by_hash(hashes: &[String]) -> Result<HashMap<String, ModrinthVersion>> {
+    let client = reqwest::Client::new();
+    let body = serde_json::json!({
+        "hashes": hashes,
+        "algorithm": "sha1"
+    });
+
+    let versions: HashMap<String, ModrinthVersion> = client
+        .post("https://api.modrinth.com/v2/version_files")
+        .header("User-Agent", "anvil-cli/0.1.0")
+        .json(&body)
+        .send_with_backoff()
+        .await?
+        .json()
+        .await?;
     Ok(versions)
 }
 
 pub async fn download_plugin(
     file_url: &str,
     filename: &str,
-    plugins_dir: &std::path::PathBuf,
-) -> Result<()> {
+    plugins_dir: &std::path::Path,
+    pb: &ProgressBar,
+) -> Result<String> {
     let client = reqwest::Client::new();
-    let response = client.get(file_url).send().await?;
-
-    let total_size = response.content_length().unwrap_or(0);
+    let response = client.get(file_url).send_with_backoff().await?;
 
-    let pb = ProgressBar::new(total_size);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] {bar:40.cyan/blue} {bytes}/{total_bytes} {msg}")?
-            .progress_chars("█▉▊▋▌▍▎▏  "),
-    );
-    pb.set_message(format!("Downloading {}", filename));
-
-    let mut stream = response.bytes_stream();
-    let mut downloaded = 0u64;
-    let mut file_data = Vec::new();
+    pb.set_length(response.content_length().unwrap_or(0));
 
-    while let Some(chunk) = stream.try_next().await? {
-        file_data.extend_from_slice(&chunk);
-        downloaded += chunk.len() as u64;
-        pb.set_position(downloaded);
-    }
+    download_with_hash::<sha1::Sha1>(response, &plugins_dir.join(filename), None, pb).await
+}
 
-    let file_path = plugins_dir.join(filename);
-    tokio::fs::write(file_path, file_data).await?;
+pub async fn download_datapack(file: &ModrinthFile, datapacks_dir: &std::path::Path, pb: &ProgressBar) -> Result<String> {
+    let response = reqwest::Client::new().get(&file.url).send_with_backoff().await?;
 
-    pb.finish_with_message("Download complete!");
+    pb.set_length(response.content_length().unwrap_or(0));
 
-    Ok(())
-}
\ No newline at end of file
+    let expected = file.hashes.get("sha1").map(String::as_str);
+    download_with_hash::<sha1::Sha1>(response, &datapacks_dir.join(&file.filename), expected, pb).await
+}
@@ -0,0 +1,54 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::time::Duration;
+
+// Names can change at most every 30 days, so a day-old answer is almost always still right
+const PROFILE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Deserialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String
+}
+
+impl Profile {
+    pub fn hyphenated_id(&self) -> String {
+        hyphenate(&self.id)
+    }
+}
+
+pub async fn lookup(name_or_uuid: &str) -> Result<Option<Profile>> {
+    let compact = name_or_uuid.replace('-', "");
+    let url = if compact.len() == 32 && compact.chars().all(|c| c.is_ascii_hexdigit()) {
+        format!("https://sessionserver.mojang.com/session/minecraft/profile/{}", compact.to_lowercase())
+    } else if is_valid_name(name_or_uuid) {
+        format!("https://api.mojang.com/users/profiles/minecraft/{}", name_or_uuid)
+    } else {
+        return Err(anyhow!("'{}' is neither a Minecraft username nor a UUID", name_or_uuid));
+    };
+
+    let body = match super::cache::get_text("mojang", &url, PROFILE_TTL).await {
+        Ok(body) => body,
+        Err(e) if e.downcast_ref::<reqwest::Error>().and_then(reqwest::Error::status) == Some(reqwest::StatusCode::NOT_FOUND) => {
+            return Ok(None);
+        }
+        Err(e) => return Err(e)
+    };
+
+    // Unknown profiles used to come back as 204 No Content
+    if body.trim().is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&body)?))
+}
+
+pub fn hyphenate(uuid: &str) -> String {
+    if uuid.len() != 32 {
+        return uuid.to_string();
+    }
+    format!("{}-{}-{}-{}-{}", &uuid[..8], &uuid[8..12], &uuid[12..16], &uuid[16..20], &uuid[20..])
+}
+
+pub fn is_valid_name(name: &str) -> bool {
+    (1..=16).contains(&name.len()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -1,8 +1,13 @@
+use super::hash::{download_with_hash, file_hash};
+use super::http::SendWithBackoff;
 use anyhow::Result;
 use indicatif::ProgressBar;
 use serde::Deserialize;
-use std::path::PathBuf;
-use tokio::io::AsyncWriteExt;
+use sha2::Sha256;
+use std::path::Path;
+
+const STABLE_CHANNEL: &str = "default";
+const MAX_VERSIONS_PROBED: usize = 5;
 
 #[derive(Deserialize)]
 struct PaperVersions {
@@ -11,33 +16,117 @@ struct PaperVersions {
 
 #[derive(Deserialize)]
 struct PaperBuilds {
-    builds: Vec<u32>
+    builds: Vec<PaperBuildSummary>
+}
+
+#[derive(Deserialize)]
+struct PaperBuildSummary {
+    build: u32,
+    channel: String
+}
+
+#[derive(Deserialize)]
+struct PaperBuild {
+    downloads: PaperDownloads
+}
+
+#[derive(Deserialize)]
+struct PaperDownloads {
+    application: PaperDownload
 }
 
-pub async fn get_latest_version() -> Result<String> {
+#[derive(Deserialize)]
+struct PaperDownload {
+    sha256: String
+}
+
+pub async fn get_versions() -> Result<Vec<String>> {
     let client = reqwest::Client::new();
     let response: PaperVersions = client
         .get("https://api.papermc.io/v2/projects/paper")
-        .send()
+        .send_with_backoff()
         .await?
         .json()
         .await?;
 
-    Ok(response.versions.into_iter().last().unwrap())
+    Ok(response.versions)
+}
+
+pub async fn get_latest_version(experimental: bool) -> Result<String> {
+    let versions = get_versions().await?;
+
+    // A freshly released Minecraft version is listed before Paper has published any builds for it
+    for version in versions.iter().rev().take(MAX_VERSIONS_PROBED) {
+        if find_latest_build(version, experimental).await?.is_some() {
+            return Ok(version.clone());
+        }
+    }
+
+    Err(anyhow::anyhow!("None of the newest Paper versions have {}builds yet", channel_label(experimental)))
+}
+
+pub async fn get_latest_build(version: &str, experimental: bool) -> Result<u32> {
+    match find_latest_build(version, experimental).await? {
+        Some(build) => Ok(build),
+        None => Err(anyhow::anyhow!(missing_builds_message(version, experimental).await?))
+    }
+}
+
+pub async fn missing_builds_message(version: &str, experimental: bool) -> Result<String> {
+    let mut message = format!("Paper has no {}builds for {} yet", channel_label(experimental), version);
+    if !experimental && find_latest_build(version, true).await?.is_some() {
+        message.push_str("; pass --experimental to use an experimental build");
+    }
+    if let Ok(newest) = get_latest_version(experimental).await {
+        message.push_str(&format!("; the newest version with builds is {}", newest));
+    }
+
+    Ok(message)
 }
 
-pub async fn get_latest_build(version: &str) -> Result<u32> {
+pub async fn has_builds(version: &str, experimental: bool) -> Result<bool> {
+    Ok(find_latest_build(version, experimental).await?.is_some())
+}
+
+async fn find_latest_build(version: &str, experimental: bool) -> Result<Option<u32>> {
     let client = reqwest::Client::new();
-    let url = format!("https://api.papermc.io/v2/projects/paper/versions/{}", version);
-    let response: PaperBuilds = client.get(&url).send().await?.json().await?;
+    let url = format!("https://api.papermc.io/v2/projects/paper/versions/{}/builds", version);
+    let response: PaperBuilds = match client.get(&url).send_with_backoff().await {
+        Ok(response) => response.json().await?,
+        // Versions Paper has never heard of are reported as missing rather than empty
+        Err(e) if e.downcast_ref::<reqwest::Error>().and_then(reqwest::Error::status) == Some(reqwest::StatusCode::NOT_FOUND) => {
+            return Ok(None);
+        }
+        Err(e) => return Err(e)
+    };
 
-    Ok(*response.builds.last().unwrap())
+    Ok(response.builds
+        .iter()
+        .rev()
+        .find(|build| experimental || build.channel == STABLE_CHANNEL)
+        .map(|build| build.build))
+}
+
+fn channel_label(experimental: bool) -> &'static str {
+    if experimental { "" } else { "stable " }
+}
+
+pub async fn get_build_sha256(version: &str, build: u32) -> Result<String> {
+    let client = reqwest::Client::new();
+    let url = format!("https://api.papermc.io/v2/projects/paper/versions/{}/builds/{}", version, build);
+    let response: PaperBuild = client.get(&url).send_with_backoff().await?.json().await?;
+
+    Ok(response.downloads.application.sha256)
+}
+
+pub async fn sha256_hash(file_path: &Path) -> Result<String> {
+    file_hash::<Sha256>(file_path).await
 }
 
 pub async fn download_paper(
     version: &str,
     build: &u32,
-    output_path: &PathBuf,
+    output_path: &Path,
     pb: &ProgressBar,
 ) -> Result<()> {
     let client = reqwest::Client::new();
@@ -46,21 +135,12 @@ pub async fn download_paper(
         version, build, version, build
     );
 
-    let response = client.get(&url).send().await?;
+    let expected = get_build_sha256(version, *build).await?;
+    let response = client.get(&url).send_with_backoff().await?;
     let total_size = response.content_length().unwrap_or(0);
     pb.set_length(total_size);
 
-    let mut file = tokio::fs::File::create(output_path).await?;
-    let mut downloaded = 0u64;
-    let mut stream = response.bytes_stream();
-
-    use futures_util::stream::StreamExt;
-    while let Some(chunk) = stream.next().await {
-        let chunk = chunk?;
-        file.write_all(&chunk).await?;
-        downloaded += chunk.len() as u64;
-        pb.set_position(downloaded);
-    }
+    download_with_hash::<Sha256>(response, output_path, Some(&expected), pb).await?;
 
     Ok(())
 }
\ No newline at end of file
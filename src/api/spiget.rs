@@ -0,0 +1,70 @@
+use super::http::SendWithBackoff;
+use anyhow::Result;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpigetResource {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub external: bool,
+    #[serde(default)]
+    pub tested_versions: Vec<String>
+}
+
+#[derive(Deserialize)]
+pub struct SpigetVersion {
+    pub id: u64,
+    pub name: String
+}
+
+pub async fn search_resource(query: &str) -> Result<Option<SpigetResource>> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "https://api.spiget.org/v2/search/resources/{}?field=name&size=1&sort=-downloads",
+        urlencoding::encode(query)
+    );
+
+    let response = client
+        .get(&url)
+        .header("User-Agent", "anvil-cli/0.1.0")
+        .send_with_backoff()
+        .await?;
+
+    // Spiget answers an empty search with a 404
+    let resources: Vec<SpigetResource> = response.json().await.unwrap_or_default();
+    Ok(resources.into_iter().next())
+}
+
+pub async fn get_resource(resource_id: &str) -> Result<SpigetResource> {
+    let client = reqwest::Client::new();
+    let url = format!("https://api.spiget.org/v2/resources/{}", resource_id);
+
+    let resource: SpigetResource = client
+        .get(&url)
+        .header("User-Agent", "anvil-cli/0.1.0")
+        .send_with_backoff()
+        .await?
+        .json()
+        .await?;
+    Ok(resource)
+}
+
+pub async fn get_latest_version(resource_id: &str) -> Result<SpigetVersion> {
+    let client = reqwest::Client::new();
+    let url = format!("https://api.spiget.org/v2/resources/{}/versions/latest", resource_id);
+
+    let version: SpigetVersion = client
+        .get(&url)
+        .header("User-Agent", "anvil-cli/0.1.0")
+        .send_with_backoff()
+        .await?
+        .json()
+        .await?;
+    Ok(version)
+}
+
+pub fn download_url(resource_id: &str) -> String {
+    format!("https://api.spiget.org/v2/resources/{}/download", resource_id)
+}
@@ -1,8 +1,10 @@
+use super::hash::{download_with_hash, file_hash};
+use super::http::SendWithBackoff;
 use anyhow::Result;
 use indicatif::ProgressBar;
 use serde::Deserialize;
-use std::path::PathBuf;
-use tokio::io::AsyncWriteExt;
+use sha1::Sha1;
+use std::path::Path;
 
 #[derive(Deserialize)]
 struct VersionManifest {
@@ -42,7 +44,7 @@ pub async fn get_latest_version() -> Result<String> {
     let client = reqwest::Client::new();
     let response: VersionManifest = client
         .get("https://piston-meta.mojang.com/mc/game/version_manifest.json")
-        .send()
+        .send_with_backoff()
         .await?
         .json()
         .await?;
@@ -50,16 +52,22 @@ pub async fn get_latest_version() -> Result<String> {
     Ok(response.latest.release)
 }
 
-pub async fn download_vanilla_server(
-    version: &str,
-    output_path: &PathBuf,
-    pb: &ProgressBar,
-) -> Result<String> {
+pub async fn get_versions() -> Result<Vec<String>> {
     let client = reqwest::Client::new();
+    let response: VersionManifest = client
+        .get("https://piston-meta.mojang.com/mc/game/version_manifest.json")
+        .send_with_backoff()
+        .await?
+        .json()
+        .await?;
+
+    Ok(response.versions.into_iter().map(|version| version.id).collect())
+}
 
+async fn get_server_download(client: &reqwest::Client, version: &str) -> Result<ServerDownload> {
     let manifest: VersionManifest = client
         .get("https://piston-meta.mojang.com/mc/game/version_manifest.json")
-        .send()
+        .send_with_backoff()
         .await?
         .json()
         .await?;
@@ -72,50 +80,41 @@ pub async fn download_vanilla_server(
 
     let version_details: VersionDetails = client
         .get(&version_info.url)
-        .send()
+        .send_with_backoff()
         .await?
         .json()
         .await?;
 
-    let server_download = version_details
+    version_details
         .downloads
         .server
-        .ok_or_else(|| anyhow::anyhow!("No server download available for version {}", version))?;
+        .ok_or_else(|| anyhow::anyhow!("No server download available for version {}", version))
+}
+
+pub async fn get_server_sha1(version: &str) -> Result<String> {
+    let client = reqwest::Client::new();
+    Ok(get_server_download(&client, version).await?.sha1)
+}
+
+pub async fn download_vanilla_server(
+    version: &str,
+    output_path: &Path,
+    pb: &ProgressBar,
+) -> Result<String> {
+    let client = reqwest::Client::new();
+    let server_download = get_server_download(&client, version).await?;
 
     let jar_name = format!("vanilla-{}.jar", version);
     let jar_path = output_path.join(&jar_name);
 
-    let response = client.get(&server_download.url).send().await?;
+    let response = client.get(&server_download.url).send_with_backoff().await?;
     pb.set_length(server_download.size);
 
-    let mut file = tokio::fs::File::create(&jar_path).await?;
-    let mut downloaded = 0u64;
-    let mut stream = response.bytes_stream();
-
-    use futures_util::stream::StreamExt;
-    while let Some(chunk) = stream.next().await {
-        let chunk = chunk?;
-        file.write_all(&chunk).await?;
-        downloaded += chunk.len() as u64;
-        pb.set_position(downloaded);
-    }
-
-    let file_hash = sha1_hash(&jar_path).await?;
-    if file_hash != server_download.sha1 {
-        return Err(anyhow::anyhow!(
-            "Downloaded file hash doesn't match expected hash"
-        ));
-    }
+    download_with_hash::<Sha1>(response, &jar_path, Some(&server_download.sha1), pb).await?;
 
     Ok(jar_name)
 }
 
-async fn sha1_hash(file_path: &PathBuf) -> Result<String> {
-    use sha1::{Digest, Sha1};
-
-    let contents = tokio::fs::read(file_path).await?;
-    let mut hasher = Sha1::new();
-    hasher.update(&contents);
-    let result = hasher.finalize();
-    Ok(hex::encode(result))
+pub async fn sha1_hash(file_path: &Path) -> Result<String> {
+    file_hash::<Sha1>(file_path).await
 }
\ No newline at end of file
@@ -0,0 +1,368 @@
+use crate::server::get_anvil_dir;
+use crate::{
+    AutostartAction, BackupAction, BorderAction, ChatBridgeAction, Commands, CompatAction, ConfigAction, DbAction,
+    DdnsAction, EnvAction, HealthAction, HookAction, IdleAction, LayoutAction, MacroAction, MaintenanceAction,
+    MapAction, NetworkAction, PermsAction, PlayersAction, PluginAction, ProxyAction, ResourcePackAction,
+    ScheduleAction, SecretAction, SettingsAction, SyncAction, TagAction, TriggerAction, WorldAction,
+};
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+use clap::{Arg, ArgAction, Command, CommandFactory};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+#[derive(Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub user: String,
+    pub action: String,
+    pub server: Option<String>,
+    pub args: Vec<String>,
+    pub result: String
+}
+
+pub struct PendingAudit {
+    action: &'static str,
+    server: Option<String>
+}
+
+fn audit_log_path() -> PathBuf {
+    get_anvil_dir().join("audit.log")
+}
+
+pub fn describe(command: &Commands) -> Option<PendingAudit> {
+    let (action, server) = match command {
+        Commands::Create { name, .. } => ("create", Some(name)),
+        Commands::Delete { name, .. } => ("delete", Some(name)),
+        Commands::RestoreDeleted { name: Some(name) } => ("restore-deleted", Some(name)),
+        Commands::Upgrade { server, check: false, plan: false, .. } => ("upgrade", Some(server)),
+        Commands::Verify { server, repair: true } => ("verify", Some(server)),
+        Commands::PruneOrphans { dry_run: false } => ("prune-orphans", None),
+        Commands::Mode { server, mode: Some(_) } => ("mode", Some(server)),
+        Commands::Tune { server, dry_run: false, .. } => ("tune", Some(server)),
+        Commands::Run { server, .. } => ("macro run", Some(server)),
+        Commands::Players { server, action } => match action {
+            Some(PlayersAction::Kick { .. }) => ("players kick", Some(server)),
+            Some(PlayersAction::Msg { .. }) => ("players msg", Some(server)),
+            Some(PlayersAction::Tp { .. }) => ("players tp", Some(server)),
+            None => return None
+        },
+        Commands::Plugin { server, action } => (plugin_action(action)?, Some(server)),
+        Commands::Hook { server, action } => match action {
+            HookAction::Set { .. } => ("hook set", Some(server)),
+            HookAction::Remove { .. } => ("hook remove", Some(server)),
+            HookAction::List => return None
+        },
+        Commands::Trigger { server, action } => match action {
+            TriggerAction::Add { .. } => ("trigger add", Some(server)),
+            TriggerAction::Remove { .. } => ("trigger remove", Some(server)),
+            TriggerAction::List | TriggerAction::Test { .. } => return None
+        },
+        Commands::Backup { server, action } => match action {
+            BackupAction::Create { .. } => ("backup create", Some(server)),
+            BackupAction::Restore { .. } => ("backup restore", Some(server)),
+            BackupAction::List => return None
+        },
+        Commands::Announce { server, every: None, cron: None, .. } => ("announce", Some(server)),
+        Commands::Announce { server, .. } => ("announce schedule", Some(server)),
+        Commands::Schedule { server, action } => match action {
+            ScheduleAction::Add { .. } => ("schedule add", Some(server)),
+            ScheduleAction::Remove { .. } => ("schedule remove", Some(server)),
+            ScheduleAction::Run { .. } => ("schedule run", Some(server)),
+            ScheduleAction::List | ScheduleAction::Crontab => return None
+        },
+        Commands::Macro { server, action } => match action {
+            MacroAction::Add { .. } => ("macro add", Some(server)),
+            MacroAction::Remove { .. } => ("macro remove", Some(server)),
+            MacroAction::List => return None
+        },
+        Commands::Proxy { action } => match action {
+            ProxyAction::Attach { server, .. } => ("proxy attach", Some(server)),
+            ProxyAction::Detach { server } => ("proxy detach", Some(server)),
+            ProxyAction::Motd { server, .. } => ("proxy motd", Some(server))
+        },
+        Commands::Health { action: HealthAction::Enable { server, .. } } => ("health enable", Some(server)),
+        Commands::Health { action: HealthAction::Disable { server } } => ("health disable", Some(server)),
+        Commands::Env { action: EnvAction::Set { server, .. } } => ("env set", Some(server)),
+        Commands::Env { action: EnvAction::Unset { server, .. } } => ("env unset", Some(server)),
+        Commands::Layout { action: LayoutAction::Set { server, .. } } => ("layout set", Some(server)),
+        Commands::Layout { action: LayoutAction::Reset { server } } => ("layout reset", Some(server)),
+        Commands::Autostart { action: AutostartAction::Enable { server, .. } } => ("autostart enable", Some(server)),
+        Commands::Autostart { action: AutostartAction::Disable { server } } => ("autostart disable", Some(server)),
+        Commands::ResourcePack { action: ResourcePackAction::Set { server, .. } } => ("resource-pack set", Some(server)),
+        Commands::ResourcePack { action: ResourcePackAction::Clear { server } } => ("resource-pack clear", Some(server)),
+        Commands::Idle { action: IdleAction::Enable { server, .. } } => ("idle enable", Some(server)),
+        Commands::Idle { action: IdleAction::Disable { server } } => ("idle disable", Some(server)),
+        Commands::ChatBridge { action: ChatBridgeAction::Enable { server, .. } } => ("chat-bridge enable", Some(server)),
+        Commands::ChatBridge { action: ChatBridgeAction::Disable { server } } => ("chat-bridge disable", Some(server)),
+        Commands::Maintenance { action: MaintenanceAction::On { server, .. } } => ("maintenance on", Some(server)),
+        Commands::Maintenance { action: MaintenanceAction::Off { server } } => ("maintenance off", Some(server)),
+        Commands::Import { name, .. } => ("import", name.as_ref()),
+        Commands::Apply { check: false, .. } => ("apply", None),
+        Commands::Network { action: NetworkAction::Harden } => ("network harden", None),
+        Commands::Perms { action: PermsAction::Init { server, .. } } => ("perms init", Some(server)),
+        Commands::Compat { action: CompatAction::Enable { server, .. } } => ("compat enable", Some(server)),
+        Commands::Map { action: MapAction::Enable { server, .. } } => ("map enable", Some(server)),
+        Commands::Ddns { action } => match action {
+            DdnsAction::Enable { server, .. } => ("ddns enable", Some(server)),
+            DdnsAction::Update { server } => ("ddns update", Some(server)),
+            DdnsAction::Disable { server } => ("ddns disable", Some(server))
+        },
+        Commands::Db { action } => match action {
+            DbAction::Create { server, .. } => ("db create", Some(server)),
+            DbAction::Remove { server } => ("db remove", Some(server)),
+            DbAction::Info { .. } => return None
+        },
+        Commands::Config { action: ConfigAction::Set { server, .. } } => ("config set", Some(server)),
+        Commands::Settings { action: SettingsAction::Set { .. } } => ("settings set", None),
+        Commands::World { action } => match action {
+            WorldAction::Upgrade { server, .. } => ("world upgrade", Some(server)),
+            WorldAction::Create { server, .. } => ("world create", Some(server)),
+            WorldAction::Prepare { server, .. } => ("world prepare", Some(server)),
+            WorldAction::MigrateUuids { server, dry_run: false, .. } => ("world migrate-uuids", Some(server)),
+            WorldAction::MigrateUuids { dry_run: true, .. } => return None,
+            WorldAction::Border { server, action: BorderAction::Set { .. } } => ("world border set", Some(server)),
+            WorldAction::Border { action: BorderAction::Get, .. } => return None
+        },
+        Commands::Tag { action } => match action {
+            TagAction::Add { server, .. } => ("tag add", Some(server)),
+            TagAction::Remove { server, .. } => ("tag remove", Some(server)),
+            TagAction::List { .. } => return None
+        },
+        Commands::Secret { action: SecretAction::Rotate { server, .. } } => ("secret rotate", Some(server)),
+        Commands::Expose { server, .. } => ("expose", Some(server)),
+        Commands::Revert { server, to: Some(_) } => ("revert", Some(server)),
+        Commands::Sync { action: SyncAction::Players { dry_run: false, .. } } => ("sync players", None),
+        _ => return None
+    };
+
+    Some(PendingAudit {
+        action,
+        server: server.cloned()
+    })
+}
+
+fn plugin_action(action: &PluginAction) -> Option<&'static str> {
+    Some(match action {
+        PluginAction::Add { .. } => "plugin add",
+        PluginAction::Remove { .. } => "plugin remove",
+        PluginAction::Disable { .. } => "plugin disable",
+        PluginAction::Enable { .. } => "plugin enable",
+        PluginAction::AddBundle { .. } => "plugin add-bundle",
+        PluginAction::Update { .. } => "plugin update",
+        PluginAction::Rollback { .. } => "plugin rollback",
+        PluginAction::Browse { .. } => "plugin browse",
+        PluginAction::Config { get: None, .. } => "plugin config",
+        PluginAction::Sources { sources, reset } if reset | !sources.is_empty() => "plugin sources",
+        _ => return None
+    })
+}
+
+impl PendingAudit {
+    pub fn server(&self) -> Option<&str> {
+        self.server.as_deref()
+    }
+
+    pub fn summary(&self) -> String {
+        format!("anvil {}", redacted_args().join(" "))
+    }
+}
+
+pub fn record(pending: PendingAudit, result: &Result<()>) {
+    let entry = AuditEntry {
+        timestamp: Utc::now(),
+        user: current_user(),
+        action: pending.action.to_string(),
+        server: pending.server,
+        args: redacted_args(),
+        result: match result {
+            Ok(()) => "ok".to_string(),
+            Err(e) => format!("error: {}", e)
+        }
+    };
+
+    // Auditing must never turn a successful command into a failure
+    if let Err(e) = append(&entry) {
+        eprintln!("⚠️  Could not write audit log: {}", e);
+    }
+}
+
+fn append(entry: &AuditEntry) -> Result<()> {
+    let path = audit_log_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn redacted_args() -> Vec<String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    redact_args(&args)
+}
+
+// Walks the arguments the way clap parses them, so a secret is masked however it was passed:
+// --token X, --token=X, -t X, -tX, or as the value of a secret config key like rcon.password
+fn redact_args(args: &[String]) -> Vec<String> {
+    let root = crate::Cli::command();
+    let mut command = &root;
+    let mut positional = 0;
+    let mut secret_key = false;
+    let mut redacted = Vec::with_capacity(args.len());
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            redacted.push(arg.clone());
+            redacted.extend(iter.map(|_| MASK.to_string()));
+            break;
+        }
+
+        if let Some(long) = arg.strip_prefix("--") {
+            let (name, inline) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (long, None)
+            };
+            let found = find_arg(command, &root, |candidate| candidate.get_long() == Some(name));
+            let secret = found.is_some_and(is_secret_arg);
+            match inline {
+                Some(_) if secret => redacted.push(format!("--{}={}", name, MASK)),
+                _ => redacted.push(arg.clone())
+            }
+            if inline.is_none() && found.is_some_and(takes_value) && let Some(value) = iter.next() {
+                redacted.push(if secret { MASK.to_string() } else { value.clone() });
+            }
+            continue;
+        }
+
+        if let Some(shorts) = arg.strip_prefix('-').filter(|shorts| !shorts.is_empty()) {
+            let mut masked = String::from("-");
+            let mut needs_value = None;
+            for (index, short) in shorts.char_indices() {
+                masked.push(short);
+                let Some(found) = find_arg(command, &root, |candidate| candidate.get_short() == Some(short)) else {
+                    continue;
+                };
+                if takes_value(found) {
+                    // -tVALUE carries the value in the same argument
+                    let rest = &shorts[index + short.len_utf8()..];
+                    if rest.is_empty() {
+                        needs_value = Some(is_secret_arg(found));
+                    } else {
+                        masked.push_str(if is_secret_arg(found) { MASK } else { rest });
+                    }
+                    break;
+                }
+            }
+            redacted.push(masked);
+            if let Some(secret) = needs_value && let Some(value) = iter.next() {
+                redacted.push(if secret { MASK.to_string() } else { value.clone() });
+            }
+            continue;
+        }
+
+        let positionals: Vec<&Arg> = command.get_positionals().collect();
+        let required = positionals.iter().filter(|candidate| candidate.is_required_set()).count();
+        if positional >= required && let Some(subcommand) = command.find_subcommand(arg) {
+            command = subcommand;
+            positional = 0;
+            secret_key = false;
+            redacted.push(arg.clone());
+            continue;
+        }
+
+        let current = positionals.get(positional);
+        let secret = secret_key || current.is_some_and(|candidate| is_secret_arg(candidate));
+        match arg.split_once('=') {
+            // env set <server> KEY=VALUE keeps the name and hides the value
+            Some((key, _)) if current.is_some_and(|candidate| candidate.get_id() == "vars") => {
+                redacted.push(format!("{}={}", key, MASK));
+            }
+            _ => redacted.push(if secret { MASK.to_string() } else { arg.clone() })
+        }
+        // config set <server> rcon.password <value> names the secret in the argument before it
+        secret_key = current.is_some_and(|candidate| candidate.get_id() == "key") && crate::secrets::is_secret_name(arg);
+        // A list like env set's KEY=VALUE pairs takes every remaining argument
+        if !current.is_some_and(|candidate| matches!(candidate.get_action(), ArgAction::Append)) {
+            positional += 1;
+        }
+    }
+
+    redacted
+}
+
+const MASK: &str = "********";
+// Webhook URLs carry their token in the path, anyone holding one can post as the hook
+const WEBHOOK_ARGS: &[&str] = &["webhook", "notify"];
+
+// Arguments are looked up on the current subcommand first, global ones like --progress live on the root
+fn find_arg<'a>(command: &'a Command, root: &'a Command, matches: impl Fn(&Arg) -> bool) -> Option<&'a Arg> {
+    command
+        .get_arguments()
+        .find(|candidate| matches(candidate))
+        .or_else(|| root.get_arguments().find(|candidate| matches(candidate)))
+}
+
+fn takes_value(arg: &Arg) -> bool {
+    arg.get_action().takes_values()
+}
+
+fn is_secret_arg(arg: &Arg) -> bool {
+    let id = arg.get_id().as_str();
+    WEBHOOK_ARGS.contains(&id) || crate::secrets::is_secret_name(id)
+}
+
+pub fn show_history(server: Option<&str>, limit: usize) -> Result<()> {
+    let path = audit_log_path();
+    if !path.exists() {
+        println!("No operations recorded yet");
+        return Ok(());
+    }
+
+    let entries: Vec<AuditEntry> = fs::read_to_string(path)?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter(|entry: &AuditEntry| server.is_none_or(|server| entry.server.as_deref() == Some(server)))
+        .collect();
+
+    if entries.is_empty() {
+        match server {
+            Some(server) => println!("No operations recorded for '{}'", server),
+            None => println!("No operations recorded yet")
+        }
+        return Ok(());
+    }
+
+    match server {
+        Some(server) => println!("📜 History for '{}':", server),
+        None => println!("📜 Operation history:")
+    }
+
+    let skip = entries.len().saturating_sub(limit);
+    for entry in &entries[skip..] {
+        let status = if entry.result == "ok" { "✅" } else { "❌" };
+        println!(
+            "{} {}  {:10}  {:18}  anvil {}",
+            status,
+            entry.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"),
+            entry.user,
+            entry.action,
+            entry.args.join(" ")
+        );
+        if entry.result != "ok" {
+            println!("      {}", entry.result);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,323 @@
+use crate::server::{console_log_path, load_server_config, save_server_config, ServerConfig};
+use crate::AutostartAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tokio::process::Command;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AutostartConfig {
+    pub ram: u8
+}
+
+pub async fn handle_autostart_action(action: AutostartAction) -> Result<()> {
+    match action {
+        AutostartAction::Enable { server, ram } => {
+            let mut config = load_server_config(&server)?;
+            register(&config, ram).await?;
+            config.autostart = Some(AutostartConfig { ram });
+            save_server_config(&config)?;
+
+            println!("✅ '{}' will start with {}GB of RAM when this machine boots, without anyone logging in", server, ram);
+        }
+        AutostartAction::Disable { server } => {
+            let mut config = load_server_config(&server)?;
+            if config.autostart.is_none() && !is_registered(&config).await? {
+                return Err(anyhow!("'{}' is not set to start automatically", server));
+            }
+            unregister(&config).await?;
+            config.autostart = None;
+            save_server_config(&config)?;
+            println!("✅ '{}' will no longer start automatically", server);
+        }
+        AutostartAction::Status { server } => {
+            let config = load_server_config(&server)?;
+            println!("🚀 Autostart for '{}':", server);
+            match &config.autostart {
+                Some(autostart) => println!(" - Enabled: yes ({}GB of RAM)", autostart.ram),
+                None => println!(" - Enabled: no")
+            }
+            println!(" - Registered with: {}", service_manager()?);
+            println!(" - Entry: {}", entry_name(&config)?);
+
+            let registered = is_registered(&config).await?;
+            println!(" - Registered: {}", if registered { "yes" } else { "no" });
+            if registered != config.autostart.is_some() {
+                println!("⚠️  The system entry does not match the config, run autostart enable or disable again");
+            }
+        }
+    }
+    Ok(())
+}
+
+// Called before a server is deleted so the system does not keep trying to launch it
+pub async fn remove_autostart(config: &mut ServerConfig) -> Result<()> {
+    if config.autostart.take().is_some() {
+        unregister(config).await?;
+        save_server_config(config)?;
+        println!("✅ Removed the autostart entry");
+    }
+    Ok(())
+}
+
+fn service_manager() -> Result<&'static str> {
+    match std::env::consts::OS {
+        "linux" => Ok("systemd (user service)"),
+        "macos" => Ok("launchd (launch daemon)"),
+        "windows" => Ok("Task Scheduler (at system startup)"),
+        other => Err(anyhow!("Autostart is not supported on {}", other))
+    }
+}
+
+fn entry_name(config: &ServerConfig) -> Result<String> {
+    match std::env::consts::OS {
+        "linux" => Ok(systemd_unit_path(config)?.display().to_string()),
+        "macos" => Ok(launch_daemon_path(config).display().to_string()),
+        "windows" => Ok(task_name(config)),
+        other => Err(anyhow!("Autostart is not supported on {}", other))
+    }
+}
+
+fn systemd_unit_name(config: &ServerConfig) -> String {
+    format!("anvil-{}.service", config.name)
+}
+
+fn systemd_unit_path(config: &ServerConfig) -> Result<PathBuf> {
+    let config_dir = dirs::config_dir().ok_or_else(|| anyhow!("Could not find the user config directory"))?;
+    Ok(config_dir.join("systemd").join("user").join(systemd_unit_name(config)))
+}
+
+fn launch_agent_label(config: &ServerConfig) -> String {
+    format!("dev.anvil.{}", config.name)
+}
+
+// Launch agents only run once the user logs in, a daemon runs at boot and drops to the user itself
+fn launch_daemon_path(config: &ServerConfig) -> PathBuf {
+    PathBuf::from("/Library/LaunchDaemons").join(format!("{}.plist", launch_agent_label(config)))
+}
+
+// Where older versions registered the server, removed when it is registered again or disabled
+fn legacy_launch_agent_path(config: &ServerConfig) -> Option<PathBuf> {
+    Some(dirs::home_dir()?.join("Library").join("LaunchAgents").join(format!("{}.plist", launch_agent_label(config))))
+}
+
+fn current_user() -> Result<String> {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .map_err(|_| anyhow!("Could not tell which user to run the server as"))
+}
+
+fn task_name(config: &ServerConfig) -> String {
+    format!(r"anvil\{}", config.name)
+}
+
+async fn register(config: &ServerConfig, ram: u8) -> Result<()> {
+    let exe = std::env::current_exe()?;
+    match std::env::consts::OS {
+        "linux" => {
+            let path = systemd_unit_path(config)?;
+            fs::create_dir_all(path.parent().unwrap())?;
+            // The server runs attached so systemd owns the process; on stop it sends SIGTERM, which saves the world
+            let unit = format!(
+                "[Unit]\n\
+                 Description=Minecraft server {name} (anvil)\n\
+                 \n\
+                 [Service]\n\
+                 Type=simple\n\
+                 WorkingDirectory={dir}\n\
+                 ExecStart=\"{exe}\" start {name} --ram {ram}\n\
+                 Restart=on-failure\n\
+                 RestartSec=10\n\
+                 TimeoutStopSec=90\n\
+                 \n\
+                 [Install]\n\
+                 WantedBy=default.target\n",
+                name = config.name,
+                dir = config.path.display(),
+                exe = exe.display(),
+                ram = ram
+            );
+            fs::write(&path, unit)?;
+
+            let enabled = async {
+                run(Command::new("systemctl").args(["--user", "daemon-reload"]), "systemctl").await?;
+                run(Command::new("systemctl").args(["--user", "enable", &systemd_unit_name(config)]), "systemctl").await
+            };
+            if let Err(e) = enabled.await {
+                fs::remove_file(&path)?;
+                return Err(e);
+            }
+
+            // User services stop at logout and only start at login unless the user lingers
+            let user = current_user()?;
+            if let Err(e) = run(Command::new("loginctl").args(["enable-linger", &user]), "loginctl").await {
+                println!("⚠️  Could not enable lingering ({}), the server only starts once {} logs in", e, user);
+                println!("💡 Let it start at boot with: sudo loginctl enable-linger {}", user);
+            }
+            Ok(())
+        }
+        "macos" => {
+            let path = launch_daemon_path(config);
+            let user = current_user()?;
+            let home = dirs::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
+            if let Some(legacy) = legacy_launch_agent_path(config).filter(|legacy| legacy.exists()) {
+                let _ = Command::new("launchctl").arg("unload").arg(&legacy).output().await;
+                fs::remove_file(legacy)?;
+            }
+            if path.exists() {
+                let _ = elevated(Command::new("sudo").args(["launchctl", "unload"]).arg(&path), "launchctl").await;
+            }
+
+            let arguments = [exe.display().to_string(), "start".into(), config.name.clone(), "--ram".into(), ram.to_string()];
+            let plist = format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                 <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+                 <plist version=\"1.0\">\n\
+                 <dict>\n\
+                 \t<key>Label</key>\n\
+                 \t<string>{label}</string>\n\
+                 \t<key>ProgramArguments</key>\n\
+                 \t<array>\n{arguments}\
+                 \t</array>\n\
+                 \t<key>UserName</key>\n\
+                 \t<string>{user}</string>\n\
+                 \t<key>EnvironmentVariables</key>\n\
+                 \t<dict>\n\
+                 \t\t<key>HOME</key>\n\
+                 \t\t<string>{home}</string>\n\
+                 \t</dict>\n\
+                 \t<key>WorkingDirectory</key>\n\
+                 \t<string>{dir}</string>\n\
+                 \t<key>RunAtLoad</key>\n\
+                 \t<true/>\n\
+                 \t<key>StandardOutPath</key>\n\
+                 \t<string>{log}</string>\n\
+                 \t<key>StandardErrorPath</key>\n\
+                 \t<string>{log}</string>\n\
+                 </dict>\n\
+                 </plist>\n",
+                label = xml_escape(&launch_agent_label(config)),
+                arguments = arguments.iter().map(|arg| format!("\t\t<string>{}</string>\n", xml_escape(arg))).collect::<String>(),
+                user = xml_escape(&user),
+                home = xml_escape(&home.display().to_string()),
+                dir = xml_escape(&config.path.display().to_string()),
+                log = xml_escape(&console_log_path(config).display().to_string())
+            );
+
+            // /Library/LaunchDaemons belongs to root, so the plist is staged and installed through sudo
+            let staged = std::env::temp_dir().join(format!("{}.plist", launch_agent_label(config)));
+            fs::write(&staged, plist)?;
+            println!("🔐 Installing a launch daemon needs administrator rights, sudo may ask for your password");
+            let installed = elevated(
+                Command::new("sudo").args(["install", "-m", "644", "-o", "root", "-g", "wheel"]).arg(&staged).arg(&path),
+                "install"
+            )
+            .await;
+            let _ = fs::remove_file(&staged);
+            installed?;
+
+            if let Err(e) = elevated(Command::new("sudo").args(["launchctl", "load", "-w"]).arg(&path), "launchctl").await {
+                let _ = elevated(Command::new("sudo").arg("rm").arg(&path), "rm").await;
+                return Err(e);
+            }
+            Ok(())
+        }
+        "windows" => {
+            // Task Scheduler has no console to attach to, so the server gets a hidden one
+            let action = format!("\"{}\" start {} --window hidden --ram {}", exe.display(), config.name, ram);
+            // At startup nobody is logged in; /NP runs the task as the user without storing a password
+            let user = match std::env::var("USERDOMAIN") {
+                Ok(domain) => format!(r"{}\{}", domain, current_user()?),
+                Err(_) => current_user()?
+            };
+            let created = run(
+                Command::new("schtasks").args([
+                    "/Create", "/F", "/SC", "ONSTART", "/RU", &user, "/NP", "/TN", &task_name(config), "/TR", &action
+                ]),
+                "schtasks"
+            )
+            .await;
+            created.map_err(|e| anyhow!("{} (startup tasks can only be created from an administrator prompt)", e))
+        }
+        other => Err(anyhow!("Autostart is not supported on {}", other))
+    }
+}
+
+async fn unregister(config: &ServerConfig) -> Result<()> {
+    match std::env::consts::OS {
+        "linux" => {
+            let path = systemd_unit_path(config)?;
+            if path.exists() {
+                run(Command::new("systemctl").args(["--user", "disable", &systemd_unit_name(config)]), "systemctl").await?;
+                fs::remove_file(&path)?;
+                run(Command::new("systemctl").args(["--user", "daemon-reload"]), "systemctl").await?;
+            }
+            Ok(())
+        }
+        "macos" => {
+            if let Some(legacy) = legacy_launch_agent_path(config).filter(|legacy| legacy.exists()) {
+                run(Command::new("launchctl").arg("unload").arg("-w").arg(&legacy), "launchctl").await?;
+                fs::remove_file(legacy)?;
+            }
+            let path = launch_daemon_path(config);
+            if path.exists() {
+                elevated(Command::new("sudo").args(["launchctl", "unload", "-w"]).arg(&path), "launchctl").await?;
+                elevated(Command::new("sudo").arg("rm").arg(&path), "rm").await?;
+            }
+            Ok(())
+        }
+        "windows" => {
+            if is_registered(config).await? {
+                run(Command::new("schtasks").args(["/Delete", "/F", "/TN", &task_name(config)]), "schtasks").await?;
+            }
+            Ok(())
+        }
+        other => Err(anyhow!("Autostart is not supported on {}", other))
+    }
+}
+
+async fn is_registered(config: &ServerConfig) -> Result<bool> {
+    match std::env::consts::OS {
+        "linux" => Ok(systemd_unit_path(config)?.exists()),
+        "macos" => Ok(launch_daemon_path(config).exists()),
+        "windows" => Ok(Command::new("schtasks")
+            .args(["/Query", "/TN", &task_name(config)])
+            .output()
+            .await
+            .is_ok_and(|output| output.status.success())),
+        other => Err(anyhow!("Autostart is not supported on {}", other))
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+// Runs through sudo with the terminal attached, so it can ask for the password
+async fn elevated(command: &mut Command, program: &str) -> Result<()> {
+    let status = command
+        .status()
+        .await
+        .map_err(|e| anyhow!("Failed to run {} through sudo: {}", program, e))?;
+    if !status.success() {
+        return Err(anyhow!("{} failed ({})", program, status));
+    }
+    Ok(())
+}
+
+async fn run(command: &mut Command, program: &str) -> Result<()> {
+    let output = command
+        .output()
+        .await
+        .map_err(|e| anyhow!("Failed to run {} (is it installed?): {}", program, e))?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,379 @@
+use crate::cancel::{Cancellable, CancelFlag};
+use crate::hooks::{run_hook, HookEvent};
+use crate::server::{
+    all_world_dir_names, get_anvil_dir, get_directory_size, is_server_running, load_server_config,
+    save_server_config, ServerConfig,
+};
+use crate::BackupAction;
+use anyhow::{anyhow, Result};
+use chrono::Local;
+use dialoguer::Confirm;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const SKIPPED_DIRS: &[&str] = &["cache", "libraries", "versions"];
+const RESTORE_STAGING_DIR: &str = ".anvil-restore";
+const SNAPSHOT_SKIPPED_DIRS: &[&str] = &["logs", "crash-reports"];
+// Snapshots carry the anvil config so restoring one also rolls back the recorded version and plugins
+const SNAPSHOT_CONFIG_ENTRY: &str = ".anvil-config.json";
+
+pub async fn handle_backup_action(server_name: &str, action: BackupAction) -> Result<()> {
+    match action {
+        BackupAction::Create { worlds } => {
+            let config = load_server_config(server_name)?;
+            if worlds.is_empty() {
+                create_backup(&config).await?;
+            } else {
+                create_world_backup(&config, &worlds).await?;
+            }
+        }
+        BackupAction::Restore { backup, worlds, force } => {
+            let config = load_server_config(server_name)?;
+            restore_backup(&config, &backup, &worlds, force).await?;
+        }
+        BackupAction::List => {
+            list_backups(server_name)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn get_backups_dir(server_name: &str) -> PathBuf {
+    load_server_config(server_name)
+        .ok()
+        .and_then(|config| config.layout.backups)
+        .unwrap_or_else(|| get_anvil_dir().join("backups"))
+        .join(server_name)
+}
+
+pub async fn create_backup(config: &ServerConfig) -> Result<PathBuf> {
+    println!("💾 Backing up server '{}'...", config.name);
+    let archive_name = format!("{}-{}.tar.gz", config.name, Local::now().format("%Y%m%d-%H%M%S"));
+    write_backup(config, &archive_name, |name| !SKIPPED_DIRS.contains(&name), None).await
+}
+
+pub async fn safety_snapshot(config: &ServerConfig, operation: &str) -> Result<()> {
+    if !crate::settings::load_settings()?.safety_snapshots_enabled() {
+        return Ok(());
+    }
+
+    println!("🛟 Taking a safety snapshot of '{}' before {}...", config.name, operation);
+    let archive_name = format!(
+        "{}-pre-{}-{}.tar.gz",
+        config.name,
+        operation.replace(' ', "-"),
+        Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let include = |name: &str| !SKIPPED_DIRS.contains(&name) && !SNAPSHOT_SKIPPED_DIRS.contains(&name);
+    let archive_path = write_backup(config, &archive_name, include, Some(serde_json::to_vec_pretty(config)?)).await?;
+
+    println!(
+        "💡 Roll back with: anvil backup -s {} restore {} --force",
+        config.name,
+        archive_path.file_name().unwrap_or_default().to_string_lossy()
+    );
+    Ok(())
+}
+
+async fn create_world_backup(config: &ServerConfig, worlds: &[String]) -> Result<PathBuf> {
+    let known = all_world_dir_names(config);
+    if let Some(missing) = worlds.iter().find(|world| !known.contains(world)) {
+        return Err(anyhow!(
+            "Server '{}' has no world '{}' (available: {})",
+            config.name, missing, known.join(", ")
+        ));
+    }
+
+    println!("💾 Backing up {} of server '{}'...", worlds.join(", "), config.name);
+    let archive_name = format!(
+        "{}-{}-{}.tar.gz",
+        config.name,
+        worlds.join("+"),
+        Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let worlds = worlds.to_vec();
+    write_backup(config, &archive_name, move |name| worlds.iter().any(|world| world == name), None).await
+}
+
+async fn write_backup(
+    config: &ServerConfig,
+    archive_name: &str,
+    include: impl Fn(&str) -> bool + Send + 'static,
+    embedded_config: Option<Vec<u8>>,
+) -> Result<PathBuf> {
+    let backups_dir = get_backups_dir(&config.name);
+    fs::create_dir_all(&backups_dir)?;
+
+    // Two backups within the same second must not overwrite each other, least of all the one being restored
+    let mut archive_path = backups_dir.join(archive_name);
+    let stem = archive_name.trim_end_matches(".tar.gz");
+    let mut attempt = 1;
+    while archive_path.exists() {
+        archive_path = backups_dir.join(format!("{}-{}.tar.gz", stem, attempt));
+        attempt += 1;
+    }
+
+    let running = is_server_running(config).await;
+    if running && let Err(e) = flush_world(config).await {
+        println!("⚠️  Could not flush world through RCON, backup may be inconsistent: {}", e);
+    }
+
+    let source = config.path.clone();
+    let destination = archive_path.clone();
+    // An interrupted backup must not stay behind looking like a complete one
+    let result = crate::cancel::run_blocking(move |cancel| {
+        write_archive(&source, &destination, include, embedded_config.as_deref(), cancel)
+    })
+    .await;
+
+    if running {
+        let _ = crate::rcon::send_command(config, "save-on").await;
+    }
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&archive_path);
+        return Err(e);
+    }
+
+    let size = fs::metadata(&archive_path)?.len();
+    println!("✅ Backup created: {}", archive_path.display());
+
+    run_hook(config, HookEvent::BackupComplete, &[
+        ("ANVIL_BACKUP_PATH", archive_path.display().to_string()),
+        ("ANVIL_BACKUP_SIZE", size.to_string())
+    ]).await;
+
+    Ok(archive_path)
+}
+
+pub async fn flush_world(config: &ServerConfig) -> Result<()> {
+    let mut client = crate::rcon::connect(config).await?;
+    client.command("save-off").await?;
+    client.command("save-all flush").await?;
+    Ok(())
+}
+
+pub fn archive_world(config: &ServerConfig) -> Result<Option<PathBuf>> {
+    let worlds = crate::server::all_world_dir_names(config);
+    if worlds.is_empty() {
+        return Ok(None);
+    }
+
+    let backups_dir = get_backups_dir(&config.name);
+    fs::create_dir_all(&backups_dir)?;
+    let archive_path = backups_dir.join(format!(
+        "{}-world-{}.tar.gz",
+        config.name,
+        Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    println!("💾 Archiving {} before deletion...", worlds.join(", "));
+    let include = |name: &str| worlds.iter().any(|world| world == name);
+    if let Err(e) = write_archive(&config.path, &archive_path, include, None, &CancelFlag::default()) {
+        let _ = fs::remove_file(&archive_path);
+        return Err(e);
+    }
+
+    Ok(Some(archive_path))
+}
+
+fn write_archive(
+    source: &Path,
+    destination: &Path,
+    include: impl Fn(&str) -> bool,
+    embedded_config: Option<&[u8]>,
+    cancel: &CancelFlag,
+) -> Result<()> {
+    // The console FIFO of a running server would block the archiver when opened
+    let include = |name: &str| name != crate::console::CONSOLE_PIPE && include(name);
+
+    // Region files are already compressed, so the archive ends up close to the size of what goes in
+    let mut expected = 0;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        if include(&entry.file_name().to_string_lossy()) {
+            expected += get_directory_size(&entry.path())?;
+        }
+    }
+    let name = destination.file_name().unwrap_or_default().to_string_lossy();
+    crate::disk::ensure_free_space(destination, expected, &format!("backup {}", name))?;
+
+    let file = Cancellable::new(fs::File::create(destination)?, cancel);
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if !include(&name.to_string_lossy()) {
+            continue;
+        }
+
+        // Follows links so worlds relocated with anvil layout are archived with their contents
+        if entry.path().is_dir() {
+            builder.append_dir_all(&name, entry.path())?;
+        } else {
+            builder.append_path_with_name(entry.path(), &name)?;
+        }
+    }
+
+    if let Some(contents) = embedded_config {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(Local::now().timestamp().max(0) as u64);
+        builder.append_data(&mut header, SNAPSHOT_CONFIG_ENTRY, contents)?;
+    }
+
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+async fn restore_backup(config: &ServerConfig, backup: &str, worlds: &[String], force: bool) -> Result<()> {
+    if is_server_running(config).await {
+        return Err(anyhow!("Server '{}' is running, stop it before restoring a backup", config.name));
+    }
+
+    let archive_path = [PathBuf::from(backup), get_backups_dir(&config.name).join(backup)]
+        .into_iter()
+        .find(|path| path.is_file())
+        .ok_or_else(|| anyhow!("Backup '{}' not found, see anvil backup -s {} list", backup, config.name))?;
+
+    let contents = archive_top_level(&archive_path)?;
+    let selected: Vec<String> = if worlds.is_empty() {
+        contents.keys().cloned().collect()
+    } else {
+        if let Some(missing) = worlds.iter().find(|world| !contents.contains_key(*world)) {
+            return Err(anyhow!("{} does not contain '{}'", archive_path.display(), missing));
+        }
+        worlds.to_vec()
+    };
+
+    println!("♻️  Restoring from {}:", archive_path.display());
+    for name in &selected {
+        println!("  • {}", name);
+    }
+
+    // The backed up copies are unpacked before the live ones are removed, so both have to fit
+    let needed = selected.iter().filter_map(|name| contents.get(name)).sum();
+    crate::disk::ensure_free_space(&config.path, needed, "restoring the backup")?;
+
+    if !force {
+        let confirmed = Confirm::new()
+            .with_prompt(format!("Replace these in '{}' with the backed up copies?", config.name))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            println!("❌ Restore cancelled");
+            return Ok(());
+        }
+    }
+
+    safety_snapshot(config, "restore").await?;
+
+    // Extracting next to the live data first means a broken archive leaves the server untouched
+    let staging = config.path.join(RESTORE_STAGING_DIR);
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    fs::create_dir_all(&staging)?;
+
+    let extracted = {
+        let (archive_path, staging, selected) = (archive_path.clone(), staging.clone(), selected.clone());
+        crate::cancel::run_blocking(move |cancel| extract_selected(&archive_path, &staging, &selected, cancel)).await
+    };
+    if let Err(e) = extracted {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    let embedded_config = staging.join(SNAPSHOT_CONFIG_ENTRY);
+    if embedded_config.exists() {
+        let snapshot: ServerConfig = serde_json::from_str(&fs::read_to_string(&embedded_config)?)?;
+        // The server may have been moved since, so only the contents are rolled back
+        save_server_config(&ServerConfig { name: config.name.clone(), path: config.path.clone(), ..snapshot })?;
+        fs::remove_file(&embedded_config)?;
+        println!("✅ Restored the anvil config recorded in the snapshot");
+    }
+
+    let restored: Vec<&String> = selected.iter().filter(|name| *name != SNAPSHOT_CONFIG_ENTRY).collect();
+    for name in &restored {
+        let mut target = config.path.join(name);
+        // A relocated world is restored where its link points, which may be another disk
+        if target.is_symlink() {
+            target = fs::read_link(&target)?;
+        }
+        if target.is_dir() {
+            fs::remove_dir_all(&target)?;
+        } else if target.exists() {
+            fs::remove_file(&target)?;
+        }
+        crate::trash::move_path(&staging.join(name), &target)?;
+    }
+    fs::remove_dir_all(&staging)?;
+
+    println!("✅ Restored {} item(s) into '{}'", restored.len(), config.name);
+    Ok(())
+}
+
+fn top_level_name(path: &Path) -> Option<String> {
+    match path.components().next()? {
+        Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+        _ => None
+    }
+}
+
+// Top-level entries of the archive with their unpacked size
+fn archive_top_level(archive_path: &Path) -> Result<BTreeMap<String, u64>> {
+    let mut archive = tar::Archive::new(GzDecoder::new(fs::File::open(archive_path)?));
+    let mut names = BTreeMap::new();
+
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if let Some(name) = top_level_name(&entry.path()?) {
+            *names.entry(name).or_insert(0) += entry.header().size()?;
+        }
+    }
+
+    Ok(names)
+}
+
+fn extract_selected(archive_path: &Path, destination: &Path, selected: &[String], cancel: &CancelFlag) -> Result<()> {
+    let file = Cancellable::new(fs::File::open(archive_path)?, cancel);
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let wanted = top_level_name(&entry.path()?).is_some_and(|name| selected.contains(&name));
+        if wanted {
+            entry.unpack_in(destination)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn list_backups(server_name: &str) -> Result<()> {
+    let backups_dir = get_backups_dir(server_name);
+    if !backups_dir.exists() {
+        println!("No backups found for server '{}'", server_name);
+        return Ok(());
+    }
+
+    let mut backups: Vec<_> = fs::read_dir(&backups_dir)?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tar.gz"))
+        .collect();
+    backups.sort_by_key(|entry| entry.file_name());
+
+    println!("💾 Backups for server '{}':", server_name);
+    for entry in backups {
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        println!("  • {} ({})", entry.file_name().to_string_lossy(), crate::server::format_bytes(size));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,267 @@
+use crate::rcon::RconClient;
+use crate::server::{
+    ensure_running, format_bytes, format_duration_ms, get_anvil_dir, is_server_running, java_version, level_name,
+    load_server_config, start_script_jvm_flags, stop_server, ServerConfig,
+};
+use anyhow::{anyhow, Result};
+use chrono::{Local, Utc};
+use serde::Serialize;
+use std::fs;
+use std::time::{Duration, Instant};
+
+const CHUNK_POLL_SECS: u64 = 5;
+const CHUNK_TIMEOUT_SECS: u64 = 3600;
+
+#[derive(Serialize)]
+struct BenchReport {
+    server: String,
+    server_type: String,
+    version: String,
+    ran_at: chrono::DateTime<Utc>,
+    host: HostInfo,
+    ram_gb: u8,
+    jvm_flags: Vec<String>,
+    startup_ms: u64,
+    idle_mspt: Option<f64>,
+    resident_bytes: Option<u64>,
+    heap_used_bytes: Option<u64>,
+    chunk_stress: Option<ChunkStress>
+}
+
+#[derive(Serialize)]
+struct HostInfo {
+    os: String,
+    arch: String,
+    cpus: usize,
+    java_version: Option<String>
+}
+
+#[derive(Serialize)]
+struct ChunkStress {
+    radius: u32,
+    chunks: u64,
+    seconds: f64,
+    chunks_per_second: f64,
+    mspt: Option<f64>
+}
+
+pub async fn run_benchmark(server_name: &str, ram: u8, idle_secs: u64, chunk_radius: Option<u32>) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    if is_server_running(&config).await {
+        return Err(anyhow!("Server '{}' is running, stop it first so the startup can be measured", server_name));
+    }
+    if chunk_radius.is_some() && config.server_type != "paper" {
+        return Err(anyhow!("The chunk generation stress test uses Chunky, which needs a Paper server"));
+    }
+    if chunk_radius.is_some() && !crate::plugin::is_plugin_installed(&config, "Chunky") {
+        return Err(anyhow!("The chunk generation stress test needs Chunky: anvil plugin add {} Chunky", server_name));
+    }
+
+    println!("🏁 Benchmarking '{}' with {}GB of RAM", server_name, ram);
+    let started_at = Utc::now();
+    let launch = Instant::now();
+    let (mut client, _) = ensure_running(&config, ram).await?;
+
+    // start records the time until the "Done" line, which excludes the RCON handshake
+    let startup_ms = load_server_config(server_name)?
+        .startup_history
+        .last()
+        .filter(|record| record.started_at >= started_at)
+        .map(|record| record.duration_ms)
+        .unwrap_or_else(|| launch.elapsed().as_millis() as u64);
+
+    let result = measure(&config, &mut client, idle_secs, chunk_radius).await;
+    println!("🛑 Stopping '{}'...", server_name);
+    stop_server(&config).await?;
+    let (idle_mspt, resident_bytes, heap_used_bytes, chunk_stress) = result?;
+
+    let report = BenchReport {
+        server: config.name.clone(),
+        server_type: config.server_type.clone(),
+        version: config.version.clone(),
+        ran_at: started_at,
+        host: HostInfo {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpus: std::thread::available_parallelism().map(|cpus| cpus.get()).unwrap_or(1),
+            java_version: java_version().await
+        },
+        ram_gb: ram,
+        jvm_flags: start_script_jvm_flags(&config.path).unwrap_or_default(),
+        startup_ms,
+        idle_mspt,
+        resident_bytes,
+        heap_used_bytes,
+        chunk_stress
+    };
+
+    print_report(&report);
+
+    let reports_dir = get_anvil_dir().join("bench");
+    fs::create_dir_all(&reports_dir)?;
+    let path = reports_dir.join(format!("{}-{}.json", server_name, Local::now().format("%Y%m%d-%H%M%S")));
+    fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+    println!("💾 Saved the report to {}", path.display());
+
+    Ok(())
+}
+
+type Measurements = (Option<f64>, Option<u64>, Option<u64>, Option<ChunkStress>);
+
+async fn measure(
+    config: &ServerConfig,
+    client: &mut RconClient,
+    idle_secs: u64,
+    chunk_radius: Option<u32>,
+) -> Result<Measurements> {
+    println!("⏳ Letting the server idle for {}s...", idle_secs);
+    tokio::time::sleep(Duration::from_secs(idle_secs)).await;
+
+    let idle_mspt = query_mspt(client).await;
+    let pid = crate::debug::jvm_pid(config).await.ok();
+    let resident_bytes = pid.and_then(resident_memory);
+    let heap_used_bytes = match pid {
+        Some(pid) => heap_used(pid).await,
+        None => None
+    };
+
+    let chunk_stress = match chunk_radius {
+        Some(radius) => Some(chunk_stress(config, client, radius).await?),
+        None => None
+    };
+
+    Ok((idle_mspt, resident_bytes, heap_used_bytes, chunk_stress))
+}
+
+async fn chunk_stress(config: &ServerConfig, client: &mut RconClient, radius: u32) -> Result<ChunkStress> {
+    // Chunks are generated far from spawn so earlier runs or player builds do not skew the result
+    let world = level_name(&config.path);
+    let center = 100_000 + radius as i64;
+    println!("⛏️  Generating chunks within {} blocks of {}, {} in '{}'...", radius, center, center, world);
+    println!("💡 The generated chunks stay in the world; benchmark a copy if disk space matters");
+
+    for command in [
+        format!("chunky world {}", world),
+        format!("chunky center {} {}", center, center),
+        format!("chunky radius {}", radius),
+        "chunky start".to_string()
+    ] {
+        client.command(&command).await?;
+    }
+
+    let started = Instant::now();
+    let mut mspt_samples = Vec::new();
+    loop {
+        tokio::time::sleep(Duration::from_secs(CHUNK_POLL_SECS)).await;
+        if let Some(mspt) = query_mspt(client).await {
+            mspt_samples.push(mspt);
+        }
+
+        let progress = strip_colors(&client.command("chunky progress").await?);
+        if !progress.contains("Processed") {
+            break;
+        }
+        if started.elapsed().as_secs() > CHUNK_TIMEOUT_SECS {
+            client.command("chunky cancel").await?;
+            return Err(anyhow!("Chunk generation did not finish within {}s", CHUNK_TIMEOUT_SECS));
+        }
+    }
+
+    let seconds = started.elapsed().as_secs_f64();
+    let side = (2 * radius as u64).div_ceil(16) + 1;
+    let chunks = side * side;
+
+    Ok(ChunkStress {
+        radius,
+        chunks,
+        seconds,
+        chunks_per_second: chunks as f64 / seconds,
+        mspt: (!mspt_samples.is_empty()).then(|| mspt_samples.iter().sum::<f64>() / mspt_samples.len() as f64)
+    })
+}
+
+async fn query_mspt(client: &mut RconClient) -> Option<f64> {
+    // Paper: "Server tick times (avg/min/max) from last 5s, 10s, 1m:" followed by "◴ 1.2/0.5/3.4, ..."
+    if let Ok(response) = client.command("mspt").await {
+        let response = strip_colors(&response);
+        let average = response
+            .lines()
+            .nth(1)
+            .and_then(|line| line.split(',').next())
+            .and_then(|group| group.trim_start_matches(|c: char| !c.is_ascii_digit()).split('/').next())
+            .and_then(|value| value.trim().parse().ok());
+        if average.is_some() {
+            return average;
+        }
+    }
+
+    // Vanilla 1.20.3+: "Average time per tick: 1.2ms (Target: 50.0ms)"
+    let response = strip_colors(&client.command("tick query").await.ok()?);
+    let (_, rest) = response.split_once("Average time per tick: ")?;
+    rest.split("ms").next()?.trim().parse().ok()
+}
+
+pub fn strip_colors(text: &str) -> String {
+    let mut output = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            chars.next();
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+#[cfg(target_os = "linux")]
+fn resident_memory(pid: u32) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn resident_memory(_pid: u32) -> Option<u64> {
+    None
+}
+
+async fn heap_used(pid: u32) -> Option<u64> {
+    // G1 reports " garbage-first heap   total 262144K, used 23456K [0x...", generational collectors one line per generation
+    let output = tokio::process::Command::new("jcmd").args([&pid.to_string(), "GC.heap_info"]).output().await.ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let kilobytes: Vec<u64> = text
+        .lines()
+        .filter(|line| line.contains(" total ") && !line.trim_start().starts_with("Metaspace"))
+        .filter_map(|line| line.split_once("used ")?.1.split('K').next()?.trim().parse().ok())
+        .collect();
+    (!kilobytes.is_empty()).then(|| kilobytes.iter().sum::<u64>() * 1024)
+}
+
+fn print_report(report: &BenchReport) {
+    let unknown = || "unknown".to_string();
+
+    println!("\n📊 Benchmark results for '{}':", report.server);
+    println!(
+        " - Host: {} {} with {} CPU(s), Java {}",
+        report.host.os,
+        report.host.arch,
+        report.host.cpus,
+        report.host.java_version.clone().unwrap_or_else(unknown)
+    );
+    println!(" - Startup: {}", format_duration_ms(report.startup_ms));
+    println!(" - Idle MSPT: {}", report.idle_mspt.map(|mspt| format!("{:.2}ms", mspt)).unwrap_or_else(unknown));
+    println!(" - Resident memory: {}", report.resident_bytes.map(format_bytes).unwrap_or_else(unknown));
+    println!(" - Heap in use: {}", report.heap_used_bytes.map(format_bytes).unwrap_or_else(unknown));
+
+    if let Some(stress) = &report.chunk_stress {
+        println!(
+            " - Chunk generation: {} chunks in {:.0}s ({:.1} chunks/s, {} MSPT)",
+            stress.chunks,
+            stress.seconds,
+            stress.chunks_per_second,
+            stress.mspt.map(|mspt| format!("{:.2}ms", mspt)).unwrap_or_else(unknown)
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::plugin::{install_project, require_plugin_support};
+use crate::server::load_server_config;
+use anyhow::Result;
+use dialoguer::Select;
+use std::io::IsTerminal;
+
+const PAGE_SIZE: u32 = 10;
+
+pub async fn browse_plugins(
+    server_name: &str,
+    query: Option<&str>,
+    category: Option<&str>,
+    game_version: Option<&str>,
+) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    require_plugin_support(&config)?;
+
+    let game_version = game_version.unwrap_or(&config.version);
+    if game_version != config.version {
+        println!(
+            "⚠️  Browsing plugins for Minecraft {}, but '{}' runs {}; installs pick the release for {}",
+            game_version, server_name, config.version, config.version
+        );
+    }
+
+    let interactive = std::io::stdout().is_terminal();
+    let mut page = 0;
+
+    loop {
+        let results = crate::api::modrinth::browse_projects(
+            query,
+            category,
+            game_version,
+            page * PAGE_SIZE,
+            PAGE_SIZE,
+        ).await?;
+
+        if results.hits.is_empty() {
+            println!("❌ No plugins found for Minecraft {}", game_version);
+            return Ok(());
+        }
+
+        let pages = results.total_hits.div_ceil(PAGE_SIZE).max(1);
+        println!(
+            "🔍 Modrinth plugins for Minecraft {} - page {}/{} ({} results)",
+            game_version, page + 1, pages, results.total_hits
+        );
+
+        let mut items: Vec<String> = results
+            .hits
+            .iter()
+            .map(|hit| format!(
+                "{} ({} downloads) - {}",
+                hit.title,
+                format_count(hit.downloads),
+                truncate(&hit.description, 60)
+            ))
+            .collect();
+
+        if !interactive {
+            for item in &items {
+                println!("  • {}", item);
+            }
+            return Ok(());
+        }
+
+        let has_next = page + 1 < pages;
+        if has_next {
+            items.push("Next page ▶".to_string());
+        }
+        if page > 0 {
+            items.push("◀ Previous page".to_string());
+        }
+
+        let selection = Select::new()
+            .with_prompt("Pick a plugin to install (Esc to quit)")
+            .items(&items)
+            .default(0)
+            .interact_opt()?;
+
+        let Some(index) = selection else {
+            return Ok(());
+        };
+
+        if let Some(hit) = results.hits.get(index) {
+            return install_project(server_name, "modrinth", &hit.project_id, &hit.title, None, None, false).await;
+        }
+
+        if has_next && index == results.hits.len() {
+            page += 1;
+        } else {
+            page -= 1;
+        }
+    }
+}
+
+fn format_count(count: u64) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", count as f64 / 1_000.0),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0)
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max - 1).collect();
+    format!("{}…", truncated.trim_end())
+}
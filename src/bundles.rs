@@ -0,0 +1,77 @@
+use crate::plugin::ensure_plugin;
+use anyhow::{anyhow, Result};
+
+pub struct Bundle {
+    pub name: &'static str,
+    pub revision: u32,
+    pub description: &'static str,
+    pub plugins: &'static [&'static str]
+}
+
+pub const BUNDLES: &[Bundle] = &[
+    Bundle {
+        name: "essentials",
+        revision: 1,
+        description: "Core commands, economy API and permissions",
+        plugins: &["EssentialsX", "Vault", "LuckPerms"]
+    },
+    Bundle {
+        name: "cross-version",
+        revision: 1,
+        description: "Let newer and older clients join the server",
+        plugins: &["ViaVersion", "ViaBackwards"]
+    },
+    Bundle {
+        name: "protection",
+        revision: 1,
+        description: "World editing, region protection and rollback",
+        plugins: &["WorldEdit", "WorldGuard", "CoreProtect"]
+    },
+    Bundle {
+        name: "performance",
+        revision: 1,
+        description: "Profiling and world pregeneration",
+        plugins: &["spark", "Chunky"]
+    }
+];
+
+pub fn find_bundle(name: &str) -> Result<&'static Bundle> {
+    BUNDLES
+        .iter()
+        .find(|bundle| bundle.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            let available: Vec<&str> = BUNDLES.iter().map(|bundle| bundle.name).collect();
+            anyhow!("Unknown bundle: {} (available: {})", name, available.join(", "))
+        })
+}
+
+pub async fn add_bundle(server_name: &str, bundle_name: &str) -> Result<()> {
+    let bundle = find_bundle(bundle_name)?;
+
+    println!("📦 Installing bundle '{}' (rev {}): {}", bundle.name, bundle.revision, bundle.plugins.join(", "));
+
+    let mut failed = Vec::new();
+
+    for plugin in bundle.plugins {
+        if let Err(e) = ensure_plugin(server_name, plugin).await {
+            println!("❌ Failed to install {}: {}", plugin, e);
+            failed.push(*plugin);
+        }
+    }
+
+    if !failed.is_empty() {
+        println!("⚠️  Bundle '{}' partially installed", bundle.name);
+        return Err(anyhow!("Failed to install: {}", failed.join(", ")));
+    }
+
+    println!("🎉 Bundle '{}' installed on server '{}'", bundle.name, server_name);
+    Ok(())
+}
+
+pub fn list_bundles() {
+    println!("📦 Available plugin bundles:");
+    for bundle in BUNDLES {
+        println!("  • {} (rev {}) - {}", bundle.name, bundle.revision, bundle.description);
+        println!("      {}", bundle.plugins.join(", "));
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::{anyhow, Result};
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+// Set when Ctrl-C interrupts blocking work, which checks it between files and on every read or write
+#[derive(Clone, Default)]
+pub struct CancelFlag(Arc<AtomicBool>);
+
+impl CancelFlag {
+    pub fn check(&self) -> io::Result<()> {
+        if self.0.load(Ordering::Relaxed) {
+            return Err(io::Error::other("Interrupted"));
+        }
+        Ok(())
+    }
+
+    fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+// A reader or writer that fails once its flag is set, so tar and gzip stop part-way through an entry
+pub struct Cancellable<T> {
+    inner: T,
+    flag: CancelFlag
+}
+
+impl<T> Cancellable<T> {
+    pub fn new(inner: T, flag: &CancelFlag) -> Self {
+        Cancellable { inner, flag: flag.clone() }
+    }
+}
+
+impl<T: Read> Read for Cancellable<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.flag.check()?;
+        self.inner.read(buf)
+    }
+}
+
+impl<T: Write> Write for Cancellable<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.flag.check()?;
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// Runs blocking work until it finishes or Ctrl-C; on Ctrl-C the work is told to stop and waited for,
+// so the caller's cleanup never races files the work still has open
+pub async fn run_blocking<T: Send + 'static>(work: impl FnOnce(&CancelFlag) -> Result<T> + Send + 'static) -> Result<T> {
+    let flag = CancelFlag::default();
+    let mut task = tokio::task::spawn_blocking({
+        let flag = flag.clone();
+        move || work(&flag)
+    });
+
+    tokio::select! {
+        result = &mut task => result?,
+        _ = tokio::signal::ctrl_c() => {
+            flag.cancel();
+            let _ = task.await;
+            Err(anyhow!("Interrupted"))
+        }
+    }
+}
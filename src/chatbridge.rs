@@ -0,0 +1,210 @@
+use crate::api::discord;
+use crate::api::mojang::is_valid_name;
+use crate::server::{get_all_servers, is_server_running, load_server_config, save_server_config, ServerConfig};
+use crate::ChatBridgeAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
+
+const BRIDGE_TICK_SECS: u64 = 3;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChatBridgeConfig {
+    // The Discord channel replies are read from, None when chat is only relayed out
+    #[serde(default)]
+    pub channel_id: Option<String>
+}
+
+// What the daemon has relayed so far for one running server
+struct BridgeState {
+    log_offset: u64,
+    last_message: Option<String>
+}
+
+enum ChatEvent {
+    Chat { player: String, message: String },
+    Joined(String),
+    Left(String)
+}
+
+pub async fn handle_chat_bridge_action(action: ChatBridgeAction) -> Result<()> {
+    match action {
+        ChatBridgeAction::Enable { server, webhook, bot_token, channel } => {
+            let mut config = load_server_config(&server)?;
+            if !webhook.starts_with("https://") || !webhook.contains("/api/webhooks/") {
+                return Err(anyhow!("'{}' is not a Discord webhook URL (Channel settings → Integrations → Webhooks)", webhook));
+            }
+            if let Some(channel) = &channel
+                && !channel.chars().all(|c| c.is_ascii_digit())
+            {
+                return Err(anyhow!("The channel must be a numeric channel id, enable Developer Mode and use 'Copy Channel ID'"));
+            }
+
+            crate::secrets::set_discord_secrets(&server, Some(webhook), bot_token)?;
+            config.chat_bridge = Some(ChatBridgeConfig { channel_id: channel.clone() });
+            save_server_config(&config)?;
+
+            println!("✅ Chat from '{}' will be relayed to Discord", server);
+            match channel {
+                Some(channel) => {
+                    println!("↩️  Replies in channel {} are sent back into the game", channel);
+                    println!("💡 The bot needs the Message Content intent and permission to read the channel");
+                }
+                None => println!("💡 Add --bot-token and --channel to relay Discord replies back into the game")
+            }
+            println!("💡 The chat bridge only runs while 'anvil daemon' is running");
+        }
+        ChatBridgeAction::Disable { server } => {
+            let mut config = load_server_config(&server)?;
+            if config.chat_bridge.take().is_none() {
+                return Err(anyhow!("The chat bridge is not enabled for '{}'", server));
+            }
+            save_server_config(&config)?;
+            crate::secrets::set_discord_secrets(&server, None, None)?;
+            println!("✅ Disabled the chat bridge for '{}'", server);
+        }
+    }
+    Ok(())
+}
+
+// Runs for the lifetime of the daemon, relaying chat of every running server with a bridge
+pub async fn run_chat_bridges() {
+    let mut states: HashMap<String, BridgeState> = HashMap::new();
+
+    loop {
+        match get_all_servers() {
+            Ok(servers) => {
+                for config in servers {
+                    let Some(bridge) = config.chat_bridge.clone() else {
+                        states.remove(&config.name);
+                        continue;
+                    };
+                    // Nothing is queued while the server is down, a restart picks up from the current chat
+                    if !is_server_running(&config).await {
+                        states.remove(&config.name);
+                        continue;
+                    }
+
+                    let state = states.entry(config.name.clone()).or_insert_with(|| BridgeState {
+                        log_offset: log_length(&config),
+                        last_message: None
+                    });
+                    if let Err(e) = relay(&config, &bridge, state).await {
+                        println!("⚠️  Chat bridge for '{}': {}", config.name, e);
+                    }
+                }
+            }
+            Err(e) => println!("⚠️  Chat bridge error: {}", e)
+        }
+
+        tokio::time::sleep(Duration::from_secs(BRIDGE_TICK_SECS)).await;
+    }
+}
+
+async fn relay(config: &ServerConfig, bridge: &ChatBridgeConfig, state: &mut BridgeState) -> Result<()> {
+    let secrets = crate::secrets::server_secrets(&config.name)?;
+    let webhook = secrets
+        .discord_webhook
+        .ok_or_else(|| anyhow!("no webhook stored (run anvil chat-bridge enable again)"))?;
+
+    for line in new_log_lines(config, state)? {
+        let Some(event) = parse_line(&line) else {
+            continue;
+        };
+        match event {
+            ChatEvent::Chat { player, message } => {
+                let avatar = format!("https://mc-heads.net/avatar/{}", player);
+                discord::post_webhook(&webhook, &player, Some(&avatar), &message).await?;
+            }
+            ChatEvent::Joined(player) => {
+                discord::post_webhook(&webhook, &config.name, None, &format!("➡️ **{}** joined the game", player)).await?;
+            }
+            ChatEvent::Left(player) => {
+                discord::post_webhook(&webhook, &config.name, None, &format!("⬅️ **{}** left the game", player)).await?;
+            }
+        }
+    }
+
+    let (Some(channel), Some(bot_token)) = (&bridge.channel_id, &secrets.discord_bot_token) else {
+        return Ok(());
+    };
+    let messages = discord::channel_messages(bot_token, channel, state.last_message.as_deref()).await?;
+    let first_poll = state.last_message.is_none();
+    if let Some(last) = messages.last() {
+        state.last_message = Some(last.id.clone());
+    }
+    // The first poll only finds where the channel is, older messages were never meant for the game
+    if first_poll {
+        return Ok(());
+    }
+
+    for message in messages {
+        // Webhook messages include everything relayed from the game, relaying them back would echo
+        if message.author.bot || message.webhook_id.is_some() || message.content.trim().is_empty() {
+            continue;
+        }
+        let text = message.content.replace('\n', " ");
+        let component = json!([
+            "",
+            { "text": "[Discord] ", "color": "blue" },
+            { "text": format!("<{}> ", message.author.display_name()) },
+            { "text": text }
+        ]);
+        let command = format!("tellraw @a {}", component);
+        if crate::rcon::send_command(config, &command).await.is_err() {
+            crate::console::send(config, &command).await?;
+        }
+    }
+    Ok(())
+}
+
+fn log_path(config: &ServerConfig) -> std::path::PathBuf {
+    config.path.join("logs").join("latest.log")
+}
+
+fn log_length(config: &ServerConfig) -> u64 {
+    std::fs::metadata(log_path(config)).map(|metadata| metadata.len()).unwrap_or(0)
+}
+
+// Complete lines appended to latest.log since the last tick
+fn new_log_lines(config: &ServerConfig, state: &mut BridgeState) -> Result<Vec<String>> {
+    let Ok(mut file) = File::open(log_path(config)) else {
+        return Ok(Vec::new());
+    };
+    // The server rotates latest.log on every start, a shorter file is a new one
+    if file.metadata()?.len() < state.log_offset {
+        state.log_offset = 0;
+    }
+
+    file.seek(SeekFrom::Start(state.log_offset))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    // A line still being written is left for the next tick
+    let Some(end) = bytes.iter().rposition(|byte| *byte == b'\n') else {
+        return Ok(Vec::new());
+    };
+    state.log_offset += end as u64 + 1;
+
+    Ok(String::from_utf8_lossy(&bytes[..end]).lines().map(str::to_string).collect())
+}
+
+fn parse_line(line: &str) -> Option<ChatEvent> {
+    // "[12:34:56] [Server thread/INFO]: <Steve> hi"; unsigned chat on 1.19+ carries a "[Not Secure]" prefix
+    let (_, message) = line.split_once("]: ")?;
+    let message = message.strip_prefix("[Not Secure] ").unwrap_or(message);
+
+    if let Some((player, text)) = message.strip_prefix('<').and_then(|rest| rest.split_once("> "))
+        && is_valid_name(player)
+    {
+        return Some(ChatEvent::Chat { player: player.to_string(), message: text.to_string() });
+    }
+    if let Some(player) = message.strip_suffix(" joined the game").filter(|player| is_valid_name(player)) {
+        return Some(ChatEvent::Joined(player.to_string()));
+    }
+    let player = message.strip_suffix(" left the game").filter(|player| is_valid_name(player))?;
+    Some(ChatEvent::Left(player.to_string()))
+}
@@ -0,0 +1,103 @@
+use crate::plugin::{ensure_plugin, require_plugin_support};
+use crate::server::{load_server_config, ServerConfig};
+use crate::version::{compare_versions, is_release};
+use crate::CompatAction;
+use anyhow::{anyhow, Result};
+use std::cmp::Ordering;
+
+const VIAVERSION_MIN_SERVER: &str = "1.8";
+const VIABACKWARDS_MIN_SERVER: &str = "1.10";
+const VIABACKWARDS_MIN_CLIENT: &str = "1.10";
+
+pub async fn handle_compat_action(action: CompatAction) -> Result<()> {
+    match action {
+        CompatAction::Enable { server, no_backwards } => {
+            enable_compat(&server, !no_backwards).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn enable_compat(server_name: &str, backwards: bool) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    require_plugin_support(&config)?;
+
+    if compare_versions(&config.version, VIAVERSION_MIN_SERVER) == Ordering::Less {
+        return Err(anyhow!(
+            "ViaVersion requires Minecraft {} or newer, server '{}' runs {}",
+            VIAVERSION_MIN_SERVER, server_name, config.version
+        ));
+    }
+
+    println!("🔗 Enabling cross-version support for '{}'", server_name);
+
+    ensure_plugin(server_name, "ViaVersion").await?;
+
+    let install_backwards = backwards
+        && compare_versions(&config.version, VIABACKWARDS_MIN_SERVER) != Ordering::Less;
+    if backwards && !install_backwards {
+        println!(
+            "ℹ️  Skipping ViaBackwards: it only supports servers running {} or newer",
+            VIABACKWARDS_MIN_SERVER
+        );
+    }
+    if install_backwards {
+        ensure_plugin(server_name, "ViaBackwards").await?;
+    }
+
+    let config = load_server_config(server_name)?;
+    verify_protocol_range(&config, install_backwards).await?;
+
+    println!("💡 Restart the server to load the new plugins");
+
+    Ok(())
+}
+
+async fn verify_protocol_range(config: &ServerConfig, backwards: bool) -> Result<()> {
+    let newest_client = match supported_client_versions(config, "ViaVersion").await? {
+        Some(versions) => versions.last().cloned(),
+        None => {
+            println!("⚠️  ViaVersion is not tracked by anvil, cannot verify its supported versions");
+            None
+        }
+    };
+
+    let latest_release = crate::api::vanilla::get_latest_version().await?;
+
+    println!("📋 Supported client versions:");
+    println!(" - Server version: {}", config.version);
+
+    if let Some(newest) = &newest_client {
+        println!(" - Newer clients: up to {}", newest);
+
+        if compare_versions(newest, &latest_release) == Ordering::Less {
+            println!(
+                "⚠️  The installed ViaVersion only supports clients up to {}, but {} is out - players on {} will not be able to join",
+                newest, latest_release, latest_release
+            );
+        } else {
+            println!("✅ Players on the latest release ({}) can join", latest_release);
+        }
+    }
+
+    if backwards {
+        println!(" - Older clients: down to {}", VIABACKWARDS_MIN_CLIENT);
+    }
+
+    Ok(())
+}
+
+async fn supported_client_versions(config: &ServerConfig, plugin_name: &str) -> Result<Option<Vec<String>>> {
+    let Some(installed) = config.plugins.iter().find(|p| p.name.eq_ignore_ascii_case(plugin_name)) else {
+        return Ok(None);
+    };
+
+    let version = crate::api::modrinth::get_version(&installed.version_id).await?;
+    let mut versions: Vec<String> = version.game_versions
+        .into_iter()
+        .filter(|v| is_release(v))
+        .collect();
+    versions.sort_by(|a, b| compare_versions(a, b));
+
+    Ok(Some(versions))
+}
@@ -0,0 +1,511 @@
+use crate::plugin::find_plugin_jars;
+use crate::properties::PropertiesFile;
+use crate::server::{
+    apply_stack_flags, load_server_config, read_server_properties, save_server_config, validate_network, ServerConfig,
+};
+use crate::yaml::YamlDocument;
+use crate::ConfigAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub struct ConfigFile {
+    pub prefix: &'static str,
+    pub path: &'static str
+}
+
+const PROPERTIES_PREFIX: &str = "server.";
+// Settings anvil keeps in its own server config rather than in a file of the server
+const IP_STACK_KEY: &str = "anvil.ip-stack";
+// Paper reads per-world overrides of paper-world-defaults.yml from this file inside each world folder
+const PAPER_WORLD_CONFIG: &str = "paper-world.yml";
+const PAPER_WORLD_PREFIX: &str = "paper-world.";
+
+// A per-world key anvil owns; once applied, a different value in the file means someone edited it by hand
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WorldSetting {
+    pub value: String,
+    #[serde(default)]
+    pub applied: bool
+}
+
+pub const CONFIG_FILES: &[ConfigFile] = &[
+    ConfigFile { prefix: "paper", path: "config/paper-global.yml" },
+    ConfigFile { prefix: "paper-world", path: "config/paper-world-defaults.yml" },
+    ConfigFile { prefix: "spigot", path: "spigot.yml" },
+    ConfigFile { prefix: "bukkit", path: "bukkit.yml" }
+];
+
+const VANILLA_DEFAULTS: &[(&str, &str)] = &[
+    ("allow-flight", "false"),
+    ("allow-nether", "true"),
+    ("broadcast-console-to-ops", "true"),
+    ("broadcast-rcon-to-ops", "true"),
+    ("difficulty", "easy"),
+    ("enable-command-block", "false"),
+    ("enable-query", "false"),
+    ("enable-rcon", "false"),
+    ("enable-status", "true"),
+    ("enforce-secure-profile", "true"),
+    ("enforce-whitelist", "false"),
+    ("entity-broadcast-range-percentage", "100"),
+    ("force-gamemode", "false"),
+    ("function-permission-level", "2"),
+    ("gamemode", "survival"),
+    ("generate-structures", "true"),
+    ("hardcore", "false"),
+    ("hide-online-players", "false"),
+    ("level-name", "world"),
+    ("level-type", "minecraft:normal"),
+    ("max-players", "20"),
+    ("max-tick-time", "60000"),
+    ("max-world-size", "29999984"),
+    ("motd", "A Minecraft Server"),
+    ("network-compression-threshold", "256"),
+    ("online-mode", "true"),
+    ("op-permission-level", "4"),
+    ("player-idle-timeout", "0"),
+    ("prevent-proxy-connections", "false"),
+    ("pvp", "true"),
+    ("rate-limit", "0"),
+    ("require-resource-pack", "false"),
+    ("server-port", "25565"),
+    ("simulation-distance", "10"),
+    ("spawn-monsters", "true"),
+    ("spawn-protection", "16"),
+    ("sync-chunk-writes", "true"),
+    ("view-distance", "10"),
+    ("white-list", "false")
+];
+
+pub async fn handle_config_action(action: ConfigAction) -> Result<()> {
+    match action {
+        ConfigAction::Set { server, key, value, world: Some(world) } => {
+            set_world_config(&server, &world, &key, &value)?;
+        }
+        ConfigAction::Set { server, key, value, world: None } => {
+            set_config(&server, &key, &value)?;
+        }
+        ConfigAction::Get { server, key, world: Some(world) } => {
+            get_world_config(&server, &world, &key)?;
+        }
+        ConfigAction::Get { server, key, world: None } => {
+            get_config(&server, &key)?;
+        }
+        ConfigAction::Diff { server, other } => {
+            diff_config(&server, &other)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn resolve_key<'a>(config: &ServerConfig, key: &'a str) -> Result<(PathBuf, Vec<&'a str>)> {
+    let (prefix, path) = key
+        .split_once('.')
+        .ok_or_else(|| anyhow!("Config keys look like <file>.<path>, e.g. paper.chunk-loading-basic.player-max-chunk-send-rate"))?;
+
+    let file = CONFIG_FILES
+        .iter()
+        .find(|file| file.prefix == prefix)
+        .ok_or_else(|| {
+            let mut prefixes: Vec<&str> = CONFIG_FILES.iter().map(|file| file.prefix).collect();
+            prefixes.push("server");
+            anyhow!("Unknown config file '{}' (expected one of {})", prefix, prefixes.join(", "))
+        })?;
+
+    let file_path = config.path.join(file.path);
+    if !file_path.exists() {
+        return Err(anyhow!(
+            "{} does not exist yet - start the server once to generate it",
+            file.path
+        ));
+    }
+
+    Ok((file_path, path.split('.').collect()))
+}
+
+fn set_config(server_name: &str, key: &str, value: &str) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+
+    if key == IP_STACK_KEY {
+        let properties = PropertiesFile::load(&config.path.join("server.properties"))?;
+        validate_network(properties.get("server-ip").as_deref(), Some(value))?;
+        let previous = config.ip_stack.replace(value.to_string()).unwrap_or_else(|| "dual".to_string());
+        config.ip_stack = config.ip_stack.filter(|stack| stack != "dual");
+        apply_stack_flags(&config.path, config.ip_stack.as_deref())?;
+        save_server_config(&config)?;
+        println!("✅ {}: {} -> {}", key, previous, value);
+        crate::output::hint("server.restart_to_apply", &[]);
+        return Ok(());
+    }
+
+    let previous = if let Some(property) = key.strip_prefix(PROPERTIES_PREFIX) {
+        if property == "server-ip" {
+            validate_network(Some(value), config.ip_stack.as_deref())?;
+        }
+
+        let file_path = config.path.join("server.properties");
+        let mut properties = PropertiesFile::load(&file_path)?;
+        let previous = properties.set(property, value);
+        properties.save(&file_path)?;
+        previous
+    } else {
+        let (file_path, path) = resolve_key(&config, key)?;
+        let mut document = YamlDocument::parse(&fs::read_to_string(&file_path)?);
+        let previous = document.set(&path, value)?;
+        fs::write(&file_path, document.render())?;
+        previous
+    };
+
+    match previous {
+        Some(previous) => println!("✅ {}: {} -> {}", key, previous, value),
+        None => println!("✅ {}: {} (added)", key, value)
+    }
+    crate::output::hint("server.restart_to_apply", &[]);
+
+    Ok(())
+}
+
+fn get_config(server_name: &str, key: &str) -> Result<()> {
+    let config = load_server_config(server_name)?;
+
+    if key == IP_STACK_KEY {
+        println!("{} = {}", key, config.ip_stack.as_deref().unwrap_or("dual"));
+        return Ok(());
+    }
+
+    let value = if let Some(property) = key.strip_prefix(PROPERTIES_PREFIX) {
+        PropertiesFile::load(&config.path.join("server.properties"))?.get(property)
+    } else {
+        let (file_path, path) = resolve_key(&config, key)?;
+        YamlDocument::parse(&fs::read_to_string(&file_path)?).get(&path)
+    };
+
+    match value {
+        Some(value) => println!("{} = {}", key, redact(key, &value)),
+        None => println!("❌ {} is not set", key)
+    }
+
+    Ok(())
+}
+
+fn world_key<'a>(config: &ServerConfig, world: &str, key: &'a str) -> Result<Vec<&'a str>> {
+    if config.server_type != "paper" {
+        return Err(anyhow!("Per-world settings need a Paper server, '{}' is {}", config.name, config.server_type));
+    }
+
+    let worlds = crate::layout::world_names(config);
+    if !worlds.iter().any(|name| name == world) {
+        return Err(anyhow!("'{}' has no world '{}' (expected one of {})", config.name, world, worlds.join(", ")));
+    }
+
+    let key = key.strip_prefix(PAPER_WORLD_PREFIX).unwrap_or(key);
+    if key.is_empty() {
+        return Err(anyhow!("Per-world keys are paths in {}, e.g. entities.spawning.spawn-limits.monster", PAPER_WORLD_CONFIG));
+    }
+    Ok(key.split('.').collect())
+}
+
+fn set_world_config(server_name: &str, world: &str, key: &str, value: &str) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let path = world_key(&config, world, key)?;
+    let file_path = config.path.join(world).join(PAPER_WORLD_CONFIG);
+
+    let (previous, setting) = if file_path.exists() {
+        let mut document = YamlDocument::parse(&fs::read_to_string(&file_path)?);
+        let previous = document.set(&path, value)?;
+        fs::write(&file_path, document.render())?;
+        let value = document.get(&path).unwrap_or_else(|| value.to_string());
+        (previous, WorldSetting { value, applied: true })
+    } else {
+        (None, WorldSetting { value: value.to_string(), applied: false })
+    };
+
+    let applied = setting.applied;
+    config.world_settings.entry(world.to_string()).or_default().insert(path.join("."), setting);
+    save_server_config(&config)?;
+
+    let label = format!("{}/{}", world, path.join("."));
+    match previous {
+        Some(previous) => println!("✅ {}: {} -> {}", label, previous, value),
+        None => println!("✅ {}: {} (added)", label, value)
+    }
+    if applied {
+        crate::output::hint("server.restart_to_apply", &[]);
+    } else {
+        println!(
+            "💡 Paper creates {}/{} on the next start; anvil applies the setting before the start after that",
+            world, PAPER_WORLD_CONFIG
+        );
+    }
+
+    Ok(())
+}
+
+fn get_world_config(server_name: &str, world: &str, key: &str) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let path = world_key(&config, world, key)?;
+    let label = format!("{}/{}", world, path.join("."));
+
+    let managed = config.world_settings.get(world).and_then(|settings| settings.get(&path.join(".")));
+    let current = fs::read_to_string(config.path.join(world).join(PAPER_WORLD_CONFIG))
+        .ok()
+        .and_then(|contents| YamlDocument::parse(&contents).get(&path));
+
+    match (current, managed) {
+        (_, Some(setting)) if !setting.applied => println!("{} = {} (applied on a later start)", label, setting.value),
+        (Some(value), Some(_)) => println!("{} = {} (managed by anvil)", label, value),
+        (Some(value), None) => println!("{} = {}", label, value),
+        (None, _) => println!("❌ {} is not set, {} uses paper-world-defaults.yml", label, world)
+    }
+
+    Ok(())
+}
+
+// Writes the per-world keys anvil manages before a launch, leaving every other key and hand edits alone
+pub fn apply_world_settings(config: &ServerConfig) -> Result<()> {
+    if config.world_settings.is_empty() {
+        return Ok(());
+    }
+
+    let mut stored = load_server_config(&config.name)?;
+    let mut changed = false;
+    for (world, settings) in stored.world_settings.iter_mut() {
+        let file_path = stored.path.join(world).join(PAPER_WORLD_CONFIG);
+        let Ok(contents) = fs::read_to_string(&file_path) else {
+            continue;
+        };
+
+        let mut document = YamlDocument::parse(&contents);
+        let mut written = false;
+        let mut released = Vec::new();
+        for (key, setting) in settings.iter_mut() {
+            let path: Vec<&str> = key.split('.').collect();
+            let current = document.get(&path);
+            if current.as_deref() == Some(setting.value.as_str()) && setting.applied {
+                continue;
+            }
+
+            if setting.applied && let Some(current) = current {
+                println!(
+                    "⚠️  {}/{} was changed by hand to {}, anvil no longer manages it",
+                    world, key, current
+                );
+                released.push(key.clone());
+                continue;
+            }
+
+            document.set(&path, &setting.value)?;
+            setting.value = document.get(&path).unwrap_or_else(|| setting.value.clone());
+            setting.applied = true;
+            written = true;
+        }
+
+        changed |= written || !released.is_empty();
+        for key in released {
+            settings.remove(&key);
+        }
+        if written {
+            fs::write(&file_path, document.render())?;
+        }
+    }
+
+    if changed {
+        stored.world_settings.retain(|_, settings| !settings.is_empty());
+        save_server_config(&stored)?;
+    }
+    Ok(())
+}
+
+pub fn collect_settings(config: &ServerConfig) -> Result<BTreeMap<String, String>> {
+    let mut settings = BTreeMap::new();
+
+    if let Ok(properties) = read_server_properties(&config.path) {
+        for (key, value) in properties {
+            settings.insert(format!("{}{}", PROPERTIES_PREFIX, key), value);
+        }
+    }
+
+    for file in CONFIG_FILES {
+        let Ok(contents) = fs::read_to_string(config.path.join(file.path)) else {
+            continue;
+        };
+
+        for (key, value) in YamlDocument::parse(&contents).flatten() {
+            settings.insert(format!("{}.{}", file.prefix, key), value);
+        }
+    }
+
+    Ok(settings)
+}
+
+pub fn plugin_config(
+    server_name: &str,
+    plugin_name: &str,
+    get: Option<&str>,
+    set: Option<&[String]>,
+) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let file_path = plugin_config_file(&config, plugin_name)?;
+
+    match (get, set) {
+        (Some(key), _) => {
+            let path: Vec<&str> = key.split('.').collect();
+            let document = YamlDocument::parse(&fs::read_to_string(&file_path)?);
+            match document.get(&path) {
+                Some(value) => println!("{} = {}", key, value),
+                None => println!("❌ {} is not set", key)
+            }
+        }
+        (None, Some([key, value])) => {
+            let path: Vec<&str> = key.split('.').collect();
+            let mut document = YamlDocument::parse(&fs::read_to_string(&file_path)?);
+            let previous = document.set(&path, value)?;
+            fs::write(&file_path, document.render())?;
+
+            match previous {
+                Some(previous) => println!("✅ {}: {} -> {}", key, previous, value),
+                None => println!("✅ {}: {} (added)", key, value)
+            }
+            println!("💡 Reload the plugin or restart the server to apply the change");
+        }
+        _ => open_in_editor(&file_path)?
+    }
+
+    Ok(())
+}
+
+fn plugin_config_file(config: &ServerConfig, plugin_name: &str) -> Result<PathBuf> {
+    let plugins_dir = config.path.join("plugins");
+
+    let folder_name = find_plugin_jars(&plugins_dir, plugin_name)?
+        .first()
+        .and_then(|jar| crate::jar::read_plugin_descriptor(jar).ok().flatten())
+        .map(|descriptor| descriptor.name)
+        .unwrap_or_else(|| plugin_name.to_string());
+
+    let data_dir = fs::read_dir(&plugins_dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_dir()
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().eq_ignore_ascii_case(&folder_name))
+        })
+        .ok_or_else(|| anyhow!(
+            "No data folder for '{}' - start the server once so the plugin can generate its config",
+            folder_name
+        ))?;
+
+    let main_config = data_dir.join("config.yml");
+    if main_config.exists() {
+        return Ok(main_config);
+    }
+
+    let mut yaml_files: Vec<PathBuf> = fs::read_dir(&data_dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yml" || ext == "yaml"))
+        .collect();
+    yaml_files.sort();
+
+    yaml_files
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("{} has no YAML config files", data_dir.display()))
+}
+
+fn open_in_editor(file_path: &Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| if cfg!(windows) { "notepad".to_string() } else { "vi".to_string() });
+
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or_else(|| anyhow!("$EDITOR is empty"))?;
+
+    println!("📝 Opening {} in {}", file_path.display(), program);
+    let status = Command::new(program).args(parts).arg(file_path).status()?;
+    if !status.success() {
+        return Err(anyhow!("{} exited with {}", program, status));
+    }
+
+    Ok(())
+}
+
+fn diff_config(server_name: &str, other: &str) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let settings = collect_settings(&config)?;
+
+    let (label, baseline) = if other == "defaults" {
+        let defaults: BTreeMap<String, String> = VANILLA_DEFAULTS
+            .iter()
+            .map(|(key, value)| (format!("{}{}", PROPERTIES_PREFIX, key), value.to_string()))
+            .collect();
+        ("defaults".to_string(), defaults)
+    } else {
+        let other_config = load_server_config(other)?;
+        (other.to_string(), collect_settings(&other_config)?)
+    };
+
+    let compare_defaults = other == "defaults";
+    let mut differences = Vec::new();
+
+    for (key, value) in &settings {
+        match baseline.get(key) {
+            Some(expected) if expected == value => {}
+            Some(expected) => differences.push((key, value.as_str(), expected.as_str())),
+            None if compare_defaults => {}
+            None => differences.push((key, value.as_str(), "(unset)"))
+        }
+    }
+
+    if !compare_defaults {
+        for (key, expected) in &baseline {
+            if !settings.contains_key(key) {
+                differences.push((key, "(unset)", expected.as_str()));
+            }
+        }
+        differences.sort_by(|a, b| a.0.cmp(b.0));
+    }
+
+    if differences.is_empty() {
+        println!("✅ No differences between '{}' and {}", server_name, label);
+        return Ok(());
+    }
+
+    let width = differences.iter().map(|(key, _, _)| key.len()).max().unwrap_or(0);
+
+    println!("🔍 Settings on '{}' that differ from {}:", server_name, label);
+    println!("  {:width$}  {:24}  {}", "KEY", server_name.to_uppercase(), label.to_uppercase(), width = width);
+    for (key, value, expected) in &differences {
+        println!(
+            "  {:width$}  {:24}  {}",
+            key,
+            redact(key, value),
+            redact(key, expected),
+            width = width
+        );
+    }
+
+    if compare_defaults {
+        println!("ℹ️  Only server.properties has built-in defaults; compare against another server to diff Paper/Spigot/Bukkit files");
+    }
+
+    Ok(())
+}
+
+pub fn is_secret_key(key: &str) -> bool {
+    crate::secrets::is_secret_name(key)
+}
+
+pub fn redact<'a>(key: &str, value: &'a str) -> &'a str {
+    if is_secret_key(key) && !value.is_empty() && value != "(unset)" {
+        "********"
+    } else {
+        value
+    }
+}
@@ -0,0 +1,148 @@
+use crate::server::{is_server_running, load_server_config, read_server_properties, ServerConfig};
+use anyhow::Result;
+use std::net::IpAddr;
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+const CONNECT_TIMEOUT_SECS: u64 = 3;
+
+pub async fn check_connection(server_name: &str) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let properties = read_server_properties(&config.path).unwrap_or_default();
+    let bind_address = properties.get("server-ip").filter(|ip| !ip.is_empty()).cloned();
+
+    println!("🔍 Checking how players can reach '{}' on port {}...", server_name, config.port);
+
+    let local = probe(&format!("127.0.0.1:{}", config.port)).await;
+    if !local && !is_server_running(&config).await {
+        println!("⚠️  Server '{}' does not appear to be running (nothing is listening on port {})", server_name, config.port);
+    }
+    report("Local", local);
+
+    let lan_ip = crate::expose::local_ip_towards("1.1.1.1:80").await.ok();
+    let lan = match lan_ip {
+        Some(ip) => {
+            let reachable = probe(&format!("{}:{}", ip, config.port)).await;
+            report(&format!("LAN ({})", ip), reachable);
+            Some(reachable)
+        }
+        None => {
+            println!("  ❓ LAN: no network interface with a route out was found");
+            None
+        }
+    };
+
+    let public_ip = crate::ddns::public_ip().await.ok();
+    let external = match &public_ip {
+        Some(ip) => {
+            let address = format!("{}:{}", ip, config.port);
+            match crate::api::mcsrvstat::get_status(&address).await {
+                Ok(status) => {
+                    report(&format!("Internet ({})", address), status.online);
+                    if let Some(error) = status.debug.and_then(|debug| debug.error).filter(|_| !status.online) {
+                        println!("      probe said: {}", error);
+                    }
+                    Some(status.online)
+                }
+                Err(e) => {
+                    println!("  ❓ Internet: the external probe failed: {}", e);
+                    None
+                }
+            }
+        }
+        None => {
+            println!("  ❓ Internet: could not determine the public IP address");
+            None
+        }
+    };
+
+    println!("\n🩺 Diagnosis:");
+
+    if !local {
+        match &bind_address {
+            Some(address) if address != "0.0.0.0" && address != "127.0.0.1" => {
+                println!("  • The server binds only to {} (server-ip in server.properties)", address);
+                println!("    💡 Clear it with: anvil config set {} server.server-ip \"\"", server_name);
+            }
+            _ => {
+                println!("  • Nothing is listening on port {}", config.port);
+                println!("    💡 Start the server with: anvil start {}", server_name);
+            }
+        }
+        return Ok(());
+    }
+
+    if lan == Some(false) {
+        match bind_address.as_deref() {
+            Some("127.0.0.1") | Some("localhost") => {
+                println!("  • The server only listens on localhost (server-ip={})", bind_address.unwrap_or_default());
+                println!("    💡 Clear it with: anvil config set {} server.server-ip \"\"", server_name);
+            }
+            _ => {
+                println!("  • A local firewall is blocking port {}", config.port);
+                println!("    💡 Linux: sudo ufw allow {}/tcp  (or firewall-cmd --add-port={}/tcp --permanent)", config.port, config.port);
+                println!("    💡 Windows: allow Java through Windows Defender Firewall");
+            }
+        }
+        return Ok(());
+    }
+
+    match external {
+        Some(true) => {
+            println!("  ✅ Players can join at {}:{}", public_ip.unwrap_or_default(), config.port);
+            if properties.get("online-mode").is_some_and(|mode| mode == "false") {
+                println!("  ⚠️  online-mode is off, so anyone on the internet can join as any player, including operators");
+                if config.proxy.is_some() {
+                    println!("    💡 Only the proxy should reach this backend: anvil expose {} --remove", server_name);
+                }
+            }
+        }
+        Some(false) if behind_carrier_grade_nat(&config, public_ip.as_deref()) => {
+            println!("  • Your router's WAN address is not your public IP (carrier-grade NAT), so port forwarding cannot work");
+            println!("    💡 Use a tunnel instead: anvil start {} --tunnel", server_name);
+        }
+        Some(false) => {
+            println!("  • The router is not forwarding port {} to this machine", config.port);
+            match &config.exposure {
+                Some(mapping) => println!("    💡 A {} mapping exists; your router may have dropped it, try: anvil expose {} --remove && anvil expose {}", mapping.method, server_name, server_name),
+                None => println!("    💡 Try: anvil expose {}  (or forward TCP {} to {} on your router)", server_name, config.port, lan_ip.map(|ip| ip.to_string()).unwrap_or_else(|| "this machine".to_string()))
+            }
+            println!("    💡 No router access? Use: anvil start {} --tunnel", server_name);
+        }
+        None => println!("  ❓ External reachability is unknown; ask a friend to try {}:{}", public_ip.unwrap_or_else(|| "<your public IP>".to_string()), config.port)
+    }
+
+    Ok(())
+}
+
+async fn probe(address: &str) -> bool {
+    tokio::time::timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS), TcpStream::connect(address))
+        .await
+        .is_ok_and(|result| result.is_ok())
+}
+
+fn report(label: &str, reachable: bool) {
+    if reachable {
+        println!("  ✅ {}: reachable", label);
+    } else {
+        println!("  ❌ {}: not reachable", label);
+    }
+}
+
+fn behind_carrier_grade_nat(config: &ServerConfig, public_ip: Option<&str>) -> bool {
+    // The router reports its WAN address when a port is mapped; a mismatch means another NAT sits upstream
+    let Some(wan_ip) = config.exposure.as_ref().and_then(|mapping| mapping.external_ip.as_deref()) else {
+        return false;
+    };
+
+    let shared_range = match wan_ip.parse::<IpAddr>() {
+        // 100.64.0.0/10 is reserved for carrier-grade NAT
+        Ok(IpAddr::V4(ip)) => {
+            let [first, second, ..] = ip.octets();
+            first == 100 && (64..128).contains(&second)
+        }
+        _ => false
+    };
+
+    shared_range || public_ip.is_some_and(|public_ip| public_ip != wan_ip)
+}
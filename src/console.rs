@@ -0,0 +1,148 @@
+use crate::server::ServerConfig;
+use anyhow::{anyhow, Result};
+use std::io::BufRead;
+use std::path::PathBuf;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::ChildStdin;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+// Never copied or archived with the server, it's recreated on every start
+pub const CONSOLE_PIPE: &str = "console.in";
+
+// Feeds the server console from the terminal and from the console pipe until dropped
+pub struct ConsoleBridge {
+    path: PathBuf,
+    tasks: Vec<JoinHandle<()>>
+}
+
+impl Drop for ConsoleBridge {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+        #[cfg(unix)]
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+// A FIFO inside the server directory on Unix, a named pipe on Windows where the filesystem has none
+pub fn pipe_path(config: &ServerConfig) -> PathBuf {
+    #[cfg(unix)]
+    return config.path.join(CONSOLE_PIPE);
+    #[cfg(windows)]
+    return PathBuf::from(format!(r"\\.\pipe\anvil-{}", config.name));
+}
+
+pub fn bridge(config: &ServerConfig, mut stdin: ChildStdin) -> Result<ConsoleBridge> {
+    let (sender, mut receiver) = mpsc::channel::<String>(64);
+
+    // Blocking stdin reads would keep the runtime from shutting down, so they get a plain thread
+    let terminal = sender.clone();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+            if terminal.blocking_send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let writer = tokio::spawn(async move {
+        while let Some(line) = receiver.recv().await {
+            let written = async {
+                stdin.write_all(format!("{}\n", line).as_bytes()).await?;
+                stdin.flush().await
+            };
+            if written.await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let path = pipe_path(config);
+    let reader = listen(&path, sender)?;
+    println!("⌨️  Console commands can also be written to {}", path.display());
+
+    Ok(ConsoleBridge { path, tasks: vec![writer, reader] })
+}
+
+#[cfg(unix)]
+fn listen(path: &std::path::Path, sender: mpsc::Sender<String>) -> Result<JoinHandle<()>> {
+    use std::os::unix::fs::FileTypeExt;
+    use tokio::net::unix::pipe;
+
+    // A leftover regular file or a FIFO from a crashed run is replaced
+    let _ = std::fs::remove_file(path);
+    let status = std::process::Command::new("mkfifo").arg("-m").arg("600").arg(path).status();
+    if !status.is_ok_and(|status| status.success()) || !std::fs::metadata(path)?.file_type().is_fifo() {
+        return Err(anyhow!("Could not create the console pipe {}", path.display()));
+    }
+
+    let path = path.to_path_buf();
+    Ok(tokio::spawn(async move {
+        loop {
+            let mut options = pipe::OpenOptions::new();
+            // Holding the write end too keeps the pipe open between writers instead of reporting EOF
+            #[cfg(target_os = "linux")]
+            options.read_write(true);
+            let Ok(receiver) = options.open_receiver(&path) else {
+                return;
+            };
+
+            let mut lines = BufReader::new(receiver).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if sender.send(line).await.is_err() {
+                    return;
+                }
+            }
+            // Without a writer the pipe reports EOF right away, wait before reopening it
+            tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        }
+    }))
+}
+
+#[cfg(windows)]
+fn listen(path: &std::path::Path, sender: mpsc::Sender<String>) -> Result<JoinHandle<()>> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let name = path.as_os_str().to_os_string();
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(&name)
+        .map_err(|e| anyhow!("Could not create the console pipe {}: {}", path.display(), e))?;
+
+    Ok(tokio::spawn(async move {
+        loop {
+            if server.connect().await.is_err() {
+                return;
+            }
+            // Each writer gets its own instance, the next one is created before reading this one
+            let Ok(next) = ServerOptions::new().create(&name) else {
+                return;
+            };
+            let connected = std::mem::replace(&mut server, next);
+
+            let mut lines = BufReader::new(connected).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if sender.send(line).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }))
+}
+
+// Writes a command to the console of a server anvil started, works without RCON
+pub async fn send(config: &ServerConfig, command: &str) -> Result<()> {
+    let path = pipe_path(config);
+    let unavailable = |e: std::io::Error| anyhow!("'{}' has no console pipe, it was not started by anvil: {}", config.name, e);
+
+    #[cfg(unix)]
+    let mut pipe = tokio::net::unix::pipe::OpenOptions::new().open_sender(&path).map_err(unavailable)?;
+    #[cfg(windows)]
+    let mut pipe = tokio::net::windows::named_pipe::ClientOptions::new().open(&path).map_err(unavailable)?;
+
+    pipe.write_all(format!("{}\n", command).as_bytes()).await?;
+    pipe.flush().await?;
+    Ok(())
+}
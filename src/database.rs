@@ -0,0 +1,259 @@
+use crate::server::{load_server_config, save_server_config};
+use crate::DbAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::net::TcpListener;
+use tokio::process::Command;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DatabaseConfig {
+    pub engine: String,
+    pub host: String,
+    pub port: u16,
+    pub database: String,
+    pub user: String,
+    // Kept in secrets.json, the server config is readable by anyone on the machine
+    #[serde(skip)]
+    pub password: String,
+    pub container: Option<String>
+}
+
+impl DatabaseConfig {
+    pub fn jdbc_url(&self) -> String {
+        format!("jdbc:{}://{}:{}/{}", self.engine, self.host, self.port, self.database)
+    }
+}
+
+struct Engine {
+    name: &'static str,
+    image: &'static str,
+    default_port: u16,
+    data_dir: &'static str
+}
+
+const ENGINES: &[Engine] = &[
+    Engine {
+        name: "mariadb",
+        image: "mariadb:11",
+        default_port: 3306,
+        data_dir: "/var/lib/mysql"
+    },
+    Engine {
+        name: "postgresql",
+        image: "postgres:16",
+        default_port: 5432,
+        data_dir: "/var/lib/postgresql/data"
+    }
+];
+
+pub async fn handle_db_action(action: DbAction) -> Result<()> {
+    match action {
+        DbAction::Create { server, engine, local } => {
+            create_database(&server, &engine, local).await?;
+        }
+        DbAction::Info { server, reveal } => {
+            let config = load_server_config(&server)?;
+            let mut database = config.database
+                .ok_or_else(|| anyhow!("No database provisioned for server '{}'", server))?;
+            database.password = crate::secrets::server_secrets(&server)?.database_password.unwrap_or_default();
+            print_connection_details(&database, reveal);
+            if !reveal {
+                println!("💡 Show the password with: anvil db info {} --reveal", server);
+            }
+        }
+        DbAction::Remove { server } => {
+            remove_database(&server).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn create_database(server_name: &str, engine_name: &str, local: bool) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+
+    if config.database.is_some() {
+        return Err(anyhow!(
+            "Server '{}' already has a database (see `anvil db info {}`)",
+            server_name, server_name
+        ));
+    }
+
+    let engine = ENGINES
+        .iter()
+        .find(|engine| engine.name == engine_name || (engine_name == "postgres" && engine.name == "postgresql"))
+        .ok_or_else(|| anyhow!("Unsupported database engine: {} (expected mariadb or postgresql)", engine_name))?;
+
+    let identifier = format!("anvil_{}", sanitize_identifier(&config.name));
+    let password = uuid::Uuid::new_v4().simple().to_string();
+
+    let database = if local {
+        println!("🗄️  Creating {} database on the local service...", engine.name);
+        provision_local(engine, &identifier, &password).await?;
+
+        DatabaseConfig {
+            engine: engine.name.to_string(),
+            host: "127.0.0.1".to_string(),
+            port: engine.default_port,
+            database: identifier.clone(),
+            user: identifier,
+            password,
+            container: None
+        }
+    } else {
+        let port = find_free_port(engine.default_port)?;
+        let container = format!("anvil-{}-{}", sanitize_identifier(&config.name), engine.name);
+
+        println!("🐳 Starting {} container '{}' on port {}...", engine.name, container, port);
+        provision_docker(engine, &container, port, &identifier, &password).await?;
+
+        DatabaseConfig {
+            engine: engine.name.to_string(),
+            host: "127.0.0.1".to_string(),
+            port,
+            database: identifier.clone(),
+            user: identifier,
+            password,
+            container: Some(container)
+        }
+    };
+
+    crate::secrets::set_database_password(server_name, Some(database.password.clone()))?;
+    config.database = Some(database.clone());
+    save_server_config(&config)?;
+
+    println!("✅ Database ready for server '{}'", server_name);
+    print_connection_details(&database, false);
+    println!("💡 Show the password with: anvil db info {} --reveal", server_name);
+
+    Ok(())
+}
+
+async fn provision_docker(
+    engine: &Engine,
+    container: &str,
+    port: u16,
+    identifier: &str,
+    password: &str,
+) -> Result<()> {
+    let env = match engine.name {
+        "mariadb" => vec![
+            "MARIADB_RANDOM_ROOT_PASSWORD=yes".to_string(),
+            format!("MARIADB_DATABASE={}", identifier),
+            format!("MARIADB_USER={}", identifier),
+            format!("MARIADB_PASSWORD={}", password)
+        ],
+        _ => vec![
+            format!("POSTGRES_DB={}", identifier),
+            format!("POSTGRES_USER={}", identifier),
+            format!("POSTGRES_PASSWORD={}", password)
+        ]
+    };
+
+    let mut command = Command::new("docker");
+    command
+        .args(["run", "-d", "--restart", "unless-stopped", "--name", container])
+        .args(["-p", &format!("127.0.0.1:{}:{}", port, engine.default_port)])
+        .args(["-v", &format!("{}:{}", container, engine.data_dir)]);
+
+    for var in &env {
+        command.args(["-e", var]);
+    }
+
+    run_command(command.arg(engine.image), "docker").await
+}
+
+async fn provision_local(engine: &Engine, identifier: &str, password: &str) -> Result<()> {
+    let mut command = match engine.name {
+        "mariadb" => {
+            let sql = format!(
+                "CREATE DATABASE IF NOT EXISTS `{id}`; \
+                 CREATE USER IF NOT EXISTS '{id}'@'localhost' IDENTIFIED BY '{password}'; \
+                 GRANT ALL PRIVILEGES ON `{id}`.* TO '{id}'@'localhost'; \
+                 FLUSH PRIVILEGES;",
+                id = identifier
+            );
+            let mut command = Command::new("mysql");
+            command.args(["-u", "root", "-e", &sql]);
+            command
+        }
+        _ => {
+            let mut command = Command::new("psql");
+            command
+                .args(["-U", "postgres"])
+                .args(["-c", &format!("CREATE USER {} WITH PASSWORD '{}';", identifier, password)])
+                .args(["-c", &format!("CREATE DATABASE {} OWNER {};", identifier, identifier)]);
+            command
+        }
+    };
+
+    let program = if engine.name == "mariadb" { "mysql" } else { "psql" };
+    run_command(&mut command, program).await
+}
+
+async fn remove_database(server_name: &str) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let database = config.database
+        .take()
+        .ok_or_else(|| anyhow!("No database provisioned for server '{}'", server_name))?;
+
+    match &database.container {
+        Some(container) => {
+            println!("🐳 Removing container '{}' (data volume is kept)...", container);
+            run_command(Command::new("docker").args(["rm", "-f", container]), "docker").await?;
+        }
+        None => println!(
+            "ℹ️  Database '{}' lives on a local service and was not dropped",
+            database.database
+        )
+    }
+
+    save_server_config(&config)?;
+    crate::secrets::set_database_password(server_name, None)?;
+    println!("✅ Database removed from server '{}'", server_name);
+
+    Ok(())
+}
+
+async fn run_command(command: &mut Command, program: &str) -> Result<()> {
+    let output = command
+        .output()
+        .await
+        .map_err(|e| anyhow!("Failed to run {} (is it installed?): {}", program, e))?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
+}
+
+fn find_free_port(preferred: u16) -> Result<u16> {
+    (preferred..preferred.saturating_add(100))
+        .find(|port| TcpListener::bind(("127.0.0.1", *port)).is_ok())
+        .ok_or_else(|| anyhow!("No free port found near {}", preferred))
+}
+
+fn sanitize_identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect()
+}
+
+fn print_connection_details(database: &DatabaseConfig, reveal: bool) {
+    println!("🔌 Connection details:");
+    println!(" - Engine: {}", database.engine);
+    println!(" - Address: {}:{}", database.host, database.port);
+    println!(" - Database: {}", database.database);
+    println!(" - Username: {}", database.user);
+    let password = Some(database.password.as_str()).filter(|password| !password.is_empty());
+    println!(" - Password: {}", crate::secrets::display_secret(password, reveal));
+    println!(" - JDBC URL: {}", database.jdbc_url());
+    if let Some(container) = &database.container {
+        println!(" - Container: {}", container);
+    }
+}
+
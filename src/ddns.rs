@@ -0,0 +1,176 @@
+use crate::api::{cloudflare, duckdns};
+use crate::server::{get_all_servers, load_server_config, save_server_config, ServerConfig};
+use crate::DdnsAction;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+pub const DDNS_REFRESH_SECS: u64 = 300;
+
+const PROVIDERS: &[&str] = &["cloudflare", "duckdns"];
+const DEFAULT_MINECRAFT_PORT: u16 = 25565;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DdnsConfig {
+    pub provider: String,
+    pub hostname: String,
+    #[serde(default)]
+    pub zone_id: Option<String>,
+    #[serde(default)]
+    pub srv: bool,
+    #[serde(default)]
+    pub last_ip: Option<String>,
+    #[serde(default)]
+    pub last_updated: Option<DateTime<Utc>>
+}
+
+pub async fn handle_ddns_action(action: DdnsAction) -> Result<()> {
+    match action {
+        DdnsAction::Enable { server, hostname, provider, token, zone_id, no_srv } => {
+            enable_ddns(&server, &hostname, &provider, &token, zone_id, !no_srv).await?;
+        }
+        DdnsAction::Update { server } => {
+            let config = load_server_config(&server)?;
+            refresh_server(&config, true).await?;
+        }
+        DdnsAction::Disable { server } => {
+            let mut config = load_server_config(&server)?;
+            let ddns = config.ddns.take().ok_or_else(|| anyhow!("Dynamic DNS is not enabled for '{}'", server))?;
+            save_server_config(&config)?;
+            crate::secrets::set_ddns_token(&server, None)?;
+            println!("✅ Stopped updating {} for '{}'", ddns.hostname, server);
+            println!("💡 The existing DNS records were left in place");
+        }
+    }
+    Ok(())
+}
+
+async fn enable_ddns(
+    server_name: &str,
+    hostname: &str,
+    provider: &str,
+    token: &str,
+    zone_id: Option<String>,
+    srv: bool,
+) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+
+    if !PROVIDERS.contains(&provider) {
+        return Err(anyhow!("Unknown DNS provider '{}' (expected one of {})", provider, PROVIDERS.join(", ")));
+    }
+
+    let hostname = hostname.trim_end_matches('.').to_lowercase();
+    let zone_id = match (provider, zone_id) {
+        ("cloudflare", Some(zone_id)) => Some(zone_id),
+        ("cloudflare", None) => Some(cloudflare::find_zone_id(token, &hostname).await?),
+        _ => None
+    };
+
+    if provider == "duckdns" && srv && config.port != DEFAULT_MINECRAFT_PORT {
+        println!("⚠️  DuckDNS does not support SRV records; players will need to type port {}", config.port);
+    }
+
+    crate::secrets::set_ddns_token(server_name, Some(token.to_string()))?;
+    config.ddns = Some(DdnsConfig {
+        provider: provider.to_string(),
+        hostname: hostname.clone(),
+        zone_id,
+        srv,
+        last_ip: None,
+        last_updated: None
+    });
+    save_server_config(&config)?;
+
+    println!("✅ Dynamic DNS enabled for '{}' ({} via {})", server_name, hostname, provider);
+    refresh_server(&config, true).await?;
+    println!("💡 Run 'anvil daemon' to keep the record up to date when your IP changes");
+
+    Ok(())
+}
+
+pub async fn refresh_server(config: &ServerConfig, force: bool) -> Result<bool> {
+    let ddns = config.ddns.as_ref().ok_or_else(|| anyhow!("Dynamic DNS is not enabled for '{}'", config.name))?;
+    let ip = public_ip().await?;
+
+    if !force && ddns.last_ip.as_deref() == Some(ip.as_str()) {
+        return Ok(false);
+    }
+
+    let token = crate::secrets::server_secrets(&config.name)?
+        .ddns_token
+        .ok_or_else(|| anyhow!("No DNS token stored for '{}' (run anvil ddns enable again)", config.name))?;
+
+    match ddns.provider.as_str() {
+        "cloudflare" => update_cloudflare(config, ddns, &token, &ip).await?,
+        "duckdns" => {
+            let subdomain = ddns.hostname.trim_end_matches(duckdns::DOMAIN_SUFFIX);
+            duckdns::update(subdomain, &token, &ip).await?;
+        }
+        other => return Err(anyhow!("Unknown DNS provider '{}'", other))
+    }
+
+    println!("🌐 {} -> {}", ddns.hostname, ip);
+
+    // Reload so a concurrent config change is not overwritten
+    let mut latest = load_server_config(&config.name)?;
+    if let Some(latest_ddns) = latest.ddns.as_mut() {
+        latest_ddns.last_ip = Some(ip);
+        latest_ddns.last_updated = Some(Utc::now());
+        save_server_config(&latest)?;
+    }
+
+    Ok(true)
+}
+
+async fn update_cloudflare(config: &ServerConfig, ddns: &DdnsConfig, token: &str, ip: &str) -> Result<()> {
+    let zone_id = ddns.zone_id.as_deref().ok_or_else(|| anyhow!("No Cloudflare zone id stored for '{}'", config.name))?;
+
+    cloudflare::upsert_record(token, zone_id, "A", &ddns.hostname, json!({
+        "type": "A",
+        "name": ddns.hostname,
+        "content": ip,
+        "ttl": 1,
+        "proxied": false
+    })).await?;
+
+    // Clients only look up SRV records when no port is typed, so the default port needs none
+    if ddns.srv && config.port != DEFAULT_MINECRAFT_PORT {
+        let srv_name = format!("_minecraft._tcp.{}", ddns.hostname);
+        cloudflare::upsert_record(token, zone_id, "SRV", &srv_name, json!({
+            "type": "SRV",
+            "name": srv_name,
+            "ttl": 1,
+            "data": {
+                "priority": 0,
+                "weight": 5,
+                "port": config.port,
+                "target": ddns.hostname
+            }
+        })).await?;
+        println!("🧭 {} -> {}:{}", srv_name, ddns.hostname, config.port);
+    }
+
+    Ok(())
+}
+
+pub async fn refresh_all() {
+    let Ok(servers) = get_all_servers() else {
+        return;
+    };
+
+    for config in servers.iter().filter(|config| config.ddns.is_some()) {
+        if let Err(e) = refresh_server(config, false).await {
+            println!("⚠️  Dynamic DNS update for '{}' failed: {}", config.name, e);
+        }
+    }
+}
+
+pub async fn public_ip() -> Result<String> {
+    let ip = reqwest::get("https://api.ipify.org")
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    Ok(ip.trim().to_string())
+}
@@ -0,0 +1,292 @@
+use crate::server::{
+    format_bytes, gc_log_flags, is_server_running, launcher_pid, load_server_config, ServerConfig, GC_LOG_FILE,
+};
+use crate::DebugAction;
+use anyhow::{anyhow, Result};
+use chrono::Local;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+pub async fn handle_debug_action(action: DebugAction) -> Result<()> {
+    match action {
+        DebugAction::Dump { server, heap, threads } => {
+            capture_dumps(&server, heap, threads).await?;
+        }
+        DebugAction::Gc { server, enable } => {
+            summarize_gc(&server, enable)?;
+        }
+    }
+    Ok(())
+}
+
+async fn capture_dumps(server_name: &str, heap: bool, threads: bool) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    if !is_server_running(&config).await {
+        return Err(anyhow!("Server '{}' is not running", server_name));
+    }
+
+    let pid = jvm_pid(&config).await?;
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let logs_dir = config.path.join("logs");
+    fs::create_dir_all(&logs_dir)?;
+
+    if threads {
+        let path = logs_dir.join(format!("threads-{}.txt", stamp));
+        let output = run_tool("jstack", &[&pid.to_string()]).await?;
+        fs::write(&path, output)?;
+        println!("🧵 Saved thread dump to {}", path.display());
+    }
+
+    if heap {
+        // jcmd asks the JVM to write the file itself, so it needs an absolute path
+        let path = fs::canonicalize(&logs_dir)?.join(format!("heap-{}.hprof", stamp));
+        println!("🧠 Writing heap dump of process {}, the server pauses while this runs...", pid);
+        run_tool("jcmd", &[&pid.to_string(), "GC.heap_dump", &path.to_string_lossy()]).await?;
+        let size = fs::metadata(&path).map(|metadata| metadata.len()).unwrap_or(0);
+        println!("✅ Saved heap dump to {} ({})", path.display(), format_bytes(size));
+        println!("💡 Open it with Eclipse MAT or VisualVM to find what is holding memory");
+    }
+
+    Ok(())
+}
+
+async fn run_tool(tool: &str, args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new(tool)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| anyhow!("Could not run {} ({}), it ships with the JDK but not with a JRE", tool, e))?;
+
+    if !output.status.success() {
+        return Err(anyhow!("{} failed: {}", tool, String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(output.stdout)
+}
+
+pub async fn jvm_pid(config: &ServerConfig) -> Result<u32> {
+    // The start script runs java as its child, other children such as wrappers are skipped
+    #[cfg(unix)]
+    if let Some(launcher) = launcher_pid(config) {
+        let output = Command::new("pgrep").args(["-P", &launcher.to_string(), "java"]).output().await?;
+        if let Some(pid) = String::from_utf8_lossy(&output.stdout).lines().find_map(|line| line.trim().parse().ok()) {
+            return Ok(pid);
+        }
+    }
+
+    // Otherwise look for a JVM running this server's jar from its directory
+    let listing = run_tool("jcmd", &["-l"]).await?;
+    let server_dir = fs::canonicalize(&config.path).ok();
+    String::from_utf8_lossy(&listing)
+        .lines()
+        .filter(|line| line.contains(&config.jar_file))
+        .filter_map(|line| line.split_whitespace().next()?.parse::<u32>().ok())
+        .find(|pid| server_dir.as_deref().is_none_or(|dir| process_cwd_matches(*pid, dir)))
+        .ok_or_else(|| anyhow!("Could not find the JVM of '{}', was it started with anvil start?", config.name))
+}
+
+#[cfg(target_os = "linux")]
+fn process_cwd_matches(pid: u32, dir: &Path) -> bool {
+    fs::read_link(format!("/proc/{}/cwd", pid)).is_ok_and(|cwd| cwd == dir)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_cwd_matches(_pid: u32, _dir: &Path) -> bool {
+    true
+}
+
+#[derive(Default)]
+struct GcSummary {
+    pauses: Vec<f64>,
+    kinds: BTreeMap<String, (usize, f64)>,
+    full_collections: usize,
+    heap_after: Vec<u64>,
+    heap_capacity: u64,
+    uptime_secs: f64
+}
+
+fn enable_gc_logging(config: &ServerConfig) -> Result<()> {
+    if gc_log_flags(&config.version).is_empty() {
+        return Err(anyhow!(
+            "Minecraft {} may run on Java 8, which does not support unified GC logging",
+            config.version
+        ));
+    }
+
+    let mut updated = 0;
+    for script in ["start.sh", "start.bat"] {
+        let path = config.path.join(script);
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if contents.contains("-Xlog:gc") {
+            continue;
+        }
+
+        let flags = gc_log_flags(&config.version).join(" ");
+        let rewritten: Vec<String> = contents
+            .lines()
+            .map(|line| match line.strip_prefix("java ") {
+                Some(rest) => format!("java {} {}", flags, rest),
+                None => line.to_string()
+            })
+            .collect();
+        fs::write(&path, rewritten.join("\n") + "\n")?;
+        updated += 1;
+    }
+
+    if updated == 0 {
+        println!("ℹ️  GC logging is already enabled for '{}'", config.name);
+    } else {
+        println!("✅ Enabled GC logging in {} start script(s), restart '{}' to apply it", updated, config.name);
+    }
+    Ok(())
+}
+
+fn summarize_gc(server_name: &str, enable: bool) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    if enable {
+        return enable_gc_logging(&config);
+    }
+
+    let log_path = config.path.join(GC_LOG_FILE);
+    let logs_dir = log_path.parent().unwrap_or(&config.path).to_path_buf();
+    let mut files: Vec<PathBuf> = fs::read_dir(&logs_dir)
+        .map(|dir| {
+            dir.filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.file_name().is_some_and(|name| name.to_string_lossy().starts_with("gc.log")))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+
+    if files.is_empty() {
+        println!("📭 No GC log found at {}", log_path.display());
+        println!("💡 Turn it on with: anvil debug gc {} --enable", server_name);
+        return Ok(());
+    }
+
+    let mut summary = GcSummary::default();
+    for file in &files {
+        if let Ok(contents) = fs::read_to_string(file) {
+            contents.lines().for_each(|line| summary.record(line));
+        }
+    }
+
+    if summary.pauses.is_empty() {
+        println!("📭 {} has no collections logged yet", log_path.display());
+        return Ok(());
+    }
+    summary.print(server_name);
+    Ok(())
+}
+
+impl GcSummary {
+    // [2024-01-05T12:00:00.000+0000][12.345s][info][gc] GC(7) Pause Young (Normal) (G1 Evacuation Pause) 120M->40M(512M) 8.1ms
+    fn record(&mut self, line: &str) {
+        let uptime = line.split("][").nth(1).and_then(|field| field.strip_suffix('s'));
+        if let Some(uptime) = uptime.and_then(|uptime| uptime.parse::<f64>().ok()) {
+            self.uptime_secs = self.uptime_secs.max(uptime);
+        }
+
+        let Some((_, event)) = line.split_once("] GC(") else {
+            return;
+        };
+        let Some((_, event)) = event.split_once(") ") else {
+            return;
+        };
+        if !event.starts_with("Pause") {
+            return;
+        }
+
+        let tokens: Vec<&str> = event.split_whitespace().collect();
+        let duration = tokens.last().and_then(|token| token.strip_suffix("ms"));
+        let Some(duration) = duration.and_then(|ms| ms.parse::<f64>().ok()) else {
+            return;
+        };
+
+        let kind = tokens.iter().take(2).copied().collect::<Vec<_>>().join(" ");
+        // Explicit System.gc() calls force a full collection regardless of heap pressure
+        if kind == "Pause Full" && !event.contains("(System.gc())") {
+            self.full_collections += 1;
+        }
+        let entry = self.kinds.entry(kind).or_default();
+        entry.0 += 1;
+        entry.1 += duration;
+        self.pauses.push(duration);
+
+        if let Some((after, capacity)) = tokens.iter().find_map(|token| parse_heap_change(token)) {
+            self.heap_after.push(after);
+            self.heap_capacity = capacity;
+        }
+    }
+
+    fn print(&self, server_name: &str) {
+        let mut sorted = self.pauses.clone();
+        sorted.sort_by(f64::total_cmp);
+        let total: f64 = sorted.iter().sum();
+        let p95 = sorted[((sorted.len() as f64 * 0.95).ceil() as usize).saturating_sub(1)];
+        let max = sorted[sorted.len() - 1];
+
+        println!("🗑️  GC summary for '{}' over {}:", server_name, format_uptime(self.uptime_secs));
+        println!(" - Pauses: {} ({:.0}ms total, {:.1}ms average)", sorted.len(), total, total / sorted.len() as f64);
+        println!(" - Pause times: p95 {:.1}ms, max {:.1}ms", p95, max);
+        for (kind, (count, time)) in &self.kinds {
+            println!("  • {}: {} ({:.1}ms average)", kind, count, time / *count as f64);
+        }
+
+        let mut occupancy = 0.0;
+        if self.heap_capacity > 0 && !self.heap_after.is_empty() {
+            let peak = self.heap_after.iter().copied().max().unwrap_or(0);
+            let average = self.heap_after.iter().sum::<u64>() / self.heap_after.len() as u64;
+            occupancy = peak as f64 / self.heap_capacity as f64;
+            println!(
+                " - Heap after GC: {} average, {} peak of {} ({:.0}%)",
+                format_bytes(average),
+                format_bytes(peak),
+                format_bytes(self.heap_capacity),
+                occupancy * 100.0
+            );
+        }
+
+        // What survives a collection is the live set, so little headroom above it means -Xmx is too low
+        if self.full_collections > 0 || occupancy > 0.8 {
+            println!(
+                "⚠️  The heap is under pressure ({} full collection(s)), raise -Xmx with: anvil start {} --ram <GB>",
+                self.full_collections, server_name
+            );
+        } else if max > 200.0 {
+            println!("⚠️  Some pauses exceed 200ms, players will notice them as lag spikes");
+        } else {
+            println!("✅ The heap has room to spare, raising -Xmx would not help");
+        }
+    }
+}
+
+// Parses "120M->40M(512M)" into bytes after the collection and the heap capacity
+fn parse_heap_change(token: &str) -> Option<(u64, u64)> {
+    let (_, rest) = token.split_once("->")?;
+    let (after, capacity) = rest.strip_suffix(')')?.split_once('(')?;
+    Some((parse_size(after)?, parse_size(capacity)?))
+}
+
+fn parse_size(value: &str) -> Option<u64> {
+    let unit = match value.chars().last()? {
+        'K' => 1024,
+        'M' => 1024 * 1024,
+        'G' => 1024 * 1024 * 1024,
+        _ => return None
+    };
+    Some(value[..value.len() - 1].parse::<u64>().ok()? * unit)
+}
+
+fn format_uptime(seconds: f64) -> String {
+    let minutes = (seconds / 60.0) as u64;
+    if minutes < 60 {
+        format!("{}m of uptime", minutes)
+    } else {
+        format!("{}h {:02}m of uptime", minutes / 60, minutes % 60)
+    }
+}
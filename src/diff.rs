@@ -0,0 +1,160 @@
+use crate::config::redact;
+use crate::jar::read_plugin_descriptor;
+use crate::plugin::jar_files;
+use crate::server::{load_server_config, read_server_properties, ServerConfig};
+use anyhow::Result;
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
+
+const KEY_PROPERTIES: &[&str] = &[
+    "server-port",
+    "online-mode",
+    "white-list",
+    "enforce-whitelist",
+    "max-players",
+    "view-distance",
+    "simulation-distance",
+    "difficulty",
+    "gamemode",
+    "hardcore",
+    "pvp",
+    "level-type",
+    "motd",
+    "enable-rcon",
+    "network-compression-threshold"
+];
+
+const UNSET: &str = "(none)";
+
+struct Row {
+    section: &'static str,
+    key: String,
+    left: String,
+    right: String
+}
+
+pub fn diff_servers(left_name: &str, right_name: &str, json_output: bool, all: bool) -> Result<()> {
+    let left = load_server_config(left_name)?;
+    let right = load_server_config(right_name)?;
+
+    let mut rows = vec![
+        row("server", "type", &left.server_type, &right.server_type),
+        row("server", "version", &left.version, &right.version),
+        row("server", "jar", &left.jar_file, &right.jar_file),
+    ];
+    rows.extend(compare_maps("plugin", &plugin_versions(&left)?, &plugin_versions(&right)?));
+    rows.extend(compare_maps("property", &key_properties(&left), &key_properties(&right)));
+
+    let differences: Vec<&Row> = rows.iter().filter(|row| row.left != row.right).collect();
+    let shown: Vec<&Row> = if all { rows.iter().collect() } else { differences.clone() };
+
+    if json_output {
+        let entries: Vec<serde_json::Value> = shown
+            .iter()
+            .map(|row| json!({
+                "section": row.section,
+                "key": row.key,
+                "left": redact(&row.key, &row.left),
+                "right": redact(&row.key, &row.right),
+                "same": row.left == row.right
+            }))
+            .collect();
+
+        let output = json!({
+            "servers": [left_name, right_name],
+            "differences": differences.len(),
+            "rows": entries
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if shown.is_empty() {
+        println!("✅ '{}' and '{}' match", left_name, right_name);
+        return Ok(());
+    }
+
+    let key_width = shown.iter().map(|row| row.key.len()).max().unwrap_or(0).max(3);
+    let left_width = shown
+        .iter()
+        .map(|row| row.left.len())
+        .chain([left_name.len()])
+        .max()
+        .unwrap_or(0)
+        .min(40);
+
+    println!("🔍 Comparing '{}' with '{}':", left_name, right_name);
+    println!(
+        "    {:8}  {:key_width$}  {:left_width$}  {}",
+        "SECTION", "KEY", left_name.to_uppercase(), right_name.to_uppercase(),
+        key_width = key_width,
+        left_width = left_width
+    );
+
+    for row in &shown {
+        let marker = if row.left == row.right { "  " } else { "≠ " };
+        println!(
+            "  {}{:8}  {:key_width$}  {:left_width$}  {}",
+            marker,
+            row.section,
+            row.key,
+            redact(&row.key, &row.left),
+            redact(&row.key, &row.right),
+            key_width = key_width,
+            left_width = left_width
+        );
+    }
+
+    println!("📊 {} difference(s)", differences.len());
+
+    Ok(())
+}
+
+fn row(section: &'static str, key: &str, left: &str, right: &str) -> Row {
+    Row {
+        section,
+        key: key.to_string(),
+        left: left.to_string(),
+        right: right.to_string()
+    }
+}
+
+fn compare_maps(
+    section: &'static str,
+    left: &BTreeMap<String, String>,
+    right: &BTreeMap<String, String>,
+) -> Vec<Row> {
+    let keys: BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+
+    keys.into_iter()
+        .map(|key| row(
+            section,
+            key,
+            left.get(key).map(String::as_str).unwrap_or(UNSET),
+            right.get(key).map(String::as_str).unwrap_or(UNSET),
+        ))
+        .collect()
+}
+
+fn plugin_versions(config: &ServerConfig) -> Result<BTreeMap<String, String>> {
+    let mut plugins = BTreeMap::new();
+
+    for jar in jar_files(&config.path.join("plugins"))? {
+        let filename = jar.file_name().unwrap_or_default().to_string_lossy().to_string();
+        match read_plugin_descriptor(&jar).ok().flatten() {
+            Some(descriptor) => plugins.insert(descriptor.name, descriptor.version),
+            None => plugins.insert(filename.clone(), filename)
+        };
+    }
+
+    Ok(plugins)
+}
+
+fn key_properties(config: &ServerConfig) -> BTreeMap<String, String> {
+    let properties = read_server_properties(&config.path).unwrap_or_default();
+
+    KEY_PROPERTIES
+        .iter()
+        .filter_map(|key| properties.get(*key).map(|value| (key.to_string(), value.clone())))
+        .collect()
+}
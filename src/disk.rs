@@ -0,0 +1,26 @@
+use crate::server::format_bytes;
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+// Left free on top of the estimate so the server can still write logs and chunks afterwards
+const HEADROOM_BYTES: u64 = 256 * 1024 * 1024;
+
+pub fn ensure_free_space(path: &Path, needed: u64, operation: &str) -> Result<()> {
+    // The target may not exist yet, the closest existing parent is on the same filesystem
+    let existing = path.ancestors().find(|dir| dir.exists()).unwrap_or(path);
+    let Ok(available) = fs4::available_space(existing) else {
+        return Ok(());
+    };
+
+    let required = needed.saturating_add(HEADROOM_BYTES);
+    if available < required {
+        return Err(anyhow!(
+            "Not enough disk space for {}: it needs about {} on {} but only {} is free",
+            operation,
+            format_bytes(required),
+            existing.display(),
+            format_bytes(available)
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::server::{is_server_running, load_server_config};
+use crate::EnvAction;
+use anyhow::{anyhow, Result};
+
+pub async fn handle_env_action(action: EnvAction) -> Result<()> {
+    match action {
+        EnvAction::Set { server, vars } => {
+            let config = load_server_config(&server)?;
+            let mut env = crate::secrets::server_secrets(&server)?.env;
+            for var in &vars {
+                let (key, value) = var
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("Expected KEY=VALUE, got '{}'", var))?;
+                validate_key(key)?;
+                env.insert(key.to_string(), value.to_string());
+                println!("✅ Set {} for '{}'", key, server);
+            }
+            crate::secrets::set_server_env(&server, env)?;
+            warn_if_running(&config).await;
+        }
+        EnvAction::Unset { server, keys } => {
+            let config = load_server_config(&server)?;
+            let mut env = crate::secrets::server_secrets(&server)?.env;
+            for key in &keys {
+                if env.remove(key).is_none() {
+                    return Err(anyhow!("'{}' has no environment variable {}", server, key));
+                }
+                println!("🗑️  Removed {} from '{}'", key, server);
+            }
+            crate::secrets::set_server_env(&server, env)?;
+            warn_if_running(&config).await;
+        }
+        EnvAction::List { server, reveal } => {
+            load_server_config(&server)?;
+            let env = crate::secrets::server_secrets(&server)?.env;
+            if env.is_empty() {
+                println!("📭 '{}' has no environment variables", server);
+                return Ok(());
+            }
+
+            println!("🌱 Environment of '{}':", server);
+            for (key, value) in &env {
+                // Values are usually connection strings and API keys
+                let shown = if reveal { value.as_str() } else { "********" };
+                println!("  • {}={}", key, shown);
+            }
+            if !reveal {
+                println!("💡 Show the values with: anvil env list {} --reveal", server);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_key(key: &str) -> Result<()> {
+    let mut chars = key.chars();
+    let valid = chars.next().is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(anyhow!("'{}' is not a valid variable name, use letters, digits and underscores", key));
+    }
+    Ok(())
+}
+
+async fn warn_if_running(config: &crate::server::ServerConfig) {
+    if is_server_running(config).await {
+        crate::output::warn("server.running_old_settings", &[&config.name]);
+    }
+}
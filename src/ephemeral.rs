@@ -0,0 +1,125 @@
+use crate::jar::read_plugin_descriptor;
+use crate::server::{
+    boot_and_stop, create_eula_file, create_server_properties, download_paper_server, free_port,
+    resolve_version, set_server_property,
+};
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub async fn run_test_server(
+    version: &str,
+    plugins: &[PathBuf],
+    ram: u8,
+    timeout: u64,
+    keep: bool,
+    experimental: bool,
+) -> Result<()> {
+    for plugin in plugins {
+        if !plugin.is_file() {
+            return Err(anyhow!("Plugin jar {} does not exist", plugin.display()));
+        }
+    }
+
+    let server_dir = std::env::temp_dir().join(format!("anvil-test-{}", uuid::Uuid::new_v4().simple()));
+    fs::create_dir_all(&server_dir)?;
+    println!("🧪 Creating throwaway server in {}", server_dir.display());
+
+    let result = tokio::select! {
+        result = boot_test_server(&server_dir, version, plugins, ram, timeout, experimental) => result,
+        _ = tokio::signal::ctrl_c() => Err(anyhow!("Interrupted"))
+    };
+
+    if keep {
+        println!("📁 Kept test server at {}", server_dir.display());
+    } else if let Err(e) = fs::remove_dir_all(&server_dir) {
+        println!("⚠️  Could not clean up {}: {}", server_dir.display(), e);
+    } else {
+        println!("🧹 Cleaned up test server");
+    }
+
+    result
+}
+
+async fn boot_test_server(
+    server_dir: &Path,
+    version: &str,
+    plugins: &[PathBuf],
+    ram: u8,
+    timeout: u64,
+    experimental: bool,
+) -> Result<()> {
+    let version = resolve_version("paper", version, experimental).await?;
+    let jar_file = download_paper_server(&version, server_dir, experimental).await?;
+
+    create_server_properties(server_dir, free_port()?, &crate::secrets::generate_secret())?;
+    set_server_property(server_dir, "enable-rcon", "false")?;
+    set_server_property(server_dir, "level-type", "minecraft:flat")?;
+    set_server_property(server_dir, "generate-structures", "false")?;
+    create_eula_file(server_dir)?;
+
+    let plugins_dir = server_dir.join("plugins");
+    fs::create_dir_all(&plugins_dir)?;
+
+    let mut expected = Vec::new();
+    for plugin in plugins {
+        let filename = plugin.file_name().ok_or_else(|| anyhow!("Invalid plugin path {}", plugin.display()))?;
+        fs::copy(plugin, plugins_dir.join(filename))?;
+
+        let name = read_plugin_descriptor(plugin)?
+            .map(|descriptor| descriptor.name)
+            .ok_or_else(|| anyhow!("{} has no plugin.yml or paper-plugin.yml", plugin.display()))?;
+        println!("🔌 Installed {} from {}", name, plugin.display());
+        expected.push(name);
+    }
+
+    let mut enabled = Vec::new();
+    let report = boot_and_stop(
+        server_dir,
+        &jar_file,
+        ram,
+        &[],
+        &Default::default(),
+        Duration::from_secs(timeout),
+        |line| {
+            println!("{}", line);
+            for name in &expected {
+                if line.contains(&format!("Enabling {} v", name)) && !enabled.contains(name) {
+                    enabled.push(name.clone());
+                }
+            }
+        },
+    ).await?;
+
+    let mut failures = Vec::new();
+
+    if report.ready {
+        println!("✅ Server booted in {:.1}s", report.duration_ms as f64 / 1000.0);
+    } else {
+        failures.push(format!("server did not finish booting within {}s", timeout));
+    }
+
+    for name in &expected {
+        if enabled.contains(name) {
+            println!("✅ {} enabled", name);
+        } else {
+            failures.push(format!("{} was never enabled", name));
+        }
+    }
+
+    if !report.errors.is_empty() {
+        println!("❌ {} error line(s) in the server log:", report.errors.len());
+        for error in &report.errors {
+            println!("  • {}", error);
+        }
+        failures.push(format!("{} error line(s) logged", report.errors.len()));
+    }
+
+    if !failures.is_empty() {
+        return Err(anyhow!("Test server failed: {}", failures.join("; ")));
+    }
+
+    println!("🎉 All checks passed");
+    Ok(())
+}
@@ -0,0 +1,387 @@
+use crate::server::{load_server_config, save_server_config, ServerConfig};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+const SSDP_ADDRESS: &str = "239.255.255.250:1900";
+const SSDP_TIMEOUT_SECS: u64 = 3;
+const NATPMP_PORT: u16 = 5351;
+const NATPMP_ATTEMPTS: u32 = 4;
+// NAT-PMP leases are finite, the daemon renews them at half their lifetime (RFC 6886 recommends two hours)
+const NATPMP_LIFETIME_SECS: u32 = 7200;
+
+const IGD_SERVICES: &[&str] = &[
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1"
+];
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PortMapping {
+    pub method: String,
+    pub external_port: u16,
+    #[serde(default)]
+    pub external_ip: Option<String>,
+    #[serde(default)]
+    pub control_url: Option<String>,
+    #[serde(default)]
+    pub service_type: Option<String>,
+    #[serde(default)]
+    pub remove_on_stop: bool
+}
+
+struct Gateway {
+    control_url: String,
+    service_type: String,
+    local_ip: IpAddr
+}
+
+pub async fn expose_server(server_name: &str, remove: bool, until_stop: bool) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+
+    if remove {
+        return match config.exposure.take() {
+            Some(mapping) => {
+                remove_mapping(&config, &mapping).await?;
+                save_server_config(&config)?;
+                println!("✅ Removed port mapping for '{}'", server_name);
+                Ok(())
+            }
+            None => Err(anyhow!("Server '{}' is not exposed", server_name))
+        };
+    }
+
+    if let Some(mapping) = &config.exposure {
+        println!("ℹ️  Server '{}' is already exposed via {}", server_name, mapping.method);
+        print_address(mapping);
+        return Ok(());
+    }
+
+    println!("🔍 Looking for a UPnP gateway...");
+    let mut mapping = match map_upnp(&config).await {
+        Ok(mapping) => mapping,
+        Err(upnp_error) => {
+            println!("⚠️  UPnP failed: {}", upnp_error);
+            println!("🔍 Trying NAT-PMP...");
+            map_natpmp(config.port).await.map_err(|natpmp_error| {
+                anyhow!(
+                    "Could not map port {} (UPnP: {}; NAT-PMP: {}). Enable UPnP on your router or forward the port manually",
+                    config.port,
+                    upnp_error,
+                    natpmp_error
+                )
+            })?
+        }
+    };
+    mapping.remove_on_stop = until_stop;
+
+    println!("✅ Exposed '{}' via {}", server_name, mapping.method);
+    print_address(&mapping);
+    if mapping.method == "nat-pmp" {
+        println!("💡 NAT-PMP mappings expire after {} hours unless 'anvil daemon' is running to renew them", NATPMP_LIFETIME_SECS / 3600);
+    }
+    if until_stop {
+        println!("💡 The mapping will be removed when the server stops");
+    } else {
+        println!("💡 Remove it with: anvil expose {} --remove", server_name);
+    }
+
+    config.exposure = Some(mapping);
+    save_server_config(&config)?;
+
+    Ok(())
+}
+
+fn print_address(mapping: &PortMapping) {
+    match &mapping.external_ip {
+        Some(ip) => println!("🌐 Public address: {}:{}", ip, mapping.external_port),
+        None => println!("🌐 Public port: {} (the router did not report its external IP)", mapping.external_port)
+    }
+}
+
+pub async fn remove_on_stop(server_name: &str) {
+    let Ok(mut config) = load_server_config(server_name) else {
+        return;
+    };
+    let Some(mapping) = config.exposure.take().filter(|mapping| mapping.remove_on_stop) else {
+        return;
+    };
+
+    match remove_mapping(&config, &mapping).await {
+        Ok(()) => println!("🔒 Removed port mapping for '{}'", server_name),
+        Err(e) => println!("⚠️  Could not remove port mapping for '{}': {}", server_name, e)
+    }
+
+    if let Err(e) = save_server_config(&config) {
+        println!("⚠️  Could not save config for '{}': {}", server_name, e);
+    }
+}
+
+// Runs for the lifetime of the daemon, renewing NAT-PMP leases before the gateway drops them
+pub async fn renew_natpmp_mappings() {
+    loop {
+        match crate::server::get_all_servers() {
+            Ok(servers) => {
+                for mut config in servers {
+                    let Some(mapping) = config.exposure.as_mut().filter(|mapping| mapping.method == "nat-pmp") else {
+                        continue;
+                    };
+                    match natpmp_map(config.port, mapping.external_port, NATPMP_LIFETIME_SECS).await {
+                        // The gateway may hand out another port when the old one was taken meanwhile
+                        Ok(external_port) if external_port != mapping.external_port => {
+                            println!("⚠️  The gateway moved '{}' to public port {}", config.name, external_port);
+                            mapping.external_port = external_port;
+                            if let Err(e) = save_server_config(&config) {
+                                println!("⚠️  Could not save config for '{}': {}", config.name, e);
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(e) => println!("⚠️  Could not renew the NAT-PMP mapping of '{}': {}", config.name, e)
+                    }
+                }
+            }
+            Err(e) => println!("⚠️  NAT-PMP renewal error: {}", e)
+        }
+
+        tokio::time::sleep(Duration::from_secs(u64::from(NATPMP_LIFETIME_SECS / 2))).await;
+    }
+}
+
+async fn remove_mapping(config: &ServerConfig, mapping: &PortMapping) -> Result<()> {
+    match mapping.method.as_str() {
+        "upnp" => {
+            let control_url = mapping.control_url.as_deref().ok_or_else(|| anyhow!("Mapping has no UPnP control URL"))?;
+            let service_type = mapping.service_type.as_deref().ok_or_else(|| anyhow!("Mapping has no UPnP service type"))?;
+            let external_port = mapping.external_port.to_string();
+            soap_request(control_url, service_type, "DeletePortMapping", &[
+                ("NewRemoteHost", ""),
+                ("NewExternalPort", &external_port),
+                ("NewProtocol", "TCP")
+            ]).await?;
+            Ok(())
+        }
+        "nat-pmp" => {
+            natpmp_map(config.port, 0, 0).await?;
+            Ok(())
+        }
+        other => Err(anyhow!("Unknown port mapping method: {}", other))
+    }
+}
+
+async fn map_upnp(config: &ServerConfig) -> Result<PortMapping> {
+    let gateway = discover_upnp().await?;
+
+    let port = config.port.to_string();
+    let local_ip = gateway.local_ip.to_string();
+    let description = format!("anvil {}", config.name);
+    soap_request(&gateway.control_url, &gateway.service_type, "AddPortMapping", &[
+        ("NewRemoteHost", ""),
+        ("NewExternalPort", &port),
+        ("NewProtocol", "TCP"),
+        ("NewInternalPort", &port),
+        ("NewInternalClient", &local_ip),
+        ("NewEnabled", "1"),
+        ("NewPortMappingDescription", &description),
+        ("NewLeaseDuration", "0")
+    ]).await?;
+
+    let external_ip = soap_request(&gateway.control_url, &gateway.service_type, "GetExternalIPAddress", &[])
+        .await
+        .ok()
+        .and_then(|response| xml_value(&response, "NewExternalIPAddress"))
+        .filter(|ip| !ip.is_empty());
+
+    Ok(PortMapping {
+        method: "upnp".to_string(),
+        external_port: config.port,
+        external_ip,
+        control_url: Some(gateway.control_url),
+        service_type: Some(gateway.service_type),
+        remove_on_stop: false
+    })
+}
+
+async fn discover_upnp() -> Result<Gateway> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    let search = format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: {}\r\nST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\nMAN: \"ssdp:discover\"\r\nMX: 2\r\n\r\n",
+        SSDP_ADDRESS
+    );
+    socket.send_to(search.as_bytes(), SSDP_ADDRESS).await?;
+
+    let mut buffer = [0u8; 2048];
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(SSDP_TIMEOUT_SECS);
+
+    loop {
+        let (length, _) = tokio::time::timeout_at(deadline, socket.recv_from(&mut buffer))
+            .await
+            .map_err(|_| anyhow!("no gateway answered the SSDP search"))??;
+
+        let response = String::from_utf8_lossy(&buffer[..length]);
+        let Some(location) = header_value(&response, "location") else {
+            continue;
+        };
+
+        if let Ok(gateway) = describe_gateway(&location).await {
+            return Ok(gateway);
+        }
+    }
+}
+
+async fn describe_gateway(location: &str) -> Result<Gateway> {
+    let base = reqwest::Url::parse(location)?;
+    let description = reqwest::get(base.clone()).await?.error_for_status()?.text().await?;
+
+    for block in description.split("<service>").skip(1) {
+        let Some(service_type) = xml_value(block, "serviceType") else {
+            continue;
+        };
+        if !IGD_SERVICES.contains(&service_type.as_str()) {
+            continue;
+        }
+
+        let control_url = xml_value(block, "controlURL").ok_or_else(|| anyhow!("{} has no controlURL", service_type))?;
+        let host = base.host_str().ok_or_else(|| anyhow!("Gateway location has no host"))?;
+        let port = base.port_or_known_default().unwrap_or(80);
+
+        return Ok(Gateway {
+            control_url: base.join(&control_url)?.to_string(),
+            service_type,
+            local_ip: local_ip_towards(&format!("{}:{}", host, port)).await?
+        });
+    }
+
+    Err(anyhow!("{} does not offer a WAN connection service", location))
+}
+
+async fn soap_request(control_url: &str, service_type: &str, action: &str, args: &[(&str, &str)]) -> Result<String> {
+    let arguments: String = args
+        .iter()
+        .map(|(name, value)| format!("<{0}>{1}</{0}>", name, value))
+        .collect();
+    let body = format!(
+        "<?xml version=\"1.0\"?><s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+         s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\"><s:Body>\
+         <u:{0} xmlns:u=\"{1}\">{2}</u:{0}></s:Body></s:Envelope>",
+        action, service_type, arguments
+    );
+
+    let response = reqwest::Client::new()
+        .post(control_url)
+        .header("Content-Type", "text/xml; charset=\"utf-8\"")
+        .header("SOAPAction", format!("\"{}#{}\"", service_type, action))
+        .body(body)
+        .send()
+        .await?;
+
+    let status = response.status();
+    let text = response.text().await?;
+    if !status.is_success() {
+        let reason = xml_value(&text, "errorDescription").unwrap_or_else(|| status.to_string());
+        return Err(anyhow!("{} was refused: {}", action, reason));
+    }
+
+    Ok(text)
+}
+
+async fn map_natpmp(port: u16) -> Result<PortMapping> {
+    let external_port = natpmp_map(port, port, NATPMP_LIFETIME_SECS).await?;
+
+    let external_ip = natpmp_request(&[0, 0], 12)
+        .await
+        .ok()
+        .map(|response| Ipv4Addr::new(response[8], response[9], response[10], response[11]).to_string());
+
+    Ok(PortMapping {
+        method: "nat-pmp".to_string(),
+        external_port,
+        external_ip,
+        control_url: None,
+        service_type: None,
+        remove_on_stop: false
+    })
+}
+
+async fn natpmp_map(internal_port: u16, external_port: u16, lifetime: u32) -> Result<u16> {
+    let mut request = vec![0, 2, 0, 0];
+    request.extend_from_slice(&internal_port.to_be_bytes());
+    request.extend_from_slice(&external_port.to_be_bytes());
+    request.extend_from_slice(&lifetime.to_be_bytes());
+
+    let response = natpmp_request(&request, 16).await?;
+    Ok(u16::from_be_bytes([response[10], response[11]]))
+}
+
+async fn natpmp_request(request: &[u8], response_length: usize) -> Result<Vec<u8>> {
+    let gateway = default_gateway()?;
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.connect(SocketAddr::new(IpAddr::V4(gateway), NATPMP_PORT)).await?;
+
+    let mut buffer = [0u8; 16];
+    let mut wait = Duration::from_millis(250);
+
+    for _ in 0..NATPMP_ATTEMPTS {
+        socket.send(request).await?;
+
+        if let Ok(received) = tokio::time::timeout(wait, socket.recv(&mut buffer)).await {
+            let length = received?;
+            if length < response_length || buffer[1] != request[1] + 128 {
+                return Err(anyhow!("unexpected response from gateway {}", gateway));
+            }
+
+            let result = u16::from_be_bytes([buffer[2], buffer[3]]);
+            if result != 0 {
+                return Err(anyhow!("gateway {} refused the request (result code {})", gateway, result));
+            }
+
+            return Ok(buffer[..length].to_vec());
+        }
+
+        wait *= 2;
+    }
+
+    Err(anyhow!("gateway {} did not answer", gateway))
+}
+
+#[cfg(target_os = "linux")]
+fn default_gateway() -> Result<Ipv4Addr> {
+    let routes = std::fs::read_to_string("/proc/net/route")?;
+
+    routes
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            (fields.len() > 2 && fields[1] == "00000000").then(|| fields[2])
+        })
+        .filter_map(|gateway| u32::from_str_radix(gateway, 16).ok())
+        .map(|gateway| Ipv4Addr::from(gateway.to_le_bytes()))
+        .find(|gateway| !gateway.is_unspecified())
+        .ok_or_else(|| anyhow!("no default gateway found"))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn default_gateway() -> Result<Ipv4Addr> {
+    Err(anyhow!("default gateway discovery is only supported on Linux"))
+}
+
+pub async fn local_ip_towards(address: &str) -> Result<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.connect(address).await?;
+    Ok(socket.local_addr()?.ip())
+}
+
+fn header_value(response: &str, name: &str) -> Option<String> {
+    response.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim().eq_ignore_ascii_case(name).then(|| value.trim().to_string())
+    })
+}
+
+fn xml_value(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = xml[start..].find(&format!("</{}>", tag))? + start;
+    Some(xml[start..end].trim().to_string())
+}
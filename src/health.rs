@@ -0,0 +1,242 @@
+use crate::server::{
+    get_all_servers, is_server_running, kill_server, load_server_config, save_server_config, spawn_detached,
+    stop_server, ServerConfig,
+};
+use crate::HealthAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const HEALTH_TICK_SECS: u64 = 5;
+const PING_TIMEOUT_SECS: u64 = 5;
+const GRACEFUL_STOP_SECS: u64 = 30;
+const MAX_STATUS_BYTES: usize = 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HealthConfig {
+    pub interval_secs: u64,
+    pub failure_threshold: u32,
+    pub ram: u8
+}
+
+pub struct StatusPing {
+    pub latency: Duration,
+    pub online: u64,
+    pub max: u64,
+    pub version: String
+}
+
+#[derive(Default)]
+struct HealthState {
+    last_check: Option<Instant>,
+    failures: u32
+}
+
+pub async fn handle_health_action(action: HealthAction) -> Result<()> {
+    match action {
+        HealthAction::Enable { server, interval, threshold, ram } => {
+            if interval == 0 || threshold == 0 {
+                return Err(anyhow!("The check interval and failure threshold must be at least 1"));
+            }
+
+            let mut config = load_server_config(&server)?;
+            config.health = Some(HealthConfig { interval_secs: interval, failure_threshold: threshold, ram });
+            save_server_config(&config)?;
+
+            println!(
+                "✅ anvil daemon will ping '{}' every {}s and restart it after {} failed check(s)",
+                server, interval, threshold
+            );
+            println!("💡 Health checks only run while 'anvil daemon' is running");
+        }
+        HealthAction::Disable { server } => {
+            let mut config = load_server_config(&server)?;
+            if config.health.take().is_none() {
+                return Err(anyhow!("Health checks are not enabled for '{}'", server));
+            }
+            save_server_config(&config)?;
+            println!("✅ Disabled health checks for '{}'", server);
+        }
+        HealthAction::Check { server } => {
+            let config = load_server_config(&server)?;
+            if !is_server_running(&config).await {
+                return Err(anyhow!("Server '{}' is not running", server));
+            }
+
+            match ping(&config).await {
+                Ok(status) => {
+                    println!("💚 '{}' answered in {}ms", server, status.latency.as_millis());
+                    println!(" - Version: {}", status.version);
+                    println!(" - Players: {}/{}", status.online, status.max);
+                }
+                Err(e) => println!("💔 '{}' accepts connections but did not answer a status ping: {}", server, e)
+            }
+        }
+    }
+    Ok(())
+}
+
+// Runs for the lifetime of the daemon, checking each server on its own interval
+pub async fn run_health_checks() {
+    let mut states: HashMap<String, HealthState> = HashMap::new();
+
+    loop {
+        match get_all_servers() {
+            Ok(servers) => {
+                for config in servers {
+                    let Some(health) = config.health.clone() else {
+                        states.remove(&config.name);
+                        continue;
+                    };
+
+                    let state = states.entry(config.name.clone()).or_default();
+                    if state.last_check.is_some_and(|last| last.elapsed().as_secs() < health.interval_secs) {
+                        continue;
+                    }
+                    state.last_check = Some(Instant::now());
+
+                    check_server(&config, &health, state).await;
+                }
+            }
+            Err(e) => println!("⚠️  Health check error: {}", e)
+        }
+
+        tokio::time::sleep(Duration::from_secs(HEALTH_TICK_SECS)).await;
+    }
+}
+
+async fn check_server(config: &ServerConfig, health: &HealthConfig, state: &mut HealthState) {
+    // A closed port means the process is gone or still booting, which is not a hang
+    if !is_server_running(config).await {
+        state.failures = 0;
+        return;
+    }
+
+    match ping(config).await {
+        Ok(_) => {
+            if state.failures > 0 {
+                println!("💚 '{}' is responding again", config.name);
+            }
+            state.failures = 0;
+            return;
+        }
+        Err(e) => {
+            state.failures += 1;
+            println!(
+                "💔 '{}' failed a health check ({}/{}): {}",
+                config.name, state.failures, health.failure_threshold, e
+            );
+        }
+    }
+
+    if state.failures < health.failure_threshold {
+        return;
+    }
+    state.failures = 0;
+
+    println!("🔄 '{}' stopped responding, restarting it...", config.name);
+    if let Err(e) = restart_hung_server(config, health.ram).await {
+        println!("❌ Could not restart '{}': {}", config.name, e);
+    }
+}
+
+async fn restart_hung_server(config: &ServerConfig, ram: u8) -> Result<()> {
+    // A hung JVM rarely answers RCON either, so the graceful stop gets a short leash
+    let stopped = tokio::time::timeout(Duration::from_secs(GRACEFUL_STOP_SECS), stop_server(config)).await;
+    if !matches!(stopped, Ok(Ok(()))) {
+        println!("⚠️  '{}' did not stop gracefully, killing the process", config.name);
+        kill_server(config).await?;
+    }
+
+    spawn_detached(config, ram)?;
+    println!("🎮 Restarted '{}' in the background", config.name);
+    Ok(())
+}
+
+pub async fn ping(config: &ServerConfig) -> Result<StatusPing> {
+    tokio::time::timeout(Duration::from_secs(PING_TIMEOUT_SECS), status_request(config.port))
+        .await
+        .map_err(|_| anyhow!("no answer within {}s", PING_TIMEOUT_SECS))?
+}
+
+async fn status_request(port: u16) -> Result<StatusPing> {
+    let started = Instant::now();
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await?;
+
+    // Handshake with protocol -1 and next state 1 (status), then an empty status request
+    let mut handshake = vec![0x00];
+    write_varint(&mut handshake, -1);
+    write_varint(&mut handshake, "127.0.0.1".len() as i32);
+    handshake.extend_from_slice(b"127.0.0.1");
+    handshake.extend_from_slice(&port.to_be_bytes());
+    write_varint(&mut handshake, 1);
+
+    let mut packets = Vec::new();
+    write_varint(&mut packets, handshake.len() as i32);
+    packets.extend_from_slice(&handshake);
+    packets.extend_from_slice(&[0x01, 0x00]);
+    stream.write_all(&packets).await?;
+
+    let length = read_varint(&mut stream).await? as usize;
+    if length > MAX_STATUS_BYTES {
+        return Err(anyhow!("status response of {} bytes is too large", length));
+    }
+    let mut body = vec![0; length];
+    stream.read_exact(&mut body).await?;
+    let latency = started.elapsed();
+
+    let mut cursor = body.as_slice();
+    if read_varint_slice(&mut cursor)? != 0x00 {
+        return Err(anyhow!("unexpected status response packet"));
+    }
+    let json_length = read_varint_slice(&mut cursor)? as usize;
+    let json = cursor.get(..json_length).ok_or_else(|| anyhow!("truncated status response"))?;
+    let status: serde_json::Value = serde_json::from_slice(json)?;
+
+    Ok(StatusPing {
+        latency,
+        online: status["players"]["online"].as_u64().unwrap_or(0),
+        max: status["players"]["max"].as_u64().unwrap_or(0),
+        version: status["version"]["name"].as_str().unwrap_or("unknown").to_string()
+    })
+}
+
+pub fn write_varint(out: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7f == 0 {
+            out.push(value as u8);
+            return;
+        }
+        out.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+}
+
+pub async fn read_varint(stream: &mut TcpStream) -> Result<i32> {
+    let mut value = 0u32;
+    for position in 0..5 {
+        let byte = stream.read_u8().await?;
+        value |= ((byte & 0x7f) as u32) << (position * 7);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(anyhow!("malformed varint in status response"))
+}
+
+pub fn read_varint_slice(cursor: &mut &[u8]) -> Result<i32> {
+    let mut value = 0u32;
+    for position in 0..5 {
+        let (&byte, rest) = cursor.split_first().ok_or_else(|| anyhow!("truncated status response"))?;
+        *cursor = rest;
+        value |= ((byte & 0x7f) as u32) << (position * 7);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(anyhow!("malformed varint in status response"))
+}
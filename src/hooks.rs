@@ -0,0 +1,156 @@
+use crate::server::{load_server_config, save_server_config, ServerConfig};
+use crate::HookAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Hooks {
+    pub on_start: Option<String>,
+    pub on_stop: Option<String>,
+    pub on_crash: Option<String>,
+    pub on_backup_complete: Option<String>
+}
+
+#[derive(Clone, Copy)]
+pub enum HookEvent {
+    Start,
+    Stop,
+    Crash,
+    BackupComplete
+}
+
+impl HookEvent {
+    const ALL: [HookEvent; 4] = [
+        HookEvent::Start,
+        HookEvent::Stop,
+        HookEvent::Crash,
+        HookEvent::BackupComplete
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::Start => "on_start",
+            HookEvent::Stop => "on_stop",
+            HookEvent::Crash => "on_crash",
+            HookEvent::BackupComplete => "on_backup_complete"
+        }
+    }
+
+    fn parse(name: &str) -> Result<Self> {
+        HookEvent::ALL
+            .into_iter()
+            .find(|event| event.as_str() == name)
+            .ok_or_else(|| anyhow!(
+                "Unknown hook event: {} (expected one of on_start, on_stop, on_crash, on_backup_complete)",
+                name
+            ))
+    }
+}
+
+impl Hooks {
+    pub fn configured(&self) -> Vec<&'static str> {
+        HookEvent::ALL
+            .into_iter()
+            .filter(|event| self.get(*event).is_some())
+            .map(|event| event.as_str())
+            .collect()
+    }
+
+    fn get(&self, event: HookEvent) -> Option<&String> {
+        match event {
+            HookEvent::Start => self.on_start.as_ref(),
+            HookEvent::Stop => self.on_stop.as_ref(),
+            HookEvent::Crash => self.on_crash.as_ref(),
+            HookEvent::BackupComplete => self.on_backup_complete.as_ref()
+        }
+    }
+
+    fn slot(&mut self, event: HookEvent) -> &mut Option<String> {
+        match event {
+            HookEvent::Start => &mut self.on_start,
+            HookEvent::Stop => &mut self.on_stop,
+            HookEvent::Crash => &mut self.on_crash,
+            HookEvent::BackupComplete => &mut self.on_backup_complete
+        }
+    }
+}
+
+pub async fn handle_hook_action(server_name: &str, action: HookAction) -> Result<()> {
+    match action {
+        HookAction::Set { event, command } => {
+            let mut config = load_server_config(server_name)?;
+            let event = HookEvent::parse(&event)?;
+            *config.hooks.slot(event) = Some(command.clone());
+            save_server_config(&config)?;
+            println!("✅ {} hook for '{}' set to: {}", event.as_str(), server_name, command);
+        }
+        HookAction::Remove { event } => {
+            let mut config = load_server_config(server_name)?;
+            let event = HookEvent::parse(&event)?;
+            if config.hooks.slot(event).take().is_none() {
+                println!("❌ No {} hook configured for '{}'", event.as_str(), server_name);
+                return Ok(());
+            }
+            save_server_config(&config)?;
+            println!("🗑️  Removed {} hook from '{}'", event.as_str(), server_name);
+        }
+        HookAction::List => {
+            let config = load_server_config(server_name)?;
+            println!("🪝 Hooks for server '{}':", server_name);
+            for event in HookEvent::ALL {
+                match config.hooks.get(event) {
+                    Some(command) => println!("  • {}: {}", event.as_str(), command),
+                    None => println!("  • {}: (not set)", event.as_str())
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn run_hook(config: &ServerConfig, event: HookEvent, extra_env: &[(&str, String)]) {
+    let Some(hook) = config.hooks.get(event) else {
+        return;
+    };
+
+    let mut command = shell_command(config, hook);
+    command.env("ANVIL_EVENT", event.as_str());
+
+    for (key, value) in extra_env {
+        command.env(key, value);
+    }
+
+    match command.status().await {
+        Ok(status) if status.success() => {}
+        Ok(status) => println!("⚠️  {} hook exited with {}", event.as_str(), status),
+        Err(e) => println!("⚠️  Failed to run {} hook: {}", event.as_str(), e)
+    }
+}
+
+// A shell running the given script in the server directory, with the server described in ANVIL_* variables
+pub fn shell_command(config: &ServerConfig, script: &str) -> Command {
+    #[cfg(windows)]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.args(["/C", script]);
+        command
+    };
+
+    #[cfg(unix)]
+    let mut command = {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    };
+
+    command
+        .current_dir(&config.path)
+        .env("ANVIL_SERVER_NAME", &config.name)
+        .env("ANVIL_SERVER_DIR", &config.path)
+        .env("ANVIL_SERVER_TYPE", &config.server_type)
+        .env("ANVIL_SERVER_VERSION", &config.version)
+        .env("ANVIL_SERVER_PORT", config.port.to_string());
+    command
+}
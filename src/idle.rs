@@ -0,0 +1,260 @@
+use crate::server::{
+    get_all_servers, is_server_running, load_server_config, save_server_config, spawn_detached, stop_server,
+    ServerConfig,
+};
+use crate::IdleAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+const IDLE_TICK_SECS: u64 = 30;
+const LISTEN_POLL_SECS: u64 = 2;
+// Present while the server is stopped and the daemon holds its port to wake it on the next join
+const SLEEP_FILE: &str = ".anvil-sleeping";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct IdleConfig {
+    pub timeout_mins: u64,
+    pub wake: bool,
+    pub ram: u8
+}
+
+pub async fn handle_idle_action(action: IdleAction) -> Result<()> {
+    match action {
+        IdleAction::Enable { server, minutes, wake, ram } => {
+            if minutes == 0 {
+                return Err(anyhow!("The idle timeout must be at least 1 minute"));
+            }
+
+            let mut config = load_server_config(&server)?;
+            config.idle = Some(IdleConfig { timeout_mins: minutes, wake, ram });
+            save_server_config(&config)?;
+
+            println!("✅ anvil daemon will stop '{}' after {} minute(s) without players", server, minutes);
+            if wake {
+                println!("💤 While stopped, the first player who tries to join starts it again");
+            }
+            println!("💡 Idle shutdown only runs while 'anvil daemon' is running");
+        }
+        IdleAction::Disable { server } => {
+            let mut config = load_server_config(&server)?;
+            if config.idle.take().is_none() {
+                return Err(anyhow!("Idle shutdown is not enabled for '{}'", server));
+            }
+            save_server_config(&config)?;
+            // The daemon lets go of the port once the file is gone
+            let _ = fs::remove_file(sleep_file(&config));
+            println!("✅ Disabled idle shutdown for '{}'", server);
+        }
+        IdleAction::Listen { server, ram } => {
+            listen(&server, ram).await?;
+        }
+    }
+    Ok(())
+}
+
+fn sleep_file(config: &ServerConfig) -> PathBuf {
+    config.path.join(SLEEP_FILE)
+}
+
+pub fn is_sleeping(config: &ServerConfig) -> bool {
+    sleep_file(config).exists()
+}
+
+// Takes the port back from the daemon before a launch, otherwise the server could not bind it
+pub fn release_port(config: &ServerConfig) -> Result<()> {
+    if fs::remove_file(sleep_file(config)).is_err() {
+        return Ok(());
+    }
+
+    for _ in 0..10 {
+        if std::net::TcpStream::connect(("127.0.0.1", config.port)).is_err() {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_secs(1));
+    }
+    Err(anyhow!("Port {} is still held by the anvil daemon, try again in a moment", config.port))
+}
+
+// Runs for the lifetime of the daemon, stopping servers that stay empty and waking sleeping ones
+pub async fn run_idle_checks() {
+    let mut empty_since: HashMap<String, Instant> = HashMap::new();
+    let mut listeners: HashMap<String, JoinHandle<()>> = HashMap::new();
+
+    loop {
+        listeners.retain(|_, listener| !listener.is_finished());
+
+        match get_all_servers() {
+            Ok(servers) => {
+                for config in servers {
+                    // Ram to boot with on a join, None when joins are only turned away
+                    let wake = config.idle.as_ref().filter(|idle| idle.wake).map(|idle| idle.ram);
+                    let offline_motd = config.proxy.as_ref().is_some_and(|proxy| proxy.offline_motd.is_some());
+
+                    if is_sleeping(&config) {
+                        if wake.is_none() && !offline_motd {
+                            let _ = fs::remove_file(sleep_file(&config));
+                        } else if !listeners.contains_key(&config.name) {
+                            // A daemon restart loses the listener, so it is picked up again from the sleep file
+                            let name = config.name.clone();
+                            listeners.insert(name, tokio::spawn(hold_port(config, wake)));
+                        }
+                        continue;
+                    }
+
+                    if !is_server_running(&config).await {
+                        empty_since.remove(&config.name);
+                        // Stopped by hand or crashed, pings get the offline MOTD until it is back
+                        if offline_motd && !is_starting(&config).await {
+                            put_to_sleep(&mut listeners, config, wake);
+                        }
+                        continue;
+                    }
+
+                    let Some(idle) = config.idle.clone() else {
+                        continue;
+                    };
+
+                    match online_players(&config).await {
+                        Ok(0) => {}
+                        Ok(_) => {
+                            empty_since.remove(&config.name);
+                            continue;
+                        }
+                        Err(e) => {
+                            println!("⚠️  Could not count the players on '{}': {}", config.name, e);
+                            continue;
+                        }
+                    }
+
+                    let since = *empty_since.entry(config.name.clone()).or_insert_with(Instant::now);
+                    if since.elapsed().as_secs() < idle.timeout_mins * 60 {
+                        continue;
+                    }
+                    empty_since.remove(&config.name);
+
+                    println!("💤 '{}' has been empty for {} minute(s), stopping it", config.name, idle.timeout_mins);
+                    if let Err(e) = stop_server(&config).await {
+                        println!("❌ Could not stop '{}': {}", config.name, e);
+                        continue;
+                    }
+
+                    if wake.is_some() || offline_motd {
+                        put_to_sleep(&mut listeners, config, wake);
+                    }
+                }
+            }
+            Err(e) => println!("⚠️  Idle check error: {}", e)
+        }
+
+        tokio::time::sleep(Duration::from_secs(IDLE_TICK_SECS)).await;
+    }
+}
+
+async fn online_players(config: &ServerConfig) -> Result<u64> {
+    if let Ok(status) = crate::health::ping(config).await {
+        return Ok(status.online);
+    }
+
+    // Servers with the status ping disabled still answer on RCON
+    let mut client = crate::rcon::connect(config).await?;
+    let (_, players) = crate::players::online_players(&mut client).await?;
+    Ok(players.len() as u64)
+}
+
+// A launched server has not bound its port yet while it boots, the listener must not take it
+async fn is_starting(config: &ServerConfig) -> bool {
+    crate::server::launcher_alive(config).await
+}
+
+fn put_to_sleep(listeners: &mut HashMap<String, JoinHandle<()>>, config: ServerConfig, wake: Option<u8>) {
+    if let Err(e) = fs::write(sleep_file(&config), "") {
+        println!("⚠️  Could not hold the port of '{}': {}", config.name, e);
+        return;
+    }
+    let name = config.name.clone();
+    listeners.insert(name, tokio::spawn(hold_port(config, wake)));
+}
+
+async fn hold_port(config: ServerConfig, wake: Option<u8>) {
+    match wake {
+        Some(_) => println!("💤 '{}' is asleep, the next join on port {} starts it", config.name, config.port),
+        None => println!("📴 '{}' is down, port {} answers with its offline MOTD", config.name, config.port)
+    }
+    // The sleep file goes away on a manual start or once nothing needs the port held
+    let Some(ram) = wake else {
+        if let Err(e) = crate::wake::hold_port(&config, false, || is_sleeping(&config)).await {
+            println!("⚠️  {}", e);
+            let _ = fs::remove_file(sleep_file(&config));
+        }
+        return;
+    };
+    match crate::wake::hold_port(&config, true, || is_sleeping(&config)).await {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(e) => {
+            // Usually the server was started outside anvil and already owns the port
+            println!("⚠️  {}", e);
+            let _ = fs::remove_file(sleep_file(&config));
+            return;
+        }
+    }
+
+    let _ = fs::remove_file(sleep_file(&config));
+    println!("🔔 A player is joining '{}', starting it", config.name);
+    if let Err(e) = spawn_detached(&config, ram) {
+        println!("❌ Could not start '{}': {}", config.name, e);
+    }
+}
+
+// Foreground listener mode: holds the port whenever the server is down and boots it on the next join
+async fn listen(name: &str, ram: u8) -> Result<()> {
+    let config = load_server_config(name)?;
+    println!("👂 Holding port {} for '{}' whenever it is stopped (Ctrl-C to quit)", config.port, name);
+
+    let listening = async {
+        loop {
+            if is_server_running(&config).await || is_starting(&config).await {
+                tokio::time::sleep(Duration::from_secs(LISTEN_POLL_SECS)).await;
+                continue;
+            }
+
+            fs::write(sleep_file(&config), "")?;
+            println!("💤 '{}' is asleep, the next join on port {} starts it", name, config.port);
+            let joined = crate::wake::hold_port(&config, true, || is_sleeping(&config)).await;
+            let _ = fs::remove_file(sleep_file(&config));
+            if !joined? {
+                println!("🎮 '{}' was started by hand", name);
+                // Give the launch time to record itself before the port is watched again
+                tokio::time::sleep(Duration::from_secs(LISTEN_POLL_SECS * 5)).await;
+                continue;
+            }
+
+            println!("🔔 A player is joining '{}', starting it", name);
+            let mut child = spawn_detached(&config, ram)?;
+            // Until the server binds the port nothing may take it back
+            while !is_server_running(&config).await {
+                if child.try_wait()?.is_some() {
+                    let log = crate::server::console_log_path(&config);
+                    println!("❌ '{}' exited before finishing startup, see {}", name, log.display());
+                    break;
+                }
+                tokio::time::sleep(Duration::from_secs(LISTEN_POLL_SECS)).await;
+            }
+        }
+    };
+
+    let result: Result<()> = tokio::select! {
+        result = listening => result,
+        _ = tokio::signal::ctrl_c() => {
+            println!("👋 Released port {}", config.port);
+            Ok(())
+        }
+    };
+    let _ = fs::remove_file(sleep_file(&config));
+    result
+}
@@ -0,0 +1,292 @@
+use crate::properties::PropertiesFile;
+use crate::server::{
+    create_server_properties, create_start_script, get_all_servers, load_server_config, read_server_properties,
+    save_server_config, ServerConfig,
+};
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const SOURCES: &[&str] = &["mcman", "msm", "pterodactyl"];
+const DEFAULT_PORT: u16 = 25565;
+
+// What an importer could learn from the other tool's metadata; anything missing is detected from the files
+#[derive(Default)]
+struct ImportPlan {
+    name: Option<String>,
+    server_dir: PathBuf,
+    server_type: Option<String>,
+    version: Option<String>,
+    jar_file: Option<String>,
+    ram_mb: Option<u64>,
+    notes: Vec<String>
+}
+
+#[derive(Deserialize)]
+struct McmanServer {
+    name: Option<String>,
+    mc_version: Option<String>,
+    jar: Option<toml::Value>,
+    launcher: Option<McmanLauncher>,
+    #[serde(default)]
+    plugins: Vec<toml::Value>,
+    #[serde(default)]
+    mods: Vec<toml::Value>
+}
+
+#[derive(Deserialize)]
+struct McmanLauncher {
+    memory: Option<String>
+}
+
+pub async fn import_server(
+    from: &str,
+    path: &Path,
+    name: Option<String>,
+    server_type: Option<String>,
+    version: Option<String>,
+) -> Result<()> {
+    let path = fs::canonicalize(path).map_err(|e| anyhow!("Cannot read {}: {}", path.display(), e))?;
+
+    let mut plan = match from {
+        "mcman" => plan_mcman(&path)?,
+        "msm" => plan_msm(&path)?,
+        "pterodactyl" => plan_pterodactyl(&path)?,
+        other => return Err(anyhow!("Unknown source '{}', expected one of: {}", other, SOURCES.join(", ")))
+    };
+
+    // Flags win over whatever the other tool recorded
+    if name.is_some() {
+        plan.name = name;
+    }
+    if server_type.is_some() {
+        plan.server_type = server_type;
+    }
+    if version.is_some() {
+        plan.version = version;
+    }
+
+    let name = match plan.name.clone() {
+        Some(name) => name,
+        None => {
+            let name = plan
+                .server_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .ok_or_else(|| anyhow!("Could not derive a server name, pass one with --name"))?;
+            // Panels name volumes after their internal server UUID
+            if uuid::Uuid::parse_str(&name).is_ok() {
+                plan.notes.push("The name is the panel's server UUID, pick a readable one with --name".to_string());
+            }
+            name
+        }
+    };
+    if load_server_config(&name).is_ok() {
+        return Err(anyhow!("Server '{}' already exists, pick another name with --name", name));
+    }
+    if let Some(existing) = get_all_servers()?.into_iter().find(|existing| existing.path == plan.server_dir) {
+        return Err(anyhow!("{} is already managed as '{}'", plan.server_dir.display(), existing.name));
+    }
+
+    let server_dir = plan.server_dir.clone();
+    let jar_file = match plan.jar_file.clone() {
+        Some(jar) => jar,
+        None => find_server_jar(&server_dir)?
+    };
+    if !server_dir.join(&jar_file).exists() {
+        return Err(anyhow!("{} does not contain {}", server_dir.display(), jar_file));
+    }
+
+    let server_type = match plan.server_type.clone() {
+        Some(server_type) => server_type,
+        None => detect_server_type(&server_dir, &jar_file)?
+    };
+    if !matches!(server_type.as_str(), "paper" | "vanilla") {
+        return Err(anyhow!("anvil manages Paper and vanilla servers, '{}' is {}", name, server_type));
+    }
+
+    let version = match plan.version.clone().filter(|version| version != "latest") {
+        Some(version) => version,
+        None => detect_version(&server_dir, &jar_file)
+            .ok_or_else(|| anyhow!("Could not detect the Minecraft version of '{}', pass it with --version", name))?
+    };
+
+    let port = read_server_properties(&server_dir)
+        .ok()
+        .and_then(|properties| properties.get("server-port")?.parse().ok())
+        .unwrap_or(DEFAULT_PORT);
+    if let Some(other) = get_all_servers()?.into_iter().find(|other| other.port == port) {
+        plan.notes.push(format!("Port {} is also used by '{}', change one before running both", port, other.name));
+    }
+
+    println!("📥 Importing {} server '{}' from {}", from, name, server_dir.display());
+
+    // anvil drives servers over RCON, so the imported server gets the same credentials a new one would
+    let secrets = crate::secrets::generate_server_secrets(&name, &server_type)?;
+    create_server_properties(&server_dir, port, &secrets.rcon_password.unwrap_or_default())?;
+
+    if server_dir.join(if cfg!(windows) { "start.bat" } else { "start.sh" }).exists() {
+        plan.notes.push("Kept the existing start scripts; anvil passes the RAM in GB as their first argument".to_string());
+    } else {
+        create_start_script(&server_dir, &jar_file, &version, None)?;
+    }
+
+    let config = ServerConfig::new(&name, &version, &server_type, port, server_dir, jar_file);
+    save_server_config(&config)?;
+
+    println!("✅ Imported '{}' ({} {}) on port {}", name, server_type, version, port);
+    for note in &plan.notes {
+        println!("💡 {}", note);
+    }
+    if let Some(ram_mb) = plan.ram_mb {
+        println!("💡 {} gave it {}MB of RAM: anvil start {} --ram {}", from, ram_mb, name, ram_mb.div_ceil(1024).max(1));
+    }
+
+    Ok(())
+}
+
+fn plan_mcman(path: &Path) -> Result<ImportPlan> {
+    let manifest = path.join("server.toml");
+    let contents = fs::read_to_string(&manifest)
+        .map_err(|_| anyhow!("{} is not an mcman project, server.toml is missing", path.display()))?;
+    let server: McmanServer = toml::from_str(&contents)?;
+
+    // mcman builds the runnable server into server/ from the project files
+    let server_dir = path.join("server");
+    if !server_dir.is_dir() {
+        return Err(anyhow!("{} has not been built yet, run 'mcman build' first", path.display()));
+    }
+
+    let jar_type = server.jar.as_ref().and_then(|jar| jar.get("type")).and_then(toml::Value::as_str);
+    let project = server.jar.as_ref().and_then(|jar| jar.get("project")).and_then(toml::Value::as_str);
+    let server_type = match (jar_type, project) {
+        (Some("vanilla"), _) => Some("vanilla".to_string()),
+        (Some("paper"), _) | (Some("papermc"), None | Some("paper")) => Some("paper".to_string()),
+        (Some(other), _) => Some(project.unwrap_or(other).to_string()),
+        (None, _) => None
+    };
+
+    let mut plan = ImportPlan {
+        name: server.name,
+        server_dir,
+        server_type,
+        version: server.mc_version,
+        ram_mb: server.launcher.and_then(|launcher| launcher.memory).and_then(|memory| parse_memory_mb(&memory)),
+        ..Default::default()
+    };
+
+    let addons = server.plugins.len() + server.mods.len();
+    if addons > 0 {
+        plan.notes.push(format!(
+            "{} plugin(s) and mod(s) from server.toml stay as unmanaged jars, re-add them with 'anvil plugin add' \
+             to track updates",
+            addons
+        ));
+    }
+    Ok(plan)
+}
+
+fn plan_msm(path: &Path) -> Result<ImportPlan> {
+    // MSM keeps per-server settings such as msm-ram=1024 in server.conf
+    let conf_path = path.join("server.conf");
+    if !conf_path.exists() {
+        return Err(anyhow!("{} is not an MSM server, server.conf is missing", path.display()));
+    }
+    let conf = PropertiesFile::load(&conf_path)?;
+
+    let mut plan = ImportPlan {
+        server_dir: path.to_path_buf(),
+        jar_file: conf.get("msm-jar-path"),
+        ram_mb: conf.get("msm-ram").and_then(|ram| ram.parse().ok()),
+        ..Default::default()
+    };
+
+    let storage = conf.get("msm-world-storage-path").unwrap_or_else(|| "worldstorage".to_string());
+    if path.join(&storage).is_dir() {
+        plan.notes.push(format!(
+            "MSM keeps worlds in {}/ and links them in; check the world folders resolve before deleting it",
+            storage
+        ));
+    }
+    Ok(plan)
+}
+
+fn plan_pterodactyl(path: &Path) -> Result<ImportPlan> {
+    // Volumes only hold the server files, the egg settings live in the panel database
+    if !path.join("server.properties").exists() {
+        return Err(anyhow!("{} does not look like a server volume, server.properties is missing", path.display()));
+    }
+
+    let mut plan = ImportPlan { server_dir: path.to_path_buf(), ..Default::default() };
+    plan.notes.push("Volume files belong to the panel's container user, run anvil as a user that can write them".to_string());
+    Ok(plan)
+}
+
+fn find_server_jar(server_dir: &Path) -> Result<String> {
+    let mut jars: Vec<String> = fs::read_dir(server_dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.ends_with(".jar"))
+        .collect();
+    jars.sort();
+
+    if jars.iter().any(|jar| jar == "server.jar") {
+        return Ok("server.jar".to_string());
+    }
+    match jars.as_slice() {
+        [jar] => Ok(jar.clone()),
+        [] => Err(anyhow!("{} has no server jar", server_dir.display())),
+        _ => Err(anyhow!(
+            "{} has several jars ({}), cannot tell which one is the server",
+            server_dir.display(),
+            jars.join(", ")
+        ))
+    }
+}
+
+fn detect_server_type(server_dir: &Path, jar_file: &str) -> Result<String> {
+    let jar = jar_file.to_lowercase();
+    for other in ["purpur", "spigot", "fabric", "forge", "quilt"] {
+        if jar.contains(other) {
+            return Err(anyhow!("{} looks like a {} server, which anvil does not manage", jar_file, other));
+        }
+    }
+
+    let paper = jar.contains("paper")
+        || server_dir.join("config/paper-global.yml").exists()
+        || server_dir.join("paper.yml").exists()
+        || server_dir.join("version_history.json").exists();
+    Ok(if paper { "paper" } else { "vanilla" }.to_string())
+}
+
+fn detect_version(server_dir: &Path, jar_file: &str) -> Option<String> {
+    // Paper records e.g. {"currentVersion": "git-Paper-196 (MC: 1.20.1)"} after its first start
+    let history = fs::read_to_string(server_dir.join("version_history.json")).ok();
+    let from_history = history
+        .as_deref()
+        .and_then(|history| history.split("(MC: ").nth(1))
+        .and_then(|rest| rest.split(')').next())
+        .map(str::to_string);
+    if from_history.is_some() {
+        return from_history;
+    }
+
+    // Vanilla and bundler-style jars ship a version.json whose id is the Minecraft version
+    let mut archive = zip::ZipArchive::new(fs::File::open(server_dir.join(jar_file)).ok()?).ok()?;
+    let mut contents = String::new();
+    archive.by_name("version.json").ok()?.read_to_string(&mut contents).ok()?;
+    let version: serde_json::Value = serde_json::from_str(&contents).ok()?;
+    version["id"].as_str().map(str::to_string)
+}
+
+fn parse_memory_mb(memory: &str) -> Option<u64> {
+    let memory = memory.trim().to_uppercase();
+    let (amount, multiplier) = match memory.chars().last()? {
+        'G' => (&memory[..memory.len() - 1], 1024),
+        'M' => (&memory[..memory.len() - 1], 1),
+        _ => (memory.as_str(), 1)
+    };
+    Some(amount.parse::<u64>().ok()? * multiplier)
+}
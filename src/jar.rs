@@ -0,0 +1,80 @@
+use crate::yaml::{unquote, YamlDocument};
+use anyhow::Result;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+const DESCRIPTOR_FILES: &[&str] = &["paper-plugin.yml", "plugin.yml"];
+
+pub struct PluginDescriptor {
+    pub name: String,
+    pub version: String,
+    pub api_version: Option<String>,
+    pub authors: Vec<String>,
+    pub depend: Vec<String>,
+    pub soft_depend: Vec<String>,
+    pub paper_plugin: bool
+}
+
+pub fn read_plugin_descriptor(jar_path: &Path) -> Result<Option<PluginDescriptor>> {
+    let mut archive = zip::ZipArchive::new(File::open(jar_path)?)?;
+
+    for descriptor in DESCRIPTOR_FILES {
+        let Ok(mut entry) = archive.by_name(descriptor) else {
+            continue;
+        };
+
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents)?;
+
+        let yaml = YamlDocument::parse(&contents);
+        return Ok(Some(parse_descriptor(&yaml, *descriptor == "paper-plugin.yml")));
+    }
+
+    Ok(None)
+}
+
+fn parse_descriptor(yaml: &YamlDocument, paper_plugin: bool) -> PluginDescriptor {
+    let mut authors = yaml.get_list(&["authors"]);
+    if let Some(author) = scalar(yaml, &["author"]) {
+        authors.insert(0, author);
+    }
+
+    let (depend, soft_depend) = if paper_plugin {
+        paper_dependencies(yaml)
+    } else {
+        (yaml.get_list(&["depend"]), yaml.get_list(&["softdepend"]))
+    };
+
+    PluginDescriptor {
+        name: scalar(yaml, &["name"]).unwrap_or_else(|| "unknown".to_string()),
+        version: scalar(yaml, &["version"]).unwrap_or_else(|| "unknown".to_string()),
+        api_version: scalar(yaml, &["api-version"]),
+        authors,
+        depend,
+        soft_depend,
+        paper_plugin
+    }
+}
+
+fn paper_dependencies(yaml: &YamlDocument) -> (Vec<String>, Vec<String>) {
+    let mut depend = Vec::new();
+    let mut soft_depend = Vec::new();
+
+    for name in yaml.keys(&["dependencies", "server"]) {
+        let required = yaml.get(&["dependencies", "server", &name, "required"]);
+        if required.as_deref() == Some("false") {
+            soft_depend.push(name);
+        } else {
+            depend.push(name);
+        }
+    }
+
+    (depend, soft_depend)
+}
+
+// Values are kept as written, so `api-version: 1.20` does not lose its trailing zero
+fn scalar(yaml: &YamlDocument, path: &[&str]) -> Option<String> {
+    let value = unquote(&yaml.get(path)?);
+    (!value.is_empty() && value != "~" && value != "null").then_some(value)
+}
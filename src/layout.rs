@@ -0,0 +1,208 @@
+use crate::server::{
+    get_anvil_dir, is_server_running, level_name, load_server_config, save_server_config, ServerConfig,
+    WORLD_SUFFIXES,
+};
+use crate::trash::move_path;
+use crate::LayoutAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct LayoutConfig {
+    #[serde(default)]
+    pub worlds: Option<PathBuf>,
+    #[serde(default)]
+    pub backups: Option<PathBuf>,
+    #[serde(default)]
+    pub logs: Option<PathBuf>
+}
+
+impl LayoutConfig {
+    pub fn is_default(&self) -> bool {
+        self.worlds.is_none() && self.backups.is_none() && self.logs.is_none()
+    }
+}
+
+pub async fn handle_layout_action(action: LayoutAction) -> Result<()> {
+    match action {
+        LayoutAction::Set { server, worlds, backups, logs } => {
+            let mut config = load_server_config(&server)?;
+            ensure_stopped(&config).await?;
+
+            let current_backups = crate::backup::get_backups_dir(&server);
+            if let Some(worlds) = worlds {
+                config.layout.worlds = Some(absolute_dir(&worlds)?);
+            }
+            if let Some(logs) = logs {
+                config.layout.logs = Some(absolute_dir(&logs)?);
+            }
+            if let Some(backups) = backups {
+                let backups = absolute_dir(&backups)?;
+                // Each server gets its own folder, so several can share one backup disk
+                move_backups(&current_backups, &backups.join(&server))?;
+                config.layout.backups = Some(backups);
+            }
+
+            wire_layout(&config)?;
+            save_server_config(&config)?;
+            println!("✅ Updated the layout of '{}'", server);
+            print_layout(&config);
+        }
+        LayoutAction::Reset { server } => {
+            let mut config = load_server_config(&server)?;
+            ensure_stopped(&config).await?;
+            if config.layout.is_default() {
+                println!("ℹ️  '{}' already keeps everything in {}", server, config.path.display());
+                return Ok(());
+            }
+
+            for name in linked_dir_names(&config) {
+                unlink_dir(&config.path.join(&name))?;
+            }
+            if let Some(backups) = config.layout.backups.take() {
+                move_backups(&backups.join(&server), &get_anvil_dir().join("backups").join(&server))?;
+            }
+            config.layout = Default::default();
+            save_server_config(&config)?;
+            println!("✅ Moved the data of '{}' back into {}", server, config.path.display());
+        }
+        LayoutAction::Show { server } => {
+            print_layout(&load_server_config(&server)?);
+        }
+    }
+    Ok(())
+}
+
+async fn ensure_stopped(config: &ServerConfig) -> Result<()> {
+    if is_server_running(config).await {
+        return Err(anyhow!("Server '{}' is running, stop it before moving its data", config.name));
+    }
+    Ok(())
+}
+
+fn absolute_dir(path: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(path).map_err(|e| anyhow!("Cannot create {}: {}", path.display(), e))?;
+    Ok(fs::canonicalize(path)?)
+}
+
+fn print_layout(config: &ServerConfig) {
+    let location = |dir: &Option<PathBuf>, default: PathBuf| match dir {
+        Some(dir) => format!("{} (relocated)", dir.display()),
+        None => default.display().to_string()
+    };
+
+    println!("📁 Layout of '{}':", config.name);
+    println!(" - Server: {}", config.path.display());
+    println!(" - Worlds: {}", location(&config.layout.worlds, config.path.clone()));
+    println!(" - Logs: {}", location(&config.layout.logs, config.path.join("logs")));
+    println!(" - Backups: {}", crate::backup::get_backups_dir(&config.name).display());
+}
+
+// Worlds the server has or will create on its next boot, relative to the server directory
+pub fn world_names(config: &ServerConfig) -> Vec<String> {
+    let level = level_name(&config.path);
+    // Vanilla keeps the nether and end inside the main world folder
+    let suffixes: &[&str] = if config.server_type == "vanilla" { &[""] } else { WORLD_SUFFIXES };
+    let mut names: Vec<String> = suffixes.iter().map(|suffix| format!("{}{}", level, suffix)).collect();
+    names.extend(config.worlds.iter().map(|world| world.name.clone()));
+    names
+}
+
+fn linked_dir_names(config: &ServerConfig) -> Vec<String> {
+    let mut names = world_names(config);
+    names.push("logs".to_string());
+    names.into_iter().filter(|name| config.path.join(name).is_symlink()).collect()
+}
+
+// Points the world and log folders at their configured locations, moving any data still inside the server
+pub fn wire_layout(config: &ServerConfig) -> Result<()> {
+    if let Some(worlds) = &config.layout.worlds {
+        for name in world_names(config) {
+            link_dir(&config.path.join(&name), &worlds.join(&name))?;
+        }
+    }
+    if let Some(logs) = &config.layout.logs {
+        link_dir(&config.path.join("logs"), logs)?;
+    }
+    Ok(())
+}
+
+fn link_dir(link: &Path, target: &Path) -> Result<()> {
+    if link.is_symlink() {
+        let current = fs::read_link(link)?;
+        if current == target {
+            return Ok(());
+        }
+        ensure_empty(target)?;
+        if current.exists() {
+            move_path(&current, target)?;
+        }
+        fs::remove_file(link)?;
+    } else if link.exists() {
+        ensure_empty(target)?;
+        move_path(link, target)?;
+        println!("  • moved {} to {}", link.display(), target.display());
+    }
+
+    fs::create_dir_all(target)?;
+    symlink_dir(target, link)
+}
+
+fn unlink_dir(link: &Path) -> Result<()> {
+    let target = fs::read_link(link)?;
+    fs::remove_file(link)?;
+    if target.exists() {
+        move_path(&target, link)?;
+        println!("  • moved {} back to {}", target.display(), link.display());
+    }
+    Ok(())
+}
+
+// Never merge into a folder that already has data, that could mix two worlds
+fn ensure_empty(target: &Path) -> Result<()> {
+    if target.exists() {
+        if fs::read_dir(target)?.next().is_some() {
+            return Err(anyhow!("{} already contains files, pick an empty directory", target.display()));
+        }
+        fs::remove_dir(target)?;
+    }
+    Ok(())
+}
+
+fn move_backups(from: &Path, to: &Path) -> Result<()> {
+    if from == to || !from.exists() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(to)?;
+    let mut moved = 0;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let destination = to.join(entry.file_name());
+        if destination.exists() {
+            return Err(anyhow!("{} already exists, not overwriting it", destination.display()));
+        }
+        move_path(&entry.path(), &destination)?;
+        moved += 1;
+    }
+    fs::remove_dir(from)?;
+
+    if moved > 0 {
+        println!("  • moved {} backup(s) to {}", moved, to.display());
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink_dir(target: &Path, link: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, link)?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn symlink_dir(target: &Path, link: &Path) -> Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+        .map_err(|e| anyhow!("Could not link {} ({}), enable Developer Mode or run as administrator", link.display(), e))
+}
@@ -0,0 +1,107 @@
+use crate::server::{load_server_config, save_server_config};
+use crate::MacroAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroStep {
+    Command(String),
+    Wait(u64)
+}
+
+impl MacroStep {
+    fn parse(step: &str) -> Result<Self> {
+        let step = step.trim();
+
+        match step.strip_prefix("wait ") {
+            Some(seconds) => {
+                let seconds = seconds
+                    .trim()
+                    .trim_end_matches('s')
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid wait step '{}' (expected e.g. 'wait 5')", step))?;
+                Ok(MacroStep::Wait(seconds))
+            }
+            None if step.is_empty() => Err(anyhow!("Macro steps cannot be empty")),
+            None => Ok(MacroStep::Command(step.trim_start_matches('/').to_string()))
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            MacroStep::Command(command) => format!("/{}", command),
+            MacroStep::Wait(seconds) => format!("wait {}s", seconds)
+        }
+    }
+}
+
+pub async fn handle_macro_action(server_name: &str, action: MacroAction) -> Result<()> {
+    match action {
+        MacroAction::Add { name, steps } => {
+            let mut config = load_server_config(server_name)?;
+            let steps = steps
+                .iter()
+                .map(|step| MacroStep::parse(step))
+                .collect::<Result<Vec<_>>>()?;
+
+            println!("✅ Macro '{}' saved with {} step(s)", name, steps.len());
+            config.macros.insert(name, steps);
+            save_server_config(&config)?;
+        }
+        MacroAction::Remove { name } => {
+            let mut config = load_server_config(server_name)?;
+            if config.macros.remove(&name).is_none() {
+                println!("❌ Macro '{}' not found", name);
+                return Ok(());
+            }
+            save_server_config(&config)?;
+            println!("🗑️  Removed macro '{}'", name);
+        }
+        MacroAction::List => {
+            let config = load_server_config(server_name)?;
+            if config.macros.is_empty() {
+                println!("No macros defined for server '{}'", server_name);
+                return Ok(());
+            }
+
+            println!("📜 Macros for server '{}':", server_name);
+            for (name, steps) in &config.macros {
+                let steps: Vec<String> = steps.iter().map(MacroStep::describe).collect();
+                println!("  • {}: {}", name, steps.join(" → "));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_macro(server_name: &str, macro_name: &str) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let steps = config.macros
+        .get(macro_name)
+        .ok_or_else(|| anyhow!("Macro '{}' not found for server '{}'", macro_name, server_name))?;
+
+    println!("▶️  Running macro '{}' on server '{}'", macro_name, server_name);
+
+    let mut client = crate::rcon::connect(&config).await?;
+
+    for step in steps {
+        match step {
+            MacroStep::Command(command) => {
+                println!("  › /{}", command);
+                let response = client.command(command).await?;
+                if !response.is_empty() {
+                    println!("    {}", response.trim_end());
+                }
+            }
+            MacroStep::Wait(seconds) => {
+                println!("  ⏳ waiting {}s", seconds);
+                tokio::time::sleep(Duration::from_secs(*seconds)).await;
+            }
+        }
+    }
+
+    println!("✅ Macro '{}' finished", macro_name);
+    Ok(())
+}
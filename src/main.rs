@@ -1,8 +1,77 @@
 mod server;
 mod api;
 mod plugin;
+mod hooks;
+mod triggers;
+mod chatbridge;
+mod announce;
+mod maintenance;
+mod rcon;
+mod backup;
+mod schedule;
+mod macros;
+mod perms;
+mod bundles;
+mod compat;
+mod version;
+mod map;
+mod database;
+mod config;
+mod yaml;
+mod upgrade;
+mod jar;
+mod manifest;
+mod verify;
+mod scan;
+mod sources;
+mod settings;
+mod browse;
+mod modpack;
+mod world;
+mod ephemeral;
+mod diff;
+mod tags;
+mod sync;
+mod audit;
+mod secrets;
+mod properties;
+mod versioning;
+mod expose;
+mod tunnel;
+mod ddns;
+mod connection;
+mod progress;
+mod triage;
+mod orphans;
+mod trash;
+mod nbt;
+mod mode;
+mod players;
+mod stats;
+mod uuids;
+mod proxy;
+mod health;
+mod debug;
+mod env;
+mod disk;
+mod layout;
+mod window;
+mod autostart;
+mod idle;
+mod wake;
+mod tune;
+mod resourcepack;
+mod worldgen;
+mod memory;
+mod output;
+mod console;
+mod bench;
+mod import;
+mod spec;
+mod cancel;
 
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "anvil")]
@@ -10,6 +79,8 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+    #[arg(long, global = true, value_parser = ["json"])]
+    pub progress: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -22,7 +93,32 @@ pub enum Commands {
         #[arg(short, long, default_value = "paper")]
         server_type: String,
         #[arg(short, long, default_value = "25565")]
-        port: u16
+        port: u16,
+        #[arg(short, long)]
+        copy_from: Option<String>,
+        #[arg(short, long)]
+        bind: Option<String>,
+        #[arg(long)]
+        ip_stack: Option<String>,
+        #[arg(long, default_value = "false")]
+        experimental: bool,
+        #[arg(long, default_value = "false")]
+        fallback_vanilla: bool,
+        #[arg(long)]
+        level_type: Option<String>,
+        #[arg(long, conflicts_with = "superflat_preset")]
+        generator_settings: Option<String>,
+        #[arg(long)]
+        superflat_preset: Option<String>,
+        #[arg(long)]
+        biome: Option<String>,
+        #[arg(short, long)]
+        datapack: Vec<String>,
+        // Start the server once after creating it so its default config files exist
+        #[arg(long, default_value = "false")]
+        boot: bool,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram, requires = "boot")]
+        ram: u8
     },
     Plugin {
         #[arg(short, long)]
@@ -30,58 +126,1119 @@ pub enum Commands {
         #[command(subcommand)]
         action: PluginAction
     },
+    Hook {
+        #[arg(short, long)]
+        server: String,
+        #[command(subcommand)]
+        action: HookAction
+    },
+    Trigger {
+        #[arg(short, long)]
+        server: String,
+        #[command(subcommand)]
+        action: TriggerAction
+    },
+    Backup {
+        #[arg(short, long)]
+        server: String,
+        #[command(subcommand)]
+        action: BackupAction
+    },
+    Schedule {
+        #[arg(short, long)]
+        server: String,
+        #[command(subcommand)]
+        action: ScheduleAction
+    },
+    Daemon,
+    Announce {
+        server: String,
+        // Supports MiniMessage style tags, e.g. "<gold><bold>Vote</bold> at <click:open_url:https://example.com>our site</click>"
+        message: String,
+        #[arg(short, long)]
+        every: Option<String>,
+        #[arg(long, conflicts_with = "every")]
+        cron: Option<String>
+    },
+    Macro {
+        #[arg(short, long)]
+        server: String,
+        #[command(subcommand)]
+        action: MacroAction
+    },
+    Run {
+        server: String,
+        #[arg(name = "macro")]
+        macro_name: String
+    },
+    Players {
+        server: String,
+        #[command(subcommand)]
+        action: Option<PlayersAction>
+    },
+    Stats {
+        #[command(subcommand)]
+        action: StatsAction
+    },
+    Lookup {
+        player: String
+    },
+    Proxy {
+        #[command(subcommand)]
+        action: ProxyAction
+    },
+    Network {
+        #[command(subcommand)]
+        action: NetworkAction
+    },
+    Health {
+        #[command(subcommand)]
+        action: HealthAction
+    },
+    Env {
+        #[command(subcommand)]
+        action: EnvAction
+    },
+    Layout {
+        #[command(subcommand)]
+        action: LayoutAction
+    },
+    Autostart {
+        #[command(subcommand)]
+        action: AutostartAction
+    },
+    ResourcePack {
+        #[command(subcommand)]
+        action: ResourcePackAction
+    },
+    Idle {
+        #[command(subcommand)]
+        action: IdleAction
+    },
+    ChatBridge {
+        #[command(subcommand)]
+        action: ChatBridgeAction
+    },
+    Maintenance {
+        #[command(subcommand)]
+        action: MaintenanceAction
+    },
+    Debug {
+        #[command(subcommand)]
+        action: DebugAction
+    },
+    Import {
+        path: PathBuf,
+        #[arg(short, long)]
+        from: String,
+        #[arg(short, long)]
+        name: Option<String>,
+        #[arg(short, long)]
+        server_type: Option<String>,
+        #[arg(short, long)]
+        version: Option<String>
+    },
+    Export {
+        server: String,
+        #[arg(short, long, default_value = "toml")]
+        format: String,
+        #[arg(short, long)]
+        output: Option<PathBuf>
+    },
+    Apply {
+        spec: PathBuf,
+        #[arg(short, long, default_value = "false")]
+        force: bool,
+        #[arg(short, long, default_value = "false")]
+        check: bool,
+        // Remove or disable plugins the spec does not list
+        #[arg(long, default_value = "false", conflicts_with = "check")]
+        prune: bool
+    },
+    Bench {
+        server: String,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8,
+        #[arg(short, long, default_value = "30")]
+        idle: u64,
+        #[arg(short, long)]
+        chunks: Option<u32>
+    },
+    Perms {
+        #[command(subcommand)]
+        action: PermsAction
+    },
+    Compat {
+        #[command(subcommand)]
+        action: CompatAction
+    },
+    Map {
+        #[command(subcommand)]
+        action: MapAction
+    },
+    Ddns {
+        #[command(subcommand)]
+        action: DdnsAction
+    },
+    Db {
+        #[command(subcommand)]
+        action: DbAction
+    },
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction
+    },
+    Settings {
+        #[command(subcommand)]
+        action: SettingsAction
+    },
+    Modpack {
+        #[command(subcommand)]
+        action: ModpackAction
+    },
+    World {
+        #[command(subcommand)]
+        action: WorldAction
+    },
+    Upgrade {
+        server: String,
+        #[arg(default_value = "latest")]
+        version: String,
+        #[arg(short, long, default_value = "false")]
+        check: bool,
+        #[arg(long, default_value = "false", conflicts_with_all = ["check", "force", "staged"])]
+        plan: bool,
+        #[arg(short, long, default_value = "false")]
+        force: bool,
+        #[arg(long, default_value = "false")]
+        staged: bool,
+        #[arg(long, default_value = "false")]
+        experimental: bool,
+        #[arg(long, default_value = "false")]
+        allow_downgrade: bool,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8
+    },
+    Tag {
+        #[command(subcommand)]
+        action: TagAction
+    },
+    Sync {
+        #[command(subcommand)]
+        action: SyncAction
+    },
+    Secret {
+        #[command(subcommand)]
+        action: SecretAction
+    },
+    Expose {
+        server: String,
+        #[arg(short, long, default_value = "false", conflicts_with = "until_stop")]
+        remove: bool,
+        #[arg(short, long, default_value = "false")]
+        until_stop: bool
+    },
+    CheckConnection {
+        server: String
+    },
+    Verify {
+        server: String,
+        #[arg(short, long, default_value = "false")]
+        repair: bool
+    },
+    PruneOrphans {
+        #[arg(short, long, default_value = "false")]
+        dry_run: bool
+    },
+    Mode {
+        server: String,
+        mode: Option<String>
+    },
+    Tune {
+        server: String,
+        #[arg(short, long)]
+        players: u32,
+        #[arg(short, long)]
+        ram: String,
+        #[arg(short, long, default_value = "false")]
+        dry_run: bool
+    },
+    Revert {
+        server: String,
+        #[arg(short, long)]
+        to: Option<String>
+    },
+    History {
+        server: Option<String>,
+        #[arg(short, long, default_value = "20")]
+        limit: usize
+    },
+    Diff {
+        server_a: String,
+        server_b: String,
+        #[arg(short, long, default_value = "false")]
+        json: bool,
+        #[arg(short, long, default_value = "false")]
+        all: bool
+    },
+    Test {
+        #[arg(short, long, default_value = "latest")]
+        version: String,
+        #[arg(short, long, required = true)]
+        plugin: Vec<PathBuf>,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8,
+        #[arg(short, long, default_value = "300")]
+        timeout: u64,
+        #[arg(short, long, default_value = "false")]
+        keep: bool,
+        #[arg(long, default_value = "false")]
+        experimental: bool
+    },
     Start {
-        name: String,
-        #[arg(short, long, default_value = "2")]
+        #[arg(required_unless_present_any = ["all", "tag"])]
+        name: Option<String>,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
         ram: u8,
+        #[arg(long, num_args = 0..=1, default_missing_value = "playit", conflicts_with_all = ["all", "tag"])]
+        tunnel: Option<String>,
+        #[arg(short, long, default_value = "false", conflicts_with_all = ["name", "tag"])]
+        all: bool,
+        #[arg(long, conflicts_with = "name")]
+        tag: Option<String>,
+        #[arg(short, long, conflicts_with_all = ["all", "tag", "tunnel"])]
+        window: Option<String>,
+        #[arg(long, default_value = "false", conflicts_with_all = ["all", "tag"])]
+        allow_downgrade: bool
     },
     Info {
-        name: String
+        name: String,
+        #[arg(short, long, default_value = "false")]
+        json: bool
     },
     Delete {
         name: String,
         #[arg(short, long, default_value = "false")]
         force: bool,
+        #[arg(long, default_value = "false")]
+        keep_world: bool,
+        #[arg(long, default_value = "false")]
+        keep_backups: bool,
+        #[arg(long, default_value = "false")]
+        permanent: bool,
+    },
+    RestoreDeleted {
+        name: Option<String>
+    },
+    List {
+        #[arg(short, long, default_value = "false")]
+        json: bool
     },
-    List,
     Version
 }
 
 #[derive(Subcommand)]
 pub enum PluginAction {
     Add {
-        plugin: String,
+        #[arg(required_unless_present = "manifest")]
+        plugin: Option<String>,
         #[arg(short, long)]
-        version: Option<String>
+        version: Option<String>,
+        #[arg(short, long, conflicts_with = "plugin")]
+        manifest: Option<PathBuf>,
+        #[arg(long, default_value = "false")]
+        scan: bool
+    },
+    ExportManifest {
+        #[arg(short, long, default_value = "plugins.toml")]
+        output: PathBuf
     },
     Remove {
+        plugin: String,
+        #[arg(short, long, default_value = "false")]
+        all: bool
+    },
+    List,
+    Disable {
+        plugin: String
+    },
+    Enable {
         plugin: String
     },
+    AddBundle {
+        bundle: String
+    },
+    Bundles,
+    Update {
+        plugin: Option<String>,
+        #[arg(short, long, default_value = "false")]
+        all: bool
+    },
+    Rollback {
+        plugin: String
+    },
+    Verify,
+    Config {
+        plugin: String,
+        #[arg(long, conflicts_with = "set")]
+        get: Option<String>,
+        #[arg(long, num_args = 2, value_names = ["KEY", "VALUE"])]
+        set: Option<Vec<String>>
+    },
+    Browse {
+        #[arg(short, long)]
+        query: Option<String>,
+        #[arg(short, long)]
+        category: Option<String>,
+        #[arg(long)]
+        mc: Option<String>
+    },
+    Sources {
+        #[arg(value_delimiter = ',')]
+        sources: Vec<String>,
+        #[arg(short, long, default_value = "false", conflicts_with = "sources")]
+        reset: bool
+    }
+}
+
+#[derive(Subcommand)]
+pub enum HookAction {
+    Set {
+        event: String,
+        command: String
+    },
+    Remove {
+        event: String
+    },
+    List
+}
+
+#[derive(Subcommand)]
+pub enum TriggerAction {
+    Add {
+        name: String,
+        // A regular expression matched against each console line
+        pattern: String,
+        #[arg(long, required_unless_present_any = ["command", "script"], conflicts_with_all = ["command", "script"])]
+        notify: Option<String>,
+        #[arg(long, conflicts_with = "script")]
+        command: Option<String>,
+        #[arg(long)]
+        script: Option<String>,
+        #[arg(long, default_value = "60")]
+        cooldown: u64
+    },
+    Remove {
+        name: String
+    },
+    List,
+    Test {
+        line: String
+    }
+}
+
+#[derive(Subcommand)]
+pub enum BackupAction {
+    Create {
+        #[arg(short, long, value_delimiter = ',')]
+        worlds: Vec<String>
+    },
+    Restore {
+        backup: String,
+        #[arg(short, long, value_delimiter = ',')]
+        worlds: Vec<String>,
+        #[arg(short, long, default_value = "false")]
+        force: bool
+    },
+    List
+}
+
+#[derive(Subcommand)]
+pub enum ScheduleAction {
+    Add {
+        cron: String,
+        task: String,
+        #[arg(short, long)]
+        command: Option<String>,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8
+    },
+    List,
+    Remove {
+        id: String
+    },
+    Run {
+        id: String
+    },
+    Crontab
+}
+
+#[derive(Subcommand)]
+pub enum MacroAction {
+    Add {
+        name: String,
+        #[arg(required = true)]
+        steps: Vec<String>
+    },
+    Remove {
+        name: String
+    },
+    List
+}
+
+#[derive(Subcommand)]
+pub enum PlayersAction {
+    Kick {
+        player: String,
+        reason: Vec<String>
+    },
+    Msg {
+        player: String,
+        #[arg(required = true)]
+        message: Vec<String>
+    },
+    Tp {
+        player: String,
+        #[arg(required = true)]
+        destination: Vec<String>
+    }
+}
+
+#[derive(Subcommand)]
+pub enum StatsAction {
+    Players {
+        server: String,
+        #[arg(long, default_value = "false")]
+        csv: bool
+    }
+}
+
+#[derive(Subcommand)]
+pub enum ProxyAction {
+    Attach {
+        server: String,
+        #[arg(short, long, default_value = "velocity")]
+        kind: String
+    },
+    Detach {
+        server: String
+    },
+    Motd {
+        server: String,
+        #[arg(required_unless_present = "clear")]
+        text: Option<String>,
+        #[arg(short, long, default_value = "false", conflicts_with = "text")]
+        clear: bool
+    }
+}
+
+#[derive(Subcommand)]
+pub enum NetworkAction {
+    Harden
+}
+
+#[derive(Subcommand)]
+pub enum HealthAction {
+    Enable {
+        server: String,
+        #[arg(short, long, default_value = "60")]
+        interval: u64,
+        #[arg(short, long, default_value = "3")]
+        threshold: u32,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8
+    },
+    Disable {
+        server: String
+    },
+    Check {
+        server: String
+    }
+}
+
+#[derive(Subcommand)]
+pub enum EnvAction {
+    Set {
+        server: String,
+        #[arg(required = true)]
+        vars: Vec<String>
+    },
+    Unset {
+        server: String,
+        #[arg(required = true)]
+        keys: Vec<String>
+    },
+    List {
+        server: String,
+        #[arg(short, long, default_value = "false")]
+        reveal: bool
+    }
+}
+
+#[derive(Subcommand)]
+pub enum LayoutAction {
+    Set {
+        server: String,
+        #[arg(short, long, required_unless_present_any = ["backups", "logs"])]
+        worlds: Option<PathBuf>,
+        #[arg(short, long)]
+        backups: Option<PathBuf>,
+        #[arg(short, long)]
+        logs: Option<PathBuf>
+    },
+    Reset {
+        server: String
+    },
+    Show {
+        server: String
+    }
+}
+
+#[derive(Subcommand)]
+pub enum AutostartAction {
+    Enable {
+        server: String,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8
+    },
+    Disable {
+        server: String
+    },
+    Status {
+        server: String
+    }
+}
+
+#[derive(Subcommand)]
+pub enum ResourcePackAction {
+    Set {
+        server: String,
+        url: String,
+        #[arg(short, long, default_value = "false")]
+        require: bool,
+        #[arg(short, long)]
+        prompt: Option<String>
+    },
+    Clear {
+        server: String
+    },
+    Show {
+        server: String
+    }
+}
+
+#[derive(Subcommand)]
+pub enum ChatBridgeAction {
+    Enable {
+        server: String,
+        #[arg(short, long)]
+        webhook: String,
+        #[arg(short, long, requires = "channel")]
+        bot_token: Option<String>,
+        #[arg(short, long, requires = "bot_token")]
+        channel: Option<String>
+    },
+    Disable {
+        server: String
+    }
+}
+
+#[derive(Subcommand)]
+pub enum MaintenanceAction {
+    On {
+        server: String,
+        // Shown as the MOTD and to the players who get kicked
+        #[arg(short, long, default_value = "Down for maintenance, back soon")]
+        message: String
+    },
+    Off {
+        server: String
+    }
+}
+
+#[derive(Subcommand)]
+pub enum IdleAction {
+    Enable {
+        server: String,
+        #[arg(short, long, default_value = "15")]
+        minutes: u64,
+        #[arg(short, long, default_value = "false")]
+        wake: bool,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8
+    },
+    Disable {
+        server: String
+    },
+    Listen {
+        server: String,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8
+    }
+}
+
+#[derive(Subcommand)]
+pub enum DebugAction {
+    Dump {
+        server: String,
+        #[arg(long, default_value = "false", required_unless_present = "threads")]
+        heap: bool,
+        #[arg(long, default_value = "false")]
+        threads: bool
+    },
+    Gc {
+        server: String,
+        #[arg(short, long, default_value = "false")]
+        enable: bool
+    }
+}
+
+#[derive(Subcommand)]
+pub enum PermsAction {
+    Init {
+        server: String,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8
+    }
+}
+
+#[derive(Subcommand)]
+pub enum CompatAction {
+    Enable {
+        server: String,
+        #[arg(long, default_value = "false")]
+        no_backwards: bool
+    }
+}
+
+#[derive(Subcommand)]
+pub enum MapAction {
+    Enable {
+        server: String,
+        #[arg(short, long, default_value = "bluemap")]
+        engine: String,
+        #[arg(short, long)]
+        proxy: Option<String>,
+        #[arg(short, long)]
+        domain: Option<String>,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8
+    }
+}
+
+#[derive(Subcommand)]
+pub enum DdnsAction {
+    Enable {
+        server: String,
+        hostname: String,
+        #[arg(short, long, default_value = "cloudflare")]
+        provider: String,
+        #[arg(short, long)]
+        token: String,
+        #[arg(short, long)]
+        zone_id: Option<String>,
+        #[arg(long, default_value = "false")]
+        no_srv: bool
+    },
+    Update {
+        server: String
+    },
+    Disable {
+        server: String
+    }
+}
+
+#[derive(Subcommand)]
+pub enum DbAction {
+    Create {
+        server: String,
+        #[arg(short, long, default_value = "mariadb")]
+        engine: String,
+        #[arg(short, long, default_value = "false")]
+        local: bool
+    },
+    Info {
+        server: String,
+        #[arg(short, long, default_value = "false")]
+        reveal: bool
+    },
+    Remove {
+        server: String
+    }
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    Set {
+        server: String,
+        key: String,
+        value: String,
+        #[arg(short, long)]
+        world: Option<String>
+    },
+    Get {
+        server: String,
+        key: String,
+        #[arg(short, long)]
+        world: Option<String>
+    },
+    Diff {
+        server: String,
+        #[arg(default_value = "defaults")]
+        other: String
+    }
+}
+
+#[derive(Subcommand)]
+pub enum SettingsAction {
+    Set {
+        key: String,
+        value: String
+    },
+    Get {
+        key: String
+    },
     List
 }
 
+#[derive(Subcommand)]
+pub enum ModpackAction {
+    ExportClient {
+        server: String,
+        #[arg(short, long)]
+        output: Option<PathBuf>
+    }
+}
+
+#[derive(Subcommand)]
+pub enum WorldAction {
+    Upgrade {
+        server: String,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8,
+        #[arg(long, default_value = "false")]
+        no_backup: bool
+    },
+    Border {
+        server: String,
+        #[command(subcommand)]
+        action: BorderAction
+    },
+    Create {
+        server: String,
+        world: String,
+        #[arg(short = 't', long = "type", default_value = "normal")]
+        world_type: String,
+        #[arg(short, long, default_value = "normal")]
+        environment: String,
+        #[arg(short, long)]
+        seed: Option<String>,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8
+    },
+    Prepare {
+        server: String,
+        #[arg(short = 'R', long)]
+        radius: u32,
+        #[arg(short = 'x', long, default_value = "0", allow_negative_numbers = true)]
+        center_x: i32,
+        #[arg(short = 'z', long, default_value = "0", allow_negative_numbers = true)]
+        center_z: i32,
+        #[arg(short, long, default_value = "2", value_parser = memory::parse_ram)]
+        ram: u8
+    },
+    MigrateUuids {
+        server: String,
+        #[arg(long, default_value = "false", required_unless_present = "to_offline", conflicts_with = "to_offline")]
+        to_online: bool,
+        #[arg(long, default_value = "false")]
+        to_offline: bool,
+        #[arg(short, long, default_value = "false")]
+        dry_run: bool
+    }
+}
+
+#[derive(Subcommand)]
+pub enum BorderAction {
+    Get,
+    Set {
+        size: f64,
+        #[arg(short, long, default_value = "false")]
+        force: bool
+    }
+}
+
+#[derive(Subcommand)]
+pub enum TagAction {
+    Add {
+        server: String,
+        #[arg(required = true)]
+        tags: Vec<String>
+    },
+    Remove {
+        server: String,
+        tag: String
+    },
+    List {
+        tag: Option<String>
+    }
+}
+
+#[derive(Subcommand)]
+pub enum SecretAction {
+    Rotate {
+        server: String,
+        #[arg(short, long, default_value = "false")]
+        rcon: bool,
+        #[arg(short, long, default_value = "false")]
+        forwarding: bool
+    },
+    Show {
+        server: String,
+        #[arg(short, long, default_value = "false")]
+        reveal: bool
+    }
+}
+
+#[derive(Subcommand)]
+pub enum SyncAction {
+    Players {
+        #[arg(short, long)]
+        tag: String,
+        #[arg(short, long)]
+        source: String,
+        #[arg(short, long, default_value = "false")]
+        dry_run: bool,
+        #[arg(short, long, default_value = "false")]
+        force: bool
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    if cli.progress.is_some() {
+        progress::enable_json_events();
+    }
 
-    match cli.command {
-        Commands::Create { name, version, server_type, port } => {
-            server::create_server(&name, &version, &server_type, port).await?;
+    let audit = audit::describe(&cli.command);
+    let result = run(cli.command).await;
+    if let Some(audit) = audit {
+        if let Some(server) = audit.server().filter(|_| result.is_ok() && versioning::is_enabled()) {
+            versioning::record(server, &audit.summary()).await;
+        }
+        audit::record(audit, &result);
+    }
+
+    if let Err(e) = &result {
+        // The error event carries the message, a plain line would break wrappers parsing stderr
+        if progress::json_events() {
+            progress::failed(&format!("{:#}", e));
+        } else {
+            output::fatal(e);
+        }
+        std::process::exit(1);
+    }
+    result
+}
+
+async fn run(command: Commands) -> anyhow::Result<()> {
+    match command {
+        Commands::Create {
+            name, version, server_type, port, copy_from, bind, ip_stack, experimental, fallback_vanilla,
+            level_type, generator_settings, superflat_preset, biome, datapack, boot, ram
+        } => {
+            let world_gen =
+                worldgen::WorldGenOptions { level_type, generator_settings, superflat_preset, biome, datapacks: datapack };
+            server::create_server(
+                &name,
+                &version,
+                &server_type,
+                port,
+                server::CreateOptions {
+                    copy_from,
+                    bind,
+                    ip_stack,
+                    experimental,
+                    fallback_vanilla,
+                    world_gen,
+                    boot: boot.then_some(ram)
+                },
+            ).await?;
         }
         Commands::Plugin { server, action } => {
             plugin::handle_plugin_action(&server, action).await?;
         }
-        Commands::Start { name, ram } => {
-            server::start_server(&name, ram).await?;
+        Commands::Hook { server, action } => {
+            hooks::handle_hook_action(&server, action).await?;
+        }
+        Commands::Trigger { server, action } => {
+            triggers::handle_trigger_action(&server, action).await?;
+        }
+        Commands::Backup { server, action } => {
+            backup::handle_backup_action(&server, action).await?;
+        }
+        Commands::Schedule { server, action } => {
+            schedule::handle_schedule_action(&server, action).await?;
+        }
+        Commands::Announce { server, message, every, cron } => {
+            announce::handle_announce(&server, &message, every, cron).await?;
+        }
+        Commands::Daemon => {
+            schedule::run_daemon().await?;
+        }
+        Commands::Macro { server, action } => {
+            macros::handle_macro_action(&server, action).await?;
+        }
+        Commands::Run { server, macro_name } => {
+            macros::run_macro(&server, &macro_name).await?;
+        }
+        Commands::Players { server, action } => {
+            players::handle_players_action(&server, action).await?;
+        }
+        Commands::Stats { action } => {
+            stats::handle_stats_action(action).await?;
+        }
+        Commands::Lookup { player } => {
+            players::lookup_player(&player).await?;
+        }
+        Commands::Proxy { action } => {
+            proxy::handle_proxy_action(action).await?;
+        }
+        Commands::Network { action } => {
+            proxy::handle_network_action(action).await?;
+        }
+        Commands::Health { action } => {
+            health::handle_health_action(action).await?;
+        }
+        Commands::Env { action } => {
+            env::handle_env_action(action).await?;
+        }
+        Commands::Layout { action } => {
+            layout::handle_layout_action(action).await?;
+        }
+        Commands::Autostart { action } => {
+            autostart::handle_autostart_action(action).await?;
+        }
+        Commands::ResourcePack { action } => {
+            resourcepack::handle_resource_pack_action(action).await?;
+        }
+        Commands::Idle { action } => {
+            idle::handle_idle_action(action).await?;
+        }
+        Commands::Maintenance { action } => {
+            maintenance::handle_maintenance_action(action).await?;
+        }
+        Commands::ChatBridge { action } => {
+            chatbridge::handle_chat_bridge_action(action).await?;
+        }
+        Commands::Debug { action } => {
+            debug::handle_debug_action(action).await?;
+        }
+        Commands::Import { path, from, name, server_type, version } => {
+            import::import_server(&from.to_lowercase(), &path, name, server_type, version).await?;
+        }
+        Commands::Export { server, format, output } => {
+            spec::export_spec(&server, &format.to_lowercase(), output)?;
+        }
+        Commands::Apply { spec, force, check, prune } => {
+            spec::apply_spec(&spec, force, check, prune).await?;
+        }
+        Commands::Bench { server, ram, idle, chunks } => {
+            bench::run_benchmark(&server, ram, idle, chunks).await?;
+        }
+        Commands::Perms { action } => {
+            perms::handle_perms_action(action).await?;
+        }
+        Commands::Compat { action } => {
+            compat::handle_compat_action(action).await?;
+        }
+        Commands::Map { action } => {
+            map::handle_map_action(action).await?;
+        }
+        Commands::Ddns { action } => {
+            ddns::handle_ddns_action(action).await?;
+        }
+        Commands::Db { action } => {
+            database::handle_db_action(action).await?;
+        }
+        Commands::Config { action } => {
+            config::handle_config_action(action).await?;
+        }
+        Commands::Settings { action } => {
+            settings::handle_settings_action(action).await?;
+        }
+        Commands::Modpack { action } => {
+            modpack::handle_modpack_action(action).await?;
+        }
+        Commands::World { action } => {
+            world::handle_world_action(action).await?;
+        }
+        Commands::Upgrade { server, version, plan: true, experimental, .. } => {
+            upgrade::plan_upgrade(&server, &version, experimental).await?;
+        }
+        Commands::Upgrade { server, version, check, plan: false, force, staged, experimental, allow_downgrade, ram } => {
+            let options = upgrade::UpgradeOptions { check, force, staged, experimental, allow_downgrade, ram };
+            upgrade::upgrade_server(&server, &version, options).await?;
+        }
+        Commands::Tag { action } => {
+            tags::handle_tag_action(action).await?;
+        }
+        Commands::Secret { action } => {
+            secrets::handle_secret_action(action).await?;
+        }
+        Commands::Sync { action } => {
+            sync::handle_sync_action(action).await?;
+        }
+        Commands::Expose { server, remove, until_stop } => {
+            expose::expose_server(&server, remove, until_stop).await?;
+        }
+        Commands::CheckConnection { server } => {
+            connection::check_connection(&server).await?;
+        }
+        Commands::Verify { server, repair } => {
+            verify::verify_server(&server, repair).await?;
+        }
+        Commands::PruneOrphans { dry_run } => {
+            orphans::prune_orphans(dry_run)?;
+        }
+        Commands::Mode { server, mode } => {
+            mode::set_mode(&server, mode.as_deref()).await?;
+        }
+        Commands::Tune { server, players, ram, dry_run } => {
+            tune::tune_server(&server, players, &ram, dry_run).await?;
+        }
+        Commands::Revert { server, to } => {
+            versioning::revert_config(&server, to.as_deref()).await?;
+        }
+        Commands::History { server, limit } => {
+            audit::show_history(server.as_deref(), limit)?;
+        }
+        Commands::Diff { server_a, server_b, json, all } => {
+            diff::diff_servers(&server_a, &server_b, json, all)?;
+        }
+        Commands::Test { version, plugin, ram, timeout, keep, experimental } => {
+            ephemeral::run_test_server(&version, &plugin, ram, timeout, keep, experimental).await?;
+        }
+        Commands::Start { name, ram, tunnel, all: _, tag, window, allow_downgrade } => {
+            match (name, tag, window) {
+                (Some(name), _, Some(window)) => window::start_in_window(&name, ram, &window, allow_downgrade).await?,
+                (Some(name), _, None) => server::start_server(&name, ram, tunnel.as_deref(), allow_downgrade).await?,
+                (None, Some(tag), _) => server::start_servers(tags::servers_with_tag(&tag)?, ram).await?,
+                (None, None, _) => server::start_servers(server::get_all_servers()?, ram).await?
+            }
+        }
+        Commands::Info { name, json } => {
+            server::show_server_info(&name, json).await?;
         }
-        Commands::Info { name } => {
-            server::show_server_info(&name).await?;
+        Commands::Delete { name, force, keep_world, keep_backups, permanent } => {
+            server::delete_server(&name, force, keep_world, keep_backups, permanent).await?;
         }
-        Commands::Delete { name, force } => {
-            server::delete_server(&name, force).await?;
+        Commands::RestoreDeleted { name } => {
+            trash::restore_deleted(name.as_deref())?;
         }
-        Commands::List => {
-            server::list_servers().await?;
+        Commands::List { json } => {
+            server::list_servers(json).await?;
         }
         Commands::Version => {
             println!("anvil v{}", env!("CARGO_PKG_VERSION"));
@@ -0,0 +1,106 @@
+use crate::properties::PropertiesFile;
+use crate::server::{is_server_running, load_server_config, save_server_config, ServerConfig};
+use crate::MaintenanceAction;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+
+// What maintenance mode changed in server.properties, put back when it is turned off
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MaintenanceState {
+    pub motd: String,
+    pub white_list: String,
+    pub enforce_whitelist: String,
+    pub started_at: DateTime<Utc>
+}
+
+pub async fn handle_maintenance_action(action: MaintenanceAction) -> Result<()> {
+    match action {
+        MaintenanceAction::On { server, message } => enable(&server, &message).await,
+        MaintenanceAction::Off { server } => disable(&server).await
+    }
+}
+
+async fn enable(server_name: &str, message: &str) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    if let Some(state) = &config.maintenance {
+        return Err(anyhow!(
+            "'{}' has been in maintenance mode since {}",
+            server_name,
+            state.started_at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+        ));
+    }
+
+    let path = config.path.join("server.properties");
+    let mut properties = PropertiesFile::load(&path)?;
+    let state = MaintenanceState {
+        motd: properties.get("motd").unwrap_or_else(|| "A Minecraft Server".to_string()),
+        white_list: properties.get("white-list").unwrap_or_else(|| "false".to_string()),
+        enforce_whitelist: properties.get("enforce-whitelist").unwrap_or_else(|| "false".to_string()),
+        started_at: Utc::now()
+    };
+    properties.set("motd", message);
+    properties.set("white-list", "true");
+    properties.set("enforce-whitelist", "true");
+    properties.save(&path)?;
+
+    config.maintenance = Some(state);
+    save_server_config(&config)?;
+    println!("🚧 '{}' is in maintenance mode, only whitelisted players and operators can join", server_name);
+
+    if is_server_running(&config).await {
+        let mut client = crate::rcon::connect(&config).await?;
+        client.command("whitelist on").await?;
+
+        let allowed = allowed_players(&config);
+        let (_, online) = crate::players::online_players(&mut client).await?;
+        let kicked: Vec<String> = online.into_iter().filter(|player| !allowed.contains(&player.to_lowercase())).collect();
+        for player in &kicked {
+            client.command(&format!("kick {} {}", player, message)).await?;
+        }
+        if !kicked.is_empty() {
+            println!("👢 Kicked {} player(s): {}", kicked.len(), kicked.join(", "));
+        }
+        // The MOTD is only read from server.properties at startup
+        println!("💡 The maintenance MOTD shows after the next restart");
+    }
+
+    Ok(())
+}
+
+async fn disable(server_name: &str) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let state = config.maintenance.take().ok_or_else(|| anyhow!("'{}' is not in maintenance mode", server_name))?;
+
+    let path = config.path.join("server.properties");
+    let mut properties = PropertiesFile::load(&path)?;
+    properties.set("motd", &state.motd);
+    properties.set("white-list", &state.white_list);
+    properties.set("enforce-whitelist", &state.enforce_whitelist);
+    properties.save(&path)?;
+
+    save_server_config(&config)?;
+    println!("✅ '{}' is out of maintenance mode, the previous MOTD and whitelist settings are back", server_name);
+
+    if is_server_running(&config).await {
+        if state.white_list != "true" {
+            crate::rcon::send_command(&config, "whitelist off").await?;
+        }
+        println!("💡 The previous MOTD shows after the next restart");
+    }
+
+    Ok(())
+}
+
+// Lowercased names on the whitelist and the operators, who can join a whitelisted server regardless
+fn allowed_players(config: &ServerConfig) -> HashSet<String> {
+    ["whitelist.json", "ops.json"]
+        .iter()
+        .filter_map(|file| fs::read_to_string(config.path.join(file)).ok())
+        .filter_map(|contents| serde_json::from_str::<Vec<serde_json::Value>>(&contents).ok())
+        .flatten()
+        .filter_map(|entry| entry.get("name")?.as_str().map(str::to_lowercase))
+        .collect()
+}
@@ -0,0 +1,166 @@
+use crate::plugin::{install_project, require_plugin_support};
+use crate::server::{load_server_config, InstalledPlugin, ServerConfig};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct PluginManifest {
+    #[serde(default)]
+    pub plugins: Vec<ManifestEntry>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ManifestEntry {
+    pub name: String,
+    #[serde(default = "default_source")]
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>
+}
+
+fn default_source() -> String {
+    "modrinth".to_string()
+}
+
+impl ManifestEntry {
+    pub fn matches(&self, installed: &InstalledPlugin) -> bool {
+        match &self.project_id {
+            Some(project_id) => installed.source == self.source && &installed.project_id == project_id,
+            None => installed.name.eq_ignore_ascii_case(&self.name)
+        }
+    }
+}
+
+pub fn read_manifest(path: &Path) -> Result<PluginManifest> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| anyhow!("Could not read manifest {}: {}", path.display(), e))?;
+    toml::from_str(&contents).map_err(|e| anyhow!("Invalid manifest {}: {}", path.display(), e))
+}
+
+pub async fn install_manifest(server_name: &str, path: &Path, scan: bool) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    require_plugin_support(&config)?;
+
+    let manifest = read_manifest(path)?;
+    if manifest.plugins.is_empty() {
+        println!("ℹ️  Manifest {} lists no plugins", path.display());
+        return Ok(());
+    }
+
+    apply_manifest(server_name, &manifest, &path.display().to_string(), scan).await
+}
+
+pub async fn apply_manifest(server_name: &str, manifest: &PluginManifest, origin: &str, scan: bool) -> Result<()> {
+    println!("📋 Installing {} plugin(s) from {}", manifest.plugins.len(), origin);
+
+    let mut installed = 0;
+    let mut skipped = 0;
+    let mut failed = Vec::new();
+
+    for entry in &manifest.plugins {
+        let config = load_server_config(server_name)?;
+        let current = config.plugins.iter().find(|p| entry.matches(p));
+
+        let up_to_date = current.is_some_and(|current| {
+            entry.version.as_ref().is_none_or(|version| &current.version_number == version)
+        });
+        if up_to_date {
+            println!("✅ {} is already installed", entry.name);
+            skipped += 1;
+            continue;
+        }
+
+        let result = install_entry(server_name, entry, scan).await;
+
+        match result {
+            Ok(()) => installed += 1,
+            Err(e) => {
+                println!("❌ Failed to install {}: {}", entry.name, e);
+                failed.push(entry.name.as_str());
+            }
+        }
+    }
+
+    println!(
+        "📊 Manifest applied: {} installed, {} already present, {} failed",
+        installed, skipped, failed.len()
+    );
+
+    if !failed.is_empty() {
+        return Err(anyhow!("Failed to install: {}", failed.join(", ")));
+    }
+
+    Ok(())
+}
+
+async fn install_entry(server_name: &str, entry: &ManifestEntry, scan: bool) -> Result<()> {
+    crate::sources::validate_sources(std::slice::from_ref(&entry.source))?;
+
+    let (project_id, title) = match &entry.project_id {
+        Some(project_id) => (project_id.clone(), entry.name.clone()),
+        None => {
+            let project = crate::sources::search(&entry.source, &entry.name)
+                .await?
+                .ok_or_else(|| anyhow!("{} not found on {}", entry.name, entry.source))?;
+            (project.project_id, project.title)
+        }
+    };
+
+    install_project(
+        server_name,
+        &entry.source,
+        &project_id,
+        &title,
+        entry.version.as_deref(),
+        None,
+        scan,
+    ).await
+}
+
+pub fn manifest_for(config: &ServerConfig, pin_versions: bool) -> PluginManifest {
+    PluginManifest {
+        plugins: config.plugins
+            .iter()
+            .map(|plugin| ManifestEntry {
+                name: plugin.name.clone(),
+                source: plugin.source.clone(),
+                version: pin_versions.then(|| plugin.version_number.clone()),
+                project_id: Some(plugin.project_id.clone())
+            })
+            .collect()
+    }
+}
+
+pub fn export_manifest(server_name: &str, output: &Path) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let manifest = manifest_for(&config, true);
+
+    fs::write(output, toml::to_string_pretty(&manifest)?)?;
+
+    println!(
+        "✅ Exported {} plugin(s) from '{}' to {}",
+        manifest.plugins.len(),
+        server_name,
+        output.display()
+    );
+
+    let untracked = fs::read_dir(config.path.join("plugins"))
+        .map(|dir| {
+            dir.filter_map(Result::ok)
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|filename| filename.ends_with(".jar"))
+                .filter(|filename| !config.plugins.iter().any(|p| &p.filename == filename))
+                .count()
+        })
+        .unwrap_or(0);
+
+    if untracked > 0 {
+        println!("⚠️  {} jar(s) were installed manually and are not included", untracked);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use crate::plugin::{ensure_plugin, require_plugin_support};
+use crate::server::{
+    ensure_running, get_all_servers, is_server_running, load_server_config, save_server_config,
+    stop_server, ServerConfig,
+};
+use crate::MapAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::net::TcpListener;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+const MAP_PORT_RANGE: RangeInclusive<u16> = 8100..=8199;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MapConfig {
+    pub engine: String,
+    pub port: u16
+}
+
+struct MapEngine {
+    name: &'static str,
+    plugin: &'static str,
+    config_file: &'static str,
+    port_key: &'static str,
+    extra_settings: &'static [(&'static str, &'static str, &'static str)]
+}
+
+const ENGINES: &[MapEngine] = &[
+    MapEngine {
+        name: "bluemap",
+        plugin: "BlueMap",
+        config_file: "plugins/BlueMap/webserver.conf",
+        port_key: "port",
+        extra_settings: &[("plugins/BlueMap/core.conf", "accept-download", "true")]
+    },
+    MapEngine {
+        name: "dynmap",
+        plugin: "dynmap",
+        config_file: "plugins/dynmap/configuration.txt",
+        port_key: "webserver-port",
+        extra_settings: &[]
+    }
+];
+
+pub async fn handle_map_action(action: MapAction) -> Result<()> {
+    match action {
+        MapAction::Enable { server, engine, proxy, domain, ram } => {
+            enable_map(&server, &engine, proxy.as_deref(), domain.as_deref(), ram).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn enable_map(
+    server_name: &str,
+    engine_name: &str,
+    proxy: Option<&str>,
+    domain: Option<&str>,
+    ram: u8,
+) -> Result<()> {
+    let engine = ENGINES
+        .iter()
+        .find(|engine| engine.name.eq_ignore_ascii_case(engine_name))
+        .ok_or_else(|| anyhow!("Unsupported map engine: {} (expected bluemap or dynmap)", engine_name))?;
+
+    let config = load_server_config(server_name)?;
+    require_plugin_support(&config)?;
+
+    println!("🗺️  Enabling {} web map for '{}'", engine.plugin, server_name);
+
+    let installed = ensure_plugin(server_name, engine.plugin).await?;
+    let mut config = load_server_config(server_name)?;
+
+    let config_file = config.path.join(engine.config_file);
+    if !config_file.exists() {
+        generate_plugin_configs(&config, installed, ram).await?;
+    }
+
+    let port = match &config.map {
+        Some(map) if map.engine == engine.name => map.port,
+        _ => find_free_map_port(&config)?
+    };
+
+    set_config_value(&config_file, engine.port_key, &port.to_string())?;
+    for (file, key, value) in engine.extra_settings {
+        let path = config.path.join(file);
+        if path.exists() {
+            set_config_value(&path, key, value)?;
+        }
+    }
+
+    config.map = Some(MapConfig {
+        engine: engine.name.to_string(),
+        port
+    });
+    save_server_config(&config)?;
+
+    println!("✅ {} configured on port {}", engine.plugin, port);
+
+    if let Some(proxy) = proxy {
+        let domain = domain.ok_or_else(|| anyhow!("--domain is required to generate a reverse-proxy snippet"))?;
+        let snippet_path = write_proxy_snippet(&config.path, proxy, domain, port)?;
+        println!("📄 Reverse-proxy snippet written to {}", snippet_path.display());
+        println!("🌐 Map URL: https://{}", domain);
+    } else {
+        println!("🌐 Map URL: http://localhost:{}", port);
+    }
+
+    if is_server_running(&config).await {
+        println!("💡 Restart the server to apply the new web port");
+    }
+
+    Ok(())
+}
+
+async fn generate_plugin_configs(config: &ServerConfig, installed: bool, ram: u8) -> Result<()> {
+    let was_running = is_server_running(config).await;
+
+    if was_running && !installed {
+        return Ok(());
+    }
+
+    println!("⏳ Booting server once so the map plugin can generate its config...");
+    if was_running {
+        stop_server(config).await?;
+    }
+
+    ensure_running(config, ram).await?;
+
+    if !was_running {
+        stop_server(config).await?;
+    }
+
+    Ok(())
+}
+
+fn find_free_map_port(config: &ServerConfig) -> Result<u16> {
+    let taken: Vec<u16> = get_all_servers()?
+        .into_iter()
+        .filter(|server| server.name != config.name)
+        .filter_map(|server| server.map.map(|map| map.port))
+        .collect();
+
+    MAP_PORT_RANGE
+        .filter(|port| !taken.contains(port))
+        .find(|port| TcpListener::bind(("0.0.0.0", *port)).is_ok())
+        .ok_or_else(|| anyhow!(
+            "No free web map port in range {}-{}",
+            MAP_PORT_RANGE.start(),
+            MAP_PORT_RANGE.end()
+        ))
+}
+
+fn set_config_value(path: &Path, key: &str, value: &str) -> Result<()> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| anyhow!("Could not read {}: {}", path.display(), e))?;
+
+    let mut found = false;
+    let mut lines: Vec<String> = contents
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let matches = trimmed
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with(':'));
+
+            if matches && !found {
+                found = true;
+                let indent = &line[..line.len() - trimmed.len()];
+                format!("{}{}: {}", indent, key, value)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    if !found {
+        lines.push(format!("{}: {}", key, value));
+    }
+
+    fs::write(path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+fn write_proxy_snippet(server_dir: &Path, proxy: &str, domain: &str, port: u16) -> Result<PathBuf> {
+    let (filename, snippet) = match proxy {
+        "nginx" => ("map-proxy.nginx.conf", format!(
+            r#"server {{
+    listen 80;
+    server_name {domain};
+
+    location / {{
+        proxy_pass http://127.0.0.1:{port};
+        proxy_set_header Host $host;
+        proxy_set_header X-Real-IP $remote_addr;
+        proxy_set_header X-Forwarded-For $proxy_add_x_forwarded_for;
+    }}
+}}
+"#
+        )),
+        "caddy" => ("map-proxy.Caddyfile", format!(
+            r#"{domain} {{
+    reverse_proxy 127.0.0.1:{port}
+}}
+"#
+        )),
+        other => return Err(anyhow!("Unsupported reverse proxy: {} (expected nginx or caddy)", other))
+    };
+
+    let path = server_dir.join(filename);
+    fs::write(&path, snippet)?;
+    Ok(path)
+}
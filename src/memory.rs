@@ -0,0 +1,114 @@
+use crate::server::format_bytes;
+use std::fs;
+
+const GB: u64 = 1024 * 1024 * 1024;
+// Metaspace, thread stacks and network buffers live outside the heap and need roughly this much
+const JVM_OVERHEAD_BYTES: u64 = GB;
+
+pub struct MemoryLimit {
+    pub bytes: u64,
+    // Set when the limit comes from a container or cgroup, which kills processes that exceed it
+    pub cgroup: bool
+}
+
+// Value parser for --ram: a number of gigabytes, or "max" for the largest heap that fits this machine
+pub fn parse_ram(value: &str) -> Result<u8, String> {
+    if !value.eq_ignore_ascii_case("max") {
+        return value.parse::<u8>().map_err(|_| format!("expected gigabytes from 1 to 255 or 'max', got '{}'", value));
+    }
+
+    let limit = memory_limit().ok_or("could not detect how much memory is available, pass a number instead")?;
+    let ram = max_heap_gb(&limit);
+    println!("🧠 Using {}GB of heap, the most that safely fits {} of memory", ram, format_bytes(limit.bytes));
+    Ok(ram)
+}
+
+// Lowers the heap to fit a container's memory limit, which would otherwise get java OOM-killed without a trace
+pub fn fit_heap(ram: u8) -> u8 {
+    let Some(limit) = memory_limit() else {
+        return ram;
+    };
+    let heap = ram as u64 * GB;
+
+    if limit.cgroup && heap + JVM_OVERHEAD_BYTES > limit.bytes {
+        let fitted = max_heap_gb(&limit);
+        println!(
+            "⚠️  {}GB of heap does not fit the {} memory limit of this container, using {}GB so java is not killed",
+            ram,
+            format_bytes(limit.bytes),
+            fitted
+        );
+        return fitted.min(ram);
+    }
+    if !limit.cgroup && heap > limit.bytes {
+        println!("⚠️  {}GB of heap is more than the {} of memory this machine has", ram, format_bytes(limit.bytes));
+    }
+    ram
+}
+
+fn max_heap_gb(limit: &MemoryLimit) -> u8 {
+    let usable = (limit.bytes / 4 * 3).min(limit.bytes.saturating_sub(JVM_OVERHEAD_BYTES));
+    (usable / GB).clamp(1, u8::MAX as u64) as u8
+}
+
+pub fn memory_limit() -> Option<MemoryLimit> {
+    let physical = physical_memory();
+    match (cgroup_limit(), physical) {
+        (Some(cgroup), Some(physical)) if cgroup < physical => Some(MemoryLimit { bytes: cgroup, cgroup: true }),
+        (Some(cgroup), None) => Some(MemoryLimit { bytes: cgroup, cgroup: true }),
+        (_, Some(physical)) => Some(MemoryLimit { bytes: physical, cgroup: false }),
+        (None, None) => None
+    }
+}
+
+fn cgroup_limit() -> Option<u64> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    // "0::/path" is the cgroup v2 group, "4:memory:/path" the v1 memory controller's; limits live in their directories
+    let groups = fs::read_to_string("/proc/self/cgroup").unwrap_or_default();
+    let mut candidates = vec![
+        "/sys/fs/cgroup/memory.max".to_string(),
+        "/sys/fs/cgroup/memory/memory.limit_in_bytes".to_string(),
+    ];
+    for line in groups.lines() {
+        if let Some(group) = line.strip_prefix("0::") {
+            candidates.push(format!("/sys/fs/cgroup{}/memory.max", group.trim_end_matches('/')));
+        } else if let Some((_, group)) = line.split_once(":memory:") {
+            candidates.push(format!("/sys/fs/cgroup/memory{}/memory.limit_in_bytes", group.trim_end_matches('/')));
+        }
+    }
+
+    // "max" on v2 and a near-u64::MAX value on v1 both mean unlimited, which the physical memory check rules out
+    candidates.iter().filter_map(|path| fs::read_to_string(path).ok()?.trim().parse::<u64>().ok()).min()
+}
+
+#[cfg(target_os = "linux")]
+fn physical_memory() -> Option<u64> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    // "MemTotal:       16314508 kB"
+    let line = meminfo.lines().find(|line| line.starts_with("MemTotal:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(target_os = "macos")]
+fn physical_memory() -> Option<u64> {
+    let output = std::process::Command::new("sysctl").args(["-n", "hw.memsize"]).output().ok()?;
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+#[cfg(windows)]
+fn physical_memory() -> Option<u64> {
+    use windows_sys::Win32::System::SystemInformation::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+
+    let mut status: MEMORYSTATUSEX = unsafe { std::mem::zeroed() };
+    status.dwLength = std::mem::size_of::<MEMORYSTATUSEX>() as u32;
+    (unsafe { GlobalMemoryStatusEx(&mut status) } != 0).then_some(status.ullTotalPhys)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn physical_memory() -> Option<u64> {
+    None
+}
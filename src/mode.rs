@@ -0,0 +1,129 @@
+use crate::nbt::{NbtFile, Tag};
+use crate::server::{is_server_running, level_name, load_server_config, read_server_properties, set_server_property};
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+
+const DIFFICULTIES: &[&str] = &["peaceful", "easy", "normal", "hard"];
+
+// Hardcore players expect to lose their items on death and to heal normally, whatever the world had before
+const HARDCORE_GAMERULES: &[(&str, &str)] = &[("keepInventory", "false"), ("naturalRegeneration", "true")];
+
+struct Mode {
+    difficulty: &'static str,
+    hardcore: bool,
+    gamerules: &'static [(&'static str, &'static str)]
+}
+
+fn parse_mode(mode: &str) -> Result<Mode> {
+    let mode = mode.to_lowercase();
+    if mode == "hardcore" {
+        return Ok(Mode { difficulty: "hard", hardcore: true, gamerules: HARDCORE_GAMERULES });
+    }
+
+    DIFFICULTIES
+        .iter()
+        .find(|difficulty| **difficulty == mode)
+        .map(|difficulty| Mode { difficulty, hardcore: false, gamerules: &[] })
+        .ok_or_else(|| anyhow!("Unknown mode '{}', expected one of: {}, hardcore", mode, DIFFICULTIES.join(", ")))
+}
+
+fn difficulty_id(difficulty: &str) -> i8 {
+    DIFFICULTIES.iter().position(|known| *known == difficulty).unwrap_or(2) as i8
+}
+
+pub async fn set_mode(server_name: &str, mode: Option<&str>) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let properties = read_server_properties(&config.path)?;
+    let current_hardcore = properties.get("hardcore").is_some_and(|value| value == "true");
+    let current_difficulty = properties.get("difficulty").map(String::as_str).unwrap_or("easy");
+
+    let Some(mode) = mode else {
+        let label = if current_hardcore { "hardcore" } else { current_difficulty };
+        println!("🎚️  Server '{}' is in {} mode", server_name, label);
+        return Ok(());
+    };
+    let mode = parse_mode(mode)?;
+
+    let level_dat = config.path.join(level_name(&config.path)).join("level.dat");
+    let running = is_server_running(&config).await;
+
+    // The running server keeps level.dat in memory and overwrites it on the next save
+    if running && level_dat.exists() && mode.hardcore != current_hardcore {
+        return Err(anyhow!(
+            "Server '{}' is running, stop it before switching hardcore {} for an existing world",
+            server_name,
+            if mode.hardcore { "on" } else { "off" }
+        ));
+    }
+
+    set_server_property(&config.path, "difficulty", mode.difficulty)?;
+    set_server_property(&config.path, "hardcore", &mode.hardcore.to_string())?;
+    println!("✅ Set difficulty={} and hardcore={} in server.properties", mode.difficulty, mode.hardcore);
+
+    if running {
+        crate::rcon::send_command(&config, &format!("difficulty {}", mode.difficulty)).await?;
+        for (rule, value) in mode.gamerules {
+            crate::rcon::send_command(&config, &format!("gamerule {} {}", rule, value)).await?;
+        }
+        println!("🎮 Applied difficulty {} to the running server", mode.difficulty);
+        if !mode.gamerules.is_empty() {
+            println!("🎮 Set gamerules: {}", describe_gamerules(mode.gamerules));
+        }
+    } else if level_dat.exists() {
+        update_level_dat(&level_dat, &mode)?;
+        println!("🌍 Updated {} for the existing world", level_dat.display());
+    } else if !mode.gamerules.is_empty() {
+        println!(
+            "💡 The world has not been generated yet; after the first start run: anvil mode {} {} to set {}",
+            server_name,
+            if mode.hardcore { "hardcore" } else { mode.difficulty },
+            describe_gamerules(mode.gamerules)
+        );
+    }
+
+    Ok(())
+}
+
+fn update_level_dat(path: &Path, mode: &Mode) -> Result<()> {
+    let mut file = NbtFile::load_gzip(path)?;
+    let Some(data) = file.root.get_mut("Data").filter(|data| matches!(data, Tag::Compound(_))) else {
+        return Err(anyhow!("{} has no Data compound", path.display()));
+    };
+
+    data.set("hardcore", Tag::Byte(mode.hardcore as i8));
+    data.set("Difficulty", Tag::Byte(difficulty_id(mode.difficulty)));
+
+    // Newer worlds nest the same settings in their own compound next to the legacy fields
+    if let Some(settings) = data.get_mut("difficulty_settings") {
+        if settings.get("hardcore").is_some_and(|tag| tag.as_byte().is_some()) {
+            settings.set("hardcore", Tag::Byte(mode.hardcore as i8));
+        }
+        match settings.get("difficulty") {
+            Some(tag) if tag.as_str().is_some() => {
+                settings.set("difficulty", Tag::String(mode.difficulty.as_bytes().to_vec()));
+            }
+            Some(tag) if tag.as_byte().is_some() => {
+                settings.set("difficulty", Tag::Byte(difficulty_id(mode.difficulty)));
+            }
+            _ => {}
+        }
+    }
+
+    // Older worlds store every gamerule as a string; worlds without that compound are left to the RCON path
+    if let Some(rules) = data.get_mut("GameRules").filter(|rules| matches!(rules, Tag::Compound(_))) {
+        for (rule, value) in mode.gamerules {
+            if rules.get(rule).is_none_or(|tag| tag.as_str().is_some()) {
+                rules.set(rule, Tag::String(value.as_bytes().to_vec()));
+            }
+        }
+    }
+
+    // Keep the previous copy where Minecraft itself looks for a fallback
+    fs::copy(path, path.with_file_name("level.dat_old"))?;
+    file.save_gzip(path)
+}
+
+fn describe_gamerules(gamerules: &[(&str, &str)]) -> String {
+    gamerules.iter().map(|(rule, value)| format!("{}={}", rule, value)).collect::<Vec<_>>().join(", ")
+}
@@ -0,0 +1,178 @@
+use crate::api::vanilla::sha1_hash;
+use crate::plugin::jar_files;
+use crate::server::load_server_config;
+use crate::version::compare_versions;
+use crate::ModpackAction;
+use anyhow::{anyhow, Result};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+// Library folders each loader installs into, with the mrpack dependency key they map to
+const LOADERS: &[(&str, &str)] = &[
+    ("libraries/net/fabricmc/fabric-loader", "fabric-loader"),
+    ("libraries/org/quiltmc/quilt-loader", "quilt-loader"),
+    ("libraries/net/neoforged/neoforge", "neoforge"),
+    ("libraries/net/minecraftforge/forge", "forge")
+];
+
+pub async fn handle_modpack_action(action: ModpackAction) -> Result<()> {
+    match action {
+        ModpackAction::ExportClient { server, output } => {
+            export_client(&server, output).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn export_client(server_name: &str, output: Option<PathBuf>) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let mods_dir = config.path.join("mods");
+    if !mods_dir.exists() {
+        return Err(anyhow!(
+            "Server '{}' has no mods folder - client packs are for Fabric, Quilt, Forge and NeoForge servers",
+            server_name
+        ));
+    }
+
+    let jars = jar_files(&mods_dir)?;
+    if jars.is_empty() {
+        return Err(anyhow!("Server '{}' has no mods installed", server_name));
+    }
+
+    let mut dependencies = serde_json::Map::new();
+    dependencies.insert("minecraft".to_string(), json!(config.version));
+    match detect_loader(&config.path) {
+        Some((loader, version)) => {
+            println!("🧩 Detected {} {}", loader, version);
+            dependencies.insert(loader.to_string(), json!(version));
+        }
+        None => println!("⚠️  Could not detect the mod loader, players will need to pick it themselves"),
+    }
+
+    println!("🔍 Looking up {} mod(s) on Modrinth...", jars.len());
+
+    let mut hashes = Vec::new();
+    for jar in &jars {
+        hashes.push(sha1_hash(jar).await?);
+    }
+
+    let known = crate::api::modrinth::get_versions_by_hash(&hashes).await?;
+
+    let mut project_ids: Vec<String> = known.values().map(|version| version.project_id.clone()).collect();
+    project_ids.sort();
+    project_ids.dedup();
+    let client_sides: HashMap<String, String> = crate::api::modrinth::get_projects(&project_ids)
+        .await?
+        .into_iter()
+        .map(|project| (project.id, project.client_side))
+        .collect();
+
+    let mut files = Vec::new();
+    let mut overrides = Vec::new();
+    let mut server_only = 0;
+
+    for (jar, hash) in jars.iter().zip(&hashes) {
+        let filename = jar.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+        let Some(version) = known.get(hash) else {
+            println!("  ⚠️  {} is not on Modrinth, bundling the jar as an override", filename);
+            overrides.push(jar.clone());
+            continue;
+        };
+
+        let client_side = client_sides.get(&version.project_id).map(String::as_str).unwrap_or("required");
+        if client_side == "unsupported" {
+            println!("  ⏭️  {} is server-only, skipping", filename);
+            server_only += 1;
+            continue;
+        }
+
+        let Some(file) = version.files.iter().find(|f| f.hashes.get("sha1") == Some(hash)) else {
+            overrides.push(jar.clone());
+            continue;
+        };
+
+        println!("  ✅ {} {}", filename, version.version_number);
+        files.push(json!({
+            "path": format!("mods/{}", filename),
+            "hashes": {
+                "sha1": hash,
+                "sha512": file.hashes.get("sha512").cloned().unwrap_or_default()
+            },
+            "env": {
+                "client": client_side,
+                "server": "required"
+            },
+            "downloads": [file.url],
+            "fileSize": file.size
+        }));
+    }
+
+    let index = json!({
+        "formatVersion": 1,
+        "game": "minecraft",
+        "versionId": chrono::Utc::now().format("%Y.%m.%d").to_string(),
+        "name": format!("{} client pack", server_name),
+        "files": files,
+        "dependencies": dependencies
+    });
+
+    let output = output.unwrap_or_else(|| PathBuf::from(format!("{}-client.mrpack", server_name)));
+    write_mrpack(&output, &index, &overrides)?;
+
+    println!(
+        "📦 Wrote {} ({} from Modrinth, {} bundled, {} server-only skipped)",
+        output.display(),
+        files.len(),
+        overrides.len(),
+        server_only
+    );
+    println!("💡 Players can import the .mrpack into Prism Launcher, the Modrinth App or ATLauncher");
+
+    Ok(())
+}
+
+fn detect_loader(server_dir: &Path) -> Option<(&'static str, String)> {
+    for (library, key) in LOADERS {
+        let Ok(entries) = fs::read_dir(server_dir.join(library)) else {
+            continue;
+        };
+
+        let latest = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .max_by(|a, b| compare_versions(a, b))?;
+
+        // Forge folders are named <minecraft>-<forge>
+        let version = match *key {
+            "forge" => latest.split_once('-').map(|(_, forge)| forge.to_string()).unwrap_or(latest),
+            _ => latest
+        };
+
+        return Some((key, version));
+    }
+
+    None
+}
+
+fn write_mrpack(output: &Path, index: &serde_json::Value, overrides: &[PathBuf]) -> Result<()> {
+    let mut writer = zip::ZipWriter::new(File::create(output)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    writer.start_file("modrinth.index.json", options)?;
+    writer.write_all(serde_json::to_string_pretty(index)?.as_bytes())?;
+
+    for jar in overrides {
+        let filename = jar.file_name().unwrap_or_default().to_string_lossy();
+        writer.start_file(format!("overrides/mods/{}", filename), options)?;
+        writer.write_all(&fs::read(jar)?)?;
+    }
+
+    writer.finish()?;
+    Ok(())
+}
@@ -0,0 +1,363 @@
+use anyhow::{anyhow, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+const TAG_END: u8 = 0;
+const TAG_BYTE: u8 = 1;
+const TAG_SHORT: u8 = 2;
+const TAG_INT: u8 = 3;
+const TAG_LONG: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_DOUBLE: u8 = 6;
+const TAG_BYTE_ARRAY: u8 = 7;
+const TAG_STRING: u8 = 8;
+const TAG_LIST: u8 = 9;
+const TAG_COMPOUND: u8 = 10;
+const TAG_INT_ARRAY: u8 = 11;
+const TAG_LONG_ARRAY: u8 = 12;
+
+// Strings are kept as raw bytes since NBT uses modified UTF-8, which must round-trip untouched
+#[derive(Clone)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<u8>),
+    String(Vec<u8>),
+    List(u8, Vec<Tag>),
+    Compound(Vec<(Vec<u8>, Tag)>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>)
+}
+
+impl Tag {
+    fn id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => TAG_BYTE,
+            Tag::Short(_) => TAG_SHORT,
+            Tag::Int(_) => TAG_INT,
+            Tag::Long(_) => TAG_LONG,
+            Tag::Float(_) => TAG_FLOAT,
+            Tag::Double(_) => TAG_DOUBLE,
+            Tag::ByteArray(_) => TAG_BYTE_ARRAY,
+            Tag::String(_) => TAG_STRING,
+            Tag::List(..) => TAG_LIST,
+            Tag::Compound(_) => TAG_COMPOUND,
+            Tag::IntArray(_) => TAG_INT_ARRAY,
+            Tag::LongArray(_) => TAG_LONG_ARRAY
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Tag> {
+        match self {
+            Tag::Compound(entries) => entries.iter().find(|(key, _)| key == name.as_bytes()).map(|(_, tag)| tag),
+            _ => None
+        }
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Tag> {
+        match self {
+            Tag::Compound(entries) => entries.iter_mut().find(|(key, _)| key == name.as_bytes()).map(|(_, tag)| tag),
+            _ => None
+        }
+    }
+
+    pub fn set(&mut self, name: &str, value: Tag) {
+        if let Tag::Compound(entries) = self {
+            match entries.iter_mut().find(|(key, _)| key == name.as_bytes()) {
+                Some((_, tag)) => *tag = value,
+                None => entries.push((name.as_bytes().to_vec(), value))
+            }
+        }
+    }
+
+    pub fn as_byte(&self) -> Option<i8> {
+        match self {
+            Tag::Byte(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i32> {
+        match self {
+            Tag::Int(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    pub fn as_double(&self) -> Option<f64> {
+        match self {
+            Tag::Double(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Tag::String(bytes) => std::str::from_utf8(bytes).ok(),
+            _ => None
+        }
+    }
+}
+
+pub struct NbtFile {
+    pub name: Vec<u8>,
+    pub root: Tag
+}
+
+impl NbtFile {
+    pub fn load_gzip(path: &Path) -> Result<Self> {
+        let mut bytes = Vec::new();
+        GzDecoder::new(fs::File::open(path)?).read_to_end(&mut bytes)?;
+
+        let mut reader = Reader { bytes: &bytes, position: 0 };
+        if reader.u8()? != TAG_COMPOUND {
+            return Err(anyhow!("{} does not start with a compound tag", path.display()));
+        }
+        let name = reader.string()?;
+        let root = reader.payload(TAG_COMPOUND, 0)?;
+
+        Ok(NbtFile { name, root })
+    }
+
+    pub fn save_gzip(&self, path: &Path) -> Result<()> {
+        let mut bytes = vec![TAG_COMPOUND];
+        write_string(&mut bytes, &self.name);
+        write_payload(&mut bytes, &self.root);
+
+        let temporary = path.with_extension("dat.tmp");
+        let mut encoder = GzEncoder::new(fs::File::create(&temporary)?, Compression::default());
+        encoder.write_all(&bytes)?;
+        encoder.finish()?.sync_all()?;
+        fs::rename(&temporary, path)?;
+
+        Ok(())
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize
+}
+
+impl Reader<'_> {
+    fn take(&mut self, length: usize) -> Result<&[u8]> {
+        let end = self.position.checked_add(length).filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| anyhow!("NBT data ends unexpectedly"))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into()?)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn length(&mut self) -> Result<usize> {
+        let length = i32::from_be_bytes(self.array()?);
+        usize::try_from(length).map_err(|_| anyhow!("Negative NBT length {}", length))
+    }
+
+    fn string(&mut self) -> Result<Vec<u8>> {
+        let length = u16::from_be_bytes(self.array()?) as usize;
+        Ok(self.take(length)?.to_vec())
+    }
+
+    fn payload(&mut self, id: u8, depth: usize) -> Result<Tag> {
+        // Deeply nested data is never legitimate in level.dat and would overflow the stack
+        if depth > 512 {
+            return Err(anyhow!("NBT data is nested too deeply"));
+        }
+
+        Ok(match id {
+            TAG_BYTE => Tag::Byte(i8::from_be_bytes(self.array()?)),
+            TAG_SHORT => Tag::Short(i16::from_be_bytes(self.array()?)),
+            TAG_INT => Tag::Int(i32::from_be_bytes(self.array()?)),
+            TAG_LONG => Tag::Long(i64::from_be_bytes(self.array()?)),
+            TAG_FLOAT => Tag::Float(f32::from_be_bytes(self.array()?)),
+            TAG_DOUBLE => Tag::Double(f64::from_be_bytes(self.array()?)),
+            TAG_BYTE_ARRAY => {
+                let length = self.length()?;
+                Tag::ByteArray(self.take(length)?.to_vec())
+            }
+            TAG_STRING => Tag::String(self.string()?),
+            TAG_LIST => {
+                let element = self.u8()?;
+                let length = self.length()?;
+                let mut items = Vec::new();
+                for _ in 0..length {
+                    items.push(self.payload(element, depth + 1)?);
+                }
+                Tag::List(element, items)
+            }
+            TAG_COMPOUND => {
+                let mut entries = Vec::new();
+                loop {
+                    let child = self.u8()?;
+                    if child == TAG_END {
+                        break;
+                    }
+                    let name = self.string()?;
+                    entries.push((name, self.payload(child, depth + 1)?));
+                }
+                Tag::Compound(entries)
+            }
+            TAG_INT_ARRAY => {
+                let length = self.length()?;
+                let mut values = Vec::new();
+                for _ in 0..length {
+                    values.push(i32::from_be_bytes(self.array()?));
+                }
+                Tag::IntArray(values)
+            }
+            TAG_LONG_ARRAY => {
+                let length = self.length()?;
+                let mut values = Vec::new();
+                for _ in 0..length {
+                    values.push(i64::from_be_bytes(self.array()?));
+                }
+                Tag::LongArray(values)
+            }
+            other => return Err(anyhow!("Unknown NBT tag type {}", other))
+        })
+    }
+}
+
+fn write_string(out: &mut Vec<u8>, value: &[u8]) {
+    out.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    out.extend_from_slice(value);
+}
+
+fn write_payload(out: &mut Vec<u8>, tag: &Tag) {
+    match tag {
+        Tag::Byte(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::Short(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::Int(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::Long(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::Float(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::Double(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::ByteArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            out.extend_from_slice(values);
+        }
+        Tag::String(value) => write_string(out, value),
+        Tag::List(element, items) => {
+            out.push(*element);
+            out.extend_from_slice(&(items.len() as i32).to_be_bytes());
+            for item in items {
+                write_payload(out, item);
+            }
+        }
+        Tag::Compound(entries) => {
+            for (name, child) in entries {
+                out.push(child.id());
+                write_string(out, name);
+                write_payload(out, child);
+            }
+            out.push(TAG_END);
+        }
+        Tag::IntArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            for value in values {
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+        Tag::LongArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            for value in values {
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(bytes: &[u8], id: u8) -> Result<Tag> {
+        Reader { bytes, position: 0 }.payload(id, 0)
+    }
+
+    fn level() -> Tag {
+        Tag::Compound(vec![(
+            b"Data".to_vec(),
+            Tag::Compound(vec![
+                (b"hardcore".to_vec(), Tag::Byte(1)),
+                (b"DataVersion".to_vec(), Tag::Int(3953)),
+                (b"LevelName".to_vec(), Tag::String(b"world".to_vec())),
+                (b"Positions".to_vec(), Tag::List(TAG_DOUBLE, vec![Tag::Double(0.5), Tag::Double(-2.0)])),
+                (b"Seeds".to_vec(), Tag::LongArray(vec![i64::MIN, 7])),
+                (b"Empty".to_vec(), Tag::List(TAG_END, Vec::new()))
+            ])
+        )])
+    }
+
+    #[test]
+    fn payload_round_trips() {
+        let mut bytes = Vec::new();
+        write_payload(&mut bytes, &level());
+
+        let parsed = read(&bytes, TAG_COMPOUND).unwrap();
+        let data = parsed.get("Data").unwrap();
+        assert_eq!(data.get("hardcore").and_then(Tag::as_byte), Some(1));
+        assert_eq!(data.get("DataVersion").and_then(Tag::as_int), Some(3953));
+        assert_eq!(data.get("LevelName").and_then(Tag::as_str), Some("world"));
+
+        let mut rewritten = Vec::new();
+        write_payload(&mut rewritten, &parsed);
+        assert_eq!(rewritten, bytes);
+    }
+
+    #[test]
+    fn truncated_data_is_an_error() {
+        let mut bytes = Vec::new();
+        write_payload(&mut bytes, &level());
+
+        for end in 0..bytes.len() {
+            assert!(read(&bytes[..end], TAG_COMPOUND).is_err(), "accepted {} of {} bytes", end, bytes.len());
+        }
+    }
+
+    #[test]
+    fn negative_lengths_are_rejected() {
+        let error = read(&(-1i32).to_be_bytes(), TAG_BYTE_ARRAY).err().unwrap();
+        assert!(error.to_string().contains("Negative NBT length -1"));
+
+        let mut list = vec![TAG_INT];
+        list.extend_from_slice(&i32::MIN.to_be_bytes());
+        assert!(read(&list, TAG_LIST).is_err());
+        assert!(read(&(-5i32).to_be_bytes(), TAG_LONG_ARRAY).is_err());
+    }
+
+    #[test]
+    fn oversized_lengths_fail_without_reading_past_the_end() {
+        assert!(read(&i32::MAX.to_be_bytes(), TAG_BYTE_ARRAY).is_err());
+        assert!(read(&i32::MAX.to_be_bytes(), TAG_INT_ARRAY).is_err());
+        assert!(read(&[0xff, 0xff, b'a'], TAG_STRING).is_err());
+    }
+
+    #[test]
+    fn unknown_tags_and_deep_nesting_are_rejected() {
+        assert!(read(&[13, 0, 0], TAG_COMPOUND).is_err());
+
+        let mut nested = Vec::new();
+        for _ in 0..600 {
+            nested.push(TAG_LIST);
+            nested.extend_from_slice(&1i32.to_be_bytes());
+        }
+        let error = read(&nested, TAG_LIST).err().unwrap();
+        assert!(error.to_string().contains("nested too deeply"));
+    }
+}
@@ -0,0 +1,188 @@
+use crate::server::{
+    get_servers_dir, load_server_config, read_server_properties, save_server_config, ServerConfig,
+};
+use crate::verify::parse_jar_name;
+use anyhow::Result;
+use dialoguer::Select;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIGS_DIR: &str = "configs";
+const DEFAULT_PORT: u16 = 25565;
+
+enum Orphan {
+    MissingDirectory { config_file: PathBuf, config: Box<ServerConfig> },
+    UnreadableConfig { config_file: PathBuf, error: String },
+    UntrackedDirectory { name: String, path: PathBuf }
+}
+
+impl Orphan {
+    fn describe(&self) -> String {
+        match self {
+            Orphan::MissingDirectory { config, .. } => {
+                format!("'{}' points at {}, which no longer exists", config.name, config.path.display())
+            }
+            Orphan::UnreadableConfig { config_file, error } => {
+                format!("{} cannot be read: {}", config_file.display(), error)
+            }
+            Orphan::UntrackedDirectory { path, .. } => {
+                format!("{} has no anvil config", path.display())
+            }
+        }
+    }
+}
+
+pub fn prune_orphans(dry_run: bool) -> Result<()> {
+    let orphans = find_orphans()?;
+
+    if orphans.is_empty() {
+        println!("✅ Every server config has a directory and every directory has a config");
+        return Ok(());
+    }
+
+    println!("🔍 Found {} orphan(s):", orphans.len());
+    for orphan in &orphans {
+        println!("  • {}", orphan.describe());
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+
+    let mut resolved = 0;
+    for orphan in orphans {
+        println!();
+        if resolve(orphan)? {
+            resolved += 1;
+        }
+    }
+
+    println!("\n✅ Resolved {} orphan(s)", resolved);
+    Ok(())
+}
+
+fn find_orphans() -> Result<Vec<Orphan>> {
+    let servers_dir = get_servers_dir();
+    let mut orphans = Vec::new();
+    let mut tracked = HashSet::new();
+
+    let configs_dir = servers_dir.join(CONFIGS_DIR);
+    if configs_dir.exists() {
+        for entry in fs::read_dir(&configs_dir)? {
+            let config_file = entry?.path();
+            if config_file.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+
+            let parsed = fs::read_to_string(&config_file)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| Ok(serde_json::from_str::<ServerConfig>(&json)?));
+
+            match parsed {
+                Ok(config) if config.path.is_dir() => {
+                    tracked.insert(config.path.clone());
+                }
+                Ok(config) => orphans.push(Orphan::MissingDirectory { config_file, config: Box::new(config) }),
+                Err(e) => orphans.push(Orphan::UnreadableConfig { config_file, error: e.to_string() })
+            }
+        }
+    }
+
+    if servers_dir.exists() {
+        for entry in fs::read_dir(&servers_dir)? {
+            let path = entry?.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            if !path.is_dir() || name == CONFIGS_DIR || tracked.contains(&path) {
+                continue;
+            }
+            orphans.push(Orphan::UntrackedDirectory { name, path });
+        }
+    }
+
+    Ok(orphans)
+}
+
+fn resolve(orphan: Orphan) -> Result<bool> {
+    println!("❓ {}", orphan.describe());
+
+    let choice = match &orphan {
+        Orphan::MissingDirectory { .. } | Orphan::UnreadableConfig { .. } => {
+            Select::new()
+                .with_prompt("What should happen to this config?")
+                .items(&["Remove the config", "Keep it"])
+                .default(1)
+                .interact()?
+        }
+        Orphan::UntrackedDirectory { .. } => {
+            Select::new()
+                .with_prompt("What should happen to this directory?")
+                .items(&["Re-adopt it as a server", "Delete the directory", "Keep it"])
+                .default(2)
+                .interact()?
+        }
+    };
+
+    match (orphan, choice) {
+        (Orphan::MissingDirectory { config_file, config }, 0) => {
+            fs::remove_file(&config_file)?;
+            // A copied config can carry another server's name, whose secrets must survive
+            if config_file.file_stem().is_some_and(|stem| *stem == *config.name) {
+                crate::secrets::remove_server_secrets(&config.name)?;
+            }
+            println!("🗑️  Removed the config for '{}'", config.name);
+        }
+        (Orphan::UnreadableConfig { config_file, .. }, 0) => {
+            fs::remove_file(&config_file)?;
+            println!("🗑️  Removed {}", config_file.display());
+        }
+        (Orphan::UntrackedDirectory { name, path }, 0) => adopt(&name, &path)?,
+        (Orphan::UntrackedDirectory { path, .. }, 1) => {
+            fs::remove_dir_all(&path)?;
+            println!("🗑️  Deleted {}", path.display());
+        }
+        _ => {
+            println!("⏭️  Left as is");
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn adopt(name: &str, path: &Path) -> Result<()> {
+    if load_server_config(name).is_ok() {
+        return Err(anyhow::anyhow!("A server named '{}' already exists elsewhere, rename the directory first", name));
+    }
+
+    let jars = crate::plugin::jar_files(path)?;
+    let detected = jars.iter().find_map(|jar| {
+        let jar_file = jar.file_name()?.to_string_lossy().to_string();
+        ["paper", "vanilla"]
+            .iter()
+            .find_map(|server_type| parse_jar_name(server_type, &jar_file).map(|origin| (*server_type, origin)))
+            .map(|(server_type, origin)| (server_type, origin.version().to_string(), jar_file))
+    });
+
+    let Some((server_type, version, jar_file)) = detected else {
+        return Err(anyhow::anyhow!(
+            "No paper-<version>-<build>.jar or vanilla-<version>.jar found in {}, cannot tell what server it is",
+            path.display()
+        ));
+    };
+
+    let port = read_server_properties(path)
+        .ok()
+        .and_then(|properties| properties.get("server-port")?.parse().ok())
+        .unwrap_or(DEFAULT_PORT);
+
+    let config = ServerConfig::new(name, &version, server_type, port, path.to_path_buf(), jar_file);
+    save_server_config(&config)?;
+
+    println!("📥 Adopted '{}' as a {} {} server on port {}", name, server_type, version, port);
+    if !crate::plugin::jar_files(&path.join("plugins"))?.is_empty() {
+        println!("💡 Existing plugins are not tracked; reinstall them with anvil plugin -s {} add to manage updates", name);
+    }
+
+    Ok(())
+}
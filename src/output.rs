@@ -0,0 +1,611 @@
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+pub const LANGUAGES: &[&str] = &["en", "es", "de"];
+
+// Messages by key, with one translation per entry of LANGUAGES; each {} is filled from the arguments in order
+// Covers the output and prompts of create, start, list, info and delete plus the notices shared with other
+// commands; what is still English is listed under Languages in the README
+const CATALOG: &[(&str, [&str; 3])] = &[
+    ("error", ["Error: {}", "Error: {}", "Fehler: {}"]),
+    ("server.not_found", [
+        "Server '{}' does not exist",
+        "El servidor '{}' no existe",
+        "Server '{}' existiert nicht"
+    ]),
+    ("server.starting", ["Starting server: {}", "Iniciando servidor: {}", "Starte Server: {}"]),
+    ("server.created", [
+        "Server '{}' created successfully!",
+        "¡Servidor '{}' creado correctamente!",
+        "Server '{}' erfolgreich erstellt!"
+    ]),
+    ("server.location", ["Location: {}", "Ubicación: {}", "Speicherort: {}"]),
+    ("server.deleted", [
+        "Server '{}' deleted successfully!",
+        "¡Servidor '{}' eliminado correctamente!",
+        "Server '{}' erfolgreich gelöscht!"
+    ]),
+    ("server.already_on_version", [
+        "Server '{}' is already on Minecraft {}",
+        "El servidor '{}' ya usa Minecraft {}",
+        "Server '{}' läuft bereits mit Minecraft {}"
+    ]),
+    ("server.running_old_settings", [
+        "Server '{}' is running with the old settings; restart it to apply the change",
+        "El servidor '{}' sigue con la configuración anterior; reinícialo para aplicar el cambio",
+        "Server '{}' läuft noch mit den alten Einstellungen; starte ihn neu, um die Änderung zu übernehmen"
+    ]),
+    ("server.restart_to_apply", [
+        "Restart the server to apply the change",
+        "Reinicia el servidor para aplicar el cambio",
+        "Starte den Server neu, um die Änderung zu übernehmen"
+    ]),
+    ("server.stopping_after_setup", [
+        "Stopping server after setup...",
+        "Deteniendo el servidor tras la configuración...",
+        "Stoppe den Server nach der Einrichtung..."
+    ]),
+    ("server.trying_vanilla", [
+        "{}, trying vanilla instead",
+        "{}, se intentará con vanilla",
+        "{}, versuche es mit Vanilla"
+    ]),
+    ("server.no_paper_builds", [
+        "Paper has no builds for {} yet, creating a vanilla server instead",
+        "Paper aún no tiene builds para {}, se creará un servidor vanilla",
+        "Paper hat noch keine Builds für {}, erstelle stattdessen einen Vanilla-Server"
+    ]),
+    ("server.creating", [
+        "Creating {} server: {}",
+        "Creando servidor {}: {}",
+        "Erstelle {}-Server: {}"
+    ]),
+    ("server.first_boot", [
+        "Starting '{}' once to generate its default configs...",
+        "Iniciando '{}' una vez para generar su configuración predeterminada...",
+        "Starte '{}' einmal, um die Standardkonfiguration zu erzeugen..."
+    ]),
+    ("server.first_boot_finished", [
+        "First boot finished in {}",
+        "Primer arranque terminado en {}",
+        "Erster Start nach {} abgeschlossen"
+    ]),
+    ("server.generated", [
+        "Generated: {}",
+        "Generado: {}",
+        "Erzeugt: {}"
+    ]),
+    ("server.world_setting", [
+        "Set {} to {}",
+        "{} establecido en {}",
+        "{} auf {} gesetzt"
+    ]),
+    ("server.copied_settings", [
+        "Copied server.properties and JVM flags from '{}'",
+        "Copiados server.properties y los flags de JVM de '{}'",
+        "server.properties und JVM-Flags von '{}' übernommen"
+    ]),
+    ("server.cleaned_up", [
+        "Removed the partially created server '{}'",
+        "Eliminado el servidor '{}' creado a medias",
+        "Teilweise erstellten Server '{}' entfernt"
+    ]),
+    ("server.cleanup_failed", [
+        "Could not fully clean up '{}', remove {} before retrying",
+        "No se pudo limpiar '{}' por completo, elimina {} antes de reintentar",
+        "'{}' konnte nicht vollständig entfernt werden, lösche {} vor dem nächsten Versuch"
+    ]),
+    ("server.skipping_plugins", [
+        "Skipping {} plugin(s) from '{}': {} servers do not support plugins",
+        "Se omiten {} plugin(s) de '{}': los servidores {} no admiten plugins",
+        "Überspringe {} Plugin(s) von '{}': {}-Server unterstützen keine Plugins"
+    ]),
+    ("server.resolved_version", [
+        "Resolved {} {} to {}",
+        "{} {} resuelto como {}",
+        "{} {} aufgelöst zu {}"
+    ]),
+    ("server.downloading_paper_experimental", [
+        "Downloading Paper {} (build {}, experimental builds allowed)...",
+        "Descargando Paper {} (build {}, builds experimentales permitidas)...",
+        "Lade Paper {} herunter (Build {}, experimentelle Builds erlaubt)..."
+    ]),
+    ("server.downloading_paper", [
+        "Downloading Paper {} (build {})...",
+        "Descargando Paper {} (build {})...",
+        "Lade Paper {} herunter (Build {})..."
+    ]),
+    ("server.downloading_vanilla", [
+        "Downloading Vanilla Minecraft {}...",
+        "Descargando Minecraft Vanilla {}...",
+        "Lade Vanilla Minecraft {} herunter..."
+    ]),
+    ("server.proxy_settings_next_start", [
+        "Proxy forwarding settings will be applied on the next start, once this boot has created them",
+        "La configuración de reenvío del proxy se aplicará en el próximo inicio, cuando este arranque la haya creado",
+        "Die Proxy-Weiterleitung wird beim nächsten Start eingerichtet, sobald dieser Start die Dateien erzeugt hat"
+    ]),
+    ("server.started_in", [
+        "Server started in {}",
+        "Servidor iniciado en {}",
+        "Server nach {} gestartet"
+    ]),
+    ("server.none_to_start", [
+        "No servers to start",
+        "No hay servidores que iniciar",
+        "Keine Server zum Starten"
+    ]),
+    ("server.boot_timeout", [
+        "Server did not finish within {}s, killing it",
+        "El servidor no terminó en {}s, se detiene a la fuerza",
+        "Server wurde nicht innerhalb von {}s fertig, wird beendet"
+    ]),
+    ("server.booting_background", [
+        "Booting server '{}' in the background...",
+        "Arrancando el servidor '{}' en segundo plano...",
+        "Starte Server '{}' im Hintergrund..."
+    ]),
+    ("server.ready", [
+        "Server '{}' is ready",
+        "El servidor '{}' está listo",
+        "Server '{}' ist bereit"
+    ]),
+    ("server.none_found", [
+        "No servers found.",
+        "No se encontraron servidores.",
+        "Keine Server gefunden."
+    ]),
+    ("server.available", [
+        "Available servers:",
+        "Servidores disponibles:",
+        "Verfügbare Server:"
+    ]),
+    ("server.info.title", [
+        "Server Information: {}",
+        "Información del servidor: {}",
+        "Serverinformationen: {}"
+    ]),
+    ("server.info.type", [
+        "Type: {}",
+        "Tipo: {}",
+        "Typ: {}"
+    ]),
+    ("server.info.version", [
+        "Version: {}",
+        "Versión: {}",
+        "Version: {}"
+    ]),
+    ("server.info.port", [
+        "Port: {}",
+        "Puerto: {}",
+        "Port: {}"
+    ]),
+    ("server.info.jar", [
+        "JAR: {}",
+        "JAR: {}",
+        "JAR: {}"
+    ]),
+    ("server.info.experimental", [
+        "Builds: experimental allowed",
+        "Builds: experimentales permitidas",
+        "Builds: experimentelle erlaubt"
+    ]),
+    ("server.info.java", [
+        "Java: {}",
+        "Java: {}",
+        "Java: {}"
+    ]),
+    ("server.info.java_missing", [
+        "not found on PATH",
+        "no encontrado en el PATH",
+        "nicht im PATH gefunden"
+    ]),
+    ("server.info.jvm_flags", [
+        "JVM flags: {}",
+        "Flags de JVM: {}",
+        "JVM-Flags: {}"
+    ]),
+    ("server.info.jvm_flags_none", [
+        "JVM flags: none",
+        "Flags de JVM: ninguno",
+        "JVM-Flags: keine"
+    ]),
+    ("server.info.jvm_flags_no_script", [
+        "JVM flags: no start script",
+        "Flags de JVM: sin script de inicio",
+        "JVM-Flags: kein Startskript"
+    ]),
+    ("server.info.status_running", [
+        "Status: running",
+        "Estado: en ejecución",
+        "Status: läuft"
+    ]),
+    ("server.info.status_stopped", [
+        "Status: stopped",
+        "Estado: detenido",
+        "Status: gestoppt"
+    ]),
+    ("server.info.rcon_disabled", [
+        "RCON: disabled",
+        "RCON: desactivado",
+        "RCON: deaktiviert"
+    ]),
+    ("server.info.rcon_connected", [
+        "RCON: enabled on port {}, connected",
+        "RCON: activado en el puerto {}, conectado",
+        "RCON: aktiv auf Port {}, verbunden"
+    ]),
+    ("server.info.rcon_error", [
+        "RCON: enabled on port {}, cannot connect: {}",
+        "RCON: activado en el puerto {}, no se puede conectar: {}",
+        "RCON: aktiv auf Port {}, keine Verbindung: {}"
+    ]),
+    ("server.info.rcon_enabled", [
+        "RCON: enabled on port {}",
+        "RCON: activado en el puerto {}",
+        "RCON: aktiv auf Port {}"
+    ]),
+    ("server.info.whitelist_on", [
+        "Whitelist: on ({} player(s))",
+        "Lista blanca: activada ({} jugador(es))",
+        "Whitelist: an ({} Spieler)"
+    ]),
+    ("server.info.whitelist_off", [
+        "Whitelist: off ({} player(s) listed)",
+        "Lista blanca: desactivada ({} jugador(es) en la lista)",
+        "Whitelist: aus ({} Spieler eingetragen)"
+    ]),
+    ("server.info.world_size_none", [
+        "World size: not generated yet",
+        "Tamaño del mundo: aún no generado",
+        "Weltgröße: noch nicht erzeugt"
+    ]),
+    ("server.info.world_size", [
+        "World size: {} ({})",
+        "Tamaño del mundo: {} ({})",
+        "Weltgröße: {} ({})"
+    ]),
+    ("server.info.plugins", [
+        "Plugins: {}",
+        "Plugins: {}",
+        "Plugins: {}"
+    ]),
+    ("server.info.untracked_jars", [
+        "{} untracked jar(s)",
+        "{} jar(s) sin seguimiento",
+        "{} nicht verwaltete Jar(s)"
+    ]),
+    ("server.info.disabled_plugins", [
+        "Disabled plugins: {}",
+        "Plugins desactivados: {}",
+        "Deaktivierte Plugins: {}"
+    ]),
+    ("server.info.world_missing", [
+        "missing",
+        "no existe",
+        "fehlt"
+    ]),
+    ("server.info.world", [
+        "World: {} ({}, {}, {})",
+        "Mundo: {} ({}, {}, {})",
+        "Welt: {} ({}, {}, {})"
+    ]),
+    ("server.info.last_stop", [
+        "Last stop: {}",
+        "Última parada: {}",
+        "Letzter Stopp: {}"
+    ]),
+    ("server.info.idle", [
+        "Idle shutdown: after {} minute(s)",
+        "Apagado por inactividad: tras {} minuto(s)",
+        "Abschaltung bei Inaktivität: nach {} Minute(n)"
+    ]),
+    ("server.info.idle_wake", [
+        "Idle shutdown: after {} minute(s), wakes on join",
+        "Apagado por inactividad: tras {} minuto(s), se reactiva al entrar un jugador",
+        "Abschaltung bei Inaktivität: nach {} Minute(n), startet beim Beitreten"
+    ]),
+    ("server.info.autostart", [
+        "Autostart: on ({}GB of RAM)",
+        "Inicio automático: activado ({}GB de RAM)",
+        "Autostart: an ({}GB RAM)"
+    ]),
+    ("server.info.last_backup", [
+        "Last backup: {} ({})",
+        "Última copia de seguridad: {} ({})",
+        "Letztes Backup: {} ({})"
+    ]),
+    ("server.info.last_backup_never", [
+        "Last backup: never",
+        "Última copia de seguridad: nunca",
+        "Letztes Backup: nie"
+    ]),
+    ("server.info.map", [
+        "Web map: {} on http://localhost:{}",
+        "Mapa web: {} en http://localhost:{}",
+        "Webkarte: {} auf http://localhost:{}"
+    ]),
+    ("server.info.database", [
+        "Database: {} at {}:{} ({})",
+        "Base de datos: {} en {}:{} ({})",
+        "Datenbank: {} auf {}:{} ({})"
+    ]),
+    ("server.info.ddns", [
+        "Dynamic DNS: {} via {} ({})",
+        "DNS dinámico: {} mediante {} ({})",
+        "Dynamisches DNS: {} über {} ({})"
+    ]),
+    ("server.info.ddns_pending", [
+        "not updated yet",
+        "aún sin actualizar",
+        "noch nicht aktualisiert"
+    ]),
+    ("server.info.hooks", [
+        "Hooks: {}",
+        "Hooks: {}",
+        "Hooks: {}"
+    ]),
+    ("server.info.rcon_password", [
+        "RCON password: {}",
+        "Contraseña de RCON: {}",
+        "RCON-Passwort: {}"
+    ]),
+    ("server.info.forwarding_secret", [
+        "Velocity forwarding secret: {}",
+        "Secreto de reenvío de Velocity: {}",
+        "Velocity-Weiterleitungsgeheimnis: {}"
+    ]),
+    ("server.info.start_commands", [
+        "Start Commands:",
+        "Comandos de inicio:",
+        "Startbefehle:"
+    ]),
+    ("server.info.start_cli", [
+        "CLI: anvil start {}",
+        "CLI: anvil start {}",
+        "CLI: anvil start {}"
+    ]),
+    ("server.info.start_direct_windows", [
+        "Direct: cd \"{}\" && start.bat [RAM_GB]",
+        "Directo: cd \"{}\" && start.bat [RAM_GB]",
+        "Direkt: cd \"{}\" && start.bat [RAM_GB]"
+    ]),
+    ("server.info.start_double_click", [
+        "Double-click: start.bat",
+        "Doble clic: start.bat",
+        "Doppelklick: start.bat"
+    ]),
+    ("server.info.start_direct", [
+        "Direct: cd {} && ./start.sh [RAM_GB]",
+        "Directo: cd {} && ./start.sh [RAM_GB]",
+        "Direkt: cd {} && ./start.sh [RAM_GB]"
+    ]),
+    ("server.info.startup_never", [
+        "Startup time: never started",
+        "Tiempo de arranque: nunca iniciado",
+        "Startzeit: nie gestartet"
+    ]),
+    ("server.info.startup_last", [
+        "Startup time: {} (last start {})",
+        "Tiempo de arranque: {} (último inicio {})",
+        "Startzeit: {} (letzter Start {})"
+    ]),
+    ("server.info.startup_average", [
+        "Average startup: {} over {} previous starts",
+        "Arranque medio: {} en {} inicios anteriores",
+        "Durchschnittlicher Start: {} über {} vorherige Starts"
+    ]),
+    ("server.info.startup_slow", [
+        "Last startup was {}x slower than average - did a new plugin slow things down?",
+        "El último arranque fue {}x más lento que la media: ¿lo ralentizó un plugin nuevo?",
+        "Der letzte Start war {}x langsamer als der Durchschnitt - bremst ein neues Plugin?"
+    ]),
+    ("server.info.startup_recent", [
+        "Recent startups: {}",
+        "Arranques recientes: {}",
+        "Letzte Starts: {}"
+    ]),
+    ("server.delete_running", [
+        "Server '{}' is running on port {}, it will be stopped first",
+        "El servidor '{}' está en ejecución en el puerto {}, se detendrá primero",
+        "Server '{}' läuft auf Port {} und wird zuerst gestoppt"
+    ]),
+    ("server.delete_permanent", [
+        "This will permanently delete:",
+        "Esto eliminará permanentemente:",
+        "Folgendes wird endgültig gelöscht:"
+    ]),
+    ("server.delete_trash", [
+        "This will move to the trash:",
+        "Esto se moverá a la papelera:",
+        "Folgendes wird in den Papierkorb verschoben:"
+    ]),
+    ("server.delete.directory", [
+        "📁 Server directory: {}",
+        "📁 Directorio del servidor: {}",
+        "📁 Serververzeichnis: {}"
+    ]),
+    ("server.delete.config", [
+        "⚙️ Configuration file",
+        "⚙️ Archivo de configuración",
+        "⚙️ Konfigurationsdatei"
+    ]),
+    ("server.delete.plugins", [
+        "🔌 {} plugins",
+        "🔌 {} plugins",
+        "🔌 {} Plugins"
+    ]),
+    ("server.delete.worlds", [
+        "🌍 World data (including player data, builds, etc.)",
+        "🌍 Datos del mundo (incluidos datos de jugadores, construcciones, etc.)",
+        "🌍 Weltdaten (einschließlich Spielerdaten, Bauwerke usw.)"
+    ]),
+    ("server.delete.backups", [
+        "💾 Backups in {} ({})",
+        "💾 Copias de seguridad en {} ({})",
+        "💾 Backups in {} ({})"
+    ]),
+    ("server.delete.total", [
+        "📊 Total size: {}",
+        "📊 Tamaño total: {}",
+        "📊 Gesamtgröße: {}"
+    ]),
+    ("server.delete.archive_first", [
+        "The world will be archived to {} first",
+        "El mundo se archivará primero en {}",
+        "Die Welt wird zuerst nach {} archiviert"
+    ]),
+    ("server.delete.relocated_kept", [
+        "Data relocated to {} is left in place",
+        "Los datos reubicados en {} se conservan",
+        "Nach {} verschobene Daten bleiben erhalten"
+    ]),
+    ("server.delete.confirm", [
+        "Are you sure you want to delete server '{}'?",
+        "¿Seguro que quieres eliminar el servidor '{}'?",
+        "Server '{}' wirklich löschen?"
+    ]),
+    ("server.delete.confirm_world", [
+        "This destroys the world. Type '{}' to confirm",
+        "Esto destruye el mundo. Escribe '{}' para confirmar",
+        "Dadurch wird die Welt zerstört. Gib '{}' zur Bestätigung ein"
+    ]),
+    ("server.delete.cancelled", [
+        "Deletion cancelled",
+        "Eliminación cancelada",
+        "Löschen abgebrochen"
+    ]),
+    ("server.delete.world_archived", [
+        "World archived to {}",
+        "Mundo archivado en {}",
+        "Welt nach {} archiviert"
+    ]),
+    ("server.deleting", [
+        "Deleting server '{}'...",
+        "Eliminando el servidor '{}'...",
+        "Lösche Server '{}'..."
+    ]),
+    ("server.delete.in_trash", [
+        "It stays in the trash for {} days; undo with: anvil restore-deleted {}",
+        "Permanece en la papelera {} días; deshazlo con: anvil restore-deleted {}",
+        "Er bleibt {} Tage im Papierkorb; rückgängig mit: anvil restore-deleted {}"
+    ]),
+    ("server.delete.removed_directory", [
+        "Removed server directory",
+        "Directorio del servidor eliminado",
+        "Serververzeichnis entfernt"
+    ]),
+    ("server.delete.removed_config", [
+        "Removed configuration file",
+        "Archivo de configuración eliminado",
+        "Konfigurationsdatei entfernt"
+    ]),
+    ("server.delete.removed_backups", [
+        "Removed backups",
+        "Copias de seguridad eliminadas",
+        "Backups entfernt"
+    ]),
+    ("server.stopping", [
+        "Stopping server '{}'...",
+        "Deteniendo el servidor '{}'...",
+        "Stoppe Server '{}'..."
+    ]),
+    ("server.stopped", [
+        "Server stopped",
+        "Servidor detenido",
+        "Server gestoppt"
+    ]),
+    ("server.delete.stop_failed_forced", [
+        "Could not stop the server gracefully ({}), deleting anyway because of --force",
+        "No se pudo detener el servidor de forma ordenada ({}), se elimina igualmente por --force",
+        "Server konnte nicht sauber gestoppt werden ({}), wird wegen --force trotzdem gelöscht"
+    ])
+];
+
+enum Color {
+    Green,
+    Yellow,
+    Red,
+    Cyan
+}
+
+// The language from ANVIL_LANG, the language setting or the system locale, English when none is supported
+fn language() -> usize {
+    static LANGUAGE: OnceLock<usize> = OnceLock::new();
+    *LANGUAGE.get_or_init(|| {
+        let configured = std::env::var("ANVIL_LANG")
+            .ok()
+            .or_else(|| crate::settings::load_settings().ok().and_then(|settings| settings.language));
+        // LANG looks like "de_DE.UTF-8"; C and POSIX locales fall through to English
+        let system = ["LC_ALL", "LC_MESSAGES", "LANG"].iter().find_map(|var| std::env::var(var).ok());
+
+        configured
+            .into_iter()
+            .chain(system)
+            .find_map(|locale| {
+                let code = locale.split(['_', '.', '-']).next().unwrap_or_default().to_lowercase();
+                LANGUAGES.iter().position(|language| *language == code)
+            })
+            .unwrap_or(0)
+    })
+}
+
+pub fn text(key: &str, args: &[&dyn Display]) -> String {
+    let template = CATALOG
+        .iter()
+        .find(|(entry, _)| *entry == key)
+        .map_or(key, |(_, translations)| translations[language()]);
+
+    let mut output = String::new();
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    output.push_str(parts.next().unwrap_or_default());
+    for part in parts {
+        if let Some(arg) = args.next() {
+            output.push_str(&arg.to_string());
+        }
+        output.push_str(part);
+    }
+    output
+}
+
+fn colored(text: &str, color: Color, terminal: bool) -> String {
+    // https://no-color.org, and never write escape codes into pipes or log files
+    if std::env::var_os("NO_COLOR").is_some() || !terminal {
+        return text.to_string();
+    }
+    let code = match color {
+        Color::Green => 32,
+        Color::Yellow => 33,
+        Color::Red => 31,
+        Color::Cyan => 36
+    };
+    format!("\x1b[{}m{}\x1b[0m", code, text)
+}
+
+pub fn say(icon: &str, key: &str, args: &[&dyn Display]) {
+    println!("{} {}", icon, text(key, args));
+}
+
+pub fn success(key: &str, args: &[&dyn Display]) {
+    println!("✅ {}", colored(&text(key, args), Color::Green, std::io::stdout().is_terminal()));
+}
+
+pub fn warn(key: &str, args: &[&dyn Display]) {
+    println!("⚠️  {}", colored(&text(key, args), Color::Yellow, std::io::stdout().is_terminal()));
+}
+
+pub fn error(key: &str, args: &[&dyn Display]) {
+    println!("❌ {}", colored(&text(key, args), Color::Red, std::io::stdout().is_terminal()));
+}
+
+pub fn hint(key: &str, args: &[&dyn Display]) {
+    println!("💡 {}", colored(&text(key, args), Color::Cyan, std::io::stdout().is_terminal()));
+}
+
+// The error that ends a command, on stderr like the rest of the diagnostics; the causes are joined on one line
+pub fn fatal(error: &anyhow::Error) {
+    let message = text("error", &[&format!("{:#}", error)]);
+    eprintln!("❌ {}", colored(&message, Color::Red, std::io::stderr().is_terminal()));
+}
@@ -0,0 +1,104 @@
+use crate::server::{ensure_running, is_server_running, load_server_config, stop_server};
+use crate::PermsAction;
+use anyhow::Result;
+
+struct StarterGroup {
+    name: &'static str,
+    parent: Option<&'static str>,
+    weight: u32,
+    permissions: &'static [&'static str]
+}
+
+const STARTER_GROUPS: &[StarterGroup] = &[
+    StarterGroup {
+        name: "default",
+        parent: None,
+        weight: 0,
+        permissions: &[
+            "minecraft.command.help",
+            "minecraft.command.list",
+            "minecraft.command.me",
+            "minecraft.command.msg",
+            "minecraft.command.trigger"
+        ]
+    },
+    StarterGroup {
+        name: "mod",
+        parent: Some("default"),
+        weight: 10,
+        permissions: &[
+            "minecraft.command.kick",
+            "minecraft.command.ban",
+            "minecraft.command.ban-ip",
+            "minecraft.command.banlist",
+            "minecraft.command.pardon",
+            "minecraft.command.pardon-ip",
+            "minecraft.command.teleport",
+            "minecraft.command.gamemode",
+            "minecraft.command.spectate",
+            "luckperms.user.info",
+            "luckperms.user.parent.info"
+        ]
+    },
+    StarterGroup {
+        name: "admin",
+        parent: Some("mod"),
+        weight: 100,
+        permissions: &["*"]
+    }
+];
+
+pub async fn handle_perms_action(action: PermsAction) -> Result<()> {
+    match action {
+        PermsAction::Init { server, ram } => {
+            init_permissions(&server, ram).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn init_permissions(server_name: &str, ram: u8) -> Result<()> {
+    println!("🔐 Setting up LuckPerms for server '{}'", server_name);
+
+    let installed = crate::plugin::ensure_plugin(server_name, "LuckPerms").await?;
+    let config = load_server_config(server_name)?;
+
+    let was_running = is_server_running(&config).await;
+
+    if installed && was_running {
+        println!("🔄 Restarting server so LuckPerms gets loaded...");
+        stop_server(&config).await?;
+    }
+
+    let (mut client, _) = ensure_running(&config, ram).await?;
+
+    println!("👥 Applying starter group layout...");
+    for group in STARTER_GROUPS {
+        if group.name != "default" {
+            client.command(&format!("lp creategroup {}", group.name)).await?;
+        }
+
+        client.command(&format!("lp group {} setweight {}", group.name, group.weight)).await?;
+
+        if let Some(parent) = group.parent {
+            client.command(&format!("lp group {} parent add {}", group.name, parent)).await?;
+        }
+
+        for permission in group.permissions {
+            client.command(&format!("lp group {} permission set {} true", group.name, permission)).await?;
+        }
+
+        println!("  • {} ({} permissions)", group.name, group.permissions.len());
+    }
+
+    if !was_running {
+        crate::output::say("🛑", "server.stopping_after_setup", &[]);
+        stop_server(&config).await?;
+    }
+
+    println!("✅ LuckPerms is ready! Promote players with:");
+    println!("   lp user <player> parent add mod");
+    println!("   lp user <player> parent add admin");
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::rcon::RconClient;
+use crate::server::{is_server_running, load_server_config};
+use crate::PlayersAction;
+use anyhow::{anyhow, Result};
+
+pub async fn handle_players_action(server_name: &str, action: Option<PlayersAction>) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    if !is_server_running(&config).await {
+        return Err(anyhow!("Server '{}' is not running", server_name));
+    }
+
+    let mut client = crate::rcon::connect(&config).await?;
+
+    match action {
+        None => list_players(&mut client, server_name).await?,
+        Some(PlayersAction::Kick { player, reason }) => {
+            require_online(&mut client, &player).await?;
+            let command = if reason.is_empty() {
+                format!("kick {}", player)
+            } else {
+                format!("kick {} {}", player, reason.join(" "))
+            };
+            let response = client.command(&command).await?;
+            println!("👢 {}", response.trim());
+        }
+        Some(PlayersAction::Msg { player, message }) => {
+            require_online(&mut client, &player).await?;
+            client.command(&format!("tell {} {}", player, message.join(" "))).await?;
+            println!("💬 Sent to {}: {}", player, message.join(" "));
+        }
+        Some(PlayersAction::Tp { player, destination }) => {
+            require_online(&mut client, &player).await?;
+            if destination.len() != 1 && destination.len() != 3 {
+                return Err(anyhow!("Teleport to a player name or to x y z coordinates"));
+            }
+            if destination.len() == 1 {
+                require_online(&mut client, &destination[0]).await?;
+            }
+            let response = client.command(&format!("tp {} {}", player, destination.join(" "))).await?;
+            println!("🌀 {}", response.trim());
+        }
+    }
+
+    Ok(())
+}
+
+async fn list_players(client: &mut RconClient, server_name: &str) -> Result<()> {
+    let (summary, players) = online_players(client).await?;
+
+    if players.is_empty() {
+        println!("👥 Nobody is online on '{}' ({})", server_name, summary);
+        return Ok(());
+    }
+
+    println!("👥 Online on '{}' ({}):", server_name, summary);
+    for player in players {
+        println!("  • {}", player);
+    }
+    Ok(())
+}
+
+pub async fn online_players(client: &mut RconClient) -> Result<(String, Vec<String>)> {
+    // "There are 2 of a max of 20 players online: Alex, Steve"
+    let response = client.command("list").await?;
+    let (summary, names) = response.split_once(':').unwrap_or((&response, ""));
+
+    let players = names
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    let summary = summary
+        .trim()
+        .trim_start_matches("There are ")
+        .replace(" of a max of ", "/");
+    Ok((summary, players))
+}
+
+async fn require_online(client: &mut RconClient, player: &str) -> Result<()> {
+    let (_, players) = online_players(client).await?;
+    if players.iter().any(|online| online.eq_ignore_ascii_case(player)) {
+        Ok(())
+    } else {
+        Err(anyhow!("{} is not online", player))
+    }
+}
+
+pub async fn lookup_player(name_or_uuid: &str) -> Result<()> {
+    let Some(profile) = crate::api::mojang::lookup(name_or_uuid).await? else {
+        return Err(anyhow!("No Minecraft account found for '{}'", name_or_uuid));
+    };
+
+    println!("🔎 {}", profile.name);
+    println!(" - UUID: {}", profile.hyphenated_id());
+    Ok(())
+}
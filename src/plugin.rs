@@ -1,97 +1,393 @@
-use crate::{server::load_server_config, PluginAction};
+use crate::server::{
+    is_server_running, load_server_config, save_server_config, InstalledPlugin, PreviousVersion,
+    ServerConfig,
+};
+use crate::jar::read_plugin_descriptor;
+use crate::progress::{BulkProgress, Outcome};
+use crate::sources::{source_priority, PluginRelease};
+use crate::version::VersionConstraint;
+use crate::PluginAction;
 use anyhow::Result;
+use dialoguer::Select;
+use futures_util::future::join_all;
+use indicatif::{ProgressBar, ProgressStyle};
 use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DISABLED_DIR: &str = ".disabled";
+const PREVIOUS_DIR: &str = ".previous";
+// Downloads wait here, next to the plugins folder, until the scan lets them in
+const QUARANTINE_DIR: &str = ".quarantine";
 
 pub async fn handle_plugin_action(server_name: &str, action: PluginAction) -> Result<()> {
     match action {
-        PluginAction::Add { plugin, version } => {
-            add_plugin(server_name, &plugin, version.as_deref()).await?;
+        PluginAction::Add { plugin, version, manifest, scan } => {
+            match (plugin, manifest) {
+                (_, Some(manifest)) => {
+                    crate::manifest::install_manifest(server_name, &manifest, scan).await?;
+                }
+                (Some(plugin), None) => {
+                    add_plugin(server_name, &plugin, version.as_deref(), scan).await?;
+                }
+                (None, None) => {
+                    return Err(anyhow::anyhow!("Specify a plugin to add or pass --manifest"));
+                }
+            }
+        }
+        PluginAction::ExportManifest { output } => {
+            crate::manifest::export_manifest(server_name, &output)?;
         }
-        PluginAction::Remove { plugin } => {
-            remove_plugin(server_name, &plugin).await?;
+        PluginAction::Remove { plugin, all } => {
+            remove_plugin(server_name, &plugin, all).await?;
         }
         PluginAction::List => {
             list_plugins(server_name).await?;
         }
+        PluginAction::Disable { plugin } => {
+            set_plugin_enabled(server_name, &plugin, false)?;
+        }
+        PluginAction::Enable { plugin } => {
+            set_plugin_enabled(server_name, &plugin, true)?;
+        }
+        PluginAction::AddBundle { bundle } => {
+            crate::bundles::add_bundle(server_name, &bundle).await?;
+        }
+        PluginAction::Bundles => {
+            crate::bundles::list_bundles();
+        }
+        PluginAction::Update { plugin, all } => {
+            if plugin.is_none() && !all {
+                return Err(anyhow::anyhow!("Specify a plugin to update or pass --all"));
+            }
+            if plugin.is_none() {
+                crate::backup::safety_snapshot(&load_server_config(server_name)?, "plugin update").await?;
+            }
+            update_plugins(server_name, plugin.as_deref()).await?;
+        }
+        PluginAction::Rollback { plugin } => {
+            rollback_plugin(server_name, &plugin).await?;
+        }
+        PluginAction::Verify => {
+            crate::verify::verify_plugins(server_name).await?;
+        }
+        PluginAction::Config { plugin, get, set } => {
+            crate::config::plugin_config(server_name, &plugin, get.as_deref(), set.as_deref())?;
+        }
+        PluginAction::Browse { query, category, mc } => {
+            crate::browse::browse_plugins(
+                server_name,
+                query.as_deref(),
+                category.as_deref(),
+                mc.as_deref(),
+            ).await?;
+        }
+        PluginAction::Sources { sources, reset } => {
+            set_plugin_sources(server_name, sources, reset)?;
+        }
     }
     Ok(())
 }
 
-async fn add_plugin(
+pub async fn add_plugin(
     server_name: &str,
     plugin_query: &str,
     version: Option<&str>,
+    scan: bool,
 ) -> Result<()> {
+    let (plugin_query, constraint) = match plugin_query.split_once('@') {
+        Some((query, constraint)) => (query, Some(constraint)),
+        None => (plugin_query, None)
+    };
+
+    if constraint.is_some() && version.is_some() {
+        return Err(anyhow::anyhow!("Use either <plugin>@<constraint> or --version, not both"));
+    }
+
+    let parsed_constraint = constraint.map(VersionConstraint::parse).transpose()?;
     let config = load_server_config(server_name)?;
-    let plugins_dir = config.path.join("plugins");
-    fs::create_dir_all(&plugins_dir)?;
+    let (sources, _) = source_priority(&config)?;
 
-    println!("🔍 Searching for plugin: {}", plugin_query);
+    for source in &sources {
+        println!("🔍 Searching {} for plugin: {}", source, plugin_query);
 
-    let project = crate::api::modrinth::search_project(plugin_query).await?;
-    println!("📦 Found: {} - {}", project.title, project.description);
+        let project = match crate::sources::search(source, plugin_query).await {
+            Ok(Some(project)) => project,
+            Ok(None) => continue,
+            Err(e) => {
+                println!("⚠️  {} search failed: {}", source, e);
+                continue;
+            }
+        };
 
-    let versions = crate::api::modrinth::get_project_versions(
-        &project.project_id,
-        &config.version,
-    ).await?;
+        match &project.description {
+            Some(description) => println!("📦 Found: {} - {}", project.title, description),
+            None => println!("📦 Found: {}", project.title)
+        }
+
+        let releases = match crate::sources::releases(source, &project.project_id, &config.version).await {
+            Ok(releases) => releases,
+            Err(e) => {
+                println!("⚠️  Could not list {} releases of {}: {}", source, project.title, e);
+                continue;
+            }
+        };
 
-    if versions.is_empty() {
+        let Some(release) = select_release(&releases, version, parsed_constraint.as_ref()) else {
+            println!(
+                "⚠️  {} has no matching release on {} for Minecraft {}",
+                project.title, source, config.version
+            );
+            continue;
+        };
+
+        return install_release(
+            server_name,
+            source,
+            &project.project_id,
+            &project.title,
+            release,
+            constraint,
+            scan,
+        ).await;
+    }
+
+    Err(anyhow::anyhow!(
+        "No compatible release of '{}' found on {}",
+        plugin_query,
+        sources.join(", ")
+    ))
+}
+
+pub async fn install_project(
+    server_name: &str,
+    source: &str,
+    project_id: &str,
+    title: &str,
+    version: Option<&str>,
+    constraint: Option<&str>,
+    scan: bool,
+) -> Result<()> {
+    let parsed_constraint = constraint.map(VersionConstraint::parse).transpose()?;
+    let config = load_server_config(server_name)?;
+
+    let releases = crate::sources::releases(source, project_id, &config.version).await?;
+    if releases.is_empty() {
         return Err(anyhow::anyhow!(
             "No compatible versions found for Minecraft {}",
             config.version
         ));
     }
 
-    let selected_version = if let Some(v) = version {
-        versions
-            .iter()
-            .find(|ver| ver.version_number == v)
-            .ok_or_else(|| anyhow::anyhow!("Version {} not found", v))?
-    } else {
-        &versions[0]
-    };
+    let release = select_release(&releases, version, parsed_constraint.as_ref()).ok_or_else(|| {
+        match (version, constraint) {
+            (Some(v), _) => anyhow::anyhow!("Version {} not found", v),
+            (None, range) => anyhow::anyhow!(
+                "No version of {} matching {} for Minecraft {}",
+                title, range.unwrap_or_default(), config.version
+            )
+        }
+    })?;
 
-    let primary_file = selected_version
-        .files
-        .iter()
-        .find(|f| f.primary)
-        .unwrap_or(&selected_version.files[0]);
+    install_release(server_name, source, project_id, title, release, constraint, scan).await
+}
+
+fn select_release<'a>(
+    releases: &'a [PluginRelease],
+    version: Option<&str>,
+    constraint: Option<&VersionConstraint>,
+) -> Option<&'a PluginRelease> {
+    match (version, constraint) {
+        (Some(v), _) => releases.iter().find(|release| release.version_number == v),
+        (None, Some(constraint)) => releases.iter().find(|release| constraint.matches(&release.version_number)),
+        (None, None) => releases.first()
+    }
+}
+
+async fn install_release(
+    server_name: &str,
+    source: &str,
+    project_id: &str,
+    title: &str,
+    release: &PluginRelease,
+    constraint: Option<&str>,
+    scan: bool,
+) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let plugins_dir = config.path.join("plugins");
+    fs::create_dir_all(&plugins_dir)?;
 
     println!(
-        "📥 Downloading {} v{}...",
-        project.title, selected_version.version_number
+        "📥 Downloading {} v{} from {}...",
+        title, release.version_number, source
     );
 
-    crate::api::modrinth::download_plugin(
-        &primary_file.url,
-        &primary_file.filename,
+    let existing = config.plugins
+        .iter()
+        .position(|p| p.source == source && p.project_id == project_id)
+        .map(|index| config.plugins.remove(index));
+
+    let (previous, sha1) = replace_plugin_jar(
         &plugins_dir,
+        existing.as_ref(),
+        &release.version_id,
+        &release.url,
+        &release.filename,
+        scan,
+        None,
     ).await?;
 
-    println!("✅ Plugin {} installed successfully!", project.title);
+    config.plugins.push(InstalledPlugin {
+        name: title.to_string(),
+        source: source.to_string(),
+        project_id: project_id.to_string(),
+        version_id: release.version_id.clone(),
+        version_number: release.version_number.clone(),
+        filename: release.filename.clone(),
+        constraint: constraint.map(str::to_string),
+        previous,
+        sha1: Some(sha1)
+    });
+    save_server_config(&config)?;
+
+    println!("✅ Plugin {} installed successfully!", title);
+
+    Ok(())
+}
+
+fn set_plugin_sources(server_name: &str, sources: Vec<String>, reset: bool) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+
+    if reset || !sources.is_empty() {
+        let sources: Vec<String> = sources.iter().map(|s| s.to_lowercase()).collect();
+        crate::sources::validate_sources(&sources)?;
+        config.plugin_sources = sources;
+        save_server_config(&config)?;
+    }
+
+    let (sources, origin) = source_priority(&config)?;
+    println!("🔌 Plugin sources for '{}' ({}): {}", server_name, origin, sources.join(" -> "));
 
     Ok(())
 }
 
-async fn remove_plugin(server_name: &str, plugin_name: &str) -> Result<()> {
+pub fn require_plugin_support(config: &ServerConfig) -> Result<()> {
+    if config.server_type == "vanilla" {
+        return Err(anyhow::anyhow!(
+            "Server '{}' is a vanilla server and cannot load plugins",
+            config.name
+        ));
+    }
+    Ok(())
+}
+
+pub fn is_plugin_installed(config: &ServerConfig, plugin_name: &str) -> bool {
+    let needle = plugin_name.to_lowercase();
+
+    if config.plugins.iter().any(|p| p.name.to_lowercase() == needle) {
+        return true;
+    }
+
+    fs::read_dir(config.path.join("plugins"))
+        .map(|dir| {
+            dir.filter_map(Result::ok).any(|entry| {
+                let filename = entry.file_name().to_string_lossy().to_lowercase();
+                filename.ends_with(".jar") && filename.contains(&needle)
+            })
+        })
+        .unwrap_or(false)
+}
+
+pub fn plugin_name_from_filename(filename: &str) -> String {
+    let stem = filename.trim_end_matches(".jar");
+
+    let parts: Vec<&str> = stem
+        .split(['-', '_'])
+        .take_while(|part| !part.trim_start_matches('v').starts_with(|c: char| c.is_ascii_digit()))
+        .collect();
+
+    if parts.is_empty() {
+        stem.to_string()
+    } else {
+        parts.join("-")
+    }
+}
+
+pub async fn ensure_plugin(server_name: &str, plugin_name: &str) -> Result<bool> {
     let config = load_server_config(server_name)?;
+    require_plugin_support(&config)?;
+
+    if is_plugin_installed(&config, plugin_name) {
+        println!("✅ {} is already installed", plugin_name);
+        return Ok(false);
+    }
+
+    add_plugin(server_name, plugin_name, None, false).await?;
+    Ok(true)
+}
+
+async fn remove_plugin(server_name: &str, plugin_name: &str, all: bool) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
     let plugins_dir = config.path.join("plugins");
 
-    for entry in fs::read_dir(&plugins_dir)? {
-        let entry = entry?;
-        let filename = entry.file_name().to_string_lossy().to_lowercase();
-        if filename.contains(&plugin_name.to_lowercase()) {
-            fs::remove_file(entry.path())?;
-            println!("🗑️  Removed plugin: {}", entry.file_name().to_string_lossy());
+    let matches = find_plugin_jars(&plugins_dir, plugin_name)?;
+    let selected = match matches.len() {
+        0 => {
+            println!("❌ Plugin '{}' not found", plugin_name);
             return Ok(());
         }
+        1 => matches,
+        _ if all => matches,
+        _ => {
+            let names: Vec<String> = matches.iter().map(|path| describe_jar(path)).collect();
+            println!("🔍 '{}' matches {} plugins:", plugin_name, matches.len());
+
+            let selection = Select::new()
+                .with_prompt("Which plugin should be removed?")
+                .items(&names)
+                .default(0)
+                .interact_opt()
+                .map_err(|_| anyhow::anyhow!(
+                    "'{}' matches several plugins ({}); be more specific or pass --all",
+                    plugin_name,
+                    names.join(", ")
+                ))?;
+
+            match selection {
+                Some(index) => vec![matches[index].clone()],
+                None => {
+                    println!("❌ Removal cancelled");
+                    return Ok(());
+                }
+            }
+        }
+    };
+
+    for jar in selected {
+        let removed = delete_plugin_jar(&mut config, &jar)?;
+        println!("🗑️  Removed plugin: {}", removed);
     }
+    save_server_config(&config)?;
 
-    println!("❌ Plugin '{}' not found", plugin_name);
     Ok(())
 }
 
+// Deletes the jar along with its tracking entry and kept previous version, the caller saves the config
+pub fn delete_plugin_jar(config: &mut ServerConfig, jar: &Path) -> Result<String> {
+    fs::remove_file(jar)?;
+
+    let removed = jar.file_name().unwrap_or_default().to_string_lossy().to_string();
+    if let Some(stale) = config.plugins
+        .iter()
+        .find(|p| p.filename == removed)
+        .and_then(|p| p.previous.as_ref())
+    {
+        let _ = fs::remove_file(config.path.join("plugins").join(PREVIOUS_DIR).join(&stale.filename));
+    }
+    config.plugins.retain(|p| p.filename != removed);
+
+    Ok(removed)
+}
+
 async fn list_plugins(server_name: &str) -> Result<()> {
     let config = load_server_config(server_name)?;
     let plugins_dir = config.path.join("plugins");
@@ -103,12 +399,470 @@ async fn list_plugins(server_name: &str) -> Result<()> {
 
     println!("🔌 Plugins for server '{}':", server_name);
 
-    for entry in fs::read_dir(&plugins_dir)? {
-        let entry = entry?;
-        if entry.path().extension().map_or(false, |ext| ext == "jar") {
-            println!("  • {}", entry.file_name().to_string_lossy());
+    let mut jars: Vec<_> = fs::read_dir(&plugins_dir)?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "jar"))
+        .collect();
+    jars.sort_by_key(|entry| entry.file_name());
+
+    for entry in jars {
+        let filename = entry.file_name().to_string_lossy().to_string();
+
+        let descriptor = match read_plugin_descriptor(&entry.path()) {
+            Ok(Some(descriptor)) => descriptor,
+            Ok(None) => {
+                println!("  • {} (no plugin.yml)", filename);
+                continue;
+            }
+            Err(e) => {
+                println!("  • {} (unreadable: {})", filename, e);
+                continue;
+            }
+        };
+
+        let api = descriptor.api_version
+            .as_ref()
+            .map(|api| format!(", api {}", api))
+            .unwrap_or_default();
+        let kind = if descriptor.paper_plugin { ", paper plugin" } else { "" };
+        println!("  • {} v{}{}{} [{}]", descriptor.name, descriptor.version, api, kind, filename);
+
+        let constraint = config.plugins
+            .iter()
+            .find(|p| p.filename == filename)
+            .and_then(|p| p.constraint.as_ref());
+        if let Some(constraint) = constraint {
+            println!("      pinned to {}", constraint);
+        }
+
+        if !descriptor.authors.is_empty() {
+            println!("      by {}", descriptor.authors.join(", "));
+        }
+        if !descriptor.depend.is_empty() {
+            println!("      depends on: {}", descriptor.depend.join(", "));
+        }
+        if !descriptor.soft_depend.is_empty() {
+            println!("      soft-depends on: {}", descriptor.soft_depend.join(", "));
+        }
+    }
+
+    let disabled = jar_files(&plugins_dir.join(DISABLED_DIR))?;
+    if !disabled.is_empty() {
+        println!("⏸️  Disabled plugins:");
+        for path in disabled {
+            println!("  • {}", describe_jar(&path));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn jar_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut jars: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jar"))
+        .collect();
+    jars.sort();
+
+    Ok(jars)
+}
+
+fn describe_jar(path: &Path) -> String {
+    let filename = path.file_name().unwrap_or_default().to_string_lossy();
+    match read_plugin_descriptor(path) {
+        Ok(Some(descriptor)) => format!("{} v{} [{}]", descriptor.name, descriptor.version, filename),
+        _ => filename.to_string()
+    }
+}
+
+pub fn find_plugin_jars(dir: &Path, query: &str) -> Result<Vec<PathBuf>> {
+    let jars = jar_files(dir)?;
+    let query = query.to_lowercase();
+
+    let by_name: Vec<PathBuf> = jars
+        .iter()
+        .filter(|path| {
+            read_plugin_descriptor(path)
+                .ok()
+                .flatten()
+                .is_some_and(|descriptor| descriptor.name.to_lowercase() == query)
+        })
+        .cloned()
+        .collect();
+
+    if !by_name.is_empty() {
+        return Ok(by_name);
+    }
+
+    let by_partial_name: Vec<PathBuf> = jars
+        .iter()
+        .filter(|path| {
+            read_plugin_descriptor(path)
+                .ok()
+                .flatten()
+                .is_some_and(|descriptor| descriptor.name.to_lowercase().contains(&query))
+        })
+        .cloned()
+        .collect();
+
+    if !by_partial_name.is_empty() {
+        return Ok(by_partial_name);
+    }
+
+    Ok(jars
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| name.to_string_lossy().to_lowercase().contains(&query))
+        })
+        .collect())
+}
+
+fn set_plugin_enabled(server_name: &str, plugin_name: &str, enabled: bool) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let plugins_dir = config.path.join("plugins");
+    let disabled_dir = plugins_dir.join(DISABLED_DIR);
+
+    let (source, destination) = if enabled {
+        (&disabled_dir, &plugins_dir)
+    } else {
+        (&plugins_dir, &disabled_dir)
+    };
+
+    let matches = find_plugin_jars(source, plugin_name)?;
+    let jar = match matches.as_slice() {
+        [] => {
+            let state = if enabled { "disabled" } else { "enabled" };
+            return Err(anyhow::anyhow!("No {} plugin matching '{}'", state, plugin_name));
         }
+        [jar] => jar,
+        _ => {
+            let names: Vec<String> = matches.iter().map(|path| describe_jar(path)).collect();
+            return Err(anyhow::anyhow!(
+                "'{}' matches several plugins, be more specific: {}",
+                plugin_name,
+                names.join(", ")
+            ));
+        }
+    };
+
+    let filename = jar.file_name().unwrap_or_default().to_string_lossy().to_string();
+    fs::create_dir_all(destination)?;
+    fs::rename(jar, destination.join(&filename))?;
+
+    config.disabled_plugins.retain(|disabled| disabled != &filename);
+    if !enabled {
+        config.disabled_plugins.push(filename.clone());
+    }
+    save_server_config(&config)?;
+
+    if enabled {
+        println!("▶️  Enabled plugin: {}", filename);
+    } else {
+        println!("⏸️  Disabled plugin: {}", filename);
     }
+    crate::output::hint("server.restart_to_apply", &[]);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+enum PluginUpdate {
+    Updated(Box<InstalledPlugin>),
+    Unchanged(String)
+}
+
+pub async fn update_plugins(server_name: &str, plugin_name: Option<&str>) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let plugins_dir = config.path.join("plugins");
+
+    let targets: Vec<usize> = config.plugins
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| plugin_name.is_none_or(|name| p.name.eq_ignore_ascii_case(name)))
+        .map(|(index, _)| index)
+        .collect();
+
+    if targets.is_empty() {
+        match plugin_name {
+            Some(name) => println!("❌ Plugin '{}' is not tracked by anvil", name),
+            None => println!("No tracked plugins to update for server '{}'", server_name)
+        }
+        return Ok(());
+    }
+
+    let progress = BulkProgress::new()?;
+    let checks = targets.iter().map(|&index| {
+        let installed = &config.plugins[index];
+        let bar = progress.add(&installed.name);
+        let (config, plugins_dir, progress) = (&config, &plugins_dir, &progress);
+
+        async move {
+            let result = update_plugin(config, installed, plugins_dir, &bar).await;
+            match &result {
+                Ok(PluginUpdate::Updated(updated)) => progress.finish(
+                    &bar,
+                    Outcome::Success,
+                    format!("{} -> {}", installed.version_number, updated.version_number)
+                ),
+                Ok(PluginUpdate::Unchanged(detail)) => progress.finish(&bar, Outcome::Skipped, detail.as_str()),
+                Err(e) => progress.finish(&bar, Outcome::Failed, e.to_string())
+            }
+            (index, result)
+        }
+    });
+    let results = join_all(checks).await;
+
+    let mut updated = 0;
+    for (index, result) in results {
+        if let Ok(PluginUpdate::Updated(plugin)) = result {
+            config.plugins[index] = *plugin;
+            updated += 1;
+        }
+    }
+    if updated > 0 {
+        save_server_config(&config)?;
+    }
+
+    let failed = progress.summary(&format!("Plugin updates on '{}'", server_name));
+    if failed > 0 {
+        return Err(anyhow::anyhow!("{} plugin update(s) failed", failed));
+    }
+
+    Ok(())
+}
+
+async fn update_plugin(
+    config: &ServerConfig,
+    installed: &InstalledPlugin,
+    plugins_dir: &Path,
+    bar: &ProgressBar,
+) -> Result<PluginUpdate> {
+    if config.disabled_plugins.contains(&installed.filename) {
+        return Ok(PluginUpdate::Unchanged("disabled".to_string()));
+    }
+
+    bar.set_message(format!("checking {}", installed.version_number));
+
+    let releases = crate::sources::releases(
+        &installed.source,
+        &installed.project_id,
+        &config.version,
+    ).await?;
+
+    let constraint = installed.constraint.as_deref().map(VersionConstraint::parse).transpose()?;
+    let latest = select_release(&releases, None, constraint.as_ref());
+
+    let Some(latest) = latest else {
+        return Ok(PluginUpdate::Unchanged(match &installed.constraint {
+            Some(range) => format!("no versions matching {} for Minecraft {}", range, config.version),
+            None => format!("no compatible versions for Minecraft {}", config.version)
+        }));
+    };
+
+    if latest.version_id == installed.version_id {
+        return Ok(PluginUpdate::Unchanged(match &installed.constraint {
+            Some(range) => format!("up to date within {}", range),
+            None => "up to date".to_string()
+        }));
+    }
+
+    let (previous, sha1) = replace_plugin_jar(
+        plugins_dir,
+        Some(installed),
+        &latest.version_id,
+        &latest.url,
+        &latest.filename,
+        false,
+        Some(bar),
+    ).await?;
+
+    Ok(PluginUpdate::Updated(Box::new(InstalledPlugin {
+        version_id: latest.version_id.clone(),
+        version_number: latest.version_number.clone(),
+        filename: latest.filename.clone(),
+        previous,
+        sha1: Some(sha1),
+        ..installed.clone()
+    })))
+}
+
+async fn replace_plugin_jar(
+    plugins_dir: &Path,
+    existing: Option<&InstalledPlugin>,
+    version_id: &str,
+    url: &str,
+    filename: &str,
+    scan: bool,
+    progress: Option<&ProgressBar>,
+) -> Result<(Option<PreviousVersion>, String)> {
+    let current = existing.filter(|installed| {
+        installed.version_id != version_id && plugins_dir.join(&installed.filename).exists()
+    });
+
+    let Some(current) = current else {
+        let sha1 = download_plugin_jar(url, filename, plugins_dir, scan, progress).await?;
+        return Ok((existing.and_then(|installed| installed.previous.clone()), sha1));
+    };
+
+    let previous_dir = plugins_dir.join(PREVIOUS_DIR);
+    fs::create_dir_all(&previous_dir)?;
+    let stale = current.previous.as_ref().map(|stale| previous_dir.join(&stale.filename));
+    let stashed = previous_dir.join(&current.filename);
+    // A stale jar with the same name would be overwritten by the stash, it is parked until the download is done
+    let parked = stale
+        .as_ref()
+        .filter(|stale| **stale == stashed && stale.exists())
+        .map(|_| stashed.with_extension("jar.stale"));
+    if let Some(parked) = &parked {
+        fs::rename(&stashed, parked)?;
+    }
+    fs::rename(plugins_dir.join(&current.filename), &stashed)?;
+
+    let sha1 = match download_plugin_jar(url, filename, plugins_dir, scan, progress).await {
+        Ok(sha1) => sha1,
+        Err(e) => {
+            fs::rename(&stashed, plugins_dir.join(&current.filename))?;
+            if let Some(parked) = &parked {
+                fs::rename(parked, &stashed)?;
+            }
+            return Err(e);
+        }
+    };
+
+    // Only dropped once the update went through, a failed one leaves the saved rollback target in place
+    match (&parked, &stale) {
+        (Some(parked), _) => {
+            let _ = fs::remove_file(parked);
+        }
+        (None, Some(stale)) if *stale != stashed => {
+            let _ = fs::remove_file(stale);
+        }
+        _ => {}
+    }
+
+    Ok((Some(PreviousVersion {
+        version_id: current.version_id.clone(),
+        version_number: current.version_number.clone(),
+        filename: current.filename.clone(),
+        sha1: current.sha1.clone()
+    }), sha1))
+}
+
+async fn download_plugin_jar(
+    url: &str,
+    filename: &str,
+    plugins_dir: &Path,
+    scan: bool,
+    progress: Option<&ProgressBar>,
+) -> Result<String> {
+    // A jar with the same name may already be installed, it must survive a download the scan rejects
+    let quarantine_dir = plugins_dir.parent().unwrap_or(plugins_dir).join(QUARANTINE_DIR);
+    fs::create_dir_all(&quarantine_dir)?;
+
+    let sha1 = match progress {
+        Some(pb) => {
+            pb.set_message(format!("downloading {}", filename));
+            crate::api::modrinth::download_plugin(url, filename, &quarantine_dir, pb).await?
+        }
+        None => {
+            let pb = ProgressBar::new(0);
+            pb.set_style(
+                ProgressStyle::default_bar()
+                    .template("{spinner:.green} [{elapsed_precise}] {bar:40.cyan/blue} {bytes}/{total_bytes} {msg}")?
+                    .progress_chars("█▉▊▋▌▍▎▏  "),
+            );
+            pb.set_message(format!("Downloading {}", filename));
+            let sha1 = crate::api::modrinth::download_plugin(url, filename, &quarantine_dir, &pb).await?;
+            pb.finish_with_message("Download complete!");
+            sha1
+        }
+    };
+
+    let quarantined = quarantine_dir.join(filename);
+    let result = if scan && !crate::scan::confirm_jar(&quarantined)? {
+        fs::remove_file(&quarantined)?;
+        Err(anyhow::anyhow!("Installation of {} cancelled after scan", filename))
+    } else {
+        fs::rename(&quarantined, plugins_dir.join(filename)).map(|_| sha1).map_err(anyhow::Error::from)
+    };
+    // Only removed once empty, a concurrent install may still be using it
+    let _ = fs::remove_dir(&quarantine_dir);
+
+    result
+}
+
+async fn rollback_plugin(server_name: &str, plugin_name: &str) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let plugins_dir = config.path.join("plugins");
+    let previous_dir = plugins_dir.join(PREVIOUS_DIR);
+
+    let index = config.plugins
+        .iter()
+        .position(|p| p.name.eq_ignore_ascii_case(plugin_name))
+        .ok_or_else(|| anyhow::anyhow!("Plugin '{}' is not tracked by anvil", plugin_name))?;
+
+    let installed = config.plugins[index].clone();
+    let Some(previous) = installed.previous.clone() else {
+        return Err(anyhow::anyhow!("No previous version of {} to roll back to", installed.name));
+    };
+
+    if config.disabled_plugins.contains(&installed.filename) {
+        return Err(anyhow::anyhow!("{} is disabled, enable it before rolling back", installed.name));
+    }
+
+    let stashed = previous_dir.join(&previous.filename);
+    if !stashed.exists() {
+        return Err(anyhow::anyhow!("Previous jar {} is missing", stashed.display()));
+    }
+
+    // Both versions may share a filename, so park the current jar before swapping
+    let parked = previous_dir.join(format!("{}.rollback", installed.filename));
+    fs::rename(plugins_dir.join(&installed.filename), &parked)?;
+    fs::rename(&stashed, plugins_dir.join(&previous.filename))?;
+    fs::rename(&parked, previous_dir.join(&installed.filename))?;
+
+    config.plugins[index] = InstalledPlugin {
+        version_id: previous.version_id,
+        version_number: previous.version_number.clone(),
+        filename: previous.filename,
+        sha1: previous.sha1,
+        previous: Some(PreviousVersion {
+            version_id: installed.version_id,
+            version_number: installed.version_number.clone(),
+            filename: installed.filename,
+            sha1: installed.sha1
+        }),
+        ..config.plugins[index].clone()
+    };
+    save_server_config(&config)?;
+
+    println!(
+        "⏪ Rolled back {} {} -> {}",
+        installed.name, installed.version_number, previous.version_number
+    );
+    println!("💡 Run the rollback again to return to {}", installed.version_number);
+
+    if is_server_running(&config).await {
+        crate::output::hint("server.restart_to_apply", &[]);
+    }
+
+    Ok(())
+}
+
+pub async fn redownload_plugin(config: &ServerConfig, installed: &InstalledPlugin, dir: &Path) -> Result<String> {
+    let releases = crate::sources::releases(&installed.source, &installed.project_id, &config.version).await?;
+    let release = releases
+        .iter()
+        .find(|release| release.version_id == installed.version_id)
+        .ok_or_else(|| anyhow::anyhow!(
+            "{} v{} is no longer available from {}",
+            installed.name, installed.version_number, installed.source
+        ))?;
+
+    download_plugin_jar(&release.url, &installed.filename, dir, false, None).await
+}
@@ -0,0 +1,163 @@
+use anyhow::Result;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use serde_json::{json, Value};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+// Download events are throttled to one per this many bytes, plus the final one
+const EVENT_BYTES_STEP: u64 = 1024 * 1024;
+
+static JSON_EVENTS: OnceLock<bool> = OnceLock::new();
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Success,
+    Skipped,
+    Failed
+}
+
+impl Outcome {
+    fn icon(self) -> &'static str {
+        match self {
+            Outcome::Success => "✅",
+            Outcome::Skipped => "⏭️ ",
+            Outcome::Failed => "❌"
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Outcome::Success => "ok",
+            Outcome::Skipped => "skipped",
+            Outcome::Failed => "failed"
+        }
+    }
+}
+
+struct ItemResult {
+    name: String,
+    outcome: Outcome,
+    detail: String
+}
+
+pub struct BulkProgress {
+    multi: MultiProgress,
+    style: ProgressStyle,
+    results: Mutex<Vec<ItemResult>>
+}
+
+impl BulkProgress {
+    pub fn new() -> Result<Self> {
+        Ok(BulkProgress {
+            multi: MultiProgress::new(),
+            style: ProgressStyle::with_template("{spinner:.green} {prefix:24.bold} {msg}")?,
+            results: Mutex::new(Vec::new())
+        })
+    }
+
+    pub fn add(&self, name: &str) -> ProgressBar {
+        let bar = self.multi.add(ProgressBar::new_spinner());
+        bar.set_style(self.style.clone());
+        bar.set_prefix(name.to_string());
+        bar.set_message("waiting");
+        bar.enable_steady_tick(Duration::from_millis(100));
+        bar
+    }
+
+    pub fn finish(&self, bar: &ProgressBar, outcome: Outcome, detail: impl Into<String>) {
+        let detail = detail.into();
+        bar.finish_with_message(format!("{} {}", outcome.icon(), detail));
+
+        if let Ok(mut results) = self.results.lock() {
+            results.push(ItemResult {
+                name: bar.prefix(),
+                outcome,
+                detail
+            });
+        }
+    }
+
+    pub fn summary(&self, title: &str) -> usize {
+        let _ = self.multi.clear();
+        let Ok(results) = self.results.lock() else {
+            return 0;
+        };
+
+        let width = results.iter().map(|result| result.name.len()).max().unwrap_or(0).max(4);
+
+        println!("📊 {}:", title);
+        println!("  {:width$}  {:10}  DETAIL", "NAME", "STATUS", width = width);
+        for result in results.iter() {
+            println!(
+                "  {:width$}  {} {:7}  {}",
+                result.name,
+                result.outcome.icon(),
+                result.outcome.label(),
+                result.detail,
+                width = width
+            );
+        }
+
+        let count = |outcome: Outcome| results.iter().filter(|result| result.outcome == outcome).count();
+        let failed = count(Outcome::Failed);
+        println!("{} succeeded, {} skipped, {} failed", count(Outcome::Success), count(Outcome::Skipped), failed);
+
+        failed
+    }
+}
+
+// With --progress json, wrappers read newline-delimited events from stderr instead of parsing the bars
+pub fn enable_json_events() {
+    let _ = JSON_EVENTS.set(true);
+}
+
+pub fn json_events() -> bool {
+    JSON_EVENTS.get().copied().unwrap_or(false)
+}
+
+fn emit(event: Value) {
+    if json_events() {
+        eprintln!("{}", event);
+    }
+}
+
+pub fn stage(stage: &str, message: &str) {
+    emit(json!({ "event": "stage", "stage": stage, "message": message }));
+}
+
+pub fn failed(message: &str) {
+    emit(json!({ "event": "error", "message": message }));
+}
+
+// Tracks one download; the terminal bar is hidden while events are emitted so stderr stays parseable
+pub struct DownloadEvents {
+    file: String,
+    total: Option<u64>,
+    last_emitted: u64
+}
+
+impl DownloadEvents {
+    pub fn new(file: &str, total: Option<u64>, pb: &ProgressBar) -> Self {
+        if json_events() {
+            pb.set_draw_target(ProgressDrawTarget::hidden());
+        }
+        let events = DownloadEvents { file: file.to_string(), total, last_emitted: 0 };
+        events.emit(0, false);
+        events
+    }
+
+    pub fn update(&mut self, bytes: u64) {
+        if bytes >= self.last_emitted + EVENT_BYTES_STEP {
+            self.last_emitted = bytes;
+            self.emit(bytes, false);
+        }
+    }
+
+    pub fn finish(&self, bytes: u64) {
+        self.emit(bytes, true);
+    }
+
+    fn emit(&self, bytes: u64, done: bool) {
+        emit(json!({ "event": "download", "file": self.file, "bytes": bytes, "total": self.total, "done": done }));
+    }
+}
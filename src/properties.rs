@@ -0,0 +1,238 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+pub struct PropertiesFile {
+    lines: Vec<String>
+}
+
+struct Entry {
+    line: usize,
+    end: usize,
+    key: String,
+    value: String
+}
+
+impl PropertiesFile {
+    pub fn parse(contents: &str) -> Self {
+        PropertiesFile {
+            lines: contents.lines().map(str::to_string).collect()
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(PropertiesFile { lines: Vec::new() });
+        }
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.render())?;
+        Ok(())
+    }
+
+    pub fn render(&self) -> String {
+        self.lines.join("\n") + "\n"
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.entries()
+            .into_iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.value)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries().iter().any(|entry| entry.key == key)
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> Option<String> {
+        let line = format!("{}={}", escape(key, true), escape(value, false));
+
+        let entries = self.entries();
+        let Some(entry) = entries.iter().find(|entry| entry.key == key) else {
+            self.lines.push(line);
+            return None;
+        };
+
+        // A continued value spans several physical lines; collapse it into one
+        self.lines.splice(entry.line..entry.end, [line]);
+        Some(entry.value.clone())
+    }
+
+    pub fn set_default(&mut self, key: &str, value: &str) {
+        if !self.contains(key) {
+            self.set(key, value);
+        }
+    }
+
+    pub fn values(&self) -> Vec<(String, String)> {
+        self.entries()
+            .into_iter()
+            .map(|entry| (entry.key, entry.value))
+            .collect()
+    }
+
+    fn entries(&self) -> Vec<Entry> {
+        let mut entries = Vec::new();
+        let mut index = 0;
+
+        while index < self.lines.len() {
+            let start = index;
+            let mut logical = self.lines[index].trim_start().to_string();
+            index += 1;
+
+            if logical.is_empty() || logical.starts_with('#') || logical.starts_with('!') {
+                continue;
+            }
+
+            while ends_with_continuation(&logical) && index < self.lines.len() {
+                logical.pop();
+                logical.push_str(self.lines[index].trim_start());
+                index += 1;
+            }
+
+            let (key, value) = split_entry(&logical);
+            entries.push(Entry {
+                line: start,
+                end: index,
+                key: unescape(&key),
+                value: unescape(&value)
+            });
+        }
+
+        entries
+    }
+}
+
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+fn split_entry(line: &str) -> (String, String) {
+    let mut chars = line.char_indices();
+    let mut key_end = line.len();
+
+    while let Some((position, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '=' | ':' | ' ' | '\t' => {
+                key_end = position;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let key = &line[..key_end];
+    let rest = line[key_end..].trim_start_matches([' ', '\t']);
+    let rest = rest
+        .strip_prefix(['=', ':'])
+        .map(|rest| rest.trim_start_matches([' ', '\t']))
+        .unwrap_or(rest);
+
+    (key.to_string(), rest.to_string())
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('f') => result.push('\u{c}'),
+            Some('u') => {
+                let code: String = chars.by_ref().take(4).collect();
+                match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                    Some(decoded) => result.push(decoded),
+                    None => result.push_str(&code)
+                }
+            }
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+
+    result
+}
+
+fn escape(value: &str, is_key: bool) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for (position, c) in value.chars().enumerate() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            '\r' => result.push_str("\\r"),
+            '=' | ':' | '#' | '!' => {
+                result.push('\\');
+                result.push(c);
+            }
+            ' ' if is_key || position == 0 => result.push_str("\\ "),
+            _ => result.push(c)
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn continued_values_are_joined() {
+        let file = PropertiesFile::parse("motd=Hello \\\n    world\nmax-players=20\n");
+        assert_eq!(file.get("motd").as_deref(), Some("Hello world"));
+        assert_eq!(file.get("max-players").as_deref(), Some("20"));
+    }
+
+    #[test]
+    fn untouched_files_render_unchanged() {
+        let contents = "#Minecraft server properties\nmotd=A \\\n  B\n\nlevel-name=world\n";
+        assert_eq!(PropertiesFile::parse(contents).render(), contents);
+    }
+
+    #[test]
+    fn setting_a_continued_value_collapses_it() {
+        let mut file = PropertiesFile::parse("motd=A \\\n  B\nlevel-name=world\n");
+        assert_eq!(file.set("motd", "C").as_deref(), Some("A B"));
+        assert_eq!(file.render(), "motd=C\nlevel-name=world\n");
+    }
+
+    #[test]
+    fn escaped_backslash_does_not_continue() {
+        let file = PropertiesFile::parse("path=C\\:\\\\\nnext=1\n");
+        assert_eq!(file.get("path").as_deref(), Some("C:\\"));
+        assert_eq!(file.get("next").as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn escaped_values_round_trip() {
+        let mut file = PropertiesFile::parse("");
+        file.set("motd", " Line one\nkey=value #1");
+        let reparsed = PropertiesFile::parse(&file.render());
+        assert_eq!(reparsed.get("motd").as_deref(), Some(" Line one\nkey=value #1"));
+    }
+
+    #[test]
+    fn separators_and_comments() {
+        let file = PropertiesFile::parse("# a=1\n! b=2\nc : 3\nd 4\ne=\n");
+        assert!(!file.contains("a") && !file.contains("b"));
+        assert_eq!(file.get("c").as_deref(), Some("3"));
+        assert_eq!(file.get("d").as_deref(), Some("4"));
+        assert_eq!(file.get("e").as_deref(), Some(""));
+    }
+}
@@ -0,0 +1,333 @@
+use crate::plugin::{ensure_plugin, is_plugin_installed};
+use crate::secrets::{generate_secret, server_secrets, update_server_secrets};
+use crate::server::{
+    get_all_servers, is_server_running, load_server_config, read_server_properties, save_server_config,
+    set_server_property, ServerConfig,
+};
+use crate::yaml::YamlDocument;
+use crate::{NetworkAction, ProxyAction};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const PROXY_KINDS: &[&str] = &["velocity", "bungeecord"];
+const SPIGOT_CONFIG: &str = "spigot.yml";
+const PAPER_GLOBAL_CONFIG: &str = "config/paper-global.yml";
+const LEGACY_PAPER_CONFIG: &str = "paper.yml";
+const BUNGEEGUARD: &str = "BungeeGuard";
+const BUNGEEGUARD_CONFIG: &str = "plugins/BungeeGuard/config.yml";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProxyConfig {
+    pub kind: String,
+    // Shown through the proxy's ping passthrough while the backend is down
+    #[serde(default)]
+    pub offline_motd: Option<String>
+}
+
+pub async fn handle_proxy_action(action: ProxyAction) -> Result<()> {
+    match action {
+        ProxyAction::Attach { server, kind } => attach(&server, &kind.to_lowercase())?,
+        ProxyAction::Detach { server } => detach(&server)?,
+        ProxyAction::Motd { server, text, clear } => set_offline_motd(&server, text, clear)?
+    }
+    Ok(())
+}
+
+pub async fn handle_network_action(action: NetworkAction) -> Result<()> {
+    match action {
+        NetworkAction::Harden => harden_network().await?
+    }
+    Ok(())
+}
+
+fn attach(server_name: &str, kind: &str) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    if !PROXY_KINDS.contains(&kind) {
+        return Err(anyhow!("Unknown proxy '{}', expected one of: {}", kind, PROXY_KINDS.join(", ")));
+    }
+    if config.server_type != "paper" {
+        return Err(anyhow!(
+            "Server '{}' is {}, only Paper servers can sit behind a proxy",
+            server_name,
+            config.server_type
+        ));
+    }
+
+    let offline_motd = config.proxy.take().and_then(|proxy| proxy.offline_motd);
+    config.proxy = Some(ProxyConfig { kind: kind.to_string(), offline_motd });
+    // The proxy authenticates players, so the backend must not try again; set once so a later
+    // 'config set server.online-mode' is not undone by every start
+    set_server_property(&config.path, "online-mode", "false")?;
+    let complete = apply_proxy_settings(&config)?;
+    save_server_config(&config)?;
+
+    println!("🔗 '{}' is now a {} backend running in offline mode", server_name, kind);
+    if !complete {
+        println!("💡 Some config files are created on first start; anvil applies the rest before every start");
+    }
+    if kind == "velocity" {
+        println!("💡 Use the forwarding secret on the proxy: anvil secret show {} --reveal", server_name);
+    }
+    warn_if_exposed(&config);
+
+    Ok(())
+}
+
+fn detach(server_name: &str) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let Some(proxy) = config.proxy.take() else {
+        println!("ℹ️  '{}' is not attached to a proxy", server_name);
+        return Ok(());
+    };
+
+    set_server_property(&config.path, "online-mode", "true")?;
+    apply_proxy_settings(&config)?;
+    save_server_config(&config)?;
+
+    println!("🔓 Detached '{}' from {}, online-mode is back on", server_name, proxy.kind);
+    Ok(())
+}
+
+fn set_offline_motd(server_name: &str, text: Option<String>, clear: bool) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let proxy = config
+        .proxy
+        .as_mut()
+        .ok_or_else(|| anyhow!("'{}' is not attached to a proxy; attach it with: anvil proxy attach {}", server_name, server_name))?;
+
+    if clear {
+        if proxy.offline_motd.take().is_none() {
+            return Err(anyhow!("'{}' has no offline MOTD", server_name));
+        }
+        save_server_config(&config)?;
+        crate::idle::release_port(&config)?;
+        println!("✅ Removed the offline MOTD of '{}'", server_name);
+        return Ok(());
+    }
+
+    let text = text.unwrap_or_default();
+    proxy.offline_motd = Some(text.replace("\\n", "\n"));
+    let velocity = proxy.kind == "velocity";
+    save_server_config(&config)?;
+
+    println!("✅ While '{}' is down, pings will show: {}", server_name, text);
+    println!("💡 anvil daemon answers on port {} whenever the server is stopped", config.port);
+    if velocity {
+        println!("💡 Let the proxy show it by setting ping-passthrough = \"description\" in velocity.toml");
+    }
+    Ok(())
+}
+
+// Returns false when a config file the proxy needs does not exist yet
+pub fn apply_proxy_settings(config: &ServerConfig) -> Result<bool> {
+    let kind = config.proxy.as_ref().map(|proxy| proxy.kind.as_str());
+    let velocity = kind == Some("velocity");
+    let bungeecord = kind == Some("bungeecord");
+    let mut secrets = server_secrets(&config.name)?;
+    // Servers created before the secret existed would otherwise enable modern forwarding without one
+    if velocity && secrets.forwarding_secret.is_none() {
+        let generated = generate_secret();
+        update_server_secrets(&config.name, |secrets| secrets.forwarding_secret = Some(generated.clone()))?;
+        secrets.forwarding_secret = Some(generated);
+        println!("🔑 Generated a forwarding secret for '{}', see it with: anvil secret show {} --reveal", config.name, config.name);
+    }
+    let secret = secrets.forwarding_secret;
+
+    let mut complete = update_yaml(config, SPIGOT_CONFIG, &[(&["settings", "bungeecord"], bungeecord.to_string())])?;
+
+    let mut velocity_settings = vec![(["proxies", "velocity", "enabled"].as_slice(), velocity.to_string())];
+    let mut legacy_settings = vec![(["settings", "velocity-support", "enabled"].as_slice(), velocity.to_string())];
+    if velocity {
+        velocity_settings.push((&["proxies", "velocity", "online-mode"], "true".to_string()));
+        legacy_settings.push((&["settings", "velocity-support", "online-mode"], "true".to_string()));
+        if let Some(secret) = &secret {
+            velocity_settings.push((&["proxies", "velocity", "secret"], secret.clone()));
+            legacy_settings.push((&["settings", "velocity-support", "secret"], secret.clone()));
+        }
+    }
+
+    // Older Paper builds keep the same settings in paper.yml
+    if config.path.join(LEGACY_PAPER_CONFIG).exists() {
+        update_yaml(config, LEGACY_PAPER_CONFIG, &legacy_settings)?;
+    } else {
+        complete &= update_yaml(config, PAPER_GLOBAL_CONFIG, &velocity_settings)?;
+    }
+
+    // BungeeGuard writes its config on first start, after that anvil owns the token list
+    if bungeecord && let Some(token) = &secrets.bungeeguard_token {
+        let path = config.path.join(BUNGEEGUARD_CONFIG);
+        if path.exists() {
+            let mut document = YamlDocument::parse(&fs::read_to_string(&path)?);
+            document.set_list(&["allowed-tokens"], std::slice::from_ref(token))?;
+            fs::write(path, document.render())?;
+        } else if is_plugin_installed(config, BUNGEEGUARD) {
+            complete = false;
+        }
+    }
+
+    Ok(complete || kind.is_none())
+}
+
+fn update_yaml(config: &ServerConfig, file: &str, settings: &[(&[&str], String)]) -> Result<bool> {
+    let path = config.path.join(file);
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    let mut document = YamlDocument::parse(&fs::read_to_string(&path)?);
+    for (key, value) in settings {
+        document.set(key, value)?;
+    }
+    fs::write(path, document.render())?;
+
+    Ok(true)
+}
+
+// Returns true when players could bypass the proxy and join the offline backend directly
+pub fn warn_if_exposed(config: &ServerConfig) -> bool {
+    let Some(proxy) = &config.proxy else {
+        return false;
+    };
+
+    let properties = read_server_properties(&config.path).unwrap_or_default();
+    let bind = properties.get("server-ip").map(String::as_str).unwrap_or("");
+    if matches!(bind, "127.0.0.1" | "localhost" | "::1") {
+        return false;
+    }
+
+    if config.exposure.is_some() {
+        println!(
+            "⚠️  '{}' is forwarded to the internet on port {} while in offline mode; anyone can join as any player",
+            config.name, config.port
+        );
+        println!("💡 Remove the forwarding with: anvil expose {} --remove", config.name);
+    } else {
+        println!(
+            "⚠️  '{}' runs in offline mode behind {} but listens on all interfaces, players must only reach it via the proxy",
+            config.name, proxy.kind
+        );
+    }
+    println!(
+        "💡 Bind it to the proxy host with: anvil config set {} server.server-ip 127.0.0.1, or firewall port {}",
+        config.name, config.port
+    );
+    true
+}
+
+async fn harden_network() -> Result<()> {
+    let backends: Vec<ServerConfig> = get_all_servers()?.into_iter().filter(|server| server.proxy.is_some()).collect();
+    if backends.is_empty() {
+        println!("ℹ️  No servers are attached to a proxy; attach one with: anvil proxy attach <server>");
+        return Ok(());
+    }
+
+    let mut issues = 0;
+    for kind in PROXY_KINDS {
+        let members: Vec<&ServerConfig> = backends
+            .iter()
+            .filter(|server| server.proxy.as_ref().is_some_and(|proxy| proxy.kind == *kind))
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+
+        let velocity = *kind == "velocity";
+        let names: Vec<&str> = members.iter().map(|server| server.name.as_str()).collect();
+        println!("🛡️  Hardening {} {} backend(s): {}", members.len(), kind, names.join(", "));
+
+        // Anvil manages one proxy per kind, so every backend behind it must accept the same secret
+        let synced = sync_shared_secret(&members, velocity)?;
+        if synced > 0 {
+            println!("🔑 Synced the {} across {} backend(s)", secret_label(velocity), synced);
+        }
+
+        for config in &members {
+            issues += harden_backend(config, velocity).await?;
+        }
+
+        println!(
+            "💡 Configure the proxy with the same {}: anvil secret show {} --reveal",
+            secret_label(velocity),
+            names[0]
+        );
+        if velocity {
+            println!("💡 velocity.toml needs player-info-forwarding-mode = \"modern\"");
+        } else {
+            println!("💡 The proxy needs ip_forward: true and BungeeGuard installed with that token");
+        }
+    }
+
+    if issues == 0 {
+        println!("✅ All proxy backends are hardened");
+    } else {
+        println!("⚠️  Found {} issue(s) that need attention", issues);
+    }
+
+    Ok(())
+}
+
+fn secret_label(velocity: bool) -> &'static str {
+    if velocity { "Velocity forwarding secret" } else { "BungeeGuard token" }
+}
+
+// Returns how many backends had to be updated
+fn sync_shared_secret(backends: &[&ServerConfig], velocity: bool) -> Result<usize> {
+    let pick = |secrets: crate::secrets::ServerSecrets| {
+        if velocity { secrets.forwarding_secret } else { secrets.bungeeguard_token }
+    };
+
+    let mut current = Vec::new();
+    for backend in backends {
+        current.push(pick(server_secrets(&backend.name)?));
+    }
+    let shared = current.iter().flatten().next().cloned().unwrap_or_else(generate_secret);
+
+    let mut updated = 0;
+    for (backend, secret) in backends.iter().zip(current) {
+        if secret.as_ref() == Some(&shared) {
+            continue;
+        }
+        update_server_secrets(&backend.name, |secrets| {
+            let slot = if velocity { &mut secrets.forwarding_secret } else { &mut secrets.bungeeguard_token };
+            *slot = Some(shared.clone());
+        })?;
+        updated += 1;
+    }
+
+    Ok(updated)
+}
+
+async fn harden_backend(config: &ServerConfig, velocity: bool) -> Result<usize> {
+    let mut issues = 0;
+
+    // Legacy BungeeCord forwarding trusts whatever IP and UUID the connection claims
+    if !velocity && !is_plugin_installed(config, BUNGEEGUARD) {
+        ensure_plugin(&config.name, BUNGEEGUARD).await?;
+        if !is_plugin_installed(&load_server_config(&config.name)?, BUNGEEGUARD) {
+            println!(
+                "❌ Could not install {} on '{}', download it from https://github.com/lucko/BungeeGuard/releases",
+                BUNGEEGUARD, config.name
+            );
+            issues += 1;
+        }
+    }
+
+    if apply_proxy_settings(config)? {
+        let method = if velocity { "modern forwarding" } else { "BungeeGuard" };
+        println!("✅ '{}' only trusts the proxy through {}", config.name, method);
+    } else {
+        println!("⚠️  '{}' has not generated its config files yet; start it once, then run harden again", config.name);
+        issues += 1;
+    }
+
+    if warn_if_exposed(config) {
+        issues += 1;
+    }
+
+    if is_server_running(config).await {
+        crate::output::warn("server.running_old_settings", &[&config.name]);
+    }
+
+    Ok(issues)
+}
@@ -0,0 +1,115 @@
+use crate::server::{read_server_properties, ServerConfig};
+use anyhow::{anyhow, Result};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::{Duration, Instant};
+
+const PACKET_AUTH: i32 = 3;
+const PACKET_COMMAND: i32 = 2;
+const MAX_PACKET_SIZE: i32 = 4096 + 10;
+
+pub struct RconClient {
+    stream: TcpStream,
+    next_id: i32
+}
+
+impl RconClient {
+    pub async fn connect(address: &str, password: &str) -> Result<Self> {
+        let stream = TcpStream::connect(address).await?;
+        let mut client = RconClient { stream, next_id: 1 };
+
+        let id = client.send(PACKET_AUTH, password).await?;
+        let (response_id, _) = client.receive().await?;
+        if response_id == -1 || response_id != id {
+            return Err(anyhow!("RCON authentication failed"));
+        }
+
+        Ok(client)
+    }
+
+    pub async fn command(&mut self, command: &str) -> Result<String> {
+        let id = self.send(PACKET_COMMAND, command).await?;
+
+        loop {
+            let (response_id, body) = self.receive().await?;
+            if response_id == id {
+                return Ok(body);
+            }
+        }
+    }
+
+    async fn send(&mut self, packet_type: i32, body: &str) -> Result<i32> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let length = 4 + 4 + body.len() as i32 + 2;
+        let mut packet = Vec::with_capacity(length as usize + 4);
+        packet.extend_from_slice(&length.to_le_bytes());
+        packet.extend_from_slice(&id.to_le_bytes());
+        packet.extend_from_slice(&packet_type.to_le_bytes());
+        packet.extend_from_slice(body.as_bytes());
+        packet.extend_from_slice(&[0, 0]);
+
+        self.stream.write_all(&packet).await?;
+        Ok(id)
+    }
+
+    async fn receive(&mut self) -> Result<(i32, String)> {
+        let length = self.stream.read_i32_le().await?;
+        if !(10..=MAX_PACKET_SIZE).contains(&length) {
+            return Err(anyhow!("Invalid RCON packet length: {}", length));
+        }
+
+        let id = self.stream.read_i32_le().await?;
+        let _packet_type = self.stream.read_i32_le().await?;
+
+        let mut body = vec![0u8; length as usize - 8];
+        self.stream.read_exact(&mut body).await?;
+        body.truncate(body.len() - 2);
+
+        Ok((id, String::from_utf8_lossy(&body).into_owned()))
+    }
+}
+
+pub async fn connect(config: &ServerConfig) -> Result<RconClient> {
+    let properties = read_server_properties(&config.path)?;
+
+    if properties.get("enable-rcon").map(String::as_str) != Some("true") {
+        return Err(anyhow!(
+            "RCON is not enabled for server '{}' (set enable-rcon=true in server.properties)",
+            config.name
+        ));
+    }
+
+    let port = properties
+        .get("rcon.port")
+        .map(String::as_str)
+        .unwrap_or("25575");
+    let password = properties
+        .get("rcon.password")
+        .filter(|password| !password.is_empty())
+        .ok_or_else(|| anyhow!("No rcon.password set for server '{}'", config.name))?;
+
+    RconClient::connect(&format!("127.0.0.1:{}", port), password)
+        .await
+        .map_err(|e| anyhow!("Could not connect to RCON for server '{}': {}", config.name, e))
+}
+
+pub async fn send_command(config: &ServerConfig, command: &str) -> Result<String> {
+    let mut client = connect(config).await?;
+    client.command(command).await
+}
+
+pub async fn wait_until_available(config: &ServerConfig, timeout: Duration) -> Result<RconClient> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match connect(config).await {
+            Ok(client) => return Ok(client),
+            Err(e) if Instant::now() >= deadline => {
+                return Err(anyhow!("Server '{}' did not become ready in time: {}", config.name, e));
+            }
+            Err(_) => tokio::time::sleep(Duration::from_secs(2)).await
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::api::http::SendWithBackoff;
+use crate::properties::PropertiesFile;
+use crate::server::{format_bytes, is_server_running, load_server_config, read_server_properties, ServerConfig};
+use crate::version::compare_versions;
+use crate::ResourcePackAction;
+use anyhow::{anyhow, Result};
+use futures_util::TryStreamExt;
+use sha1::{Digest, Sha1};
+
+const MB: u64 = 1024 * 1024;
+
+pub async fn handle_resource_pack_action(action: ResourcePackAction) -> Result<()> {
+    match action {
+        ResourcePackAction::Set { server, url, require, prompt } => {
+            set_pack(&server, &url, require, prompt.as_deref()).await?;
+        }
+        ResourcePackAction::Clear { server } => {
+            let config = load_server_config(&server)?;
+            write_properties(&config, &[
+                ("resource-pack", String::new()),
+                ("resource-pack-sha1", String::new()),
+                ("require-resource-pack", "false".to_string()),
+                ("resource-pack-prompt", String::new())
+            ])?;
+            println!("✅ Removed the resource pack from '{}'", server);
+            warn_if_running(&config).await;
+        }
+        ResourcePackAction::Show { server } => {
+            let config = load_server_config(&server)?;
+            let properties = read_server_properties(&config.path)?;
+            let value = |key: &str| properties.get(key).filter(|value| !value.is_empty()).cloned();
+
+            let Some(url) = value("resource-pack") else {
+                println!("📭 '{}' has no resource pack", server);
+                return Ok(());
+            };
+            println!("🎨 Resource pack of '{}':", server);
+            println!(" - URL: {}", url);
+            println!(" - SHA-1: {}", value("resource-pack-sha1").unwrap_or_else(|| "not set".to_string()));
+            println!(" - Required: {}", value("require-resource-pack").unwrap_or_else(|| "false".to_string()));
+            if let Some(prompt) = value("resource-pack-prompt") {
+                println!(" - Prompt: {}", prompt);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn set_pack(server_name: &str, url: &str, require: bool, prompt: Option<&str>) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(anyhow!("The pack URL must be a direct http(s) download link, got '{}'", url));
+    }
+    // Both settings were added to server.properties in 1.17
+    if (require || prompt.is_some()) && compare_versions(&config.version, "1.17").is_lt() {
+        return Err(anyhow!("--require and --prompt need Minecraft 1.17 or newer, '{}' runs {}", server_name, config.version));
+    }
+
+    let limit = client_size_limit(&config.version);
+    println!("🔍 Checking {}...", url);
+    let (sha1, size) = check_pack(url, limit).await?;
+    println!("✅ Pack is reachable: {} (client limit {})", format_bytes(size), format_bytes(limit));
+
+    let mut settings = vec![
+        ("resource-pack", url.to_string()),
+        ("resource-pack-sha1", sha1.clone()),
+        ("require-resource-pack", require.to_string())
+    ];
+    if let Some(prompt) = prompt {
+        // The prompt is a JSON text component, plain text is wrapped so users do not have to write JSON
+        let prompt = if prompt.trim_start().starts_with('{') {
+            prompt.to_string()
+        } else {
+            serde_json::json!({ "text": prompt }).to_string()
+        };
+        settings.push(("resource-pack-prompt", prompt));
+    }
+    write_properties(&config, &settings)?;
+
+    println!("🎨 '{}' now sends {} (sha1 {})", server_name, url, sha1);
+    if require {
+        println!("🔒 Players who decline the pack are disconnected");
+    }
+    println!("💡 Run this again after updating the pack, clients cache it by its SHA-1");
+    warn_if_running(&config).await;
+    Ok(())
+}
+
+// The largest pack the matching client accepts before refusing the download
+fn client_size_limit(version: &str) -> u64 {
+    if compare_versions(version, "1.15").is_lt() {
+        50 * MB
+    } else if compare_versions(version, "1.18").is_lt() {
+        100 * MB
+    } else {
+        250 * MB
+    }
+}
+
+async fn check_pack(url: &str, limit: u64) -> Result<(String, u64)> {
+    let response = reqwest::Client::new()
+        .get(url)
+        .send_with_backoff()
+        .await
+        .map_err(|e| anyhow!("The pack URL is not reachable: {}", e))?;
+
+    let too_large = |size: u64| {
+        anyhow!("The pack is {} but clients refuse packs over {}", format_bytes(size), format_bytes(limit))
+    };
+    if let Some(size) = response.content_length().filter(|size| *size > limit) {
+        return Err(too_large(size));
+    }
+
+    // Streamed and hashed without keeping the pack, it is only needed for its size and SHA-1
+    let mut hasher = Sha1::new();
+    let mut size = 0u64;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.try_next().await? {
+        if size == 0 && !chunk.starts_with(b"PK") {
+            return Err(anyhow!("The URL does not serve a zip file, use a direct download link"));
+        }
+        size += chunk.len() as u64;
+        if size > limit {
+            return Err(too_large(size));
+        }
+        hasher.update(&chunk);
+    }
+    if size == 0 {
+        return Err(anyhow!("The pack URL returned an empty file"));
+    }
+
+    Ok((hex::encode(hasher.finalize()), size))
+}
+
+fn write_properties(config: &ServerConfig, settings: &[(&str, String)]) -> Result<()> {
+    let path = config.path.join("server.properties");
+    let mut properties = PropertiesFile::load(&path)?;
+    for (key, value) in settings {
+        properties.set(key, value);
+    }
+    properties.save(&path)
+}
+
+async fn warn_if_running(config: &ServerConfig) {
+    if is_server_running(config).await {
+        crate::output::warn("server.running_old_settings", &[&config.name]);
+    }
+}
@@ -0,0 +1,120 @@
+use anyhow::Result;
+use dialoguer::Confirm;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+const MAX_SCANNED_ENTRY_BYTES: u64 = 8 * 1024 * 1024;
+
+// Package paths and strings from the fractureiser stage 0-3 payloads
+const MALWARE_PATHS: &[(&str, &str)] = &[
+    ("dev/neko/nekoclient/", "fractureiser"),
+    ("dev/neko/nekoinjector/", "fractureiser"),
+    ("net/skyrage/", "fractureiser (skyrage)")
+];
+
+const MALWARE_STRINGS: &[(&str, &str)] = &[
+    ("85.217.144.130", "fractureiser stage 1 host"),
+    ("107.189.3.101", "fractureiser stage 1 host"),
+    ("files.skyrage.de", "skyrage payload host"),
+    ("nekoclient", "fractureiser client")
+];
+
+const SUSPICIOUS_STRINGS: &[(&str, &str)] = &[
+    ("discord.com/api/webhooks", "Discord webhook URL"),
+    ("discordapp.com/api/webhooks", "Discord webhook URL"),
+    ("pastebin.com/raw", "raw Pastebin URL"),
+    ("transfer.sh", "file sharing URL"),
+    (".ngrok.io", "ngrok tunnel URL"),
+    ("api.telegram.org/bot", "Telegram bot URL")
+];
+
+pub struct Finding {
+    pub malware: bool,
+    pub description: String,
+    pub entry: String
+}
+
+pub fn scan_jar(jar_path: &Path) -> Result<Vec<Finding>> {
+    let mut archive = zip::ZipArchive::new(File::open(jar_path)?)?;
+    let mut findings = Vec::new();
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let name = entry.name().to_string();
+
+        for (path, family) in MALWARE_PATHS {
+            if name.starts_with(path) {
+                findings.push(Finding {
+                    malware: true,
+                    description: format!("{} class", family),
+                    entry: name.clone()
+                });
+            }
+        }
+
+        if !name.ends_with(".class") || entry.size() > MAX_SCANNED_ENTRY_BYTES {
+            continue;
+        }
+
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+
+        for (marker, description) in MALWARE_STRINGS {
+            if contains(&bytes, marker) {
+                findings.push(Finding {
+                    malware: true,
+                    description: description.to_string(),
+                    entry: name.clone()
+                });
+            }
+        }
+
+        for (marker, description) in SUSPICIOUS_STRINGS {
+            if contains(&bytes, marker) {
+                findings.push(Finding {
+                    malware: false,
+                    description: description.to_string(),
+                    entry: name.clone()
+                });
+            }
+        }
+    }
+
+    Ok(findings)
+}
+
+pub fn confirm_jar(jar_path: &Path) -> Result<bool> {
+    let filename = jar_path.file_name().unwrap_or_default().to_string_lossy();
+    println!("🛡️  Scanning {} for known malware markers...", filename);
+
+    let findings = scan_jar(jar_path)?;
+    if findings.is_empty() {
+        println!("✅ No suspicious markers found");
+        return Ok(true);
+    }
+
+    println!("⚠️  {} contains suspicious markers:", filename);
+    for finding in &findings {
+        let level = if finding.malware { "known malware" } else { "suspicious" };
+        println!("  • [{}] {} in {}", level, finding.description, finding.entry);
+    }
+
+    if findings.iter().any(|finding| finding.malware) {
+        println!("❌ This jar matches known malware and will not be installed");
+        return Ok(false);
+    }
+
+    let confirmed = Confirm::new()
+        .with_prompt("Install it anyway?")
+        .default(false)
+        .interact()
+        .unwrap_or(false);
+
+    Ok(confirmed)
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    let needle = needle.as_bytes();
+    haystack.windows(needle.len()).any(|window| window == needle)
+}
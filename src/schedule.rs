@@ -0,0 +1,430 @@
+use crate::server::{
+    get_all_servers, is_server_running, load_server_config, save_server_config, spawn_detached,
+    stop_server, ServerConfig,
+};
+use crate::ScheduleAction;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, Duration, Local, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration as StdDuration;
+
+const DAEMON_TICK_SECS: u64 = 30;
+const MAX_LOOKAHEAD_MINUTES: i64 = 366 * 24 * 60;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ScheduledTask {
+    pub id: String,
+    pub cron: String,
+    pub task: TaskKind,
+    pub created_at: DateTime<Utc>,
+    pub last_run: Option<DateTime<Utc>>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskKind {
+    Backup,
+    Restart { ram: u8 },
+    Command { command: String },
+    UpdatePlugins,
+    Announce { message: String }
+}
+
+impl TaskKind {
+    fn describe(&self) -> String {
+        match self {
+            TaskKind::Backup => "backup".to_string(),
+            TaskKind::Restart { ram } => format!("restart ({}G RAM)", ram),
+            TaskKind::Command { command } => format!("command: {}", command),
+            TaskKind::UpdatePlugins => "update-plugins".to_string(),
+            TaskKind::Announce { message } => format!("announcement: {}", message)
+        }
+    }
+}
+
+pub struct CronExpr {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days_of_month: Vec<u32>,
+    months: Vec<u32>,
+    days_of_week: Vec<u32>,
+    any_day_of_month: bool,
+    any_day_of_week: bool
+}
+
+impl CronExpr {
+    pub fn parse(expression: &str) -> Result<Self> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(anyhow!(
+                "Invalid cron expression '{}': expected 5 fields (minute hour day month weekday)",
+                expression
+            ));
+        }
+
+        let mut days_of_week = parse_field(fields[4], 0, 7)?;
+        for day in days_of_week.iter_mut() {
+            if *day == 7 {
+                *day = 0;
+            }
+        }
+
+        Ok(CronExpr {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days_of_month: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            days_of_week,
+            any_day_of_month: fields[2] == "*",
+            any_day_of_week: fields[4] == "*"
+        })
+    }
+
+    fn matches(&self, time: &DateTime<Local>) -> bool {
+        let day_of_month = self.days_of_month.contains(&time.day());
+        let day_of_week = self.days_of_week.contains(&time.weekday().num_days_from_sunday());
+
+        let day_matches = match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => day_of_week,
+            (false, true) => day_of_month,
+            (false, false) => day_of_month || day_of_week
+        };
+
+        day_matches
+            && self.minutes.contains(&time.minute())
+            && self.hours.contains(&time.hour())
+            && self.months.contains(&time.month())
+    }
+
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        let mut candidate = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+
+        for _ in 0..MAX_LOOKAHEAD_MINUTES {
+            if self.matches(&candidate) {
+                return Some(candidate);
+            }
+            candidate += Duration::minutes(1);
+        }
+
+        None
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<u32>> {
+    let mut values = Vec::new();
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>()?),
+            None => (part, 1)
+        };
+
+        if step == 0 {
+            return Err(anyhow!("Invalid step in cron field '{}'", field));
+        }
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (start.parse()?, end.parse()?)
+        } else {
+            let value = range.parse()?;
+            (value, if part.contains('/') { max } else { value })
+        };
+
+        if start < min || end > max || start > end {
+            return Err(anyhow!("Value out of range in cron field '{}' ({}-{})", field, min, max));
+        }
+
+        values.extend((start..=end).step_by(step as usize));
+    }
+
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+pub async fn handle_schedule_action(server_name: &str, action: ScheduleAction) -> Result<()> {
+    match action {
+        ScheduleAction::Add { cron, task, command, ram } => {
+            add_task(server_name, &cron, &task, command, ram)?;
+        }
+        ScheduleAction::List => {
+            list_tasks(server_name)?;
+        }
+        ScheduleAction::Remove { id } => {
+            remove_task(server_name, &id)?;
+        }
+        ScheduleAction::Run { id } => {
+            let config = load_server_config(server_name)?;
+            let index = find_task(&config, &id)?;
+            execute_task(&config, &config.schedules[index].task).await?;
+            mark_task_run(server_name, &id)?;
+        }
+        ScheduleAction::Crontab => {
+            print_crontab(server_name)?;
+        }
+    }
+    Ok(())
+}
+
+fn add_task(
+    server_name: &str,
+    cron: &str,
+    task: &str,
+    command: Option<String>,
+    ram: u8,
+) -> Result<()> {
+    let task = match task {
+        "backup" => TaskKind::Backup,
+        "restart" => TaskKind::Restart { ram },
+        "command" => TaskKind::Command {
+            command: command.ok_or_else(|| anyhow!("The command task requires --command"))?
+        },
+        "update-plugins" => TaskKind::UpdatePlugins,
+        other => return Err(anyhow!(
+            "Unknown task type: {} (expected backup, restart, command or update-plugins)",
+            other
+        ))
+    };
+
+    schedule_task(server_name, cron, task)
+}
+
+pub fn schedule_task(server_name: &str, cron: &str, task: TaskKind) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let expression = CronExpr::parse(cron)?;
+
+    let id = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+    println!("✅ Scheduled {} for '{}' ({})", task.describe(), server_name, cron);
+    if let Some(next) = expression.next_after(Local::now()) {
+        println!("⏰ Next run: {}", next.format("%Y-%m-%d %H:%M"));
+    }
+    println!("🆔 Task id: {}", id);
+
+    config.schedules.push(ScheduledTask {
+        id,
+        cron: cron.to_string(),
+        task,
+        created_at: Utc::now(),
+        last_run: None
+    });
+    save_server_config(&config)?;
+
+    Ok(())
+}
+
+fn list_tasks(server_name: &str) -> Result<()> {
+    let config = load_server_config(server_name)?;
+
+    if config.schedules.is_empty() {
+        println!("No scheduled tasks for server '{}'", server_name);
+        return Ok(());
+    }
+
+    println!("⏰ Scheduled tasks for server '{}':", server_name);
+    for task in &config.schedules {
+        let next = CronExpr::parse(&task.cron)
+            .ok()
+            .and_then(|expr| expr.next_after(Local::now()))
+            .map(|next| next.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "never".to_string());
+        let last = task.last_run
+            .map(|last| last.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "never".to_string());
+
+        println!("  • [{}] {} - {} (next: {}, last: {})", task.id, task.cron, task.task.describe(), next, last);
+    }
+
+    Ok(())
+}
+
+fn remove_task(server_name: &str, id: &str) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let index = find_task(&config, id)?;
+    let task = config.schedules.remove(index);
+    save_server_config(&config)?;
+
+    println!("🗑️  Removed scheduled {} [{}]", task.task.describe(), task.id);
+    Ok(())
+}
+
+fn find_task(config: &ServerConfig, id: &str) -> Result<usize> {
+    config.schedules
+        .iter()
+        .position(|task| task.id == id)
+        .ok_or_else(|| anyhow!("No scheduled task '{}' for server '{}'", id, config.name))
+}
+
+fn mark_task_run(server_name: &str, id: &str) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    let index = find_task(&config, id)?;
+    config.schedules[index].last_run = Some(Utc::now());
+    save_server_config(&config)
+}
+
+fn print_crontab(server_name: &str) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let exe = std::env::current_exe()?;
+
+    println!("# anvil scheduled tasks for '{}' - add with `crontab -e`", server_name);
+    for task in &config.schedules {
+        println!("{} {} schedule --server {} run {}", task.cron, exe.display(), server_name, task.id);
+    }
+
+    Ok(())
+}
+
+pub async fn execute_task(config: &ServerConfig, task: &TaskKind) -> Result<()> {
+    match task {
+        TaskKind::Backup => {
+            crate::backup::create_backup(config).await?;
+        }
+        TaskKind::Restart { ram } => {
+            if is_server_running(config).await {
+                println!("🔄 Restarting server '{}'...", config.name);
+                stop_server(config).await?;
+            }
+            spawn_detached(config, *ram)?;
+        }
+        TaskKind::Command { command } => {
+            let response = crate::rcon::send_command(config, command).await?;
+            if !response.is_empty() {
+                println!("{}", response);
+            }
+        }
+        TaskKind::UpdatePlugins => {
+            crate::plugin::update_plugins(&config.name, None).await?;
+        }
+        TaskKind::Announce { message } => {
+            // Nobody would read it, and a stopped server is not an error worth reporting every interval
+            if !is_server_running(config).await {
+                println!("⏭️  '{}' is not running, skipping the announcement", config.name);
+                return Ok(());
+            }
+            crate::announce::broadcast(config, message).await?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_daemon() -> Result<()> {
+    println!("🛠️  anvil daemon running (Ctrl-C to stop)");
+
+    let mut last_dns_refresh: Option<std::time::Instant> = None;
+    tokio::spawn(crate::health::run_health_checks());
+    tokio::spawn(crate::idle::run_idle_checks());
+    tokio::spawn(crate::chatbridge::run_chat_bridges());
+    tokio::spawn(crate::expose::renew_natpmp_mappings());
+
+    loop {
+        if let Err(e) = run_due_tasks().await {
+            println!("⚠️  Scheduler error: {}", e);
+        }
+
+        if last_dns_refresh.is_none_or(|last| last.elapsed().as_secs() >= crate::ddns::DDNS_REFRESH_SECS) {
+            crate::ddns::refresh_all().await;
+            last_dns_refresh = Some(std::time::Instant::now());
+        }
+
+        tokio::time::sleep(StdDuration::from_secs(DAEMON_TICK_SECS)).await;
+    }
+}
+
+async fn run_due_tasks() -> Result<()> {
+    let now = Local::now();
+
+    for config in get_all_servers()? {
+        for task in &config.schedules {
+            let expression = match CronExpr::parse(&task.cron) {
+                Ok(expression) => expression,
+                Err(e) => {
+                    println!("⚠️  Skipping task [{}] on '{}': {}", task.id, config.name, e);
+                    continue;
+                }
+            };
+
+            let since = task.last_run.unwrap_or(task.created_at).with_timezone(&Local);
+            let due = expression.next_after(since).is_some_and(|next| next <= now);
+            if !due {
+                continue;
+            }
+
+            println!("⏰ Running {} on '{}' [{}]", task.task.describe(), config.name, task.id);
+            if let Err(e) = execute_task(&config, &task.task).await {
+                println!("❌ Task [{}] on '{}' failed: {}", task.id, config.name, e);
+            }
+
+            mark_task_run(&config.name, &task.id)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, month, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn wildcard_steps_start_at_the_minimum() {
+        assert_eq!(parse_field("*/15", 0, 59).unwrap(), vec![0, 15, 30, 45]);
+    }
+
+    #[test]
+    fn value_steps_start_at_the_value() {
+        assert_eq!(parse_field("5/15", 0, 59).unwrap(), vec![5, 20, 35, 50]);
+        assert_eq!(parse_field("10-20/5,1", 0, 59).unwrap(), vec![1, 10, 15, 20]);
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        assert!(parse_field("*/0", 0, 59).is_err());
+        assert!(parse_field("60", 0, 59).is_err());
+        assert!(parse_field("20-10", 0, 59).is_err());
+        assert!(CronExpr::parse("0 * * *").is_err());
+    }
+
+    #[test]
+    fn sunday_can_be_written_as_seven() {
+        let expression = CronExpr::parse("0 0 * * 7").unwrap();
+        assert_eq!(expression.days_of_week, vec![0]);
+        // 2026-02-01 is a Sunday
+        assert_eq!(expression.next_after(at(1, 30, 0, 0)), Some(at(2, 1, 0, 0)));
+    }
+
+    #[test]
+    fn restricted_day_of_month_and_weekday_match_either() {
+        // The 10th or any Friday, 2026-02-06 and 2026-02-13 are Fridays
+        let expression = CronExpr::parse("0 12 10 * 5").unwrap();
+        assert_eq!(expression.next_after(at(2, 1, 0, 0)), Some(at(2, 6, 12, 0)));
+        assert_eq!(expression.next_after(at(2, 6, 12, 0)), Some(at(2, 10, 12, 0)));
+        assert_eq!(expression.next_after(at(2, 10, 12, 0)), Some(at(2, 13, 12, 0)));
+    }
+
+    #[test]
+    fn wildcard_day_of_month_only_checks_the_weekday() {
+        let expression = CronExpr::parse("30 6 * * 1").unwrap();
+        assert_eq!(expression.next_after(at(2, 1, 0, 0)), Some(at(2, 2, 6, 30)));
+    }
+
+    #[test]
+    fn next_after_skips_the_current_minute() {
+        let expression = CronExpr::parse("@hourly").unwrap();
+        assert_eq!(expression.next_after(at(2, 1, 5, 0)), Some(at(2, 1, 6, 0)));
+    }
+}
@@ -0,0 +1,252 @@
+use crate::server::{get_anvil_dir, load_server_config, set_server_property};
+use crate::yaml::YamlDocument;
+use crate::SecretAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const PAPER_GLOBAL_CONFIG: &str = "config/paper-global.yml";
+// Config keys and command line arguments whose names contain one of these hold a secret
+pub const SECRET_MARKERS: &[&str] = &["password", "secret", "token"];
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct ServerSecrets {
+    #[serde(default)]
+    pub rcon_password: Option<String>,
+    #[serde(default)]
+    pub forwarding_secret: Option<String>,
+    #[serde(default)]
+    pub ddns_token: Option<String>,
+    #[serde(default)]
+    pub bungeeguard_token: Option<String>,
+    #[serde(default)]
+    pub discord_webhook: Option<String>,
+    #[serde(default)]
+    pub discord_bot_token: Option<String>,
+    #[serde(default)]
+    pub database_password: Option<String>,
+    // Set with 'anvil env', usually connection strings and API keys
+    #[serde(default)]
+    pub env: BTreeMap<String, String>
+}
+
+fn secrets_path() -> PathBuf {
+    get_anvil_dir().join("secrets.json")
+}
+
+fn load_secrets() -> Result<BTreeMap<String, ServerSecrets>> {
+    let path = secrets_path();
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let contents = fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+fn save_secrets(secrets: &BTreeMap<String, ServerSecrets>) -> Result<()> {
+    let path = secrets_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Written to a file that is private from the moment it exists, then moved over the old one
+    let temp = path.with_extension("json.tmp");
+    let _ = fs::remove_file(&temp);
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&temp)?;
+    file.write_all(serde_json::to_string_pretty(secrets)?.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temp, &path)?;
+
+    Ok(())
+}
+
+pub fn is_secret_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    SECRET_MARKERS.iter().any(|marker| name.contains(marker))
+}
+
+pub fn generate_secret() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+pub fn server_secrets(name: &str) -> Result<ServerSecrets> {
+    Ok(load_secrets()?.remove(name).unwrap_or_default())
+}
+
+pub fn generate_server_secrets(name: &str, server_type: &str) -> Result<ServerSecrets> {
+    let server_secrets = ServerSecrets {
+        rcon_password: Some(generate_secret()),
+        forwarding_secret: (server_type == "paper").then(generate_secret),
+        ddns_token: None,
+        bungeeguard_token: None,
+        discord_webhook: None,
+        discord_bot_token: None,
+        database_password: None,
+        env: BTreeMap::new()
+    };
+
+    let mut secrets = load_secrets()?;
+    secrets.insert(name.to_string(), server_secrets.clone());
+    save_secrets(&secrets)?;
+
+    Ok(server_secrets)
+}
+
+pub fn update_server_secrets(name: &str, update: impl FnOnce(&mut ServerSecrets)) -> Result<()> {
+    let mut secrets = load_secrets()?;
+    update(secrets.entry(name.to_string()).or_default());
+    save_secrets(&secrets)
+}
+
+pub fn set_ddns_token(name: &str, token: Option<String>) -> Result<()> {
+    let mut secrets = load_secrets()?;
+    secrets.entry(name.to_string()).or_default().ddns_token = token;
+    save_secrets(&secrets)
+}
+
+pub fn set_database_password(name: &str, password: Option<String>) -> Result<()> {
+    let mut secrets = load_secrets()?;
+    secrets.entry(name.to_string()).or_default().database_password = password;
+    save_secrets(&secrets)
+}
+
+pub fn set_discord_secrets(name: &str, webhook: Option<String>, bot_token: Option<String>) -> Result<()> {
+    let mut secrets = load_secrets()?;
+    let entry = secrets.entry(name.to_string()).or_default();
+    entry.discord_webhook = webhook;
+    entry.discord_bot_token = bot_token;
+    save_secrets(&secrets)
+}
+
+pub fn set_server_env(name: &str, env: BTreeMap<String, String>) -> Result<()> {
+    let mut secrets = load_secrets()?;
+    secrets.entry(name.to_string()).or_default().env = env;
+    save_secrets(&secrets)
+}
+
+pub fn remove_server_secrets(name: &str) -> Result<()> {
+    let mut secrets = load_secrets()?;
+    if secrets.remove(name).is_some() {
+        save_secrets(&secrets)?;
+    }
+    Ok(())
+}
+
+pub async fn handle_secret_action(action: SecretAction) -> Result<()> {
+    match action {
+        SecretAction::Rotate { server, rcon, forwarding } => {
+            // Without a selection both secrets are rotated
+            let both = !rcon && !forwarding;
+            rotate_secrets(&server, rcon || both, forwarding || both).await?;
+        }
+        SecretAction::Show { server, reveal } => {
+            show_secrets(&server, reveal)?;
+        }
+    }
+    Ok(())
+}
+
+async fn rotate_secrets(server_name: &str, rcon: bool, forwarding: bool) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let mut secrets = load_secrets()?;
+    let entry = secrets.entry(server_name.to_string()).or_default();
+
+    let was_running = crate::server::is_server_running(&config).await;
+
+    if rcon {
+        let password = generate_secret();
+        set_server_property(&config.path, "rcon.password", &password)?;
+        entry.rcon_password = Some(password);
+        println!("🔑 Rotated RCON password for '{}'", server_name);
+    }
+
+    if forwarding {
+        if config.server_type != "paper" {
+            return Err(anyhow!(
+                "Server '{}' is {}, only Paper servers use a Velocity forwarding secret",
+                server_name,
+                config.server_type
+            ));
+        }
+
+        let secret = generate_secret();
+        if write_forwarding_secret(&config.path, &secret)? {
+            println!("🔑 Rotated Velocity forwarding secret in {}", PAPER_GLOBAL_CONFIG);
+        } else {
+            println!("🔑 Rotated Velocity forwarding secret");
+            println!("💡 {} does not exist yet; start the server once to apply it", PAPER_GLOBAL_CONFIG);
+        }
+        entry.forwarding_secret = Some(secret);
+        println!("💡 Update forwarding.secret on your Velocity proxy: anvil secret show {} --reveal", server_name);
+    }
+
+    save_secrets(&secrets)?;
+
+    if was_running {
+        println!("⚠️  Server '{}' is running with the old secrets; restart it to apply the change", server_name);
+    }
+
+    Ok(())
+}
+
+fn write_forwarding_secret(server_dir: &Path, secret: &str) -> Result<bool> {
+    let path = server_dir.join(PAPER_GLOBAL_CONFIG);
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    let mut document = YamlDocument::parse(&fs::read_to_string(&path)?);
+    document.set(&["proxies", "velocity", "secret"], secret)?;
+    fs::write(path, document.render())?;
+
+    Ok(true)
+}
+
+fn show_secrets(server_name: &str, reveal: bool) -> Result<()> {
+    load_server_config(server_name)?;
+    let secrets = server_secrets(server_name)?;
+
+    println!("🔐 Secrets for '{}':", server_name);
+    println!(" - RCON password: {}", display_secret(secrets.rcon_password.as_deref(), reveal));
+    println!(" - Velocity forwarding secret: {}", display_secret(secrets.forwarding_secret.as_deref(), reveal));
+    if secrets.bungeeguard_token.is_some() {
+        println!(" - BungeeGuard token: {}", display_secret(secrets.bungeeguard_token.as_deref(), reveal));
+    }
+    if secrets.ddns_token.is_some() {
+        println!(" - DNS provider token: {}", display_secret(secrets.ddns_token.as_deref(), reveal));
+    }
+    if secrets.discord_webhook.is_some() {
+        println!(" - Discord webhook: {}", display_secret(secrets.discord_webhook.as_deref(), reveal));
+    }
+    if secrets.discord_bot_token.is_some() {
+        println!(" - Discord bot token: {}", display_secret(secrets.discord_bot_token.as_deref(), reveal));
+    }
+    if secrets.database_password.is_some() {
+        println!(" - Database password: {}", display_secret(secrets.database_password.as_deref(), reveal));
+    }
+
+    if !reveal {
+        println!("💡 Use --reveal to print the values");
+    }
+
+    Ok(())
+}
+
+pub fn display_secret(secret: Option<&str>, reveal: bool) -> String {
+    match secret {
+        Some(secret) if reveal => secret.to_string(),
+        Some(_) => "********".to_string(),
+        None => "(not managed by anvil)".to_string()
+    }
+}
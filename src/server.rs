@@ -1,10 +1,42 @@
+use crate::autostart::AutostartConfig;
+use crate::config::WorldSetting;
+use crate::database::DatabaseConfig;
+use crate::ddns::DdnsConfig;
+use crate::expose::PortMapping;
+use crate::health::HealthConfig;
+use crate::hooks::{run_hook, HookEvent, Hooks};
+use crate::idle::IdleConfig;
+use crate::layout::LayoutConfig;
+use crate::macros::MacroStep;
+use crate::map::MapConfig;
+use crate::progress::{BulkProgress, Outcome};
+use crate::properties::PropertiesFile;
+use crate::proxy::ProxyConfig;
+use crate::rcon::RconClient;
+use crate::schedule::ScheduledTask;
+use crate::world::ExtraWorld;
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::{Deserialize, Serialize};
-use dialoguer::Confirm;
+use dialoguer::{Confirm, Input};
+use futures_util::future::join_all;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::fs;
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::net::{IpAddr, SocketAddr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+
+const MAX_STARTUP_HISTORY: usize = 20;
+pub const IP_STACKS: &[&str] = &["dual", "ipv4", "ipv6"];
+const STOP_TIMEOUT_SECS: u64 = 60;
+const BOOT_TIMEOUT_SECS: u64 = 300;
+const CONSOLE_TAIL_LINES: usize = 200;
+pub const WORLD_SUFFIXES: &[&str] = &["", "_nether", "_the_end"];
+pub const PID_FILE: &str = ".anvil.pid";
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ServerConfig {
@@ -14,7 +46,140 @@ pub struct ServerConfig {
     pub port: u16,
     pub path: PathBuf,
     pub jar_file: String,
-    pub plugins: Vec<String>
+    pub plugins: Vec<InstalledPlugin>,
+    #[serde(default)]
+    pub disabled_plugins: Vec<String>,
+    #[serde(default)]
+    pub plugin_sources: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub startup_history: Vec<StartupRecord>,
+    #[serde(default)]
+    pub hooks: Hooks,
+    #[serde(default)]
+    pub triggers: Vec<crate::triggers::Trigger>,
+    #[serde(default)]
+    pub schedules: Vec<ScheduledTask>,
+    #[serde(default)]
+    pub macros: BTreeMap<String, Vec<MacroStep>>,
+    #[serde(default)]
+    pub map: Option<MapConfig>,
+    #[serde(default)]
+    pub database: Option<DatabaseConfig>,
+    #[serde(default)]
+    pub exposure: Option<PortMapping>,
+    #[serde(default)]
+    pub ddns: Option<DdnsConfig>,
+    #[serde(default)]
+    pub ip_stack: Option<String>,
+    #[serde(default)]
+    pub experimental_builds: bool,
+    #[serde(default)]
+    pub worlds: Vec<ExtraWorld>,
+    #[serde(default)]
+    pub last_stopped_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub proxy: Option<ProxyConfig>,
+    #[serde(default)]
+    pub health: Option<HealthConfig>,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    #[serde(default)]
+    pub autostart: Option<AutostartConfig>,
+    #[serde(default)]
+    pub idle: Option<IdleConfig>,
+    #[serde(default)]
+    pub chat_bridge: Option<crate::chatbridge::ChatBridgeConfig>,
+    #[serde(default)]
+    pub maintenance: Option<crate::maintenance::MaintenanceState>,
+    #[serde(default)]
+    pub world_settings: BTreeMap<String, BTreeMap<String, WorldSetting>>
+}
+
+impl ServerConfig {
+    pub fn new(name: &str, version: &str, server_type: &str, port: u16, path: PathBuf, jar_file: String) -> Self {
+        ServerConfig {
+            name: name.to_string(),
+            version: version.to_string(),
+            server_type: server_type.to_string(),
+            port,
+            path,
+            jar_file,
+            plugins: Vec::new(),
+            disabled_plugins: Vec::new(),
+            plugin_sources: Vec::new(),
+            tags: Vec::new(),
+            startup_history: Vec::new(),
+            hooks: Hooks::default(),
+            triggers: Vec::new(),
+            schedules: Vec::new(),
+            macros: BTreeMap::new(),
+            map: None,
+            database: None,
+            exposure: None,
+            ddns: None,
+            ip_stack: None,
+            experimental_builds: false,
+            worlds: Vec::new(),
+            last_stopped_at: None,
+            proxy: None,
+            health: None,
+            layout: LayoutConfig::default(),
+            autostart: None,
+            idle: None,
+            chat_bridge: None,
+            maintenance: None,
+            world_settings: BTreeMap::new()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct InstalledPlugin {
+    pub name: String,
+    #[serde(default = "default_plugin_source")]
+    pub source: String,
+    pub project_id: String,
+    pub version_id: String,
+    pub version_number: String,
+    pub filename: String,
+    #[serde(default)]
+    pub constraint: Option<String>,
+    #[serde(default)]
+    pub previous: Option<PreviousVersion>,
+    #[serde(default)]
+    pub sha1: Option<String>
+}
+
+fn default_plugin_source() -> String {
+    "modrinth".to_string()
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PreviousVersion {
+    pub version_id: String,
+    pub version_number: String,
+    pub filename: String,
+    #[serde(default)]
+    pub sha1: Option<String>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StartupRecord {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64
+}
+
+pub struct CreateOptions {
+    pub copy_from: Option<String>,
+    pub bind: Option<String>,
+    pub ip_stack: Option<String>,
+    pub experimental: bool,
+    pub fallback_vanilla: bool,
+    pub world_gen: crate::worldgen::WorldGenOptions,
+    // Heap for a first boot that generates the default configs, None to leave the server unstarted
+    pub boot: Option<u8>
 }
 
 pub async fn create_server(
@@ -22,65 +187,292 @@ pub async fn create_server(
     version: &str,
     server_type: &str,
     port: u16,
+    options: CreateOptions,
 ) -> Result<()> {
-    println!("🚀 Creating {} server: {}", server_type, name);
+    validate_network(options.bind.as_deref(), options.ip_stack.as_deref())?;
 
     let server_dir = get_servers_dir().join(name);
-    if server_dir.exists() {
+    if load_server_config(name).is_ok() {
         return Err(anyhow!("Server '{}' already exists", name));
     }
+    if server_dir.exists() {
+        return Err(anyhow!(
+            "{} exists without an anvil config, likely left by an interrupted create; \
+             clean it up with: anvil prune-orphans",
+            server_dir.display()
+        ));
+    }
+
+    crate::progress::stage("resolve", &format!("Resolving {} {}", server_type, version));
+    let (server_type, version) = match resolve_version(server_type, version, options.experimental).await {
+        Ok(version) => (server_type, version),
+        Err(e) if server_type == "paper" && options.fallback_vanilla => {
+            crate::output::warn("server.trying_vanilla", &[&e]);
+            ("vanilla", resolve_version("vanilla", version, false).await?)
+        }
+        Err(e) => return Err(e)
+    };
+    let version = version.as_str();
+
+    let server_type = match server_type {
+        "paper" if !crate::api::paper::has_builds(version, options.experimental).await? => {
+            if !options.fallback_vanilla {
+                let message = crate::api::paper::missing_builds_message(version, options.experimental).await?;
+                return Err(anyhow!("{}; pass --fallback-vanilla to create a vanilla server instead", message));
+            }
+            crate::output::warn("server.no_paper_builds", &[&version]);
+            "vanilla"
+        }
+        other => other
+    };
+
+    // Checked before anything is downloaded, the properties are written once the server exists
+    options.world_gen.validate(version)?;
+
+    crate::output::say("🚀", "server.creating", &[&server_type, &name]);
+
+    let source = options.copy_from.as_deref().map(load_server_config).transpose()?;
 
     fs::create_dir_all(&server_dir)?;
 
-    let jar_name = match server_type {
-        "paper" => {
-            let jar = download_paper_server(version, &server_dir).await?;
-            jar
-        }
-        "vanilla" => {
-            let jar = download_vanilla_server(version, &server_dir).await?;
-            jar
+    // Anything created past this point is removed again if a later step fails or is interrupted
+    let result = tokio::select! {
+        result = populate_server(name, version, server_type, port, &server_dir, source.as_ref(), &options) => result,
+        _ = tokio::signal::ctrl_c() => Err(anyhow!("Interrupted"))
+    };
+
+    let config = match result {
+        Ok(config) => config,
+        Err(e) => {
+            rollback_create(name, &server_dir);
+            return Err(e);
         }
+    };
+
+    if let Some(source) = &source {
+        crate::progress::stage("plugins", &format!("Copying plugins from '{}'", source.name));
+        copy_plugins(source, &config).await;
+    }
+
+    if let Some(ram) = options.boot {
+        crate::progress::stage("boot", &format!("Starting '{}' once to generate its default configs", name));
+        first_boot(&config, ram).await?;
+    }
+
+    crate::progress::stage("done", &format!("Created '{}'", name));
+    crate::output::success("server.created", &[&name]);
+    crate::output::say("📁", "server.location", &[&server_dir.display()]);
+
+    Ok(())
+}
+
+// Boots a new server until it is ready and stops it, leaving the files it writes on startup for config and plugin commands
+async fn first_boot(config: &ServerConfig, ram: u8) -> Result<()> {
+    let list_entries = || -> BTreeSet<String> {
+        fs::read_dir(&config.path)
+            .map(|entries| entries.flatten().map(|entry| entry.file_name().to_string_lossy().to_string()).collect())
+            .unwrap_or_default()
+    };
+    let before = list_entries();
+
+    crate::output::say("🔥", "server.first_boot", &[&config.name]);
+    let report = boot_and_stop(
+        &config.path,
+        &config.jar_file,
+        ram,
+        &[],
+        &crate::secrets::server_secrets(&config.name)?.env,
+        Duration::from_secs(BOOT_TIMEOUT_SECS),
+        |line| {
+            if is_error_line(line) {
+                println!("❌ {}", line);
+            }
+        },
+    ).await?;
+
+    // The server exists either way, a failed first boot points at a problem that a rollback would hide
+    if !report.ready {
+        return Err(anyhow!(
+            "'{}' was created, but its first boot did not finish; check {}",
+            config.name,
+            config.path.join("logs").join("latest.log").display()
+        ));
+    }
+
+    crate::output::success("server.first_boot_finished", &[&format_duration_ms(report.duration_ms)]);
+    let generated: Vec<String> = list_entries().difference(&before).cloned().collect();
+    if !generated.is_empty() {
+        crate::output::say("📄", "server.generated", &[&generated.join(", ")]);
+    }
+    Ok(())
+}
+
+async fn populate_server(
+    name: &str,
+    version: &str,
+    server_type: &str,
+    port: u16,
+    server_dir: &Path,
+    source: Option<&ServerConfig>,
+    options: &CreateOptions,
+) -> Result<ServerConfig> {
+    let bind = options.bind.as_deref();
+    let ip_stack = options.ip_stack.as_deref().filter(|stack| *stack != "dual");
+    let server_dir = server_dir.to_path_buf();
+
+    crate::progress::stage("download", &format!("Downloading {} {}", server_type, version));
+    let jar_name = match server_type {
+        "paper" => download_paper_server(version, &server_dir, options.experimental).await?,
+        "vanilla" => download_vanilla_server(version, &server_dir).await?,
         _ => return Err(anyhow!("Unsupported server type: {}", server_type))
     };
 
-    create_server_properties(&server_dir, port)?;
+    crate::progress::stage("configure", "Writing server.properties, EULA and start scripts");
+    let secrets = crate::secrets::generate_server_secrets(name, server_type)?;
+    let rcon_password = secrets.rcon_password.unwrap_or_default();
+
+    if let Some(source) = source {
+        let properties = source.path.join("server.properties");
+        if properties.exists() {
+            fs::copy(&properties, server_dir.join("server.properties"))?;
+        }
+    }
+
+    create_server_properties(&server_dir, port, &rcon_password)?;
+    if let Some(bind) = bind {
+        set_server_property(&server_dir, "server-ip", bind.trim_matches(['[', ']']))?;
+    }
+    for (key, value) in options.world_gen.properties(version)? {
+        set_server_property(&server_dir, key, &value)?;
+        crate::output::say("🌍", "server.world_setting", &[&key, &value]);
+    }
+    let world_dir = server_dir.join(level_name(&server_dir));
+    crate::worldgen::install_datapacks(&world_dir, version, &options.world_gen.datapacks).await?;
     create_eula_file(&server_dir)?;
-    create_start_script(&server_dir, &jar_name)?;
-
-    let config = ServerConfig {
-        name: name.to_string(),
-        version: version.to_string(),
-        server_type: server_type.to_string(),
-        port,
-        path: server_dir.clone(),
-        jar_file: jar_name,
-        plugins: Vec::new()
-    };
+    create_start_script(&server_dir, &jar_name, version, ip_stack)?;
+
+    // A copied server keeps the source's IP stack unless --ip-stack picks another one
+    let mut ip_stack = ip_stack.map(str::to_string);
+    if let Some(source) = source {
+        copy_start_scripts(source, &server_dir, &jar_name)?;
+        crate::output::say("📋", "server.copied_settings", &[&source.name]);
+        match options.ip_stack {
+            Some(_) => apply_stack_flags(&server_dir, ip_stack.as_deref())?,
+            None => ip_stack = source.ip_stack.clone()
+        }
+    }
+
+    let mut config = ServerConfig::new(name, version, server_type, port, server_dir, jar_name);
+    config.ip_stack = ip_stack;
+    config.experimental_builds = options.experimental;
 
     save_server_config(&config)?;
 
-    println!("✅ Server '{}' created successfully!", name);
-    println!("📁 Location: {}", server_dir.display());
+    Ok(config)
+}
+
+fn rollback_create(name: &str, server_dir: &Path) {
+    let results = [
+        fs::remove_dir_all(server_dir).map_err(anyhow::Error::from),
+        remove_server_config(name),
+        crate::secrets::remove_server_secrets(name)
+    ];
 
+    if results.iter().all(Result::is_ok) {
+        crate::output::say("🧹", "server.cleaned_up", &[&name]);
+    } else {
+        crate::output::warn("server.cleanup_failed", &[&name, &server_dir.display()]);
+    }
+}
+
+fn copy_start_scripts(source: &ServerConfig, server_dir: &Path, jar_name: &str) -> Result<()> {
+    for script in ["start.sh", "start.bat"] {
+        let Ok(contents) = fs::read_to_string(source.path.join(script)) else {
+            continue;
+        };
+        fs::write(server_dir.join(script), contents.replace(&source.jar_file, jar_name))?;
+    }
     Ok(())
 }
 
-async fn download_paper_server(
+async fn copy_plugins(source: &ServerConfig, config: &ServerConfig) {
+    if source.plugins.is_empty() {
+        return;
+    }
+
+    if crate::plugin::require_plugin_support(config).is_err() {
+        crate::output::warn("server.skipping_plugins", &[&source.plugins.len(), &source.name, &config.server_type]);
+        return;
+    }
+
+    // Versions are re-resolved so the plugins match the new server's Minecraft version
+    let manifest = crate::manifest::manifest_for(source, false);
+    let origin = format!("'{}'", source.name);
+    if let Err(e) = crate::manifest::apply_manifest(&config.name, &manifest, &origin, false).await {
+        println!("⚠️  {}", e);
+    }
+}
+
+pub async fn resolve_version(server_type: &str, version: &str, experimental: bool) -> Result<String> {
+    if version == "latest" {
+        return match server_type {
+            "paper" => crate::api::paper::get_latest_version(experimental).await,
+            _ => crate::api::vanilla::get_latest_version().await
+        };
+    }
+
+    let available = match server_type {
+        "paper" => crate::api::paper::get_versions().await?,
+        "vanilla" => crate::api::vanilla::get_versions().await?,
+        _ => return Err(anyhow!("Unsupported server type: {}", server_type))
+    };
+
+    let candidates = crate::version::resolve_alias(version, &available);
+    let Some(newest) = candidates.first() else {
+        return Err(match crate::version::closest_match(version, &available) {
+            Some(suggestion) => anyhow!("Unknown {} version '{}', did you mean {}?", server_type, version, suggestion),
+            None => anyhow!("Unknown {} version '{}'", server_type, version)
+        });
+    };
+
+    // Paper may list the newest patch release before it has any builds
+    let mut resolved = newest.to_string();
+    if server_type == "paper" {
+        for candidate in &candidates {
+            if crate::api::paper::has_builds(candidate, experimental).await? {
+                resolved = candidate.to_string();
+                break;
+            }
+        }
+    }
+
+    if resolved != version {
+        crate::output::say("🔎", "server.resolved_version", &[&server_type, &version, &resolved]);
+    }
+
+    Ok(resolved)
+}
+
+pub async fn download_paper_server(
     version: &str,
-    server_dir: &PathBuf
+    server_dir: &Path,
+    experimental: bool,
 ) -> Result<String> {
     let version = if version == "latest" {
-        crate::api::paper::get_latest_version().await?
+        crate::api::paper::get_latest_version(experimental).await?
     } else {
         version.to_string()
     };
 
-    let build = crate::api::paper::get_latest_build(&version).await?;
+    let build = crate::api::paper::get_latest_build(&version, experimental).await?;
     let jar_name = format!("paper-{}-{}.jar", version, build);
     let jar_path = server_dir.join(&jar_name);
 
-    println!("📥 Downloading Paper {} (build {})...", version, build);
+    if experimental {
+        crate::output::say("🧪", "server.downloading_paper_experimental", &[&version, &build]);
+    } else {
+        crate::output::say("📥", "server.downloading_paper", &[&version, &build]);
+    }
 
     let pb = ProgressBar::new(0);
     pb.set_style(
@@ -96,9 +488,9 @@ async fn download_paper_server(
     Ok(jar_name)
 }
 
-async fn download_vanilla_server(
+pub async fn download_vanilla_server(
     version: &str,
-    server_dir: &PathBuf
+    server_dir: &Path
 ) -> Result<String> {
     let version = if version == "latest" {
         crate::api::vanilla::get_latest_version().await?
@@ -108,7 +500,7 @@ async fn download_vanilla_server(
 
     let jar_name = format!("vanilla-{}.jar", version);
 
-    println!("📥 Downloading Vanilla Minecraft {}...", version);
+    crate::output::say("📥", "server.downloading_vanilla", &[&version]);
 
     let pb = ProgressBar::new(0);
     pb.set_style(
@@ -124,45 +516,176 @@ async fn download_vanilla_server(
     Ok(jar_name)
 }
 
-fn get_servers_dir() -> PathBuf {
+pub fn get_anvil_dir() -> PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join(".anvil")
-        .join("servers")
-}
-
-fn create_server_properties(server_dir: &PathBuf, port: u16) -> Result<()> {
-    let properties = format!(
-        r#"server-port={}
-online-mode=true
-white-list=false
-spawn-protection=16
-max-players=20
-level-name=world
-gamemode=survival
-difficulty=easy
-spawn-monsters=true
-spawn-animals=true
-level-type=minecraft\:normal
-"#,
-        port
+}
+
+pub fn get_servers_dir() -> PathBuf {
+    get_anvil_dir().join("servers")
+}
+
+const DEFAULT_PROPERTIES: &[(&str, &str)] = &[
+    ("online-mode", "true"),
+    ("white-list", "false"),
+    ("spawn-protection", "16"),
+    ("max-players", "20"),
+    ("level-name", "world"),
+    ("gamemode", "survival"),
+    ("difficulty", "easy"),
+    ("spawn-monsters", "true"),
+    ("spawn-animals", "true"),
+    ("level-type", "minecraft:normal")
+];
+
+pub fn create_server_properties(server_dir: &Path, port: u16, rcon_password: &str) -> Result<()> {
+    let path = server_dir.join("server.properties");
+    let mut properties = PropertiesFile::load(&path)?;
+
+    // Anvil owns the ports and RCON credentials; everything else is only filled in when missing
+    properties.set("server-port", &port.to_string());
+    properties.set("enable-rcon", "true");
+    properties.set("rcon.port", &port.saturating_add(10).to_string());
+    properties.set("rcon.password", rcon_password);
+
+    for (key, value) in DEFAULT_PROPERTIES {
+        properties.set_default(key, value);
+    }
+
+    properties.save(&path)
+}
+
+pub fn read_server_properties(server_dir: &Path) -> Result<HashMap<String, String>> {
+    let contents = fs::read_to_string(server_dir.join("server.properties"))?;
+    Ok(PropertiesFile::parse(&contents).values().into_iter().collect())
+}
+
+pub fn level_name(server_dir: &Path) -> String {
+    read_server_properties(server_dir)
+        .ok()
+        .and_then(|properties| properties.get("level-name").cloned())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "world".to_string())
+}
+
+pub fn world_dir_names(server_dir: &Path) -> Vec<String> {
+    let level_name = level_name(server_dir);
+    WORLD_SUFFIXES
+        .iter()
+        .map(|suffix| format!("{}{}", level_name, suffix))
+        .filter(|name| server_dir.join(name).is_dir())
+        .collect()
+}
+
+pub fn all_world_dir_names(config: &ServerConfig) -> Vec<String> {
+    let mut worlds = world_dir_names(&config.path);
+    worlds.extend(
+        config.worlds
+            .iter()
+            .filter(|world| config.path.join(&world.name).is_dir())
+            .map(|world| world.name.clone())
     );
+    worlds
+}
+
+pub fn set_server_property(server_dir: &Path, key: &str, value: &str) -> Result<()> {
+    let path = server_dir.join("server.properties");
+    let mut properties = PropertiesFile::parse(&fs::read_to_string(&path)?);
+    properties.set(key, value);
+    properties.save(&path)
+}
 
-    fs::write(server_dir.join("server.properties"), properties)?;
+pub const GC_LOG_FILE: &str = "logs/gc.log";
+const GC_LOG_FLAG: &str = "-Xlog:gc:file=logs/gc.log:time,uptime,level,tags:filecount=5,filesize=10M";
+
+// Unified GC logging needs Java 9+, which only 1.17+ servers are guaranteed to run on
+pub fn gc_log_flags(version: &str) -> &'static [&'static str] {
+    if crate::version::compare_versions(version, "1.17").is_lt() {
+        &[]
+    } else {
+        &[GC_LOG_FLAG]
+    }
+}
+
+pub fn stack_jvm_flags(ip_stack: Option<&str>) -> &'static [&'static str] {
+    match ip_stack {
+        Some("ipv4") => &["-Djava.net.preferIPv4Stack=true"],
+        Some("ipv6") => &["-Djava.net.preferIPv6Addresses=true"],
+        _ => &[]
+    }
+}
+
+// Swaps the IP stack flags on the java line of both start scripts, leaving every other flag alone
+pub fn apply_stack_flags(server_dir: &Path, ip_stack: Option<&str>) -> Result<()> {
+    let stack_flags: Vec<&str> = IP_STACKS.iter().flat_map(|stack| stack_jvm_flags(Some(stack))).copied().collect();
+    for script in ["start.sh", "start.bat"] {
+        let path = server_dir.join(script);
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let lines: Vec<String> = contents
+            .lines()
+            .map(|line| {
+                if !line.trim_start().starts_with("java ") {
+                    return line.to_string();
+                }
+                let mut tokens: Vec<&str> = line.split_whitespace().filter(|token| !stack_flags.contains(token)).collect();
+                let jar_at = tokens.iter().position(|token| *token == "-jar").unwrap_or(tokens.len());
+                tokens.splice(jar_at..jar_at, stack_jvm_flags(ip_stack).iter().copied());
+                tokens.join(" ")
+            })
+            .collect();
+        fs::write(&path, lines.join("\n") + "\n")?;
+    }
     Ok(())
 }
 
-fn create_eula_file(server_dir: &PathBuf) -> Result<()> {
+pub fn validate_network(bind: Option<&str>, ip_stack: Option<&str>) -> Result<()> {
+    if let Some(stack) = ip_stack.filter(|stack| !IP_STACKS.contains(stack)) {
+        return Err(anyhow!("Unknown IP stack '{}' (expected one of {})", stack, IP_STACKS.join(", ")));
+    }
+
+    let Some(bind) = bind.filter(|bind| !bind.is_empty()) else {
+        return Ok(());
+    };
+
+    let address: IpAddr = bind
+        .trim_matches(['[', ']'])
+        .parse()
+        .map_err(|_| anyhow!("'{}' is not an IP address (server-ip takes a literal IPv4 or IPv6 address)", bind))?;
+
+    if address.is_ipv6() && ip_stack == Some("ipv4") {
+        return Err(anyhow!("Cannot bind to IPv6 address {} with the ipv4 stack", address));
+    }
+
+    // Binding an ephemeral port proves the address belongs to one of this machine's interfaces
+    if !address.is_unspecified() && TcpListener::bind(SocketAddr::new(address, 0)).is_err() {
+        return Err(anyhow!("{} is not assigned to any network interface on this machine", address));
+    }
+
+    Ok(())
+}
+
+pub fn create_eula_file(server_dir: &Path) -> Result<()> {
     let eula = "eula=true\n";
     fs::write(server_dir.join("eula.txt"), eula)?;
     Ok(())
 }
 
-fn create_start_script(server_dir: &PathBuf, jar_name: &str) -> Result<()> {
+pub fn create_start_script(server_dir: &Path, jar_name: &str, version: &str, ip_stack: Option<&str>) -> Result<()> {
+    let flags: String = gc_log_flags(version)
+        .iter()
+        .chain(stack_jvm_flags(ip_stack))
+        .map(|flag| format!(" {}", flag))
+        .collect();
+
     let bash_script = format!(
         r#"#!/bin/bash
-java -Xmx${{1:-2}}G -Xms${{1:-2}}G -jar {} nogui
+java -Xmx${{1:-2}}G -Xms${{1:-2}}G{} -jar {} nogui
 "#,
+        flags,
         jar_name
     );
 
@@ -170,10 +693,12 @@ java -Xmx${{1:-2}}G -Xms${{1:-2}}G -jar {} nogui
         r#"@echo off
 set RAM=%1
 if "%RAM%"=="" set RAM=2
-java -Xmx%RAM%G -Xms%RAM%G -jar {} nogui
-pause
+java -Xmx%RAM%G -Xms%RAM%G{} -jar {} nogui
+if not "%{}%"=="1" pause
 "#,
-        jar_name
+        flags,
+        jar_name,
+        crate::window::MANAGED_ENV
     );
 
     let bash_path = server_dir.join("start.sh");
@@ -193,44 +718,453 @@ pause
     Ok(())
 }
 
-pub async fn start_server(name: &str, ram: u8) -> Result<()> {
-    let config = load_server_config(name)?;
+// Settings that have to be in place before every launch, however the server is started
+pub fn prepare_launch(config: &ServerConfig, allow_downgrade: bool) -> Result<()> {
+    crate::world::check_world_version(config, allow_downgrade)?;
 
-    println!("🎮 Starting server: {}", name);
+    if config.proxy.is_some() {
+        if !crate::proxy::apply_proxy_settings(config)? {
+            crate::output::hint("server.proxy_settings_next_start", &[]);
+        }
+        crate::proxy::warn_if_exposed(config);
+    }
+
+    crate::idle::release_port(config)?;
+    crate::config::apply_world_settings(config)?;
+
+    // Worlds created since the last start still need moving to their configured location
+    crate::layout::wire_layout(config)
+}
+
+pub async fn start_server(name: &str, ram: u8, tunnel: Option<&str>, allow_downgrade: bool) -> Result<()> {
+    let mut config = load_server_config(name)?;
+
+    let tunnel = tunnel
+        .map(|provider| crate::tunnel::start_tunnel(provider, config.port))
+        .transpose()?;
+
+    crate::output::say("🎮", "server.starting", &[&name]);
+    prepare_launch(&config, allow_downgrade)?;
+    let ram = crate::memory::fit_heap(ram);
 
     #[cfg(windows)]
-    {
-        Command::new("cmd")
-            .args(["/C", "start.bat", &ram.to_string()])
-            .current_dir(&config.path)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()?;
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start.bat", &ram.to_string()]).env(crate::window::MANAGED_ENV, "1");
+        command
+    };
+
+    #[cfg(unix)]
+    let mut command = {
+        let mut command = Command::new("bash");
+        command.arg("start.sh").arg(ram.to_string());
+        command
+    };
+
+    let started_at = Utc::now();
+    let launch = Instant::now();
+
+    let mut child = command
+        .current_dir(&config.path)
+        .envs(crate::secrets::server_secrets(name)?.env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Lets the daemon kill a hung server that no longer answers RCON
+    if let Some(pid) = child.id() {
+        fs::write(config.path.join(PID_FILE), pid.to_string())?;
+    }
+
+    let stdout = child.stdout.take().ok_or_else(|| anyhow!("Failed to capture server output"))?;
+    let stderr = child.stderr.take().ok_or_else(|| anyhow!("Failed to capture server output"))?;
+    let stdin = child.stdin.take().ok_or_else(|| anyhow!("Failed to open server console"))?;
+    let mut lines = BufReader::new(stdout).lines();
+    let mut ready = false;
+
+    // Scripts can inject console commands through the pipe even when RCON is disabled
+    let console_bridge = match crate::console::bridge(&config, stdin) {
+        Ok(bridge) => Some(bridge),
+        Err(e) => {
+            println!("⚠️  {}", e);
+            None
+        }
+    };
+
+    // Java reports launch failures such as a wrong version or corrupt jar on stderr
+    let errors = tokio::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        let mut tail = VecDeque::new();
+        while let Ok(Some(line)) = lines.next_line().await {
+            eprintln!("{}", line);
+            push_tail(&mut tail, line);
+        }
+        tail
+    });
+    let mut console = VecDeque::new();
+    let mut triggers = crate::triggers::Watcher::new(&config);
+
+    while let Some(line) = lines.next_line().await? {
+        println!("{}", line);
+        triggers.check(&config, &line);
+        if !ready {
+            push_tail(&mut console, line.clone());
+        }
+
+        if !ready && is_ready_line(&line) {
+            ready = true;
+            let duration_ms = launch.elapsed().as_millis() as u64;
+            record_startup(&mut config, started_at, duration_ms)?;
+            crate::output::say("⏱️ ", "server.started_in", &[&format_duration_ms(duration_ms)]);
+
+            let hook_config = config.clone();
+            tokio::spawn(async move {
+                run_hook(&hook_config, HookEvent::Start, &[("ANVIL_STARTUP_MS", duration_ms.to_string())]).await;
+            });
+        }
+    }
+
+    let status = child.wait().await?;
+    drop(console_bridge);
+    let _ = fs::remove_file(config.path.join(PID_FILE));
+    let exit_code = status.code().map(|code| code.to_string()).unwrap_or_default();
+
+    if !ready {
+        console.extend(errors.await.unwrap_or_default());
+        let diagnosis = crate::triage::diagnose(&config, console.make_contiguous(), started_at);
+        crate::triage::print_diagnosis(&config, diagnosis.as_ref());
+    }
+
+    // Other commands may have saved the config while the server ran, so only the stop time is written back
+    if let Ok(mut latest) = load_server_config(name) {
+        latest.last_stopped_at = Some(Utc::now());
+        let _ = save_server_config(&latest);
+    }
+
+    let event = if status.success() { HookEvent::Stop } else { HookEvent::Crash };
+    run_hook(&config, event, &[("ANVIL_EXIT_CODE", exit_code)]).await;
+    crate::expose::remove_on_stop(name).await;
+
+    if let Some(tunnel) = tunnel {
+        tunnel.stop().await;
+    }
+
+    Ok(())
+}
+
+fn push_tail(tail: &mut VecDeque<String>, line: String) {
+    if tail.len() == CONSOLE_TAIL_LINES {
+        tail.pop_front();
+    }
+    tail.push_back(line);
+}
+
+pub async fn start_servers(servers: Vec<ServerConfig>, ram: u8) -> Result<()> {
+    if servers.is_empty() {
+        println!("{}", crate::output::text("server.none_to_start", &[]));
+        return Ok(());
+    }
+
+    let progress = BulkProgress::new()?;
+    let starts = servers.iter().map(|config| {
+        let bar = progress.add(&config.name);
+        let progress = &progress;
+
+        async move {
+            if is_server_running(config).await {
+                progress.finish(&bar, Outcome::Skipped, "already running");
+                return;
+            }
+
+            bar.set_message("booting");
+            let launch = Instant::now();
+            let started_at = Utc::now();
+            let console_offset = fs::metadata(console_log_path(config)).map(|metadata| metadata.len()).unwrap_or(0);
+            let result = match spawn_detached(config, ram) {
+                Ok(child) => wait_for_boot(config, child, started_at, console_offset).await,
+                Err(e) => Err(e)
+            };
+
+            match result {
+                Ok(()) => progress.finish(&bar, Outcome::Success, format!("ready in {}", format_duration_ms(launch.elapsed().as_millis() as u64))),
+                Err(e) => progress.finish(&bar, Outcome::Failed, e.to_string())
+            }
+        }
+    });
+    join_all(starts).await;
+
+    let failed = progress.summary("Server starts");
+    if failed > 0 {
+        return Err(anyhow!("{} server(s) failed to start", failed));
+    }
+
+    Ok(())
+}
+
+async fn wait_for_boot(
+    config: &ServerConfig,
+    mut child: std::process::Child,
+    started_at: DateTime<Utc>,
+    console_offset: u64,
+) -> Result<()> {
+    let deadline = Instant::now() + Duration::from_secs(BOOT_TIMEOUT_SECS);
+
+    loop {
+        if crate::rcon::connect(config).await.is_ok() {
+            return Ok(());
+        }
+        if child.try_wait()?.is_some() {
+            // The console log is appended to on every start, so only this launch's output is relevant
+            let console: Vec<String> = fs::read(console_log_path(config))
+                .map(|log| {
+                    let start = (console_offset as usize).min(log.len());
+                    String::from_utf8_lossy(&log[start..]).lines().map(str::to_string).collect()
+                })
+                .unwrap_or_default();
+            return Err(match crate::triage::diagnose(config, &console, started_at) {
+                Some(diagnosis) => anyhow!("{} (see logs/anvil-console.log)", diagnosis.cause),
+                None => anyhow!("exited before becoming ready (see logs/anvil-console.log)")
+            });
+        }
+        if Instant::now() >= deadline {
+            return Err(anyhow!("did not become ready within {} seconds", BOOT_TIMEOUT_SECS));
+        }
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
+pub fn is_ready_line(line: &str) -> bool {
+    line.contains("Done (") && line.contains("For help, type")
+}
+
+pub fn is_error_line(line: &str) -> bool {
+    line.contains("ERROR]")
+        || line.contains("Error occurred while enabling")
+        || line.contains("Could not load 'plugins")
+}
+
+pub struct BootReport {
+    pub ready: bool,
+    pub duration_ms: u64,
+    pub errors: Vec<String>
+}
+
+pub async fn boot_and_stop(
+    server_dir: &Path,
+    jar_file: &str,
+    ram: u8,
+    extra_args: &[&str],
+    env: &BTreeMap<String, String>,
+    timeout: Duration,
+    mut on_line: impl FnMut(&str),
+) -> Result<BootReport> {
+    let memory = format!("{}G", crate::memory::fit_heap(ram));
+
+    let mut child = Command::new("java")
+        .arg(format!("-Xmx{}", memory))
+        .arg(format!("-Xms{}", memory))
+        .args(["-jar", jar_file, "nogui"])
+        .args(extra_args)
+        .current_dir(server_dir)
+        .envs(env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| anyhow!("Failed to launch java: {}", e))?;
+
+    let mut stdin = child.stdin.take().ok_or_else(|| anyhow!("Failed to open server console"))?;
+    let stdout = child.stdout.take().ok_or_else(|| anyhow!("Failed to capture server output"))?;
+    let mut lines = BufReader::new(stdout).lines();
+
+    let launch = Instant::now();
+    let mut report = BootReport { ready: false, duration_ms: 0, errors: Vec::new() };
+
+    let boot = async {
+        while let Some(line) = lines.next_line().await? {
+            on_line(&line);
+
+            if is_error_line(&line) {
+                report.errors.push(line.clone());
+            }
+
+            if !report.ready && is_ready_line(&line) {
+                report.ready = true;
+                report.duration_ms = launch.elapsed().as_millis() as u64;
+                stdin.write_all(b"stop\n").await?;
+                stdin.flush().await?;
+            }
+        }
+        Ok::<(), anyhow::Error>(())
+    };
+
+    if tokio::time::timeout(timeout, boot).await.is_err() {
+        crate::output::warn("server.boot_timeout", &[&timeout.as_secs()]);
+        child.kill().await?;
+        return Ok(report);
+    }
+
+    child.wait().await?;
+    Ok(report)
+}
+
+fn record_startup(config: &mut ServerConfig, started_at: DateTime<Utc>, duration_ms: u64) -> Result<()> {
+    config.startup_history.push(StartupRecord { started_at, duration_ms });
+
+    if config.startup_history.len() > MAX_STARTUP_HISTORY {
+        let excess = config.startup_history.len() - MAX_STARTUP_HISTORY;
+        config.startup_history.drain(..excess);
+    }
+
+    save_server_config(config)
+}
+
+pub fn format_duration_ms(duration_ms: u64) -> String {
+    format!("{:.1}s", duration_ms as f64 / 1000.0)
+}
+
+pub fn free_port() -> Result<u16> {
+    Ok(std::net::TcpListener::bind(("127.0.0.1", 0))?.local_addr()?.port())
+}
+
+pub async fn is_server_running(config: &ServerConfig) -> bool {
+    // While asleep the port belongs to the daemon waiting for a join, not to the server
+    !crate::idle::is_sleeping(config) && tokio::net::TcpStream::connect(("127.0.0.1", config.port)).await.is_ok()
+}
+
+pub async fn stop_server(config: &ServerConfig) -> Result<()> {
+    if let Err(e) = crate::rcon::send_command(config, "stop").await {
+        // Servers without RCON can still be stopped through the console pipe of the anvil process running them
+        crate::console::send(config, "stop").await.map_err(|_| e)?;
+    }
+
+    for _ in 0..STOP_TIMEOUT_SECS {
+        if !is_server_running(config).await {
+            crate::expose::remove_on_stop(&config.name).await;
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    Err(anyhow!("Server '{}' did not stop within {} seconds", config.name, STOP_TIMEOUT_SECS))
+}
+
+// Process id of the start script anvil launched, not of the JVM it runs
+pub fn launcher_pid(config: &ServerConfig) -> Option<u32> {
+    fs::read_to_string(config.path.join(PID_FILE)).ok()?.trim().parse().ok()
+}
+
+// A pid file outlives its process when anvil itself is killed, so only a live process counts
+pub async fn launcher_alive(config: &ServerConfig) -> bool {
+    let Some(pid) = launcher_pid(config) else {
+        return false;
+    };
+
+    #[cfg(windows)]
+    let alive = Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+        .output()
+        .await
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).split_whitespace().any(|word| word == pid.to_string()));
+
+    #[cfg(unix)]
+    let alive = Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .is_ok_and(|status| status.success());
+
+    if !alive {
+        let _ = fs::remove_file(config.path.join(PID_FILE));
     }
+    alive
+}
+
+pub async fn kill_server(config: &ServerConfig) -> Result<()> {
+    let pid = launcher_pid(config)
+        .ok_or_else(|| anyhow!("Server '{}' has no recorded process, it was not started by anvil", config.name))?;
 
+    #[cfg(windows)]
+    Command::new("taskkill").args(["/PID", &pid.to_string(), "/T", "/F"]).status().await?;
+
+    // start.sh runs java as a child process, so the JVM is killed before the script itself
     #[cfg(unix)]
     {
-        Command::new("bash")
-            .arg("start.sh")
-            .arg(ram.to_string())
-            .current_dir(&config.path)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()?;
+        Command::new("pkill").args(["-KILL", "-P", &pid.to_string()]).status().await?;
+        Command::new("kill").args(["-KILL", &pid.to_string()]).status().await?;
     }
 
-    Ok(())
+    for _ in 0..STOP_TIMEOUT_SECS {
+        if !is_server_running(config).await {
+            crate::expose::remove_on_stop(&config.name).await;
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    Err(anyhow!("Server '{}' is still running after killing process {}", config.name, pid))
+}
+
+pub async fn ensure_running(config: &ServerConfig, ram: u8) -> Result<(RconClient, bool)> {
+    if is_server_running(config).await {
+        let client = crate::rcon::connect(config).await?;
+        return Ok((client, false));
+    }
+
+    crate::output::say("🎮", "server.booting_background", &[&config.name]);
+    spawn_detached(config, ram)?;
+
+    let client = crate::rcon::wait_until_available(config, Duration::from_secs(BOOT_TIMEOUT_SECS)).await?;
+    crate::output::success("server.ready", &[&config.name]);
+
+    Ok((client, true))
+}
+
+pub fn console_log_path(config: &ServerConfig) -> PathBuf {
+    config.path.join("logs").join("anvil-console.log")
 }
 
-pub async fn list_servers() -> Result<()> {
+pub fn spawn_detached(config: &ServerConfig, ram: u8) -> Result<std::process::Child> {
+    fs::create_dir_all(config.path.join("logs"))?;
+
+    let console_log = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(console_log_path(config))?;
+
+    let child = std::process::Command::new(std::env::current_exe()?)
+        .args(["start", &config.name, "--ram", &ram.to_string()])
+        .stdin(Stdio::null())
+        .stdout(console_log.try_clone()?)
+        .stderr(console_log)
+        .spawn()?;
+
+    Ok(child)
+}
+
+pub async fn list_servers(json_output: bool) -> Result<()> {
     let servers = get_all_servers()?;
 
+    if json_output {
+        let java_version = java_version().await;
+        let mut entries = Vec::new();
+        for server in &servers {
+            let runtime = runtime_state(server, java_version.clone()).await;
+            entries.push(inventory_entry(server, &runtime)?);
+        }
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
     if servers.is_empty() {
-        println!("No servers found.");
+        println!("{}", crate::output::text("server.none_found", &[]));
         return Ok(());
     }
 
-    println!("📋 Available servers:");
+    crate::output::say("📋", "server.available", &[]);
     for server in servers {
         println!(" - {} ({}:{}) - {}",
                  server.name,
@@ -243,7 +1177,7 @@ pub async fn list_servers() -> Result<()> {
     Ok(())
 }
 
-fn save_server_config(config: &ServerConfig) -> Result<()> {
+pub fn save_server_config(config: &ServerConfig) -> Result<()> {
     let config_dir = get_servers_dir().join("configs");
     fs::create_dir_all(&config_dir)?;
 
@@ -254,6 +1188,17 @@ fn save_server_config(config: &ServerConfig) -> Result<()> {
     Ok(())
 }
 
+pub fn remove_server_config(name: &str) -> Result<()> {
+    let config_file = get_servers_dir()
+        .join("configs")
+        .join(format!("{}.json", name));
+
+    if config_file.exists() {
+        fs::remove_file(config_file)?;
+    }
+    Ok(())
+}
+
 pub fn load_server_config(name: &str) -> Result<ServerConfig> {
     let config_file = get_servers_dir()
         .join("configs")
@@ -265,7 +1210,7 @@ pub fn load_server_config(name: &str) -> Result<ServerConfig> {
     Ok(config)
 }
 
-fn get_all_servers() -> Result<Vec<ServerConfig>> {
+pub fn get_all_servers() -> Result<Vec<ServerConfig>> {
     let config_dir = get_servers_dir().join("configs");
     if !config_dir.exists() {
         return Ok(Vec::new());
@@ -275,7 +1220,7 @@ fn get_all_servers() -> Result<Vec<ServerConfig>> {
 
     for entry in fs::read_dir(config_dir)? {
         let entry = entry?;
-        if entry.path().extension().map_or(false, |ext| ext == "json") {
+        if entry.path().extension().is_some_and(|ext| ext == "json") {
             let json = fs::read_to_string(entry.path())?;
             let config: ServerConfig = serde_json::from_str(&json)?;
             servers.push(config);
@@ -285,55 +1230,320 @@ fn get_all_servers() -> Result<Vec<ServerConfig>> {
     Ok(servers)
 }
 
-pub async fn show_server_info(name: &str) -> Result<()> {
+#[derive(Serialize)]
+struct RuntimeState {
+    running: bool,
+    java_version: Option<String>,
+    jvm_flags: Option<Vec<String>>,
+    rcon: RconState,
+    whitelist_enabled: bool,
+    whitelisted_players: usize,
+    worlds: Vec<String>,
+    world_size_bytes: u64,
+    plugin_jars: usize,
+    last_backup: Option<BackupSummary>
+}
+
+#[derive(Serialize)]
+struct RconState {
+    enabled: bool,
+    port: Option<u16>,
+    connected: Option<bool>,
+    error: Option<String>
+}
+
+#[derive(Serialize)]
+struct BackupSummary {
+    file: String,
+    created_at: DateTime<Utc>
+}
+
+async fn runtime_state(config: &ServerConfig, java_version: Option<String>) -> RuntimeState {
+    let running = is_server_running(config).await;
+    let properties = read_server_properties(&config.path).unwrap_or_default();
+
+    let whitelisted_players = fs::read_to_string(config.path.join("whitelist.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str::<Vec<serde_json::Value>>(&json).ok())
+        .map_or(0, |entries| entries.len());
+
+    let worlds = world_dir_names(&config.path);
+    let world_size_bytes = worlds.iter().filter_map(|world| get_directory_size(&config.path.join(world)).ok()).sum();
+
+    RuntimeState {
+        running,
+        java_version,
+        jvm_flags: start_script_jvm_flags(&config.path),
+        rcon: rcon_state(config, &properties, running).await,
+        whitelist_enabled: properties.get("white-list").is_some_and(|value| value == "true"),
+        whitelisted_players,
+        worlds,
+        world_size_bytes,
+        plugin_jars: crate::plugin::jar_files(&config.path.join("plugins")).map_or(0, |jars| jars.len()),
+        last_backup: last_backup(&config.name)
+    }
+}
+
+fn inventory_entry(config: &ServerConfig, runtime: &RuntimeState) -> Result<serde_json::Value> {
+    // The JSON ends up in dashboards and playbooks, so webhook URLs are masked like in trigger list;
+    // passwords and tokens are never in the config, they live in secrets.json
+    let mut view = config.clone();
+    for trigger in &mut view.triggers {
+        if let crate::triggers::TriggerKind::Notify(url) = &mut trigger.action {
+            *url = crate::triggers::mask_webhook(url);
+        }
+    }
+    let mut entry = serde_json::to_value(&view)?;
+    entry["runtime"] = serde_json::to_value(runtime)?;
+    Ok(entry)
+}
+
+pub async fn show_server_info(name: &str, json_output: bool) -> Result<()> {
     let config = load_server_config(name)?;
+    let runtime = runtime_state(&config, java_version().await).await;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&inventory_entry(&config, &runtime)?)?);
+        return Ok(());
+    }
 
-    println!("📋 Server Information: {}", config.name);
-    println!(" - Type: {}", config.server_type);
-    println!(" - Version: {}", config.version);
-    println!(" - Port: {}", config.port);
-    println!(" - Location: {}", config.path.display());
-    println!(" - JAR: {}", config.jar_file);
+    crate::output::say("📋", "server.info.title", &[&config.name]);
+    crate::output::say(" -", "server.info.type", &[&config.server_type]);
+    crate::output::say(" -", "server.info.version", &[&config.version]);
+    crate::output::say(" -", "server.info.port", &[&config.port]);
+    crate::output::say(" -", "server.location", &[&config.path.display()]);
+    crate::output::say(" -", "server.info.jar", &[&config.jar_file]);
+    if config.experimental_builds {
+        crate::output::say(" -", "server.info.experimental", &[]);
+    }
+    let java = runtime.java_version.clone().unwrap_or_else(|| crate::output::text("server.info.java_missing", &[]));
+    crate::output::say(" -", "server.info.java", &[&java]);
+    match &runtime.jvm_flags {
+        Some(flags) if !flags.is_empty() => crate::output::say(" -", "server.info.jvm_flags", &[&flags.join(" ")]),
+        Some(_) => crate::output::say(" -", "server.info.jvm_flags_none", &[]),
+        None => crate::output::say(" -", "server.info.jvm_flags_no_script", &[])
+    }
+
+    let status = if runtime.running { "server.info.status_running" } else { "server.info.status_stopped" };
+    crate::output::say(" -", status, &[]);
+    let rcon = &runtime.rcon;
+    match (rcon.enabled, rcon.connected, &rcon.error) {
+        (false, _, _) => crate::output::say(" -", "server.info.rcon_disabled", &[]),
+        (true, Some(true), _) => crate::output::say(" -", "server.info.rcon_connected", &[&rcon.port.unwrap_or_default()]),
+        (true, _, Some(error)) => {
+            crate::output::say(" -", "server.info.rcon_error", &[&rcon.port.unwrap_or_default(), error])
+        }
+        (true, _, None) => crate::output::say(" -", "server.info.rcon_enabled", &[&rcon.port.unwrap_or_default()])
+    }
 
-    let plugins_count = config.path.join("plugins")
-        .read_dir()
-        .map(|dir| dir.filter_map(Result::ok).count())
-        .unwrap_or(0);
+    if runtime.whitelist_enabled {
+        crate::output::say(" -", "server.info.whitelist_on", &[&runtime.whitelisted_players]);
+    } else {
+        crate::output::say(" -", "server.info.whitelist_off", &[&runtime.whitelisted_players]);
+    }
 
-    println!(" - Plugins: {}", plugins_count);
+    if runtime.worlds.is_empty() {
+        crate::output::say(" -", "server.info.world_size_none", &[]);
+    } else {
+        crate::output::say(" -", "server.info.world_size", &[&format_bytes(runtime.world_size_bytes), &runtime.worlds.join(", ")]);
+    }
 
-    println!("\n🎮 Start Commands:");
+    crate::output::say(" -", "server.info.plugins", &[&runtime.plugin_jars]);
+    for plugin in &config.plugins {
+        println!("  • {} {} ({})", plugin.name, plugin.version_number, plugin.source);
+    }
+    if runtime.plugin_jars > config.plugins.len() {
+        crate::output::say("  •", "server.info.untracked_jars", &[&(runtime.plugin_jars - config.plugins.len())]);
+    }
+    if !config.disabled_plugins.is_empty() {
+        crate::output::say(" -", "server.info.disabled_plugins", &[&config.disabled_plugins.join(", ")]);
+    }
+
+    for world in &config.worlds {
+        let size = get_directory_size(&config.path.join(&world.name))
+            .map(format_bytes)
+            .unwrap_or_else(|_| crate::output::text("server.info.world_missing", &[]));
+        crate::output::say(" -", "server.info.world", &[&world.name, &world.environment, &world.world_type, &size]);
+    }
+
+    print_startup_history(&config);
+    if let Some(stopped_at) = config.last_stopped_at {
+        crate::output::say(" -", "server.info.last_stop", &[&stopped_at.format("%Y-%m-%d %H:%M UTC")]);
+    }
+    if let Some(idle) = &config.idle {
+        let key = if idle.wake { "server.info.idle_wake" } else { "server.info.idle" };
+        crate::output::say(" -", key, &[&idle.timeout_mins]);
+    }
+    if let Some(autostart) = &config.autostart {
+        crate::output::say(" -", "server.info.autostart", &[&autostart.ram]);
+    }
+    match &runtime.last_backup {
+        Some(backup) => crate::output::say(" -", "server.info.last_backup", &[&backup.file, &backup.created_at.format("%Y-%m-%d %H:%M UTC")]),
+        None => crate::output::say(" -", "server.info.last_backup_never", &[])
+    }
+
+    if let Some(map) = &config.map {
+        crate::output::say(" -", "server.info.map", &[&map.engine, &map.port]);
+    }
+
+    if let Some(database) = &config.database {
+        crate::output::say(" -", "server.info.database", &[&database.engine, &database.host, &database.port, &database.database]);
+    }
+
+    if let Some(ddns) = &config.ddns {
+        let last_ip = ddns.last_ip.clone().unwrap_or_else(|| crate::output::text("server.info.ddns_pending", &[]));
+        crate::output::say(" -", "server.info.ddns", &[&ddns.hostname, &ddns.provider, &last_ip]);
+    }
+
+    let hooks = config.hooks.configured();
+    if !hooks.is_empty() {
+        crate::output::say(" -", "server.info.hooks", &[&hooks.join(", ")]);
+    }
+
+    let secrets = crate::secrets::server_secrets(name)?;
+    crate::output::say(" -", "server.info.rcon_password", &[&crate::secrets::display_secret(secrets.rcon_password.as_deref(), false)]);
+    if secrets.forwarding_secret.is_some() {
+        crate::output::say(" -", "server.info.forwarding_secret", &[&crate::secrets::display_secret(secrets.forwarding_secret.as_deref(), false)]);
+    }
+
+    println!();
+    crate::output::say("🎮", "server.info.start_commands", &[]);
     if cfg!(windows) {
-        println!(" - CLI: anvil start {}", name);
-        println!(" - Direct: cd \"{}\" && start.bat [RAM_GB]", config.path.display());
-        println!(" - Double-click: start.bat");
+        crate::output::say(" -", "server.info.start_cli", &[&name]);
+        crate::output::say(" -", "server.info.start_direct_windows", &[&config.path.display()]);
+        crate::output::say(" -", "server.info.start_double_click", &[]);
     } else {
-        println!(" - CLI: anvil start {}", name);
-        println!(" - Direct: cd {} && ./start.sh [RAM_GB]", config.path.display());
+        crate::output::say(" -", "server.info.start_cli", &[&name]);
+        crate::output::say(" -", "server.info.start_direct", &[&config.path.display()]);
     }
 
     Ok(())
 }
 
-pub async fn delete_server(name: &str, force: bool) -> Result<()> {
-    let config = match load_server_config(name) {
+pub async fn java_version() -> Option<String> {
+    let output = Command::new("java").arg("-version").output().await.ok()?;
+    // java -version reports on stderr, e.g. openjdk version "21.0.2" 2024-01-16
+    let text = String::from_utf8_lossy(&output.stderr);
+    let first_line = text.lines().next()?;
+    let version = first_line.split('"').nth(1).unwrap_or(first_line);
+    Some(version.to_string())
+}
+
+pub fn start_script_jvm_flags(server_dir: &Path) -> Option<Vec<String>> {
+    let script = if cfg!(windows) { "start.bat" } else { "start.sh" };
+    let contents = fs::read_to_string(server_dir.join(script)).ok()?;
+    let command = contents.lines().find(|line| line.trim_start().starts_with("java "))?;
+
+    Some(
+        command
+            .split_whitespace()
+            .skip(1)
+            .take_while(|arg| *arg != "-jar")
+            .map(str::to_string)
+            .collect()
+    )
+}
+
+async fn rcon_state(config: &ServerConfig, properties: &HashMap<String, String>, running: bool) -> RconState {
+    let mut state = RconState {
+        enabled: properties.get("enable-rcon").is_some_and(|value| value == "true"),
+        port: None,
+        connected: None,
+        error: None
+    };
+    if !state.enabled {
+        return state;
+    }
+
+    state.port = Some(properties.get("rcon.port").and_then(|port| port.parse().ok()).unwrap_or(25575));
+    if running {
+        match crate::rcon::connect(config).await {
+            Ok(_) => state.connected = Some(true),
+            Err(e) => {
+                state.connected = Some(false);
+                state.error = Some(e.to_string());
+            }
+        }
+    }
+
+    state
+}
+
+fn last_backup(server_name: &str) -> Option<BackupSummary> {
+    fs::read_dir(crate::backup::get_backups_dir(server_name))
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tar.gz"))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some(BackupSummary { file: entry.file_name().to_string_lossy().to_string(), created_at: modified.into() })
+        })
+        .max_by_key(|backup| backup.created_at)
+}
+
+fn print_startup_history(config: &ServerConfig) {
+    let Some((last, previous)) = config.startup_history.split_last() else {
+        crate::output::say(" -", "server.info.startup_never", &[]);
+        return;
+    };
+
+    crate::output::say(" -", "server.info.startup_last", &[&format_duration_ms(last.duration_ms), &last.started_at.format("%Y-%m-%d %H:%M UTC")]);
+
+    if previous.is_empty() {
+        return;
+    }
+
+    let average = previous.iter().map(|r| r.duration_ms).sum::<u64>() / previous.len() as u64;
+    crate::output::say(" -", "server.info.startup_average", &[&format_duration_ms(average), &previous.len()]);
+
+    if average > 0 && last.duration_ms >= average * 3 / 2 {
+        let slowdown = format!("{:.1}", last.duration_ms as f64 / average as f64);
+        crate::output::say(" ⚠️ ", "server.info.startup_slow", &[&slowdown]);
+    }
+
+    let recent: Vec<String> = config.startup_history
+        .iter()
+        .rev()
+        .take(5)
+        .map(|r| format_duration_ms(r.duration_ms))
+        .collect();
+    crate::output::say(" -", "server.info.startup_recent", &[&recent.join(", ")]);
+}
+
+pub async fn delete_server(
+    name: &str,
+    force: bool,
+    keep_world: bool,
+    keep_backups: bool,
+    permanent: bool,
+) -> Result<()> {
+    let mut config = match load_server_config(name) {
         Ok(config) => config,
         Err(_) => {
-            println!("❌ Server '{}' does not exist", name);
+            crate::output::error("server.not_found", &[&name]);
             return Ok(());
         }
     };
 
-    println!("🗑️  This will permanently delete:");
-    println!(" - 📁 Server directory: {}", config.path.display());
-    println!(" - ⚙️ Configuration file");
+    let running = is_server_running(&config).await;
+    if running {
+        crate::output::warn("server.delete_running", &[&name, &config.port]);
+    }
+
+    if permanent {
+        crate::output::say("🗑️ ", "server.delete_permanent", &[]);
+    } else {
+        crate::output::say("🗑️ ", "server.delete_trash", &[]);
+    }
+    crate::output::say(" -", "server.delete.directory", &[&config.path.display()]);
+    crate::output::say(" -", "server.delete.config", &[]);
 
     let plugins_dir = config.path.join("plugins");
     let plugin_count = if plugins_dir.exists() {
         fs::read_dir(&plugins_dir)?
             .filter_map(Result::ok)
             .filter(|entry| {
-                entry.path().extension().map_or(false, |ext| ext == "jar")
+                entry.path().extension().is_some_and(|ext| ext == "jar")
             })
             .count()
     } else {
@@ -341,36 +1551,76 @@ pub async fn delete_server(name: &str, force: bool) -> Result<()> {
     };
 
     if plugin_count > 0 {
-        println!(" - 🔌 {} plugins", plugin_count);
+        crate::output::say(" -", "server.delete.plugins", &[&plugin_count]);
+    }
+
+    let worlds = all_world_dir_names(&config);
+    if !worlds.is_empty() {
+        crate::output::say(" -", "server.delete.worlds", &[]);
     }
 
-    let world_dir = config.path.join("world");
-    if world_dir.exists() {
-        println!(" - 🌍 World data (including player data, builds, etc.)");
+    // Archiving the world puts it in the backups directory, so that directory has to survive
+    let keep_backups = keep_backups || keep_world;
+    let backups_dir = crate::backup::get_backups_dir(name);
+    if !keep_backups && backups_dir.exists() {
+        crate::output::say(" -", "server.delete.backups", &[&backups_dir.display(), &format_bytes(get_directory_size(&backups_dir)?)]);
     }
 
     let dir_size = get_directory_size(&config.path)?;
-    println!(" - 📊 Total size: {}", format_bytes(dir_size));
+    crate::output::say(" -", "server.delete.total", &[&format_bytes(dir_size)]);
+
+    if keep_world && !worlds.is_empty() {
+        crate::output::hint("server.delete.archive_first", &[&backups_dir.display()]);
+    }
+    for relocated in [&config.layout.worlds, &config.layout.logs].into_iter().flatten() {
+        crate::output::hint("server.delete.relocated_kept", &[&relocated.display()]);
+    }
 
     if !force {
         println!();
 
-        let confirmed = Confirm::new()
-            .with_prompt(&format!("Are you sure you want to delete server '{}'?", name))
-            .default(false)
-            .interact()?;
+        let confirmed = if worlds.is_empty() || keep_world {
+            Confirm::new()
+                .with_prompt(crate::output::text("server.delete.confirm", &[&name]))
+                .default(false)
+                .interact()?
+        } else {
+            let typed: String = Input::new()
+                .with_prompt(crate::output::text("server.delete.confirm_world", &[&name]))
+                .allow_empty(true)
+                .interact_text()?;
+            typed.trim() == name
+        };
 
         if !confirmed {
-            println!("❌ Deletion cancelled");
+            crate::output::error("server.delete.cancelled", &[]);
             return Ok(());
         }
     }
 
-    println!("🗑️  Deleting server '{}'...", name);
+    // Only stopped once confirmed, declining the prompt must leave a running server running
+    if running {
+        stop_before_delete(&config, force).await?;
+    }
+
+    if keep_world && let Some(archive) = crate::backup::archive_world(&config)? {
+        crate::output::success("server.delete.world_archived", &[&archive.display()]);
+    }
+
+    crate::output::say("🗑️ ", "server.deleting", &[&name]);
+    crate::autostart::remove_autostart(&mut config).await?;
+
+    if !permanent {
+        crate::trash::move_to_trash(&config, (!keep_backups).then_some(backups_dir.as_path()))?;
+        crate::trash::purge_expired();
+        crate::output::say("🎉", "server.deleted", &[&name]);
+        crate::output::hint("server.delete.in_trash", &[&crate::trash::TRASH_RETENTION_DAYS, &name]);
+        return Ok(());
+    }
 
     if config.path.exists() {
         fs::remove_dir_all(&config.path)?;
-        println!("✅ Removed server directory");
+        crate::output::success("server.delete.removed_directory", &[]);
     }
 
     let config_file = get_servers_dir()
@@ -379,15 +1629,35 @@ pub async fn delete_server(name: &str, force: bool) -> Result<()> {
 
     if config_file.exists() {
         fs::remove_file(config_file)?;
-        println!("✅ Removed configuration file");
+        crate::output::success("server.delete.removed_config", &[]);
+    }
+
+    crate::secrets::remove_server_secrets(name)?;
+
+    if !keep_backups && backups_dir.exists() {
+        fs::remove_dir_all(&backups_dir)?;
+        crate::output::success("server.delete.removed_backups", &[]);
     }
 
-    println!("🎉 Server '{}' deleted successfully!", name);
+    crate::output::say("🎉", "server.deleted", &[&name]);
+
+    Ok(())
+}
+
+async fn stop_before_delete(config: &ServerConfig, force: bool) -> Result<()> {
+    crate::output::say("🛑", "server.stopping", &[&config.name]);
+    match stop_server(config).await {
+        Ok(()) => crate::output::success("server.stopped", &[]),
+        Err(e) if force => {
+            crate::output::warn("server.delete.stop_failed_forced", &[&e]);
+        }
+        Err(e) => return Err(anyhow!("Could not stop server '{}': {}", config.name, e))
+    }
 
     Ok(())
 }
 
-fn get_directory_size(path: &PathBuf) -> Result<u64> {
+pub fn get_directory_size(path: &Path) -> Result<u64> {
     let mut size = 0u64;
 
     if path.is_file() {
@@ -408,7 +1678,7 @@ fn get_directory_size(path: &PathBuf) -> Result<u64> {
     Ok(size)
 }
 
-fn format_bytes(bytes: u64) -> String {
+pub fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
 
     if bytes == 0 {
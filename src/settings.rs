@@ -0,0 +1,133 @@
+use crate::server::get_anvil_dir;
+use crate::sources::validate_sources;
+use crate::SettingsAction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const SETTING_KEYS: &[&str] = &["plugin-sources", "config-versioning", "safety-snapshots", "language"];
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Settings {
+    #[serde(default)]
+    pub plugin_sources: Vec<String>,
+    #[serde(default)]
+    pub config_versioning: bool,
+    #[serde(default)]
+    pub safety_snapshots: Option<bool>,
+    #[serde(default)]
+    pub language: Option<String>
+}
+
+impl Settings {
+    pub fn safety_snapshots_enabled(&self) -> bool {
+        self.safety_snapshots.unwrap_or(true)
+    }
+}
+
+fn settings_path() -> PathBuf {
+    get_anvil_dir().join("settings.json")
+}
+
+pub fn load_settings() -> Result<Settings> {
+    let path = settings_path();
+    if !path.exists() {
+        return Ok(Settings::default());
+    }
+
+    let contents = fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+pub fn save_settings(settings: &Settings) -> Result<()> {
+    let path = settings_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(settings)?)?;
+    Ok(())
+}
+
+pub async fn handle_settings_action(action: SettingsAction) -> Result<()> {
+    match action {
+        SettingsAction::Set { key, value } => {
+            set_setting(&key, &value)?;
+        }
+        SettingsAction::Get { key } => {
+            let settings = load_settings()?;
+            println!("{} = {}", key, setting_value(&settings, &key)?);
+        }
+        SettingsAction::List => {
+            let settings = load_settings()?;
+            println!("⚙️  Global settings:");
+            for key in SETTING_KEYS {
+                println!(" - {}: {}", key, setting_value(&settings, key)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn set_setting(key: &str, value: &str) -> Result<()> {
+    let mut settings = load_settings()?;
+
+    match key {
+        "plugin-sources" => {
+            let sources = parse_list(value);
+            validate_sources(&sources)?;
+            settings.plugin_sources = sources;
+        }
+        "config-versioning" => {
+            settings.config_versioning = parse_bool(value)?;
+        }
+        "safety-snapshots" => {
+            settings.safety_snapshots = Some(parse_bool(value)?);
+        }
+        "language" => {
+            let language = value.to_lowercase();
+            if language != "auto" && !crate::output::LANGUAGES.contains(&language.as_str()) {
+                return Err(anyhow!(
+                    "Unsupported language '{}' (expected auto or one of {})",
+                    value,
+                    crate::output::LANGUAGES.join(", ")
+                ));
+            }
+            // auto follows the system locale
+            settings.language = Some(language).filter(|language| language != "auto");
+        }
+        other => return Err(unknown_key(other))
+    }
+
+    save_settings(&settings)?;
+    println!("✅ {} = {}", key, setting_value(&settings, key)?);
+
+    Ok(())
+}
+
+fn setting_value(settings: &Settings, key: &str) -> Result<String> {
+    match key {
+        "plugin-sources" if settings.plugin_sources.is_empty() => Ok("(default)".to_string()),
+        "plugin-sources" => Ok(settings.plugin_sources.join(",")),
+        "config-versioning" => Ok(settings.config_versioning.to_string()),
+        "safety-snapshots" => Ok(settings.safety_snapshots_enabled().to_string()),
+        "language" => Ok(settings.language.clone().unwrap_or_else(|| "auto".to_string())),
+        other => Err(unknown_key(other))
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool> {
+    value.parse().map_err(|_| anyhow!("Expected true or false, got '{}'", value))
+}
+
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim().to_lowercase())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn unknown_key(key: &str) -> anyhow::Error {
+    anyhow!("Unknown setting '{}' (expected one of {})", key, SETTING_KEYS.join(", "))
+}
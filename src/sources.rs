@@ -0,0 +1,158 @@
+use crate::server::ServerConfig;
+use anyhow::{anyhow, Result};
+
+pub const SOURCES: &[&str] = &["modrinth", "hangar", "spiget", "curseforge"];
+pub const DEFAULT_SOURCES: &[&str] = &["modrinth", "hangar", "spiget"];
+
+pub struct PluginProject {
+    pub project_id: String,
+    pub title: String,
+    pub description: Option<String>
+}
+
+pub struct PluginRelease {
+    pub version_id: String,
+    pub version_number: String,
+    pub url: String,
+    pub filename: String
+}
+
+pub fn validate_sources(sources: &[String]) -> Result<()> {
+    for source in sources {
+        if !SOURCES.contains(&source.as_str()) {
+            return Err(anyhow!(
+                "Unknown plugin source '{}' (expected one of {})",
+                source,
+                SOURCES.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn source_priority(config: &ServerConfig) -> Result<(Vec<String>, &'static str)> {
+    if !config.plugin_sources.is_empty() {
+        return Ok((config.plugin_sources.clone(), "server"));
+    }
+
+    let settings = crate::settings::load_settings()?;
+    if !settings.plugin_sources.is_empty() {
+        return Ok((settings.plugin_sources, "global"));
+    }
+
+    Ok((DEFAULT_SOURCES.iter().map(|s| s.to_string()).collect(), "default"))
+}
+
+pub async fn search(source: &str, query: &str) -> Result<Option<PluginProject>> {
+    match source {
+        "modrinth" => Ok(crate::api::modrinth::search_project(query).await?.map(|hit| PluginProject {
+            project_id: hit.project_id,
+            title: hit.title,
+            description: Some(hit.description)
+        })),
+        "hangar" => Ok(crate::api::hangar::search_project(query).await?.map(|project| PluginProject {
+            project_id: project.namespace.slug,
+            title: project.name,
+            description: None
+        })),
+        "spiget" => Ok(crate::api::spiget::search_resource(query).await?.map(|resource| PluginProject {
+            project_id: resource.id.to_string(),
+            title: resource.name,
+            description: None
+        })),
+        "curseforge" => Ok(crate::api::curseforge::search_mod(query).await?.map(|project| PluginProject {
+            project_id: project.id.to_string(),
+            title: project.name,
+            description: None
+        })),
+        other => Err(anyhow!("Unsupported plugin source: {}", other))
+    }
+}
+
+pub async fn releases(source: &str, project_id: &str, game_version: &str) -> Result<Vec<PluginRelease>> {
+    match source {
+        "modrinth" => {
+            let versions = crate::api::modrinth::get_project_versions(project_id, game_version).await?;
+            Ok(versions
+                .into_iter()
+                .filter_map(|version| {
+                    let file = version.files.iter().find(|f| f.primary).or(version.files.first())?;
+                    Some(PluginRelease {
+                        url: file.url.clone(),
+                        filename: file.filename.clone(),
+                        version_id: version.id,
+                        version_number: version.version_number
+                    })
+                })
+                .collect())
+        }
+        "hangar" => {
+            let versions = crate::api::hangar::get_compatible_versions(project_id, game_version).await?;
+            Ok(versions
+                .into_iter()
+                .filter_map(|version| {
+                    let download = version.downloads.get("PAPER")?;
+                    let url = download.download_url.clone().or(download.external_url.clone())?;
+                    let filename = download
+                        .file_info
+                        .as_ref()
+                        .map(|info| info.name.clone())
+                        .unwrap_or_else(|| jar_filename(project_id, &version.name));
+                    Some(PluginRelease {
+                        version_id: version.id.to_string(),
+                        version_number: version.name,
+                        url,
+                        filename
+                    })
+                })
+                .collect())
+        }
+        "spiget" => {
+            let resource = crate::api::spiget::get_resource(project_id).await?;
+            if resource.external || !supports_game_version(&resource.tested_versions, game_version) {
+                return Ok(Vec::new());
+            }
+
+            // Spiget can only serve the latest upload of a resource
+            let latest = crate::api::spiget::get_latest_version(project_id).await?;
+            Ok(vec![PluginRelease {
+                version_id: latest.id.to_string(),
+                filename: jar_filename(&resource.name, &latest.name),
+                version_number: latest.name,
+                url: crate::api::spiget::download_url(project_id)
+            }])
+        }
+        "curseforge" => {
+            let files = crate::api::curseforge::get_mod_files(project_id, game_version).await?;
+            Ok(files
+                .into_iter()
+                .filter_map(|file| {
+                    Some(PluginRelease {
+                        version_id: file.id.to_string(),
+                        version_number: file.display_name,
+                        url: file.download_url?,
+                        filename: file.file_name
+                    })
+                })
+                .collect())
+        }
+        other => Err(anyhow!("Unsupported plugin source: {}", other))
+    }
+}
+
+fn supports_game_version(tested_versions: &[String], game_version: &str) -> bool {
+    tested_versions.is_empty()
+        || tested_versions.iter().any(|tested| {
+            game_version == tested || game_version.starts_with(&format!("{}.", tested))
+        })
+}
+
+fn jar_filename(name: &str, version: &str) -> String {
+    let sanitize = |value: &str| -> String {
+        value
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '-' })
+            .collect()
+    };
+    format!("{}-{}.jar", sanitize(name), sanitize(version))
+}
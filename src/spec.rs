@@ -0,0 +1,408 @@
+use crate::config::is_secret_key;
+use crate::manifest::{apply_manifest, manifest_for, ManifestEntry, PluginManifest};
+use crate::plugin::{delete_plugin_jar, jar_files, DISABLED_DIR};
+use crate::properties::PropertiesFile;
+use crate::server::{
+    create_server, is_server_running, load_server_config, save_server_config, set_server_property,
+    start_script_jvm_flags, CreateOptions, InstalledPlugin, ServerConfig,
+};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SPEC_FORMATS: &[&str] = &["toml", "json"];
+
+// Properties anvil derives from the server section or the secrets store, never taken from a spec
+const MANAGED_PROPERTIES: &[&str] = &["server-port", "rcon.port", "enable-rcon"];
+
+#[derive(Serialize, Deserialize)]
+pub struct ServerSpec {
+    pub server: SpecServer,
+    #[serde(default)]
+    pub jvm: SpecJvm,
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+    #[serde(default)]
+    pub plugins: Vec<ManifestEntry>
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SpecServer {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub server_type: String,
+    pub version: String,
+    pub port: u16
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct SpecJvm {
+    // Heap size is left out, it comes from the RAM given to anvil start
+    #[serde(default)]
+    pub flags: Vec<String>
+}
+
+pub fn spec_for(config: &ServerConfig) -> Result<ServerSpec> {
+    let properties = PropertiesFile::load(&config.path.join("server.properties"))?
+        .values()
+        .into_iter()
+        .filter(|(key, _)| !MANAGED_PROPERTIES.contains(&key.as_str()) && !is_secret_key(key))
+        .collect();
+
+    Ok(ServerSpec {
+        server: SpecServer {
+            name: config.name.clone(),
+            server_type: config.server_type.clone(),
+            version: config.version.clone(),
+            port: config.port
+        },
+        jvm: SpecJvm { flags: extra_jvm_flags(&config.path) },
+        properties,
+        plugins: manifest_for(config, true).plugins
+    })
+}
+
+pub fn export_spec(server_name: &str, format: &str, output: Option<PathBuf>) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let spec = spec_for(&config)?;
+
+    let rendered = match format {
+        "toml" => toml::to_string_pretty(&spec)?,
+        "json" => serde_json::to_string_pretty(&spec)?,
+        other => return Err(anyhow!("Unknown format '{}', expected one of: {}", other, SPEC_FORMATS.join(", ")))
+    };
+
+    let untracked = untracked_jars(&config).len();
+
+    match output {
+        Some(path) => {
+            fs::write(&path, rendered)?;
+            println!("✅ Exported the spec of '{}' to {}", server_name, path.display());
+            if untracked > 0 {
+                println!("⚠️  {} jar(s) were installed manually and are not included", untracked);
+            }
+        }
+        None => {
+            print!("{}", rendered);
+            if untracked > 0 {
+                eprintln!("⚠️  {} jar(s) were installed manually and are not included", untracked);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn read_spec(path: &Path) -> Result<ServerSpec> {
+    let contents = fs::read_to_string(path).map_err(|e| anyhow!("Could not read spec {}: {}", path.display(), e))?;
+    let spec = if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&contents).map_err(|e| anyhow!("Invalid spec {}: {}", path.display(), e))?
+    } else {
+        toml::from_str(&contents).map_err(|e| anyhow!("Invalid spec {}: {}", path.display(), e))?
+    };
+    Ok(spec)
+}
+
+// Lists how the live server differs from the spec, one readable line per difference
+pub fn spec_drift(config: &ServerConfig, spec: &ServerSpec) -> Result<Vec<String>> {
+    let mut drift = Vec::new();
+
+    if config.server_type != spec.server.server_type {
+        drift.push(format!("type: {} (spec: {})", config.server_type, spec.server.server_type));
+    }
+    if config.version != spec.server.version {
+        drift.push(format!("version: {} (spec: {})", config.version, spec.server.version));
+    }
+    if config.port != spec.server.port {
+        drift.push(format!("port: {} (spec: {})", config.port, spec.server.port));
+    }
+
+    let properties = PropertiesFile::load(&config.path.join("server.properties"))?;
+    for (key, value) in &spec.properties {
+        if MANAGED_PROPERTIES.contains(&key.as_str()) || is_secret_key(key) {
+            continue;
+        }
+        match properties.get(key) {
+            Some(live) if &live == value => {}
+            live => drift.push(format!("{}: {} (spec: {})", key, live.as_deref().unwrap_or("(unset)"), value))
+        }
+    }
+
+    let flags = extra_jvm_flags(&config.path);
+    if flags != spec.jvm.flags {
+        drift.push(format!("JVM flags: {} (spec: {})", flags.join(" "), spec.jvm.flags.join(" ")));
+    }
+
+    for entry in &spec.plugins {
+        match config.plugins.iter().find(|installed| entry.matches(installed)) {
+            None => drift.push(format!("plugin {}: missing", entry.name)),
+            Some(installed) => {
+                if let Some(version) = entry.version.as_ref().filter(|version| **version != installed.version_number) {
+                    drift.push(format!("plugin {}: {} (spec: {})", entry.name, installed.version_number, version));
+                }
+            }
+        }
+    }
+    // Like apply, a spec without plugins does not manage the plugins folder
+    if spec.plugins.is_empty() {
+        return Ok(drift);
+    }
+    for installed in extra_plugins(config, spec) {
+        drift.push(format!("plugin {}: installed but not in the spec", installed.name));
+    }
+    for jar in untracked_jars(config) {
+        let filename = jar.file_name().unwrap_or_default().to_string_lossy();
+        drift.push(format!("plugin jar {}: added by hand", filename));
+    }
+
+    Ok(drift)
+}
+
+pub fn check_spec(path: &Path) -> Result<()> {
+    let spec = read_spec(path)?;
+    let name = spec.server.name.as_str();
+    let config = load_server_config(name)
+        .map_err(|_| anyhow!("Server '{}' from {} does not exist, 'anvil apply' creates it", name, path.display()))?;
+
+    let drift = spec_drift(&config, &spec)?;
+    if drift.is_empty() {
+        println!("✅ '{}' matches {}", name, path.display());
+        return Ok(());
+    }
+
+    println!("🔍 '{}' has drifted from {}:", name, path.display());
+    for line in &drift {
+        println!("  • {}", line);
+    }
+    println!("💡 Reconcile it with: anvil apply {}", path.display());
+
+    // A failing exit status lets CI and cron jobs notice the drift
+    Err(anyhow!("{} difference(s) from the spec", drift.len()))
+}
+
+pub async fn apply_spec(path: &Path, force: bool, check: bool, prune: bool) -> Result<()> {
+    if check {
+        return check_spec(path);
+    }
+
+    let spec = read_spec(path)?;
+    let name = spec.server.name.as_str();
+
+    let config = match load_server_config(name) {
+        Ok(config) => config,
+        Err(_) => {
+            println!("📄 '{}' does not exist yet, creating it from {}", name, path.display());
+            let options = CreateOptions {
+                copy_from: None,
+                bind: None,
+                ip_stack: None,
+                experimental: false,
+                fallback_vanilla: false,
+                world_gen: Default::default(),
+                boot: None
+            };
+            create_server(name, &spec.server.version, &spec.server.server_type, spec.server.port, options).await?;
+            load_server_config(name)?
+        }
+    };
+
+    if config.server_type != spec.server.server_type {
+        return Err(anyhow!(
+            "Server '{}' is {} but the spec asks for {}, recreate it to change the type",
+            name,
+            config.server_type,
+            spec.server.server_type
+        ));
+    }
+
+    println!("📄 Applying {} to '{}'", path.display(), name);
+    let running = is_server_running(&config).await;
+
+    if config.version != spec.server.version {
+        let options = crate::upgrade::UpgradeOptions {
+            check: false,
+            force,
+            staged: false,
+            experimental: false,
+            allow_downgrade: false,
+            ram: 2
+        };
+        crate::upgrade::upgrade_server(name, &spec.server.version, options).await?;
+    }
+
+    let mut changes = 0;
+    let mut config = load_server_config(name)?;
+    if config.port != spec.server.port {
+        set_server_property(&config.path, "server-port", &spec.server.port.to_string())?;
+        set_server_property(&config.path, "rcon.port", &spec.server.port.saturating_add(10).to_string())?;
+        println!("  • port: {} -> {}", config.port, spec.server.port);
+        config.port = spec.server.port;
+        save_server_config(&config)?;
+        changes += 1;
+    }
+
+    changes += apply_properties(&config, &spec.properties)?;
+
+    if extra_jvm_flags(&config.path) != spec.jvm.flags {
+        set_jvm_flags(&config.path, &spec.jvm.flags)?;
+        println!("  • JVM flags: {}", spec.jvm.flags.join(" "));
+        changes += 1;
+    }
+
+    // A spec without plugins leaves the plugins folder alone
+    if !spec.plugins.is_empty() {
+        let manifest = PluginManifest { plugins: spec.plugins.clone() };
+        apply_manifest(name, &manifest, &path.display().to_string(), false).await?;
+
+        if prune {
+            // Jars of a running server are still loaded, pulling them out from under it breaks plugins mid-game
+            if running {
+                return Err(anyhow!("Server '{}' is running, stop it before pruning plugins", name));
+            }
+            changes += remove_extra_plugins(name, &spec).await?;
+        } else {
+            report_extra_plugins(name, &spec, path)?;
+        }
+    }
+
+    println!("✅ '{}' matches {} ({} change(s))", name, path.display(), changes);
+    if running && changes > 0 {
+        println!("⚠️  Server '{}' is running; restart it to apply the change", name);
+    }
+
+    Ok(())
+}
+
+fn report_extra_plugins(name: &str, spec: &ServerSpec, path: &Path) -> Result<()> {
+    let config = load_server_config(name)?;
+    let mut extra: Vec<String> = extra_plugins(&config, spec).map(|plugin| plugin.filename.clone()).collect();
+    extra.extend(untracked_jars(&config).iter().map(|jar| jar.file_name().unwrap_or_default().to_string_lossy().to_string()));
+    if extra.is_empty() {
+        return Ok(());
+    }
+
+    println!("⚠️  '{}' has plugins the spec does not list: {}", name, extra.join(", "));
+    println!("💡 Remove them with: anvil apply {} --prune", path.display());
+    Ok(())
+}
+
+// Plugins the spec does not list are deleted if anvil can download them again, hand-added jars are only disabled
+async fn remove_extra_plugins(name: &str, spec: &ServerSpec) -> Result<usize> {
+    let mut config = load_server_config(name)?;
+    let extra: Vec<String> = extra_plugins(&config, spec).map(|plugin| plugin.filename.clone()).collect();
+    let untracked = untracked_jars(&config);
+    if extra.is_empty() && untracked.is_empty() {
+        return Ok(0);
+    }
+
+    crate::backup::safety_snapshot(&config, "removing plugins").await?;
+
+    let plugins_dir = config.path.join("plugins");
+    for filename in &extra {
+        let removed = delete_plugin_jar(&mut config, &plugins_dir.join(filename))?;
+        println!("  • removed plugin {}", removed);
+    }
+
+    let disabled_dir = plugins_dir.join(DISABLED_DIR);
+    fs::create_dir_all(&disabled_dir)?;
+    for jar in &untracked {
+        let filename = jar.file_name().unwrap_or_default().to_string_lossy().to_string();
+        fs::rename(jar, disabled_dir.join(&filename))?;
+        if !config.disabled_plugins.contains(&filename) {
+            config.disabled_plugins.push(filename.clone());
+        }
+        println!("  • disabled hand-added plugin {}", filename);
+    }
+    save_server_config(&config)?;
+
+    if !untracked.is_empty() {
+        println!("💡 Add them to the spec to keep them, they stay in plugins/{} until then", DISABLED_DIR);
+    }
+    Ok(extra.len() + untracked.len())
+}
+
+// Tracked plugins that are enabled but not listed in the spec
+fn extra_plugins<'a>(config: &'a ServerConfig, spec: &'a ServerSpec) -> impl Iterator<Item = &'a InstalledPlugin> {
+    config.plugins.iter().filter(|installed| {
+        config.path.join("plugins").join(&installed.filename).exists()
+            && !spec.plugins.iter().any(|entry| entry.matches(installed))
+    })
+}
+
+// Jars in plugins/ that were copied in by hand rather than installed through anvil
+fn untracked_jars(config: &ServerConfig) -> Vec<PathBuf> {
+    jar_files(&config.path.join("plugins"))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|jar| !config.plugins.iter().any(|plugin| jar.ends_with(&plugin.filename)))
+        .collect()
+}
+
+fn apply_properties(config: &ServerConfig, wanted: &BTreeMap<String, String>) -> Result<usize> {
+    let path = config.path.join("server.properties");
+    let mut properties = PropertiesFile::load(&path)?;
+
+    let mut changes = 0;
+    for (key, value) in wanted {
+        if MANAGED_PROPERTIES.contains(&key.as_str()) || is_secret_key(key) {
+            println!("⚠️  Ignoring {} from the spec, anvil manages it", key);
+            continue;
+        }
+        if properties.get(key).as_ref() == Some(value) {
+            continue;
+        }
+        let previous = properties.set(key, value);
+        println!("  • {}: {} -> {}", key, previous.as_deref().unwrap_or("(unset)"), value);
+        changes += 1;
+    }
+
+    if changes > 0 {
+        properties.save(&path)?;
+    }
+    Ok(changes)
+}
+
+fn is_heap_flag(flag: &str) -> bool {
+    flag.starts_with("-Xmx") || flag.starts_with("-Xms")
+}
+
+fn extra_jvm_flags(server_dir: &Path) -> Vec<String> {
+    start_script_jvm_flags(server_dir)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|flag| !is_heap_flag(flag))
+        .collect()
+}
+
+// Rewrites the java line of both start scripts, keeping the heap flags and everything from -jar on
+fn set_jvm_flags(server_dir: &Path, flags: &[String]) -> Result<()> {
+    for script in ["start.sh", "start.bat"] {
+        let path = server_dir.join(script);
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let lines: Vec<String> = contents
+            .lines()
+            .map(|line| {
+                if !line.trim_start().starts_with("java ") {
+                    return line.to_string();
+                }
+                let tokens: Vec<&str> = line.split_whitespace().collect();
+                let jar_at = tokens.iter().position(|token| *token == "-jar").unwrap_or(tokens.len());
+
+                let mut rebuilt = vec!["java".to_string()];
+                let heap = tokens[1..jar_at].iter().filter(|token| is_heap_flag(token));
+                rebuilt.extend(heap.map(|token| token.to_string()));
+                rebuilt.extend(flags.iter().cloned());
+                rebuilt.extend(tokens[jar_at..].iter().map(|token| token.to_string()));
+                rebuilt.join(" ")
+            })
+            .collect();
+
+        fs::write(&path, lines.join("\n") + "\n")?;
+    }
+    Ok(())
+}
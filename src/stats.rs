@@ -0,0 +1,181 @@
+use crate::api::mojang::is_valid_name;
+use crate::server::load_server_config;
+use crate::StatsAction;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use flate2::read::GzDecoder;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+#[derive(Default)]
+struct PlayerStats {
+    sessions: u32,
+    playtime: Duration,
+    last_seen: Option<NaiveDateTime>
+}
+
+pub async fn handle_stats_action(action: StatsAction) -> Result<()> {
+    match action {
+        StatsAction::Players { server, csv } => {
+            player_stats(&server, csv)?;
+        }
+    }
+    Ok(())
+}
+
+fn player_stats(server_name: &str, csv: bool) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let logs = log_files(&config.path.join("logs"))?;
+
+    let mut stats: BTreeMap<String, PlayerStats> = BTreeMap::new();
+    for (date, path) in &logs {
+        let Ok(contents) = read_log(path) else {
+            continue;
+        };
+        collect_sessions(&contents, *date, &mut stats);
+    }
+
+    let mut players: Vec<(String, PlayerStats)> = stats.into_iter().collect();
+    players.sort_by_key(|(_, player)| std::cmp::Reverse(player.playtime));
+
+    if csv {
+        println!("player,sessions,playtime_seconds,last_seen");
+        for (name, player) in &players {
+            println!(
+                "{},{},{},{}",
+                name,
+                player.sessions,
+                player.playtime.num_seconds(),
+                player.last_seen.map(|seen| seen.format("%Y-%m-%dT%H:%M:%S").to_string()).unwrap_or_default()
+            );
+        }
+        return Ok(());
+    }
+
+    if players.is_empty() {
+        println!("👥 No player sessions found in {} log file(s) of '{}'", logs.len(), server_name);
+        return Ok(());
+    }
+
+    println!("👥 Player sessions on '{}' from {} log file(s):", server_name, logs.len());
+    let name_width = players.iter().map(|(name, _)| name.len()).max().unwrap_or(6).max(6);
+    println!("  {:name_width$}  {:>8}  {:>10}  LAST SEEN", "PLAYER", "SESSIONS", "PLAYTIME", name_width = name_width);
+    for (name, player) in &players {
+        println!(
+            "  {:name_width$}  {:>8}  {:>10}  {}",
+            name,
+            player.sessions,
+            format_playtime(player.playtime),
+            player.last_seen.map(|seen| seen.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default(),
+            name_width = name_width
+        );
+    }
+
+    Ok(())
+}
+
+fn log_files(logs_dir: &Path) -> Result<Vec<(NaiveDate, PathBuf)>> {
+    if !logs_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut rotated = Vec::new();
+    for entry in fs::read_dir(logs_dir)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+        // Rotated logs are named 2024-01-05-3.log.gz, numbered per day
+        let Some(stem) = name.strip_suffix(".log.gz") else {
+            continue;
+        };
+        let Some((date, index)) = stem.rsplit_once('-') else {
+            continue;
+        };
+        if let (Ok(date), Ok(index)) = (NaiveDate::parse_from_str(date, "%Y-%m-%d"), index.parse::<u32>()) {
+            rotated.push((date, index, path));
+        }
+    }
+    rotated.sort();
+
+    let mut files: Vec<(NaiveDate, PathBuf)> = rotated.into_iter().map(|(date, _, path)| (date, path)).collect();
+
+    let latest = logs_dir.join("latest.log");
+    if let Ok(modified) = latest.metadata().and_then(|metadata| metadata.modified()) {
+        files.push((DateTime::<Local>::from(modified).date_naive(), latest));
+    }
+
+    Ok(files)
+}
+
+fn read_log(path: &Path) -> Result<String> {
+    let bytes = fs::read(path)?;
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        let mut contents = String::new();
+        GzDecoder::new(bytes.as_slice()).read_to_string(&mut contents)?;
+        return Ok(contents);
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn collect_sessions(contents: &str, date: NaiveDate, stats: &mut BTreeMap<String, PlayerStats>) {
+    let mut open: HashMap<String, NaiveDateTime> = HashMap::new();
+    let mut day = date;
+    let mut previous: Option<NaiveTime> = None;
+    let mut last_timestamp = None;
+
+    for line in contents.lines() {
+        let Some(time) = line_time(line) else {
+            continue;
+        };
+        // Log lines only carry the time of day, so a jump backwards means midnight passed
+        if previous.is_some_and(|previous| time < previous) {
+            day = day.succ_opt().unwrap_or(day);
+        }
+        previous = Some(time);
+        let timestamp = day.and_time(time);
+        last_timestamp = Some(timestamp);
+
+        let Some((_, message)) = line.split_once("]: ") else {
+            continue;
+        };
+
+        if let Some(name) = message.strip_suffix(" joined the game").filter(|name| is_valid_name(name)) {
+            let player = stats.entry(name.to_string()).or_default();
+            player.sessions += 1;
+            player.last_seen = Some(timestamp);
+            open.insert(name.to_string(), timestamp);
+        } else if let Some(name) = message.strip_suffix(" left the game").filter(|name| is_valid_name(name))
+            && let Some(joined) = open.remove(name)
+        {
+            let player = stats.entry(name.to_string()).or_default();
+            player.playtime += timestamp - joined;
+            player.last_seen = Some(timestamp);
+        }
+    }
+
+    // A crash leaves sessions without a leave line, count them up to the last logged moment
+    if let Some(end) = last_timestamp {
+        for (name, joined) in open {
+            let player = stats.entry(name).or_default();
+            player.playtime += end - joined;
+            player.last_seen = Some(end);
+        }
+    }
+}
+
+fn line_time(line: &str) -> Option<NaiveTime> {
+    // Vanilla logs "[12:34:56] [Server thread/INFO]", Paper logs "[12:34:56 INFO]"
+    let time = line.strip_prefix('[')?.get(..8)?;
+    NaiveTime::parse_from_str(time, "%H:%M:%S").ok()
+}
+
+fn format_playtime(playtime: Duration) -> String {
+    let minutes = playtime.num_minutes();
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+}
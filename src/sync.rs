@@ -0,0 +1,148 @@
+use crate::server::{is_server_running, load_server_config, ServerConfig};
+use crate::tags::servers_with_tag;
+use crate::SyncAction;
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::fs;
+
+// File name and the field that identifies an entry in it
+const PLAYER_FILES: &[(&str, &str)] = &[
+    ("whitelist.json", "uuid"),
+    ("ops.json", "uuid"),
+    ("banned-players.json", "uuid"),
+    ("banned-ips.json", "ip")
+];
+
+pub async fn handle_sync_action(action: SyncAction) -> Result<()> {
+    match action {
+        SyncAction::Players { tag, source, dry_run, force } => {
+            sync_players(&tag, &source, dry_run, force).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn sync_players(tag: &str, source_name: &str, dry_run: bool, force: bool) -> Result<()> {
+    let source = load_server_config(source_name)?;
+    let targets: Vec<ServerConfig> = servers_with_tag(tag)?
+        .into_iter()
+        .filter(|server| server.name != source.name)
+        .collect();
+
+    if targets.is_empty() {
+        println!("ℹ️  No other servers tagged '{}' to sync to", tag);
+        return Ok(());
+    }
+
+    println!(
+        "🔄 Syncing player lists from '{}' to {} server(s) tagged '{}'{}",
+        source.name,
+        targets.len(),
+        tag,
+        if dry_run { " (dry run)" } else { "" }
+    );
+
+    let mut conflicts = 0;
+
+    for (file, id_field) in PLAYER_FILES {
+        let source_entries = read_entries(&source, file)?;
+
+        for target in &targets {
+            let target_entries = read_entries(target, file)?;
+
+            let added = missing(&source_entries, &target_entries, id_field).len();
+            let removed: Vec<String> = missing(&target_entries, &source_entries, id_field)
+                .into_iter()
+                .map(describe_entry)
+                .collect();
+
+            if added == 0 && removed.is_empty() {
+                continue;
+            }
+
+            if !removed.is_empty() && !force {
+                conflicts += 1;
+                println!(
+                    "⚠️  {} on '{}' has {} entr{} not on '{}': {}",
+                    file,
+                    target.name,
+                    removed.len(),
+                    if removed.len() == 1 { "y" } else { "ies" },
+                    source.name,
+                    removed.join(", ")
+                );
+                continue;
+            }
+
+            println!(
+                "  • {} -> '{}': +{} / -{}",
+                file, target.name, added, removed.len()
+            );
+
+            if !dry_run {
+                fs::write(
+                    target.path.join(file),
+                    serde_json::to_string_pretty(&Value::Array(source_entries.clone()))?
+                )?;
+            }
+        }
+    }
+
+    if !dry_run {
+        for target in &targets {
+            if is_server_running(target).await {
+                match crate::rcon::send_command(target, "whitelist reload").await {
+                    Ok(_) => println!("🔁 Reloaded whitelist on '{}'", target.name),
+                    Err(e) => println!("⚠️  Could not reload whitelist on '{}': {}", target.name, e)
+                }
+                println!("💡 Restart '{}' to apply op and ban changes", target.name);
+            }
+        }
+    }
+
+    if conflicts > 0 {
+        return Err(anyhow!(
+            "{} file(s) were skipped because targets have entries the source lacks; add them to '{}' or re-run with --force to overwrite",
+            conflicts,
+            source.name
+        ));
+    }
+
+    println!("✅ Player lists in sync");
+    Ok(())
+}
+
+fn read_entries(config: &ServerConfig, file: &str) -> Result<Vec<Value>> {
+    let path = config.path.join(file);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&path)?;
+    if contents.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    match serde_json::from_str(&contents)? {
+        Value::Array(entries) => Ok(entries),
+        _ => Err(anyhow!("{} is not a JSON list", path.display()))
+    }
+}
+
+fn missing<'a>(entries: &'a [Value], others: &[Value], id_field: &str) -> Vec<&'a Value> {
+    entries
+        .iter()
+        .filter(|entry| {
+            let id = entry.get(id_field);
+            !others.iter().any(|other| other.get(id_field) == id)
+        })
+        .collect()
+}
+
+fn describe_entry(entry: &Value) -> String {
+    ["name", "ip", "uuid"]
+        .iter()
+        .find_map(|field| entry.get(*field).and_then(Value::as_str))
+        .unwrap_or("?")
+        .to_string()
+}
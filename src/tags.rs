@@ -0,0 +1,75 @@
+use crate::server::{get_all_servers, load_server_config, save_server_config, ServerConfig};
+use crate::TagAction;
+use anyhow::{anyhow, Result};
+
+pub async fn handle_tag_action(action: TagAction) -> Result<()> {
+    match action {
+        TagAction::Add { server, tags } => {
+            let mut config = load_server_config(&server)?;
+            for tag in tags {
+                let tag = tag.to_lowercase();
+                if !config.tags.contains(&tag) {
+                    config.tags.push(tag);
+                }
+            }
+            config.tags.sort();
+            save_server_config(&config)?;
+            println!("🏷️  Tags for '{}': {}", server, config.tags.join(", "));
+        }
+        TagAction::Remove { server, tag } => {
+            let mut config = load_server_config(&server)?;
+            let before = config.tags.len();
+            config.tags.retain(|existing| !existing.eq_ignore_ascii_case(&tag));
+            if config.tags.len() == before {
+                println!("❌ Server '{}' is not tagged '{}'", server, tag);
+                return Ok(());
+            }
+            save_server_config(&config)?;
+            println!("🗑️  Removed tag '{}' from '{}'", tag, server);
+        }
+        TagAction::List { tag } => {
+            list_tags(tag.as_deref())?;
+        }
+    }
+    Ok(())
+}
+
+pub fn servers_with_tag(tag: &str) -> Result<Vec<ServerConfig>> {
+    let mut servers: Vec<ServerConfig> = get_all_servers()?
+        .into_iter()
+        .filter(|server| server.tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)))
+        .collect();
+    servers.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if servers.is_empty() {
+        return Err(anyhow!("No servers are tagged '{}'", tag));
+    }
+
+    Ok(servers)
+}
+
+fn list_tags(tag: Option<&str>) -> Result<()> {
+    if let Some(tag) = tag {
+        println!("🏷️  Servers tagged '{}':", tag);
+        for server in servers_with_tag(tag)? {
+            println!("  • {}", server.name);
+        }
+        return Ok(());
+    }
+
+    let mut servers = get_all_servers()?;
+    servers.retain(|server| !server.tags.is_empty());
+    servers.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if servers.is_empty() {
+        println!("No tagged servers");
+        return Ok(());
+    }
+
+    println!("🏷️  Server tags:");
+    for server in servers {
+        println!(" - {}: {}", server.name, server.tags.join(", "));
+    }
+
+    Ok(())
+}
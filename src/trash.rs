@@ -0,0 +1,178 @@
+use crate::server::{get_anvil_dir, get_servers_dir, load_server_config, ServerConfig};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const TRASH_RETENTION_DAYS: i64 = 7;
+
+const ENTRY_FILE: &str = "entry.json";
+const SERVER_DIR: &str = "server";
+const CONFIG_FILE: &str = "config.json";
+const BACKUPS_DIR: &str = "backups";
+
+#[derive(Serialize, Deserialize)]
+struct TrashEntry {
+    name: String,
+    original_path: PathBuf,
+    deleted_at: DateTime<Utc>
+}
+
+fn trash_dir() -> PathBuf {
+    get_anvil_dir().join("trash")
+}
+
+fn config_file(name: &str) -> PathBuf {
+    get_servers_dir().join("configs").join(format!("{}.json", name))
+}
+
+pub fn move_to_trash(config: &ServerConfig, backups_dir: Option<&Path>) -> Result<()> {
+    let entry_dir = trash_dir().join(format!("{}-{}", config.name, Local::now().format("%Y%m%d-%H%M%S")));
+    fs::create_dir_all(&entry_dir)?;
+
+    let entry = TrashEntry {
+        name: config.name.clone(),
+        original_path: config.path.clone(),
+        deleted_at: Utc::now()
+    };
+    fs::write(entry_dir.join(ENTRY_FILE), serde_json::to_string_pretty(&entry)?)?;
+
+    if config.path.exists() {
+        move_path(&config.path, &entry_dir.join(SERVER_DIR))?;
+        println!("✅ Moved server directory to the trash");
+    }
+
+    let config_file = config_file(&config.name);
+    if config_file.exists() {
+        move_path(&config_file, &entry_dir.join(CONFIG_FILE))?;
+        println!("✅ Moved configuration file to the trash");
+    }
+
+    if let Some(backups_dir) = backups_dir.filter(|dir| dir.exists()) {
+        move_path(backups_dir, &entry_dir.join(BACKUPS_DIR))?;
+        println!("✅ Moved backups to the trash");
+    }
+
+    Ok(())
+}
+
+pub fn restore_deleted(name: Option<&str>) -> Result<()> {
+    purge_expired();
+
+    let mut entries = trash_entries()?;
+    let Some(name) = name else {
+        if entries.is_empty() {
+            println!("🗑️  The trash is empty");
+            return Ok(());
+        }
+
+        println!("🗑️  Deleted servers (kept for {} days):", TRASH_RETENTION_DAYS);
+        for (_, entry) in &entries {
+            let expires = entry.deleted_at + Duration::days(TRASH_RETENTION_DAYS);
+            println!(
+                "  • {} - deleted {}, purged after {}",
+                entry.name,
+                entry.deleted_at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+                expires.with_timezone(&Local).format("%Y-%m-%d")
+            );
+        }
+        return Ok(());
+    };
+
+    entries.retain(|(_, entry)| entry.name == name);
+    let Some((entry_dir, entry)) = entries.pop() else {
+        return Err(anyhow!("No deleted server named '{}' in the trash", name));
+    };
+
+    if load_server_config(name).is_ok() {
+        return Err(anyhow!("A server named '{}' exists again, delete or rename it before restoring", name));
+    }
+    if entry.original_path.exists() {
+        return Err(anyhow!("{} is in use again, move it away before restoring", entry.original_path.display()));
+    }
+
+    let server_dir = entry_dir.join(SERVER_DIR);
+    if server_dir.exists() {
+        move_path(&server_dir, &entry.original_path)?;
+    }
+
+    let config_file = config_file(name);
+    if let Some(parent) = config_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    move_path(&entry_dir.join(CONFIG_FILE), &config_file)?;
+
+    let backups_dir = entry_dir.join(BACKUPS_DIR);
+    if backups_dir.exists() {
+        let destination = crate::backup::get_backups_dir(name);
+        if destination.exists() {
+            println!("⚠️  Backups for '{}' already exist again, the old ones stay in {}", name, backups_dir.display());
+        } else {
+            move_path(&backups_dir, &destination)?;
+        }
+    }
+
+    let _ = fs::remove_dir_all(&entry_dir);
+    println!("♻️  Restored server '{}' to {}", name, entry.original_path.display());
+
+    Ok(())
+}
+
+pub fn purge_expired() {
+    let Ok(entries) = trash_entries() else {
+        return;
+    };
+
+    let cutoff = Utc::now() - Duration::days(TRASH_RETENTION_DAYS);
+    for (entry_dir, entry) in entries.into_iter().filter(|(_, entry)| entry.deleted_at < cutoff) {
+        if let Err(e) = fs::remove_dir_all(&entry_dir) {
+            println!("⚠️  Could not purge {} from the trash: {}", entry_dir.display(), e);
+            continue;
+        }
+
+        // Secrets stay around while the server can still be restored
+        if load_server_config(&entry.name).is_err() {
+            let _ = crate::secrets::remove_server_secrets(&entry.name);
+        }
+        println!("🧹 Purged '{}' from the trash", entry.name);
+    }
+}
+
+fn trash_entries() -> Result<Vec<(PathBuf, TrashEntry)>> {
+    let trash_dir = trash_dir();
+    if !trash_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for dir in fs::read_dir(trash_dir)? {
+        let path = dir?.path();
+        let Ok(json) = fs::read_to_string(path.join(ENTRY_FILE)) else {
+            continue;
+        };
+        if let Ok(entry) = serde_json::from_str::<TrashEntry>(&json) {
+            entries.push((path, entry));
+        }
+    }
+    entries.sort_by_key(|(_, entry)| entry.deleted_at);
+
+    Ok(entries)
+}
+
+pub fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    // Servers can live on another filesystem than ~/.anvil, where a rename is not possible
+    if from.is_dir() {
+        crate::upgrade::copy_dir(from, to, &[])?;
+        fs::remove_dir_all(from)?;
+    } else {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+
+    Ok(())
+}
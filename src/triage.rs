@@ -0,0 +1,135 @@
+use crate::server::ServerConfig;
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::Path;
+
+const TAIL_LINES: usize = 200;
+
+pub struct Diagnosis {
+    pub cause: &'static str,
+    pub detail: Option<String>,
+    pub hints: Vec<String>
+}
+
+pub fn diagnose(config: &ServerConfig, console: &[String], since: DateTime<Utc>) -> Option<Diagnosis> {
+    let mut lines = console.to_vec();
+    lines.extend(recent_log_tail(&config.path.join("logs").join("latest.log"), since));
+
+    let find = |patterns: &[&str]| {
+        lines.iter().find(|line| patterns.iter().any(|pattern| line.contains(pattern))).map(|line| line.trim().to_string())
+    };
+
+    if let Some(line) = find(&["FAILED TO BIND TO PORT", "Address already in use"]) {
+        return Some(Diagnosis {
+            cause: "the port is already in use",
+            detail: Some(line),
+            hints: vec![
+                format!("Another process is listening on port {}; check whether '{}' is already running", config.port, config.name),
+                "Stop the other process, or give this server a different server-port in server.properties".to_string()
+            ]
+        });
+    }
+
+    if let Some(line) = find(&["You need to agree to the EULA"]) {
+        return Some(Diagnosis {
+            cause: "the Minecraft EULA has not been accepted",
+            detail: Some(line),
+            hints: vec![format!(
+                "Read https://aka.ms/MinecraftEULA and set eula=true in {}",
+                config.path.join("eula.txt").display()
+            )]
+        });
+    }
+
+    if let Some(line) = find(&["UnsupportedClassVersionError", "has been compiled by a more recent version"]) {
+        let required = required_java_version(&line);
+        return Some(Diagnosis {
+            cause: "the installed Java version is too old",
+            detail: Some(line),
+            hints: vec![match required {
+                Some(version) => format!("Install Java {} or newer and make sure it is first on your PATH", version),
+                None => format!("Install the Java version required by Minecraft {} and make sure it is first on your PATH", config.version)
+            }]
+        });
+    }
+
+    if let Some(line) = find(&["Invalid or corrupt jarfile", "Unable to access jarfile", "error in opening zip file", "ZipException"]) {
+        return Some(Diagnosis {
+            cause: "the server jar is missing or corrupted",
+            detail: Some(line),
+            hints: vec![format!("Re-download it with: anvil verify {} --repair", config.name)]
+        });
+    }
+
+    if let Some(line) = find(&["Could not load 'plugins", "Error occurred while enabling", "UnknownDependencyException", "InvalidPluginException"]) {
+        let plugin = plugin_from_line(&line);
+        return Some(Diagnosis {
+            cause: "a plugin failed to load",
+            detail: Some(line),
+            hints: vec![match plugin {
+                Some(plugin) => format!("Disable it with: anvil plugin -s {} disable {}", config.name, plugin),
+                None => format!("Check the plugin errors in logs/latest.log and disable the culprit with: anvil plugin -s {} disable <plugin>", config.name)
+            }]
+        });
+    }
+
+    if let Some(line) = find(&["Could not reserve enough space", "OutOfMemoryError"]) {
+        return Some(Diagnosis {
+            cause: "Java could not allocate the requested memory",
+            detail: Some(line),
+            hints: vec![format!("Start with less memory, e.g. anvil start {} --ram 1", config.name)]
+        });
+    }
+
+    None
+}
+
+pub fn print_diagnosis(config: &ServerConfig, diagnosis: Option<&Diagnosis>) {
+    let Some(diagnosis) = diagnosis else {
+        println!("❌ Server '{}' exited before finishing startup", config.name);
+        println!("💡 Check {} for details", config.path.join("logs").join("latest.log").display());
+        return;
+    };
+
+    println!("❌ Server '{}' failed to start: {}", config.name, diagnosis.cause);
+    if let Some(detail) = &diagnosis.detail {
+        println!("   {}", detail);
+    }
+    for hint in &diagnosis.hints {
+        println!("💡 {}", hint);
+    }
+}
+
+fn recent_log_tail(path: &Path, since: DateTime<Utc>) -> Vec<String> {
+    // A log older than this launch belongs to a previous run and would point at the wrong cause
+    let fresh = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| DateTime::<Utc>::from(modified) >= since);
+    if !fresh {
+        return Vec::new();
+    }
+
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = contents.lines().collect();
+    lines[lines.len().saturating_sub(TAIL_LINES)..].iter().map(|line| line.to_string()).collect()
+}
+
+fn required_java_version(line: &str) -> Option<u32> {
+    // "class file version 65.0" -> Java 21, class file versions are offset by 44
+    let (_, rest) = line.split_once("class file version ")?;
+    let major: u32 = rest.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()?;
+    major.checked_sub(44)
+}
+
+fn plugin_from_line(line: &str) -> Option<String> {
+    if let Some((_, rest)) = line.split_once("Could not load 'plugins") {
+        let path = rest.trim_start_matches(['/', '\\']).split('\'').next()?;
+        return Some(crate::plugin::plugin_name_from_filename(path));
+    }
+
+    // "Error occurred while enabling WorldEdit v7.3.0 (Is it up to date?)"
+    let (_, rest) = line.split_once("while enabling ")?;
+    rest.split_whitespace().next().map(str::to_string)
+}
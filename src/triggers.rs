@@ -0,0 +1,207 @@
+use crate::api::http::SendWithBackoff;
+use crate::server::{load_server_config, save_server_config, ServerConfig};
+use crate::TriggerAction;
+use anyhow::{anyhow, Result};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Trigger {
+    pub name: String,
+    pub pattern: String,
+    pub action: TriggerKind,
+    pub cooldown_secs: u64
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum TriggerKind {
+    // Posted to a Discord or Slack compatible webhook
+    Notify(String),
+    // Sent to the server console, $1 or ${name} are replaced with what the pattern captured
+    Command(String),
+    // Run in a shell with the line and its captures in ANVIL_* variables
+    Script(String)
+}
+
+impl TriggerKind {
+    fn describe(&self) -> String {
+        match self {
+            TriggerKind::Notify(url) => format!("notify {}", mask_webhook(url)),
+            TriggerKind::Command(command) => format!("command '{}'", command),
+            TriggerKind::Script(script) => format!("script '{}'", script)
+        }
+    }
+}
+
+// The token is part of the webhook path, only the host is shown
+pub fn mask_webhook(url: &str) -> String {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("https", url));
+    let host = rest.split('/').next().unwrap_or_default();
+    format!("{}://{}/********", scheme, host)
+}
+
+pub async fn handle_trigger_action(server_name: &str, action: TriggerAction) -> Result<()> {
+    match action {
+        TriggerAction::Add { name, pattern, notify, command, script, cooldown } => {
+            let mut config = load_server_config(server_name)?;
+            Regex::new(&pattern).map_err(|e| anyhow!("Invalid pattern: {}", e))?;
+            if config.triggers.iter().any(|trigger| trigger.name == name) {
+                return Err(anyhow!("'{}' already has a trigger named '{}'", server_name, name));
+            }
+
+            let action = match (notify, command, script) {
+                (Some(url), _, _) => TriggerKind::Notify(url),
+                (_, Some(command), _) => TriggerKind::Command(command),
+                (_, _, Some(script)) => TriggerKind::Script(script),
+                _ => return Err(anyhow!("A trigger needs one of --notify, --command or --script"))
+            };
+            println!("✅ Trigger '{}' on '{}' will {} when a line matches {}", name, server_name, action.describe(), pattern);
+            config.triggers.push(Trigger { name, pattern, action, cooldown_secs: cooldown });
+            save_server_config(&config)?;
+            // The patterns are compiled once when the server starts
+            if crate::server::is_server_running(&config).await {
+                crate::output::warn("server.running_old_settings", &[&server_name]);
+            }
+        }
+        TriggerAction::Remove { name } => {
+            let mut config = load_server_config(server_name)?;
+            let before = config.triggers.len();
+            config.triggers.retain(|trigger| trigger.name != name);
+            if config.triggers.len() == before {
+                println!("❌ No trigger named '{}' on '{}'", name, server_name);
+                return Ok(());
+            }
+            save_server_config(&config)?;
+            println!("🗑️  Removed trigger '{}' from '{}'", name, server_name);
+        }
+        TriggerAction::List => {
+            let config = load_server_config(server_name)?;
+            if config.triggers.is_empty() {
+                println!("📭 No triggers configured for '{}'", server_name);
+                return Ok(());
+            }
+            println!("🎯 Triggers for server '{}':", server_name);
+            for trigger in &config.triggers {
+                println!(
+                    "  • {}: /{}/ → {} (cooldown {}s)",
+                    trigger.name,
+                    trigger.pattern,
+                    trigger.action.describe(),
+                    trigger.cooldown_secs
+                );
+            }
+        }
+        TriggerAction::Test { line } => {
+            let config = load_server_config(server_name)?;
+            let watcher = Watcher::new(&config);
+            let mut matched = false;
+            for (trigger, regex) in &watcher.triggers {
+                let Some(captures) = regex.captures(&line) else {
+                    continue;
+                };
+                matched = true;
+                let action = match &trigger.action {
+                    TriggerKind::Command(command) => TriggerKind::Command(expand(&captures, command)),
+                    other => other.clone()
+                };
+                println!("🎯 '{}' matches and would {}", trigger.name, action.describe());
+            }
+            if !matched {
+                println!("📭 No trigger on '{}' matches that line", server_name);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Watches the console of a running server and fires the triggers whose pattern matches a line
+pub struct Watcher {
+    triggers: Vec<(Trigger, Regex)>,
+    last_fired: HashMap<String, Instant>
+}
+
+impl Watcher {
+    pub fn new(config: &ServerConfig) -> Self {
+        let mut triggers = Vec::new();
+        for trigger in &config.triggers {
+            match Regex::new(&trigger.pattern) {
+                Ok(regex) => triggers.push((trigger.clone(), regex)),
+                Err(e) => println!("⚠️  Skipping trigger '{}', its pattern is invalid: {}", trigger.name, e)
+            }
+        }
+        Watcher { triggers, last_fired: HashMap::new() }
+    }
+
+    pub fn check(&mut self, config: &ServerConfig, line: &str) {
+        for (trigger, regex) in &self.triggers {
+            let Some(captures) = regex.captures(line) else {
+                continue;
+            };
+            // Lines like "Can't keep up!" come in bursts, one action per cooldown is enough
+            let cooldown = Duration::from_secs(trigger.cooldown_secs);
+            if self.last_fired.get(&trigger.name).is_some_and(|fired| fired.elapsed() < cooldown) {
+                continue;
+            }
+            self.last_fired.insert(trigger.name.clone(), Instant::now());
+
+            let config = config.clone();
+            let trigger = trigger.clone();
+            let line = line.to_string();
+            let groups: Vec<String> = captures
+                .iter()
+                .map(|group| group.map(|group| group.as_str().to_string()).unwrap_or_default())
+                .collect();
+            let action = match &trigger.action {
+                TriggerKind::Command(command) => TriggerKind::Command(expand(&captures, command)),
+                other => other.clone()
+            };
+            tokio::spawn(async move {
+                if let Err(e) = fire(&config, &trigger, &action, &line, &groups).await {
+                    println!("⚠️  Trigger '{}' failed: {}", trigger.name, e);
+                }
+            });
+        }
+    }
+}
+
+fn expand(captures: &Captures, template: &str) -> String {
+    let mut expanded = String::new();
+    captures.expand(template, &mut expanded);
+    expanded
+}
+
+async fn fire(config: &ServerConfig, trigger: &Trigger, action: &TriggerKind, line: &str, groups: &[String]) -> Result<()> {
+    match action {
+        TriggerKind::Notify(url) => {
+            let message = format!("[{}] {}: {}", config.name, trigger.name, line);
+            // Discord reads "content" and Slack reads "text", each ignores the other
+            reqwest::Client::new()
+                .post(url)
+                .json(&json!({ "content": message, "text": message }))
+                .send_with_backoff()
+                .await?;
+        }
+        TriggerKind::Command(command) => {
+            if crate::rcon::send_command(config, command).await.is_err() {
+                crate::console::send(config, command).await?;
+            }
+        }
+        TriggerKind::Script(script) => {
+            let mut command = crate::hooks::shell_command(config, script);
+            command.env("ANVIL_TRIGGER", &trigger.name).env("ANVIL_LINE", line);
+            for (index, group) in groups.iter().enumerate() {
+                command.env(format!("ANVIL_MATCH_{}", index), group);
+            }
+            let status = command.status().await?;
+            if !status.success() {
+                return Err(anyhow!("script exited with {}", status));
+            }
+        }
+    }
+    Ok(())
+}
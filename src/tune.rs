@@ -0,0 +1,158 @@
+use crate::config::resolve_key;
+use crate::server::{is_server_running, load_server_config, read_server_properties, set_server_property, ServerConfig};
+use crate::yaml::YamlDocument;
+use anyhow::{anyhow, Result};
+use std::fs;
+
+struct Recommendation {
+    key: &'static str,
+    value: String,
+    reason: &'static str
+}
+
+pub async fn tune_server(server_name: &str, players: u32, ram: &str, dry_run: bool) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    if players == 0 {
+        return Err(anyhow!("Expected at least 1 player"));
+    }
+    let ram_gb = parse_ram_gb(ram)?;
+
+    // Memory per 10 players is what limits how many chunks each player can keep loaded
+    let headroom = ram_gb / (players as f64 / 10.0).max(1.0);
+    if headroom < 1.0 {
+        println!("⚠️  {} of RAM for {} players is tight, expect lag even with the lowest settings", ram, players);
+    }
+
+    let mut changes = Vec::new();
+    for recommendation in recommendations(&config, players, headroom) {
+        let current = current_value(&config, recommendation.key);
+        match current {
+            Some(current) if current == recommendation.value => {}
+            Some(current) => changes.push((recommendation, current)),
+            // Keys differ between Paper versions, only the ones this server has are touched
+            None if recommendation.key.starts_with("server.") => changes.push((recommendation, "(unset)".to_string())),
+            None => {}
+        }
+    }
+
+    if changes.is_empty() {
+        println!("✅ '{}' already matches the recommendations for {} players on {}", server_name, players, ram);
+        return Ok(());
+    }
+
+    let width = changes.iter().map(|(recommendation, _)| recommendation.key.len()).max().unwrap_or(0);
+    println!("🎛️  Recommended settings for '{}' with {} players on {}:", server_name, players, ram);
+    println!("  {:width$}  {:10}  {:11}  WHY", "KEY", "CURRENT", "RECOMMENDED", width = width);
+    for (recommendation, current) in &changes {
+        println!(
+            "  {:width$}  {:10}  {:11}  {}",
+            recommendation.key, current, recommendation.value, recommendation.reason,
+            width = width
+        );
+    }
+
+    if dry_run {
+        println!("💡 Apply them with: anvil tune {} --players {} --ram {}", server_name, players, ram);
+        return Ok(());
+    }
+
+    for (recommendation, _) in &changes {
+        apply(&config, recommendation.key, &recommendation.value)?;
+    }
+    println!("✅ Applied {} setting(s)", changes.len());
+    if is_server_running(&config).await {
+        crate::output::warn("server.running_old_settings", &[&server_name]);
+    }
+
+    Ok(())
+}
+
+fn parse_ram_gb(ram: &str) -> Result<f64> {
+    let lower = ram.trim().to_lowercase();
+    let (number, divisor) = if let Some(megabytes) = lower.strip_suffix("mb").or_else(|| lower.strip_suffix('m')) {
+        (megabytes, 1024.0)
+    } else {
+        (lower.strip_suffix("gb").or_else(|| lower.strip_suffix('g')).unwrap_or(&lower), 1.0)
+    };
+
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| *value > 0.0)
+        .map(|value| value / divisor)
+        .ok_or_else(|| anyhow!("Expected a RAM size like 8G or 4096M, got '{}'", ram))
+}
+
+fn recommendations(config: &ServerConfig, players: u32, headroom: f64) -> Vec<Recommendation> {
+    let (view, simulation) = match headroom {
+        h if h >= 6.0 => (12, 10),
+        h if h >= 4.0 => (10, 8),
+        h if h >= 2.5 => (8, 6),
+        h if h >= 1.5 => (7, 5),
+        _ => (6, 4)
+    };
+
+    let mut list = vec![
+        Recommendation { key: "server.view-distance", value: view.to_string(), reason: "chunks sent to each player" },
+        Recommendation {
+            key: "server.simulation-distance",
+            value: simulation.to_string(),
+            reason: "chunks that tick around each player"
+        },
+        Recommendation {
+            key: "server.network-compression-threshold",
+            // Compressing every small packet costs more CPU than it saves once many players are online
+            value: if players >= 50 { "512" } else { "256" }.to_string(),
+            reason: "smallest packet worth compressing"
+        },
+    ];
+
+    if config.server_type == "paper" {
+        // Generating new terrain is the most expensive part of exploring, loading and sending are cheap
+        let generate = match headroom {
+            h if h >= 4.0 => -1,
+            h if h >= 2.5 => 20,
+            _ => 10
+        };
+        // Paper renamed the section in 1.20.2, only the one the server has exists in its config
+        for key in [
+            "paper.chunk-loading-basic.player-max-chunk-generate-rate",
+            "paper.chunk-loading.player-max-chunk-generate-rate"
+        ] {
+            list.push(Recommendation {
+                key,
+                value: format!("{}.0", generate),
+                reason: "new chunks generated per player per second, -1 is unlimited"
+            });
+        }
+        list.push(Recommendation {
+            key: "paper-world.chunks.delay-chunk-unloads-by",
+            value: if headroom >= 2.5 { "10s" } else { "5s" }.to_string(),
+            reason: "how long chunks stay loaded after players leave"
+        });
+    }
+
+    list
+}
+
+fn current_value(config: &ServerConfig, key: &str) -> Option<String> {
+    if let Some(property) = key.strip_prefix("server.") {
+        return read_server_properties(&config.path).ok()?.get(property).cloned();
+    }
+
+    let (file_path, path) = resolve_key(config, key).ok()?;
+    YamlDocument::parse(&fs::read_to_string(file_path).ok()?).get(&path)
+}
+
+fn apply(config: &ServerConfig, key: &str, value: &str) -> Result<()> {
+    if let Some(property) = key.strip_prefix("server.") {
+        return set_server_property(&config.path, property, value);
+    }
+
+    let (file_path, path) = resolve_key(config, key)?;
+    let mut document = YamlDocument::parse(&fs::read_to_string(&file_path)?);
+    document.set(&path, value)?;
+    fs::write(file_path, document.render())?;
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anyhow::{anyhow, Result};
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+
+pub const PROVIDERS: &[&str] = &["playit", "bore"];
+
+const BORE_SERVER: &str = "bore.pub";
+
+pub struct Tunnel {
+    provider: String,
+    child: Child
+}
+
+impl Tunnel {
+    pub async fn stop(mut self) {
+        let _ = self.child.kill().await;
+        println!("🔒 Closed {} tunnel", self.provider);
+    }
+}
+
+pub fn start_tunnel(provider: &str, port: u16) -> Result<Tunnel> {
+    let mut command = match provider {
+        "playit" => Command::new("playit"),
+        "bore" => {
+            let mut command = Command::new("bore");
+            command.args(["local", &port.to_string(), "--to", BORE_SERVER]);
+            command
+        }
+        other => {
+            return Err(anyhow!("Unknown tunnel provider '{}' (expected one of {})", other, PROVIDERS.join(", ")));
+        }
+    };
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run {} (is it installed and on PATH?): {}", provider, e))?;
+
+    println!("🚇 Starting {} tunnel for port {}...", provider, port);
+
+    if let Some(stdout) = child.stdout.take() {
+        tokio::spawn(watch_output(stdout, provider.to_string()));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(watch_output(stderr, provider.to_string()));
+    }
+
+    if provider == "playit" {
+        println!("💡 Point the playit tunnel at 127.0.0.1:{} in your playit.gg dashboard", port);
+    }
+
+    Ok(Tunnel {
+        provider: provider.to_string(),
+        child
+    })
+}
+
+async fn watch_output(output: impl AsyncRead + Unpin, provider: String) {
+    let mut lines = BufReader::new(output).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        for token in line.split_whitespace() {
+            let token = token.trim_matches(|c: char| matches!(c, '"' | '\'' | ',' | '(' | ')'));
+
+            if token.contains("playit.gg/claim") {
+                println!("🔗 Link this agent to your playit.gg account: {}", token);
+            } else if is_public_address(&provider, token) {
+                println!("🌐 Public address: {}", token);
+            }
+        }
+
+        if line.to_lowercase().contains("error") {
+            println!("⚠️  {}: {}", provider, line.trim());
+        }
+    }
+}
+
+fn is_public_address(provider: &str, token: &str) -> bool {
+    match provider {
+        "playit" => token.contains(".joinmc.link") || token.contains(".ply.gg"),
+        "bore" => token.starts_with(&format!("{}:", BORE_SERVER)),
+        _ => false
+    }
+}
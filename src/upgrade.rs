@@ -0,0 +1,581 @@
+use crate::cancel::CancelFlag;
+use crate::server::{
+    all_world_dir_names, boot_and_stop, create_start_script, download_paper_server, download_vanilla_server,
+    free_port, get_directory_size, is_server_running, load_server_config, read_server_properties,
+    remove_server_config, resolve_version, save_server_config, set_server_property, spawn_detached, stop_server,
+    ServerConfig,
+};
+use crate::version::{compare_versions, VersionConstraint};
+use anyhow::{anyhow, Result};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const STAGED_BOOT_TIMEOUT_SECS: u64 = 600;
+const STAGED_SKIPPED_DIRS: &[&str] = &["logs", "cache", "crash-reports"];
+// Rough chunk conversion throughput of --forceUpgrade, used only for the plan's estimate
+const CONVERSION_BYTES_PER_SEC: u64 = 20 * 1024 * 1024;
+// Java each Minecraft version needs at least, newest first
+const JAVA_REQUIREMENTS: &[(&str, u32)] = &[("1.20.5", 21), ("1.18", 17), ("1.17", 16), ("1.0", 8)];
+
+// Settings added or whose defaults changed, by the version that introduced the change
+const DEFAULT_CHANGES: &[DefaultChange] = &[
+    DefaultChange { since: "1.16", file: "server.properties", key: "sync-chunk-writes", note: "new, defaults to true" },
+    DefaultChange { since: "1.18", file: "server.properties", key: "simulation-distance", note: "new, defaults to 10" },
+    DefaultChange {
+        since: "1.19",
+        file: "server.properties",
+        key: "enforce-secure-profile",
+        note: "new, defaults to true and rejects clients without signed chat"
+    },
+    DefaultChange {
+        since: "1.19",
+        file: "server.properties",
+        key: "max-chained-neighbor-updates",
+        note: "new, defaults to 1000000"
+    },
+    DefaultChange {
+        since: "1.19",
+        file: "paper.yml",
+        key: "paper.yml",
+        note: "split into config/paper-global.yml and per-world paper-world.yml, migrated on first start"
+    },
+    DefaultChange { since: "1.20.2", file: "server.properties", key: "log-ips", note: "new, defaults to true" },
+    DefaultChange {
+        since: "1.20.2",
+        file: "config/paper-global.yml",
+        key: "chunk-loading",
+        note: "renamed to chunk-loading-basic and chunk-system"
+    },
+    DefaultChange { since: "1.20.5", file: "server.properties", key: "accepts-transfers", note: "new, defaults to false" },
+    DefaultChange {
+        since: "1.21.2",
+        file: "server.properties",
+        key: "pause-when-empty-seconds",
+        note: "new, defaults to 60 and pauses ticking on empty servers"
+    }
+];
+
+struct DefaultChange {
+    since: &'static str,
+    file: &'static str,
+    key: &'static str,
+    note: &'static str
+}
+
+pub enum Compatibility {
+    Supported(String),
+    Unsupported,
+    Unknown
+}
+
+pub struct PluginCompatibility {
+    pub name: String,
+    pub installed_version: String,
+    pub source: String,
+    pub status: Compatibility
+}
+
+pub struct UpgradeOptions {
+    pub check: bool,
+    pub force: bool,
+    pub staged: bool,
+    pub experimental: bool,
+    pub allow_downgrade: bool,
+    pub ram: u8
+}
+
+pub async fn upgrade_server(server_name: &str, version: &str, options: UpgradeOptions) -> Result<()> {
+    let UpgradeOptions { check, force, staged, experimental, allow_downgrade, ram } = options;
+    let config = load_server_config(server_name)?;
+    // --experimental widens this one upgrade, only create decides whether a server tracks experimental builds
+    let experimental = experimental || config.experimental_builds;
+    let target = resolve_version(&config.server_type, version, experimental).await?;
+
+    if compare_versions(&target, &config.version) == Ordering::Equal {
+        crate::output::success("server.already_on_version", &[&server_name, &target]);
+        return Ok(());
+    }
+    // An older jar cannot read what a newer one saved, the world would be corrupted on its first start
+    if compare_versions(&target, &config.version).is_lt() && !check {
+        match crate::world::world_version(&config) {
+            Some(world) if !allow_downgrade => {
+                return Err(anyhow!(
+                    "{} is older than the world of '{}', which was saved by Minecraft {}; downgrading corrupts it. \
+                     Restore a backup made on {} instead, or pass --allow-downgrade to do it anyway",
+                    target, server_name, world.name, target
+                ));
+            }
+            Some(_) => println!("⚠️  Downgrading '{}' to {}, the world may lose chunks and items", server_name, target),
+            None => {}
+        }
+    }
+
+    println!("🔍 Checking plugin compatibility for {} -> {}...", config.version, target);
+    let report = check_plugin_compatibility(&config, &target).await?;
+    print_compatibility_matrix(&report, &target);
+
+    let blocking = report
+        .iter()
+        .filter(|entry| !matches!(entry.status, Compatibility::Supported(_)))
+        .count();
+
+    if check {
+        return Ok(());
+    }
+
+    if blocking > 0 && !force {
+        return Err(anyhow!(
+            "{} plugin(s) are not confirmed to support {}; re-run with --force to upgrade anyway",
+            blocking, target
+        ));
+    }
+
+    if !staged && is_server_running(&config).await {
+        return Err(anyhow!("Server '{}' is running, stop it before upgrading", server_name));
+    }
+
+    crate::backup::safety_snapshot(&config, "upgrade").await?;
+
+    if staged {
+        return staged_upgrade(config, &target, experimental, ram).await;
+    }
+
+    apply_upgrade(config, &target, experimental).await
+}
+
+// Reports what an upgrade would change and cost without touching the server
+pub async fn plan_upgrade(server_name: &str, version: &str, experimental: bool) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    let experimental = experimental || config.experimental_builds;
+    let target = resolve_version(&config.server_type, version, experimental).await?;
+    let (config, target) = (&config, target.as_str());
+    if compare_versions(target, &config.version) == Ordering::Equal {
+        crate::output::success("server.already_on_version", &[&server_name, &target]);
+        return Ok(());
+    }
+
+    println!("📋 Upgrade plan for '{}': {} -> {}", config.name, config.version, target);
+    if compare_versions(target, &config.version).is_lt() {
+        println!("⚠️  {} is older than {}, worlds saved by a newer version may not load", target, config.version);
+    }
+
+    match config.server_type.as_str() {
+        "paper" => {
+            let build = crate::api::paper::get_latest_build(target, experimental).await?;
+            println!(" - Server jar: Paper {} build #{}", target, build);
+        }
+        other => println!(" - Server jar: {} {}", other, target)
+    }
+
+    let required_java = |version: &str| {
+        JAVA_REQUIREMENTS
+            .iter()
+            .find(|(since, _)| compare_versions(version, since).is_ge())
+            .map_or(8, |(_, java)| *java)
+    };
+    let java = required_java(target);
+    let installed = crate::server::java_version().await;
+    let installed_major = installed.as_deref().and_then(|version| {
+        // "1.8.0_392" is Java 8, everything newer starts with its major version
+        let version = version.strip_prefix("1.").unwrap_or(version);
+        version.split(['.', '_', '-']).next()?.parse::<u32>().ok()
+    });
+    match installed_major {
+        Some(major) if major < java => {
+            println!(" - Java: ⚠️  {} needs Java {}, but java on PATH is {}", target, java, major);
+        }
+        Some(major) => println!(" - Java: needs {}, java on PATH is {} ✅", java, major),
+        None => println!(" - Java: needs {}, no java found on PATH", java)
+    }
+
+    println!("\n🔌 Plugins:");
+    let report = check_plugin_compatibility(config, target).await?;
+    print_compatibility_matrix(&report, target);
+
+    println!("\n⚙️  Changed settings:");
+    let properties = read_server_properties(&config.path).unwrap_or_default();
+    let changes: Vec<&DefaultChange> = DEFAULT_CHANGES
+        .iter()
+        .filter(|change| compare_versions(change.since, &config.version).is_gt())
+        .filter(|change| compare_versions(change.since, target).is_le())
+        .filter(|change| change.file == "server.properties" || config.server_type == "paper")
+        .collect();
+    if changes.is_empty() {
+        println!("  No known default changes between {} and {}", config.version, target);
+    }
+    for change in changes {
+        let current = match properties.get(change.key) {
+            Some(value) if change.file == "server.properties" => format!(" (set to {} here, kept)", value),
+            _ => String::new()
+        };
+        println!("  • {} {} in {}: {}{}", change.since, change.key, change.file, change.note, current);
+    }
+
+    println!("\n⏱️  Estimated downtime:");
+    let startup_ms = match config.startup_history.as_slice() {
+        [] => None,
+        history => Some(history.iter().map(|record| record.duration_ms).sum::<u64>() / history.len() as u64)
+    };
+    match startup_ms {
+        Some(ms) => println!(" - Restart: about {}, the average startup", crate::server::format_duration_ms(ms)),
+        None => println!(" - Restart: unknown, '{}' has never been started by anvil", config.name)
+    }
+    println!(" - Download: the server jar{}", if config.plugins.is_empty() { "" } else { " and plugin updates" });
+
+    // Chunks are converted lazily on load, only a feature release changes their format enough to matter
+    let feature = |version: &str| version.split('.').take(2).collect::<Vec<_>>().join(".");
+    let world_bytes: u64 = crate::server::all_world_dir_names(config)
+        .iter()
+        .map(|world| get_directory_size(&config.path.join(world)).unwrap_or(0))
+        .sum();
+    if feature(target) != feature(&config.version) && compare_versions(target, &config.version).is_gt() && world_bytes > 0 {
+        let seconds = world_bytes / CONVERSION_BYTES_PER_SEC;
+        println!(
+            " - World conversion: about {} min for {} with `anvil world upgrade`, otherwise chunks convert as they load",
+            seconds.div_ceil(60).max(1),
+            crate::server::format_bytes(world_bytes)
+        );
+    }
+    println!(" - With --staged the server keeps running until the tested copy is swapped in");
+
+    println!("\n💡 Nothing was changed; apply it with: anvil upgrade {} {}", config.name, target);
+    Ok(())
+}
+
+async fn staged_upgrade(config: ServerConfig, target: &str, experimental: bool, ram: u8) -> Result<()> {
+    if config.layout.worlds.is_some() {
+        return Err(anyhow!(
+            "'{}' keeps its worlds outside the server directory, which a staged copy cannot share; \
+             upgrade without --staged",
+            config.name
+        ));
+    }
+
+    let staging_name = format!("{}-staged", config.name);
+    let staging_dir = config.path.with_file_name(&staging_name);
+    if staging_dir.exists() || load_server_config(&staging_name).is_ok() {
+        return Err(anyhow!(
+            "A staged copy '{}' already exists, delete it before starting another staged upgrade",
+            staging_name
+        ));
+    }
+
+    let size = get_directory_size(&config.path)?;
+    crate::disk::ensure_free_space(&staging_dir, size, &format!("cloning '{}'", config.name))?;
+
+    println!("📋 Cloning '{}' into '{}'...", config.name, staging_name);
+    // The health check should boot worlds as they are on disk now, not half-written region files
+    let running = is_server_running(&config).await;
+    if running && let Err(e) = crate::backup::flush_world(&config).await {
+        println!("⚠️  Could not flush the world through RCON, the staged copy may be inconsistent: {}", e);
+    }
+    let cloned = {
+        let (source, destination) = (config.path.clone(), staging_dir.clone());
+        crate::cancel::run_blocking(move |cancel| copy_dir_until(&source, &destination, STAGED_SKIPPED_DIRS, cancel)).await
+    };
+    if running {
+        let _ = crate::rcon::send_command(&config, "save-on").await;
+    }
+
+    let result = match cloned {
+        Ok(()) => tokio::select! {
+            biased;
+            _ = tokio::signal::ctrl_c() => Err(anyhow!("Interrupted")),
+            result = async {
+                let staging = ServerConfig {
+                    name: staging_name.clone(),
+                    path: staging_dir.clone(),
+                    port: free_port()?,
+                    schedules: Vec::new(),
+                    hooks: Default::default(),
+                    ..config.clone()
+                };
+                set_server_property(&staging.path, "server-port", &staging.port.to_string())?;
+                set_server_property(&staging.path, "rcon.port", &free_port()?.to_string())?;
+                set_server_property(&staging.path, "enable-query", "false")?;
+                save_server_config(&staging)?;
+
+                apply_upgrade(staging, target, experimental).await?;
+                // The copy has no secrets of its own, plugins need the original's variables to start
+                let env = crate::secrets::server_secrets(&config.name)?.env;
+                health_check(&load_server_config(&staging_name)?, &env, ram).await
+            } => result
+        },
+        Err(e) => Err(e)
+    };
+
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&staging_dir);
+        let _ = remove_server_config(&staging_name);
+        return Err(anyhow!("Staged upgrade failed, '{}' was left untouched: {}", config.name, e));
+    }
+
+    swap_in(config, &staging_name, ram).await
+}
+
+async fn health_check(staging: &ServerConfig, env: &BTreeMap<String, String>, ram: u8) -> Result<()> {
+    println!("🩺 Booting '{}' on port {} for health checks...", staging.name, staging.port);
+
+    let report = boot_and_stop(
+        &staging.path,
+        &staging.jar_file,
+        ram,
+        &[],
+        env,
+        Duration::from_secs(STAGED_BOOT_TIMEOUT_SECS),
+        |_| {},
+    ).await?;
+
+    if !report.ready {
+        return Err(anyhow!("the upgraded server never reported ready"));
+    }
+    println!("✅ Upgraded server booted in {:.1}s", report.duration_ms as f64 / 1000.0);
+
+    if !report.errors.is_empty() {
+        println!("❌ {} error(s) logged during boot:", report.errors.len());
+        for error in report.errors.iter().take(10) {
+            println!("  • {}", error);
+        }
+        return Err(anyhow!("the upgraded server logged errors while booting"));
+    }
+    println!("✅ No plugin errors during boot");
+
+    Ok(())
+}
+
+async fn swap_in(original: ServerConfig, staging_name: &str, ram: u8) -> Result<()> {
+    let staging = load_server_config(staging_name)?;
+
+    let was_running = is_server_running(&original).await;
+    if was_running {
+        println!("⏹️  Stopping '{}' to swap in the upgraded copy...", original.name);
+        stop_server(&original).await?;
+
+        // Players kept playing on the original during the staged run, their progress replaces the cloned worlds
+        for world in all_world_dir_names(&original) {
+            let staged = staging.path.join(&world);
+            if staged.exists() {
+                fs::remove_dir_all(&staged)?;
+            }
+            copy_dir(&original.path.join(&world), &staged, &[])?;
+        }
+        println!("🌍 Synced the worlds saved since the clone into the upgraded copy");
+    }
+
+    let properties = read_server_properties(&original.path)?;
+    set_server_property(&staging.path, "server-port", &original.port.to_string())?;
+    for key in ["rcon.port", "enable-query"] {
+        if let Some(value) = properties.get(key) {
+            set_server_property(&staging.path, key, value)?;
+        }
+    }
+
+    let retired = original.path.with_file_name(format!("{}-pre-{}", original.name, original.version));
+    if retired.exists() {
+        fs::remove_dir_all(&retired)?;
+    }
+    fs::rename(&original.path, &retired)?;
+    fs::rename(&staging.path, &original.path)?;
+
+    let upgraded = ServerConfig {
+        version: staging.version,
+        jar_file: staging.jar_file,
+        plugins: staging.plugins,
+        disabled_plugins: staging.disabled_plugins,
+        ..original
+    };
+    save_server_config(&upgraded)?;
+    remove_server_config(staging_name)?;
+
+    println!("🔀 Swapped the upgraded copy into '{}'", upgraded.name);
+    println!("📁 Previous version kept at {}", retired.display());
+
+    if was_running {
+        spawn_detached(&upgraded, ram)?;
+        println!("🎮 Restarted '{}' on Minecraft {}", upgraded.name, upgraded.version);
+    }
+
+    Ok(())
+}
+
+pub fn copy_dir(source: &Path, destination: &Path, skipped: &[&str]) -> Result<()> {
+    copy_dir_until(source, destination, skipped, &CancelFlag::default())
+}
+
+// Stops between files once the flag is set, the caller removes what was copied so far
+fn copy_dir_until(source: &Path, destination: &Path, skipped: &[&str], cancel: &CancelFlag) -> Result<()> {
+    fs::create_dir_all(destination)?;
+
+    for entry in fs::read_dir(source)? {
+        cancel.check()?;
+        let entry = entry?;
+        let name = entry.file_name();
+        let target = destination.join(&name);
+
+        // Opening the console FIFO without a writer would block forever
+        if name == crate::console::CONSOLE_PIPE {
+            continue;
+        }
+
+        if entry.path().is_dir() {
+            if skipped.contains(&name.to_string_lossy().as_ref()) {
+                continue;
+            }
+            copy_dir_until(&entry.path(), &target, &[], cancel)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+
+    Ok(())
+}
+
+
+async fn apply_upgrade(mut config: ServerConfig, target: &str, experimental: bool) -> Result<()> {
+    println!("⬆️  Upgrading '{}' to Minecraft {}...", config.name, target);
+
+    let jar_name = match config.server_type.as_str() {
+        "paper" => download_paper_server(target, &config.path, experimental).await?,
+        "vanilla" => download_vanilla_server(target, &config.path).await?,
+        other => return Err(anyhow!("Unsupported server type: {}", other))
+    };
+
+    if jar_name != config.jar_file {
+        let _ = fs::remove_file(config.path.join(&config.jar_file));
+    }
+
+    create_start_script(&config.path, &jar_name, target, config.ip_stack.as_deref())?;
+
+    let previous = std::mem::replace(&mut config.version, target.to_string());
+    config.jar_file = jar_name;
+    save_server_config(&config)?;
+
+    if !config.plugins.is_empty() {
+        println!("📦 Updating plugins for Minecraft {}...", target);
+        crate::plugin::update_plugins(&config.name, None).await?;
+    }
+
+    println!("🎉 Server '{}' upgraded from {} to {}", config.name, previous, target);
+    println!("💡 Run `anvil world upgrade {}` to convert the world ahead of the first start", config.name);
+
+    Ok(())
+}
+
+pub async fn check_plugin_compatibility(config: &ServerConfig, target: &str) -> Result<Vec<PluginCompatibility>> {
+    let mut report = Vec::new();
+
+    for plugin in &config.plugins {
+        let constraint = plugin.constraint.as_deref().map(VersionConstraint::parse).transpose()?;
+        let status = match crate::sources::releases(&plugin.source, &plugin.project_id, target).await {
+            Ok(releases) => {
+                let candidate = releases
+                    .iter()
+                    .find(|release| constraint.as_ref().is_none_or(|c| c.matches(&release.version_number)));
+                match candidate {
+                    Some(version) => Compatibility::Supported(version.version_number.clone()),
+                    None => Compatibility::Unsupported
+                }
+            }
+            Err(_) => Compatibility::Unknown
+        };
+
+        report.push(PluginCompatibility {
+            name: plugin.name.clone(),
+            installed_version: plugin.version_number.clone(),
+            source: plugin.source.clone(),
+            status
+        });
+    }
+
+    for filename in untracked_plugin_jars(config)? {
+        let name = crate::jar::read_plugin_descriptor(&config.path.join("plugins").join(&filename))
+            .ok()
+            .flatten()
+            .map(|descriptor| descriptor.name)
+            .unwrap_or_else(|| crate::plugin::plugin_name_from_filename(&filename));
+        let status = check_hangar(&name, target).await.unwrap_or(Compatibility::Unknown);
+
+        report.push(PluginCompatibility {
+            name,
+            installed_version: filename,
+            source: "hangar".to_string(),
+            status
+        });
+    }
+
+    Ok(report)
+}
+
+async fn check_hangar(name: &str, target: &str) -> Result<Compatibility> {
+    let Some(project) = crate::api::hangar::search_project(name).await? else {
+        return Ok(Compatibility::Unknown);
+    };
+
+    if !project.name.eq_ignore_ascii_case(name) {
+        return Ok(Compatibility::Unknown);
+    }
+
+    let versions = crate::api::hangar::get_compatible_versions(&project.namespace.slug, target).await?;
+    Ok(match versions.first() {
+        Some(version) => Compatibility::Supported(version.name.clone()),
+        None => Compatibility::Unsupported
+    })
+}
+
+fn untracked_plugin_jars(config: &ServerConfig) -> Result<Vec<String>> {
+    let plugins_dir = config.path.join("plugins");
+    if !plugins_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut jars: Vec<String> = fs::read_dir(&plugins_dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|filename| filename.ends_with(".jar"))
+        .filter(|filename| !config.plugins.iter().any(|p| &p.filename == filename))
+        .collect();
+    jars.sort();
+
+    Ok(jars)
+}
+
+pub fn print_compatibility_matrix(report: &[PluginCompatibility], target: &str) {
+    if report.is_empty() {
+        println!("ℹ️  No plugins installed");
+        return;
+    }
+
+    let name_width = report.iter().map(|entry| entry.name.len()).max().unwrap_or(6).max(6);
+    let version_width = report.iter().map(|entry| entry.installed_version.len()).max().unwrap_or(9).max(9);
+
+    println!(
+        "  {:name_width$}  {:version_width$}  {:10}  {}",
+        "PLUGIN", "INSTALLED", "SOURCE", target,
+        name_width = name_width,
+        version_width = version_width
+    );
+
+    for entry in report {
+        let status = match &entry.status {
+            Compatibility::Supported(version) => format!("✅ {}", version),
+            Compatibility::Unsupported => "❌ no compatible release".to_string(),
+            Compatibility::Unknown => "❓ unknown".to_string()
+        };
+
+        println!(
+            "  {:name_width$}  {:version_width$}  {:10}  {}",
+            entry.name, entry.installed_version, entry.source, status,
+            name_width = name_width,
+            version_width = version_width
+        );
+    }
+
+    let supported = report
+        .iter()
+        .filter(|entry| matches!(entry.status, Compatibility::Supported(_)))
+        .count();
+    println!("📊 {}/{} plugins support Minecraft {}", supported, report.len(), target);
+}
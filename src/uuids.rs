@@ -0,0 +1,151 @@
+use crate::api::mojang::hyphenate;
+use crate::server::{is_server_running, level_name, load_server_config, ServerConfig};
+use anyhow::{anyhow, Result};
+use md5::{Digest, Md5};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// Per-player files inside the main world, keyed by UUID
+const PLAYER_DIRS: &[(&str, &[&str])] = &[
+    ("playerdata", &[".dat", ".dat_old"]),
+    ("stats", &[".json"]),
+    ("advancements", &[".json"])
+];
+const PLAYER_LISTS: &[&str] = &["whitelist.json", "ops.json", "banned-players.json"];
+
+#[derive(Deserialize)]
+struct CachedUser {
+    name: String,
+    uuid: String
+}
+
+pub fn offline_uuid(name: &str) -> String {
+    // Same as Java's UUID.nameUUIDFromBytes, which offline-mode servers use
+    let mut bytes: [u8; 16] = Md5::digest(format!("OfflinePlayer:{}", name).as_bytes()).into();
+    bytes[6] = (bytes[6] & 0x0f) | 0x30;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    hyphenate(&hex::encode(bytes))
+}
+
+pub async fn migrate_uuids(server_name: &str, to_online: bool, dry_run: bool) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    if is_server_running(&config).await {
+        return Err(anyhow!("Server '{}' is running, stop it before migrating player UUIDs", server_name));
+    }
+
+    let mapping = build_mapping(&config, to_online).await?;
+    if mapping.is_empty() {
+        println!("ℹ️  No players in usercache.json need a new UUID");
+        return Ok(());
+    }
+
+    println!(
+        "🔀 Remapping {} player(s) of '{}' to {} UUIDs{}",
+        mapping.len(),
+        server_name,
+        if to_online { "online (Mojang)" } else { "offline" },
+        if dry_run { " (dry run)" } else { "" }
+    );
+
+    if !dry_run {
+        crate::backup::safety_snapshot(&config, "uuid migration").await?;
+    }
+
+    let world_dir = config.path.join(level_name(&config.path));
+    let mut moved = 0;
+    for (dir, extensions) in PLAYER_DIRS {
+        for (from, (name, to)) in &mapping {
+            for extension in *extensions {
+                let source = world_dir.join(dir).join(format!("{}{}", from, extension));
+                if !source.exists() {
+                    continue;
+                }
+
+                let target = world_dir.join(dir).join(format!("{}{}", to, extension));
+                if target.exists() {
+                    println!("⚠️  {}/{}{} already exists for {}, left both in place", dir, to, extension, name);
+                    continue;
+                }
+
+                if !dry_run {
+                    fs::rename(&source, &target)?;
+                }
+                moved += 1;
+            }
+        }
+    }
+
+    let mut rewritten = 0;
+    for file in PLAYER_LISTS.iter().copied().chain(["usercache.json"]) {
+        rewritten += rewrite_player_list(&config.path.join(file), &mapping, dry_run)?;
+    }
+
+    for (from, (name, to)) in &mapping {
+        println!("  • {}: {} -> {}", name, from, to);
+    }
+    println!("✅ Moved {} player file(s) and updated {} player list entries", moved, rewritten);
+    println!(
+        "💡 Set online-mode={} before the next start: anvil config set {} server.online-mode {}",
+        to_online, server_name, to_online
+    );
+
+    Ok(())
+}
+
+async fn build_mapping(config: &ServerConfig, to_online: bool) -> Result<HashMap<String, (String, String)>> {
+    let cache_path = config.path.join("usercache.json");
+    let users: Vec<CachedUser> = match fs::read_to_string(&cache_path) {
+        Ok(json) => serde_json::from_str(&json)?,
+        Err(_) => return Err(anyhow!("{} is missing, the server has no known players yet", cache_path.display()))
+    };
+
+    let mut mapping = HashMap::new();
+    for user in users {
+        let offline = offline_uuid(&user.name);
+        let target = if to_online {
+            if user.uuid != offline {
+                continue;
+            }
+            match crate::api::mojang::lookup(&user.name).await? {
+                Some(profile) => profile.hyphenated_id(),
+                None => {
+                    println!("⚠️  {} has no Mojang account, keeping the offline UUID", user.name);
+                    continue;
+                }
+            }
+        } else {
+            if user.uuid == offline {
+                continue;
+            }
+            offline
+        };
+
+        mapping.insert(user.uuid, (user.name, target));
+    }
+
+    Ok(mapping)
+}
+
+fn rewrite_player_list(path: &Path, mapping: &HashMap<String, (String, String)>, dry_run: bool) -> Result<usize> {
+    let Ok(json) = fs::read_to_string(path) else {
+        return Ok(0);
+    };
+    let mut entries: Vec<Value> = serde_json::from_str(&json)?;
+
+    let mut changed = 0;
+    for entry in &mut entries {
+        let Some((_, target)) = entry.get("uuid").and_then(Value::as_str).and_then(|uuid| mapping.get(uuid)) else {
+            continue;
+        };
+        entry["uuid"] = Value::String(target.clone());
+        changed += 1;
+    }
+
+    if changed > 0 && !dry_run {
+        fs::write(path, serde_json::to_string_pretty(&entries)?)?;
+    }
+    Ok(changed)
+}
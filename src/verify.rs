@@ -0,0 +1,264 @@
+use crate::api::vanilla::sha1_hash;
+use crate::plugin::{jar_files, require_plugin_support, DISABLED_DIR};
+use crate::server::{download_vanilla_server, load_server_config, save_server_config, ServerConfig};
+use anyhow::{anyhow, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+pub async fn verify_plugins(server_name: &str) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    require_plugin_support(&config)?;
+
+    let plugins_dir = config.path.join("plugins");
+    let mut jars = jar_files(&plugins_dir)?;
+    jars.extend(jar_files(&plugins_dir.join(DISABLED_DIR))?);
+
+    if jars.is_empty() {
+        println!("ℹ️  No plugin jars to verify on server '{}'", server_name);
+        return Ok(());
+    }
+
+    println!("🔍 Verifying {} plugin jar(s) against Modrinth...", jars.len());
+
+    let mut hashes = Vec::new();
+    for jar in &jars {
+        hashes.push(sha1_hash(jar).await?);
+    }
+
+    let known = crate::api::modrinth::get_versions_by_hash(&hashes).await?;
+
+    let mut verified = 0;
+    let mut unknown = 0;
+    let mut tampered = Vec::new();
+
+    for (jar, hash) in jars.iter().zip(&hashes) {
+        let filename = jar.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let tracked = config.plugins.iter().find(|p| p.filename == filename);
+
+        match (known.get(hash), tracked) {
+            (Some(version), Some(tracked)) if version.id == tracked.version_id => {
+                println!("  ✅ {} - {} {}", filename, tracked.name, tracked.version_number);
+                verified += 1;
+            }
+            (Some(version), Some(tracked)) => {
+                println!(
+                    "  ⚠️  {} - matches Modrinth version {} but anvil recorded {} {}",
+                    filename, version.version_number, tracked.name, tracked.version_number
+                );
+                verified += 1;
+            }
+            (Some(version), None) => {
+                println!(
+                    "  ✅ {} - Modrinth project {} version {}",
+                    filename, version.project_id, version.version_number
+                );
+                verified += 1;
+            }
+            (None, Some(tracked)) if tracked.source != "modrinth" => {
+                println!(
+                    "  ❓ {} - installed from {}, which has no hash lookup",
+                    filename, tracked.source
+                );
+                unknown += 1;
+            }
+            (None, Some(tracked)) => {
+                println!(
+                    "  ❌ {} - hash does not match any Modrinth file, expected {} {}",
+                    filename, tracked.name, tracked.version_number
+                );
+                tampered.push(filename);
+            }
+            (None, None) => {
+                println!("  ❓ {} - unknown to Modrinth (sha1 {})", filename, hash);
+                unknown += 1;
+            }
+        }
+    }
+
+    println!(
+        "📊 {} verified, {} unknown, {} modified",
+        verified, unknown, tampered.len()
+    );
+
+    if unknown > 0 {
+        println!("💡 Unknown jars were not downloaded from Modrinth; check where they came from");
+    }
+
+    if !tampered.is_empty() {
+        return Err(anyhow!(
+            "Jars changed since they were installed: {}",
+            tampered.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+pub enum JarOrigin {
+    Paper { version: String, build: u32 },
+    Vanilla { version: String }
+}
+
+impl JarOrigin {
+    pub fn version(&self) -> &str {
+        match self {
+            JarOrigin::Paper { version, .. } | JarOrigin::Vanilla { version } => version
+        }
+    }
+}
+
+pub fn parse_jar_name(server_type: &str, jar_file: &str) -> Option<JarOrigin> {
+    let stem = jar_file.strip_suffix(".jar")?;
+    match server_type {
+        "paper" => {
+            let (version, build) = stem.strip_prefix("paper-")?.rsplit_once('-')?;
+            Some(JarOrigin::Paper { version: version.to_string(), build: build.parse().ok()? })
+        }
+        "vanilla" => Some(JarOrigin::Vanilla { version: stem.strip_prefix("vanilla-")?.to_string() }),
+        _ => None
+    }
+}
+
+pub async fn verify_server(server_name: &str, repair: bool) -> Result<()> {
+    let mut config = load_server_config(server_name)?;
+    println!("🔍 Verifying files of '{}'...", server_name);
+
+    let mut problems = 0;
+    let mut repaired = 0;
+    let mut unrecorded = 0;
+
+    match verify_server_jar(&config, repair).await {
+        Ok(JarCheck::Intact) => {}
+        Ok(JarCheck::Repaired) => repaired += 1,
+        Ok(JarCheck::Broken) => problems += 1,
+        Err(e) => {
+            println!("  ❌ {} - could not verify: {}", config.jar_file, e);
+            problems += 1;
+        }
+    }
+
+    let plugins_dir = config.path.join("plugins");
+    let mut recorded = false;
+    for index in 0..config.plugins.len() {
+        let installed = config.plugins[index].clone();
+        let dir = if config.disabled_plugins.contains(&installed.filename) {
+            plugins_dir.join(DISABLED_DIR)
+        } else {
+            plugins_dir.clone()
+        };
+        let jar = dir.join(&installed.filename);
+
+        let issue = if !jar.exists() {
+            "missing".to_string()
+        } else {
+            let hash = sha1_hash(&jar).await?;
+            match &installed.sha1 {
+                Some(expected) if *expected == hash => {
+                    println!("  ✅ {} - {} {}", installed.filename, installed.name, installed.version_number);
+                    continue;
+                }
+                Some(_) => "modified since it was installed".to_string(),
+                None if !repair => {
+                    println!("  ❓ {} - no hash was recorded at install time", installed.filename);
+                    unrecorded += 1;
+                    continue;
+                }
+                None => "no hash recorded at install time".to_string()
+            }
+        };
+
+        if !repair {
+            println!("  ❌ {} - {}", installed.filename, issue);
+            problems += 1;
+            continue;
+        }
+
+        println!("  🔧 {} - {}, re-downloading {} {}...", installed.filename, issue, installed.name, installed.version_number);
+        match crate::plugin::redownload_plugin(&config, &installed, &dir).await {
+            Ok(hash) => {
+                if installed.sha1.as_ref().is_some_and(|expected| *expected != hash) {
+                    println!("  ⚠️  {} - the upstream file no longer matches the recorded hash", installed.filename);
+                }
+                config.plugins[index].sha1 = Some(hash);
+                recorded = true;
+                repaired += 1;
+            }
+            Err(e) => {
+                println!("  ❌ {} - repair failed: {}", installed.filename, e);
+                problems += 1;
+            }
+        }
+    }
+
+    if recorded {
+        save_server_config(&config)?;
+    }
+
+    println!("📊 {} repaired, {} problem(s) remaining", repaired, problems);
+    if unrecorded > 0 {
+        println!("💡 Record a trusted hash for unverified plugins with: anvil verify {} --repair", server_name);
+    }
+
+    if problems > 0 {
+        if !repair {
+            println!("💡 Re-download the affected files with: anvil verify {} --repair", server_name);
+        }
+        return Err(anyhow!("{} file(s) of '{}' are missing or modified", problems, server_name));
+    }
+
+    Ok(())
+}
+
+enum JarCheck {
+    Intact,
+    Repaired,
+    Broken
+}
+
+async fn verify_server_jar(config: &ServerConfig, repair: bool) -> Result<JarCheck> {
+    let origin = parse_jar_name(&config.server_type, &config.jar_file)
+        .ok_or_else(|| anyhow!("unrecognised jar name"))?;
+    let jar = config.path.join(&config.jar_file);
+
+    let expected = match &origin {
+        JarOrigin::Paper { version, build } => crate::api::paper::get_build_sha256(version, *build).await?,
+        JarOrigin::Vanilla { version } => crate::api::vanilla::get_server_sha1(version).await?
+    };
+
+    let issue = if !jar.exists() {
+        "missing"
+    } else {
+        let actual = match origin {
+            JarOrigin::Paper { .. } => crate::api::paper::sha256_hash(&jar).await?,
+            JarOrigin::Vanilla { .. } => sha1_hash(&jar).await?
+        };
+        if actual == expected {
+            println!("  ✅ {} - matches upstream", config.jar_file);
+            return Ok(JarCheck::Intact);
+        }
+        "does not match the upstream hash"
+    };
+
+    if !repair {
+        println!("  ❌ {} - {}", config.jar_file, issue);
+        return Ok(JarCheck::Broken);
+    }
+
+    println!("  🔧 {} - {}, re-downloading...", config.jar_file, issue);
+    match origin {
+        JarOrigin::Paper { version, build } => {
+            let pb = ProgressBar::new(0);
+            pb.set_style(
+                ProgressStyle::default_bar()
+                    .template("{spinner:.green} [{elapsed_precise}] {bar:40.cyan/blue} {bytes}/{total_bytes} {msg}")?
+                    .progress_chars("█▉▊▋▌▍▎▏  "),
+            );
+            crate::api::paper::download_paper(&version, &build, &jar, &pb).await?;
+            pb.finish_with_message("Download complete!");
+        }
+        JarOrigin::Vanilla { version } => {
+            download_vanilla_server(&version, &config.path).await?;
+        }
+    }
+
+    Ok(JarCheck::Repaired)
+}
@@ -0,0 +1,231 @@
+use anyhow::{anyhow, Result};
+use std::cmp::Ordering;
+
+pub fn parse_version(version: &str) -> Vec<u32> {
+    version
+        .split(['.', '-'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    compare_parts(&parse_version(a), &parse_version(b))
+}
+
+pub fn is_release(version: &str) -> bool {
+    !version.is_empty() && version.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+pub fn resolve_alias<'a>(requested: &str, available: &'a [String]) -> Vec<&'a String> {
+    // A major.minor shorthand like 1.21 stands for the newest release in that line
+    let shorthand = is_release(requested) && parse_version(requested).len() == 2;
+    if !shorthand {
+        return available.iter().filter(|version| *version == requested).collect();
+    }
+
+    let prefix = format!("{}.", requested);
+    let mut family: Vec<&String> = available
+        .iter()
+        .filter(|version| is_release(version) && (*version == requested || version.starts_with(&prefix)))
+        .collect();
+    family.sort_by(|a, b| compare_versions(b, a));
+    family
+}
+
+pub fn closest_match<'a>(requested: &str, available: &'a [String]) -> Option<&'a String> {
+    available
+        .iter()
+        .map(|version| (edit_distance(requested, version), version))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, version)| version)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, left) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, right) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left != *right);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Operator {
+    Exact,
+    Caret,
+    Tilde,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq
+}
+
+struct Comparator {
+    operator: Operator,
+    version: Vec<u32>
+}
+
+pub struct VersionConstraint {
+    comparators: Vec<Comparator>
+}
+
+impl VersionConstraint {
+    pub fn parse(constraint: &str) -> Result<Self> {
+        let mut comparators = Vec::new();
+
+        for part in constraint.split([',', ' ']).filter(|part| !part.is_empty()) {
+            if part == "*" {
+                continue;
+            }
+
+            let (operator, rest) = [
+                (">=", Operator::GreaterEq),
+                ("<=", Operator::LessEq),
+                (">", Operator::Greater),
+                ("<", Operator::Less),
+                ("^", Operator::Caret),
+                ("~", Operator::Tilde),
+                ("=", Operator::Exact)
+            ]
+            .iter()
+            .find_map(|(prefix, operator)| part.strip_prefix(prefix).map(|rest| (*operator, rest)))
+            .unwrap_or((Operator::Exact, part));
+
+            let mut version = Vec::new();
+            for component in rest.trim_start_matches('v').split('.') {
+                if matches!(component, "x" | "X" | "*") {
+                    break;
+                }
+                version.push(
+                    component
+                        .parse()
+                        .map_err(|_| anyhow!("Invalid version constraint '{}'", constraint))?
+                );
+            }
+
+            if version.is_empty() {
+                return Err(anyhow!("Invalid version constraint '{}'", constraint));
+            }
+
+            comparators.push(Comparator { operator, version });
+        }
+
+        Ok(VersionConstraint { comparators })
+    }
+
+    pub fn matches(&self, version: &str) -> bool {
+        let version = parse_version(version.trim_start_matches(|c: char| !c.is_ascii_digit()));
+        if version.is_empty() {
+            return false;
+        }
+
+        self.comparators.iter().all(|comparator| comparator.matches(&version))
+    }
+}
+
+impl Comparator {
+    fn matches(&self, version: &[u32]) -> bool {
+        let ordering = compare_parts(version, &self.version);
+
+        match self.operator {
+            Operator::Greater => ordering == Ordering::Greater,
+            Operator::GreaterEq => ordering != Ordering::Less,
+            Operator::Less => ordering == Ordering::Less,
+            Operator::LessEq => ordering != Ordering::Greater,
+            Operator::Exact | Operator::Caret | Operator::Tilde => {
+                ordering != Ordering::Less
+                    && compare_parts(version, &self.upper_bound()) == Ordering::Less
+            }
+        }
+    }
+
+    fn upper_bound(&self) -> Vec<u32> {
+        // Bump the last pinned component: ^7.3 -> 8, ~7.3.1 -> 7.4, =7.3 -> 7.4
+        let pinned = match self.operator {
+            Operator::Caret => self.version.iter().position(|part| *part != 0).unwrap_or(self.version.len() - 1),
+            Operator::Tilde => (self.version.len() - 1).min(1),
+            _ => self.version.len() - 1
+        };
+
+        let mut bound = self.version[..=pinned].to_vec();
+        bound[pinned] += 1;
+        bound
+    }
+}
+
+fn compare_parts(a: &[u32], b: &[u32]) -> Ordering {
+    for index in 0..a.len().max(b.len()) {
+        let left = a.get(index).copied().unwrap_or(0);
+        let right = b.get(index).copied().unwrap_or(0);
+        match left.cmp(&right) {
+            Ordering::Equal => continue,
+            other => return other
+        }
+    }
+
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allows(constraint: &str, version: &str) -> bool {
+        VersionConstraint::parse(constraint).unwrap().matches(version)
+    }
+
+    #[test]
+    fn caret_allows_changes_below_the_major_version() {
+        assert!(allows("^7.3", "7.3.0"));
+        assert!(allows("^7.3", "7.9.2"));
+        assert!(!allows("^7.3", "7.2.9"));
+        assert!(!allows("^7.3", "8.0"));
+    }
+
+    #[test]
+    fn caret_on_zero_major_pins_the_first_nonzero_component() {
+        assert!(allows("^0.2.3", "0.2.9"));
+        assert!(!allows("^0.2.3", "0.3.0"));
+        assert!(!allows("^0.2.3", "0.2.2"));
+
+        assert!(allows("^0.0.3", "0.0.3"));
+        assert!(!allows("^0.0.3", "0.0.4"));
+
+        assert!(allows("^0.0", "0.0.7"));
+        assert!(!allows("^0.0", "0.1.0"));
+        assert!(allows("^0", "0.9"));
+        assert!(!allows("^0", "1.0"));
+    }
+
+    #[test]
+    fn tilde_and_exact_pin_the_minor_version() {
+        assert!(allows("~7.3.1", "7.3.5"));
+        assert!(!allows("~7.3.1", "7.4.0"));
+        assert!(allows("7.3", "7.3.4"));
+        assert!(!allows("=7.3", "7.4"));
+        assert!(allows("7.x", "7.12"));
+    }
+
+    #[test]
+    fn comparators_must_all_match() {
+        assert!(allows(">=2.0, <3", "2.5"));
+        assert!(!allows(">=2.0 <3", "3.0"));
+        assert!(allows("*", "1.0"));
+        assert!(allows(">1.0", "v1.0.1"));
+        assert!(!allows(">1.0", "snapshot"));
+    }
+
+    #[test]
+    fn rejects_unparseable_constraints() {
+        assert!(VersionConstraint::parse("^abc").is_err());
+        assert!(VersionConstraint::parse(">=x").is_err());
+    }
+}
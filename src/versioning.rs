@@ -0,0 +1,229 @@
+use crate::properties::PropertiesFile;
+use crate::server::{is_server_running, level_name, load_server_config, WORLD_SUFFIXES};
+use crate::yaml::YamlDocument;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+use tokio::process::Command;
+
+const HISTORY_LIMIT: &str = "20";
+const REDACTED: &str = "********";
+// Versioned files anvil writes secrets into: secret keys of server.properties are found by name,
+// the proxy forwarding secret sits at a fixed path of the Paper config
+const SECRET_FILES: &[(&str, Option<&[&str]>)] = &[
+    ("server.properties", None),
+    ("config/paper-global.yml", Some(&["proxies", "velocity", "secret"])),
+    ("paper.yml", Some(&["settings", "velocity-support", "secret"]))
+];
+
+// Everything is ignored except configuration-looking files; worlds and caches are excluded explicitly, and so is
+// BungeeGuard's config, which is nothing but the token list anvil rewrites from secrets.json on every start
+const GITIGNORE: &str = "# Managed by anvil: only configuration files are versioned
+*
+!*/
+!*.properties
+!*.yml
+!*.yaml
+!*.json
+!*.toml
+!*.conf
+!*.txt
+!start.sh
+!start.bat
+!.gitignore
+usercache.json
+logs/
+cache/
+libraries/
+versions/
+crash-reports/
+plugins/.disabled/
+plugins/.previous/
+plugins/*/web/
+bluemap/
+plugins/BungeeGuard/config.yml
+";
+
+pub fn is_enabled() -> bool {
+    crate::settings::load_settings().is_ok_and(|settings| settings.config_versioning)
+}
+
+pub async fn commit_changes(server_dir: &Path, message: &str) -> Result<Option<String>> {
+    if !server_dir.exists() {
+        return Ok(None);
+    }
+
+    ensure_repository(server_dir).await?;
+
+    git(server_dir, &["add", "-A"]).await?;
+    stage_redacted(server_dir).await?;
+    if git(server_dir, &["diff", "--cached", "--name-only"]).await?.trim().is_empty() {
+        return Ok(None);
+    }
+
+    git(server_dir, &["commit", "-q", "-m", message]).await?;
+    let commit = git(server_dir, &["rev-parse", "--short", "HEAD"]).await?;
+    Ok(Some(commit.trim().to_string()))
+}
+
+async fn ensure_repository(server_dir: &Path) -> Result<()> {
+    if server_dir.join(".git").exists() {
+        return Ok(());
+    }
+
+    git(server_dir, &["init", "-q"]).await?;
+
+    let mut gitignore = GITIGNORE.to_string();
+    let level_name = level_name(server_dir);
+    for suffix in WORLD_SUFFIXES {
+        gitignore.push_str(&format!("/{}{}/\n", level_name, suffix));
+    }
+    fs::write(server_dir.join(".gitignore"), gitignore)?;
+
+    Ok(())
+}
+
+pub async fn record(server_name: &str, message: &str) {
+    let Ok(config) = load_server_config(server_name) else {
+        return;
+    };
+
+    match commit_changes(&config.path, message).await {
+        Ok(Some(commit)) => println!("📝 Committed config changes for '{}' ({})", server_name, commit),
+        Ok(None) => {}
+        Err(e) => println!("⚠️  Could not commit config changes for '{}': {}", server_name, e)
+    }
+}
+
+pub async fn revert_config(server_name: &str, to: Option<&str>) -> Result<()> {
+    let config = load_server_config(server_name)?;
+
+    if !config.path.join(".git").exists() {
+        return Err(anyhow!(
+            "Server '{}' has no config history (enable it with: anvil settings set config-versioning true)",
+            server_name
+        ));
+    }
+
+    let Some(commit) = to else {
+        println!("📜 Config history for '{}':", server_name);
+        let log = git(&config.path, &["log", "-n", HISTORY_LIMIT, "--format=  %h  %ad  %s", "--date=format:%Y-%m-%d %H:%M"]).await?;
+        print!("{}", log);
+        println!("💡 Roll back with: anvil revert {} --to <commit>", server_name);
+        return Ok(());
+    };
+
+    let resolved = git(&config.path, &["rev-parse", "--verify", "--short", &format!("{}^{{commit}}", commit)])
+        .await
+        .map_err(|_| anyhow!("Unknown commit '{}' for server '{}'", commit, server_name))?;
+    let resolved = resolved.trim();
+
+    // Restoring into a new commit keeps the history intact so a revert can itself be reverted
+    let live = live_secrets(&config.path);
+    git(&config.path, &["restore", "--source", resolved, "--staged", "--worktree", "--", "."]).await?;
+    // The history only holds placeholders, and a rollback must not bring back a rotated secret either
+    restore_secrets(&config.path, &live)?;
+
+    match commit_changes(&config.path, &format!("Revert config to {}", resolved)).await? {
+        Some(new_commit) => println!("⏪ Reverted config of '{}' to {} ({})", server_name, resolved, new_commit),
+        None => println!("✅ Config of '{}' already matches {}", server_name, resolved)
+    }
+
+    if is_server_running(&config).await {
+        crate::output::hint("server.restart_to_apply", &[]);
+    }
+
+    Ok(())
+}
+
+// Stages a copy of each secret-bearing file with the secrets replaced, the file on disk keeps them
+async fn stage_redacted(server_dir: &Path) -> Result<()> {
+    for (file, _) in SECRET_FILES {
+        let path = server_dir.join(file);
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let secrets = secret_values(file, &contents);
+        if secrets.is_empty() || git(server_dir, &["ls-files", "--", file]).await?.trim().is_empty() {
+            continue;
+        }
+
+        let masked: Vec<(String, String)> = secrets.into_iter().map(|(key, _)| (key, REDACTED.to_string())).collect();
+        let temp = server_dir.join(".git").join("anvil-redacted");
+        fs::write(&temp, with_values(file, &contents, &masked)?)?;
+        let hash = git(server_dir, &["hash-object", "-w", "--no-filters", &temp.to_string_lossy()]).await;
+        let _ = fs::remove_file(&temp);
+        git(server_dir, &["update-index", "--cacheinfo", &format!("100644,{},{}", hash?.trim(), file)]).await?;
+    }
+    Ok(())
+}
+
+fn live_secrets(server_dir: &Path) -> Vec<(&'static str, Vec<(String, String)>)> {
+    SECRET_FILES
+        .iter()
+        .filter_map(|(file, _)| {
+            let contents = fs::read_to_string(server_dir.join(file)).ok()?;
+            Some((*file, secret_values(file, &contents)))
+        })
+        .collect()
+}
+
+fn restore_secrets(server_dir: &Path, live: &[(&str, Vec<(String, String)>)]) -> Result<()> {
+    for (file, secrets) in live {
+        let path = server_dir.join(file);
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+        fs::write(&path, with_values(file, &contents, secrets)?)?;
+    }
+    Ok(())
+}
+
+fn secret_values(file: &str, contents: &str) -> Vec<(String, String)> {
+    match SECRET_FILES.iter().find(|(name, _)| *name == file).and_then(|(_, path)| *path) {
+        Some(path) => YamlDocument::parse(contents)
+            .get(path)
+            .map(|value| crate::yaml::unquote(&value))
+            .filter(|value| !value.is_empty())
+            .map(|value| vec![(path.join("."), value)])
+            .unwrap_or_default(),
+        None => PropertiesFile::parse(contents)
+            .values()
+            .into_iter()
+            .filter(|(key, value)| crate::secrets::is_secret_name(key) && !value.is_empty())
+            .collect()
+    }
+}
+
+fn with_values(file: &str, contents: &str, values: &[(String, String)]) -> Result<String> {
+    if file.ends_with(".yml") {
+        let mut document = YamlDocument::parse(contents);
+        for (key, value) in values {
+            let path: Vec<&str> = key.split('.').collect();
+            document.set(&path, value)?;
+        }
+        return Ok(document.render());
+    }
+
+    let mut properties = PropertiesFile::parse(contents);
+    for (key, value) in values {
+        properties.set(key, value);
+    }
+    Ok(properties.render())
+}
+
+async fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(["-c", "user.name=anvil", "-c", "user.email=anvil@localhost", "-c", "commit.gpgsign=false"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .map_err(|e| anyhow!("Failed to run git (is it installed?): {}", e))?;
+
+    if !output.status.success() {
+        return Err(anyhow!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,122 @@
+use crate::health::{read_varint, read_varint_slice, write_varint};
+use crate::server::{read_server_properties, ServerConfig};
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const CLIENT_TIMEOUT_SECS: u64 = 5;
+const MAX_PACKET_BYTES: usize = 1024;
+const STARTING_MESSAGE: &str = "§eThe server is starting, reconnect in about a minute";
+const OFFLINE_MESSAGE: &str = "§cThe server is offline";
+
+// Holds the port of a stopped server, answering server list pings meanwhile. With `wake` the first join
+// attempt returns true; otherwise joins are turned away and false is returned once `keep_holding` says so.
+pub async fn hold_port(config: &ServerConfig, wake: bool, keep_holding: impl Fn() -> bool) -> Result<bool> {
+    let listener = TcpListener::bind(("0.0.0.0", config.port))
+        .await
+        .map_err(|e| anyhow!("Could not hold port {} for '{}': {}", config.port, config.name, e))?;
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let Ok((stream, _)) = accepted else {
+                    continue;
+                };
+                let answered = tokio::time::timeout(Duration::from_secs(CLIENT_TIMEOUT_SECS), answer(config, stream, wake)).await;
+                if matches!(answered, Ok(Ok(true))) {
+                    return Ok(true);
+                }
+            }
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {
+                if !keep_holding() {
+                    return Ok(false);
+                }
+            }
+        }
+    }
+}
+
+// Answers one client like a real server would, returns whether it was a login
+async fn answer(config: &ServerConfig, mut stream: TcpStream, wake: bool) -> Result<bool> {
+    let handshake = read_packet(&mut stream).await?;
+    let mut cursor = handshake.as_slice();
+    if read_varint_slice(&mut cursor)? != 0x00 {
+        return Err(anyhow!("not a handshake"));
+    }
+    let protocol = read_varint_slice(&mut cursor)?;
+    let address_length = read_varint_slice(&mut cursor)? as usize;
+    // The address is followed by the two byte port
+    let mut cursor = cursor.get(address_length + 2..).ok_or_else(|| anyhow!("truncated handshake"))?;
+
+    match read_varint_slice(&mut cursor)? {
+        1 => {
+            read_packet(&mut stream).await?;
+            write_packet(&mut stream, 0x00, &string(&status_json(config, protocol, wake))).await?;
+
+            // The client measures latency with a ping it expects echoed back
+            let ping = read_packet(&mut stream).await?;
+            if ping.first() == Some(&0x01) {
+                write_packet(&mut stream, 0x01, &ping[1..]).await?;
+            }
+            Ok(false)
+        }
+        2 => {
+            let text = if wake { STARTING_MESSAGE } else { OFFLINE_MESSAGE };
+            let message = serde_json::json!({ "text": text }).to_string();
+            write_packet(&mut stream, 0x00, &string(&message)).await?;
+            Ok(wake)
+        }
+        _ => Ok(false)
+    }
+}
+
+fn status_json(config: &ServerConfig, protocol: i32, wake: bool) -> String {
+    let properties = read_server_properties(&config.path).unwrap_or_default();
+    let motd = properties.get("motd").map(String::as_str).unwrap_or("A Minecraft Server");
+    let max_players: u64 = properties.get("max-players").and_then(|max| max.parse().ok()).unwrap_or(20);
+
+    let offline_motd = config.proxy.as_ref().and_then(|proxy| proxy.offline_motd.clone());
+    let description = match (offline_motd, wake) {
+        (Some(offline_motd), _) => offline_motd,
+        (None, true) => format!("{}\n§7💤 Sleeping, join to start it", motd),
+        (None, false) => format!("{}\n§7Offline", motd)
+    };
+
+    // Echoing the client's protocol keeps the list from flagging the server as outdated
+    serde_json::json!({
+        "version": { "name": if wake { "Sleeping" } else { "Offline" }, "protocol": protocol },
+        "players": { "max": max_players, "online": 0 },
+        "description": { "text": description }
+    })
+    .to_string()
+}
+
+async fn read_packet(stream: &mut TcpStream) -> Result<Vec<u8>> {
+    let length = read_varint(stream).await? as usize;
+    if length > MAX_PACKET_BYTES {
+        return Err(anyhow!("packet of {} bytes is too large", length));
+    }
+    let mut body = vec![0; length];
+    stream.read_exact(&mut body).await?;
+    Ok(body)
+}
+
+async fn write_packet(stream: &mut TcpStream, id: i32, payload: &[u8]) -> Result<()> {
+    let mut body = Vec::new();
+    write_varint(&mut body, id);
+    body.extend_from_slice(payload);
+
+    let mut packet = Vec::new();
+    write_varint(&mut packet, body.len() as i32);
+    packet.extend_from_slice(&body);
+    stream.write_all(&packet).await?;
+    Ok(())
+}
+
+fn string(value: &str) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_varint(&mut out, value.len() as i32);
+    out.extend_from_slice(value.as_bytes());
+    out
+}
@@ -0,0 +1,130 @@
+use crate::server::{load_server_config, prepare_launch, ServerConfig, PID_FILE};
+use anyhow::{anyhow, Result};
+use std::fs;
+
+pub const WINDOW_MODES: &[&str] = &["attached", "new", "minimized", "hidden"];
+// Generated start.bat files only wait for a key press when nobody is reading the console through anvil
+pub const MANAGED_ENV: &str = "ANVIL_MANAGED";
+
+pub async fn start_in_window(name: &str, ram: u8, mode: &str, allow_downgrade: bool) -> Result<()> {
+    if !WINDOW_MODES.contains(&mode) {
+        return Err(anyhow!("Unknown window mode '{}', expected one of: {}", mode, WINDOW_MODES.join(", ")));
+    }
+    if mode == "attached" {
+        return crate::server::start_server(name, ram, None, allow_downgrade).await;
+    }
+    if !cfg!(windows) {
+        return Err(anyhow!("--window {} is only available on Windows", mode));
+    }
+
+    let config = load_server_config(name)?;
+    crate::output::say("🎮", "server.starting", &[&name]);
+    prepare_launch(&config, allow_downgrade)?;
+
+    let pid = spawn_windowed(&config, crate::memory::fit_heap(ram), mode)?;
+    fs::write(config.path.join(PID_FILE), pid.to_string())?;
+
+    match mode {
+        "hidden" => println!(
+            "✅ '{}' is running in the background, output goes to {}",
+            name,
+            crate::server::console_log_path(&config).display()
+        ),
+        _ => println!("✅ '{}' is running in its own console window", name)
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn spawn_windowed(config: &ServerConfig, ram: u8, mode: &str) -> Result<u32> {
+    use std::collections::BTreeMap;
+    use std::ffi::{OsStr, OsString};
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::process::CommandExt;
+    use std::process::Stdio;
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        CreateProcessW, CREATE_NEW_CONSOLE, CREATE_NO_WINDOW, CREATE_UNICODE_ENVIRONMENT, PROCESS_INFORMATION,
+        STARTF_USESHOWWINDOW, STARTUPINFOW,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{SW_SHOWMINNOACTIVE, SW_SHOWNORMAL};
+
+    if mode == "hidden" {
+        // Without a console the output would be lost, so it goes to the log the daemon also writes
+        fs::create_dir_all(config.path.join("logs"))?;
+        let log = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(crate::server::console_log_path(config))?;
+
+        let child = std::process::Command::new("cmd")
+            .args(["/C", "start.bat", &ram.to_string()])
+            .current_dir(&config.path)
+            .envs(crate::secrets::server_secrets(&config.name)?.env)
+            .env(MANAGED_ENV, "1")
+            .stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log)
+            .creation_flags(CREATE_NO_WINDOW)
+            .spawn()?;
+        return Ok(child.id());
+    }
+
+    let wide = |value: &OsStr| value.encode_wide().chain(Some(0)).collect::<Vec<u16>>();
+    let mut command_line = wide(OsStr::new(&format!("cmd.exe /C start.bat {}", ram)));
+    let directory = wide(config.path.as_os_str());
+
+    // KEY=VALUE entries separated by NULs and closed by an empty one
+    let mut environment: BTreeMap<OsString, OsString> = std::env::vars_os().collect();
+    let env = crate::secrets::server_secrets(&config.name)?.env;
+    environment.extend(env.into_iter().map(|(key, value)| (key.into(), value.into())));
+    let mut block: Vec<u16> = Vec::new();
+    for (key, value) in &environment {
+        block.extend(key.encode_wide());
+        block.push(u16::from(b'='));
+        block.extend(value.encode_wide());
+        block.push(0);
+    }
+    block.push(0);
+
+    // MANAGED_ENV is left unset, so the window stays open with any crash output until a key is pressed
+    let show = if mode == "minimized" { SW_SHOWMINNOACTIVE } else { SW_SHOWNORMAL };
+    let startup = STARTUPINFOW {
+        cb: std::mem::size_of::<STARTUPINFOW>() as u32,
+        dwFlags: STARTF_USESHOWWINDOW,
+        wShowWindow: show as u16,
+        ..Default::default()
+    };
+    let mut process = PROCESS_INFORMATION::default();
+
+    // SAFETY: every pointer refers to a live buffer that outlives the call, strings are NUL-terminated
+    let created = unsafe {
+        CreateProcessW(
+            std::ptr::null(),
+            command_line.as_mut_ptr(),
+            std::ptr::null(),
+            std::ptr::null(),
+            0,
+            CREATE_NEW_CONSOLE | CREATE_UNICODE_ENVIRONMENT,
+            block.as_ptr().cast(),
+            directory.as_ptr(),
+            &startup,
+            &mut process,
+        )
+    };
+    if created == 0 {
+        return Err(anyhow!("Could not open a console window: {}", std::io::Error::last_os_error()));
+    }
+
+    // SAFETY: both handles were just returned by CreateProcessW and are not used afterwards
+    unsafe {
+        CloseHandle(process.hThread);
+        CloseHandle(process.hProcess);
+    }
+    Ok(process.dwProcessId)
+}
+
+#[cfg(not(windows))]
+fn spawn_windowed(_config: &ServerConfig, _ram: u8, mode: &str) -> Result<u32> {
+    Err(anyhow!("--window {} is only available on Windows", mode))
+}
@@ -0,0 +1,491 @@
+use crate::nbt::{NbtFile, Tag};
+use crate::properties::PropertiesFile;
+use crate::rcon::RconClient;
+use crate::plugin::{ensure_plugin, require_plugin_support};
+use crate::server::{
+    all_world_dir_names, boot_and_stop, ensure_running, format_bytes, get_directory_size, is_error_line,
+    is_server_running, level_name, load_server_config, save_server_config, stop_server, world_dir_names, ServerConfig,
+};
+use crate::version::compare_versions;
+use crate::{BorderAction, WorldAction};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const WORLD_UPGRADE_TIMEOUT_SECS: u64 = 6 * 60 * 60;
+const CHUNKY_TASKS_FILE: &str = "plugins/Chunky/tasks.properties";
+const PREPARE_POLL_SECS: u64 = 30;
+const WORLD_TYPES: &[&str] = &["normal", "flat", "large_biomes", "amplified"];
+const ENVIRONMENTS: &[&str] = &["normal", "nether", "the_end"];
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtraWorld {
+    pub name: String,
+    pub world_type: String,
+    pub environment: String,
+    #[serde(default)]
+    pub seed: Option<String>
+}
+
+pub struct WorldVersion {
+    pub data_version: i32,
+    pub name: String
+}
+
+struct Border {
+    size: f64,
+    center_x: f64,
+    center_z: f64
+}
+
+struct PregenArea {
+    center_x: f64,
+    center_z: f64,
+    radius: f64,
+    circle: bool
+}
+
+impl PregenArea {
+    // How far a border of this size reaches past the pregenerated area, if at all
+    fn overshoot(&self, border: &Border) -> f64 {
+        let half = border.size / 2.0;
+        let dx = (border.center_x - self.center_x).abs() + half;
+        let dz = (border.center_z - self.center_z).abs() + half;
+        let reach = if self.circle { dx.hypot(dz) } else { dx.max(dz) };
+        reach - self.radius
+    }
+}
+
+pub async fn handle_world_action(action: WorldAction) -> Result<()> {
+    match action {
+        WorldAction::Upgrade { server, ram, no_backup } => {
+            upgrade_world(&server, ram, no_backup).await?;
+        }
+        WorldAction::Border { server, action } => match action {
+            BorderAction::Get => show_border(&server).await?,
+            BorderAction::Set { size, force } => set_border(&server, size, force).await?
+        },
+        WorldAction::Create { server, world, world_type, environment, seed, ram } => {
+            let world = ExtraWorld {
+                name: world,
+                world_type: world_type.to_lowercase(),
+                environment: environment.to_lowercase(),
+                seed
+            };
+            create_world(&server, world, ram).await?;
+        }
+        WorldAction::Prepare { server, radius, center_x, center_z, ram } => {
+            prepare_world(&server, radius, (center_x, center_z), ram).await?;
+        }
+        WorldAction::MigrateUuids { server, to_online, to_offline: _, dry_run } => {
+            crate::uuids::migrate_uuids(&server, to_online, dry_run).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn upgrade_world(server_name: &str, ram: u8, no_backup: bool) -> Result<()> {
+    let config = load_server_config(server_name)?;
+
+    if is_server_running(&config).await {
+        return Err(anyhow!("Server '{}' is running, stop it before upgrading the world", server_name));
+    }
+
+    // Converted chunks are written next to the old ones before those are dropped
+    for world in all_world_dir_names(&config) {
+        let path = config.path.join(&world);
+        let size = get_directory_size(&path)?;
+        crate::disk::ensure_free_space(&path, size, &format!("upgrading {}", world))?;
+    }
+
+    if !no_backup {
+        println!("💾 Backing up '{}' before converting the world...", server_name);
+        let archive = crate::backup::create_backup(&config).await?;
+        println!("✅ Backup saved to {}", archive.display());
+    }
+
+    println!("🌍 Upgrading worlds of '{}' to Minecraft {}...", server_name, config.version);
+    run_force_upgrade(&config, ram).await?;
+
+    println!("🎉 World upgrade of '{}' finished", server_name);
+    Ok(())
+}
+
+async fn run_force_upgrade(config: &ServerConfig, ram: u8) -> Result<()> {
+    let report = boot_and_stop(
+        &config.path,
+        &config.jar_file,
+        ram,
+        &["--forceUpgrade", "--eraseCache"],
+        &crate::secrets::server_secrets(&config.name)?.env,
+        Duration::from_secs(WORLD_UPGRADE_TIMEOUT_SECS),
+        |line| {
+            let lower = line.to_lowercase();
+            if is_error_line(line) {
+                println!("❌ {}", line);
+            } else if lower.contains("upgrad") || lower.contains("% completed") || lower.contains("optimiz") {
+                println!("📈 {}", line);
+            }
+        },
+    ).await?;
+
+    // The server finishes converting before it binds, so the ready line means we're done
+    if !report.ready {
+        return Err(anyhow!("Server exited before the world upgrade finished"));
+    }
+    if !report.errors.is_empty() {
+        println!(
+            "⚠️  {} error line(s) were logged during conversion, check logs/latest.log",
+            report.errors.len()
+        );
+    }
+
+    Ok(())
+}
+
+async fn create_world(server_name: &str, world: ExtraWorld, ram: u8) -> Result<()> {
+    let config = load_server_config(server_name)?;
+    require_plugin_support(&config)?;
+
+    if !WORLD_TYPES.contains(&world.world_type.as_str()) {
+        return Err(anyhow!("Unknown world type '{}', expected one of: {}", world.world_type, WORLD_TYPES.join(", ")));
+    }
+    if !ENVIRONMENTS.contains(&world.environment.as_str()) {
+        return Err(anyhow!(
+            "Unknown environment '{}', expected one of: {}",
+            world.environment,
+            ENVIRONMENTS.join(", ")
+        ));
+    }
+    if world.name.is_empty() || !world.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(anyhow!("World names may only contain letters, digits, '-' and '_'"));
+    }
+    if world_dir_names(&config.path).contains(&world.name) || config.worlds.iter().any(|w| w.name == world.name) {
+        return Err(anyhow!("Server '{}' already has a world named '{}'", server_name, world.name));
+    }
+
+    println!("🌍 Creating world '{}' on '{}'", world.name, server_name);
+
+    let installed = ensure_plugin(server_name, "Multiverse-Core").await?;
+    let config = load_server_config(server_name)?;
+    let was_running = is_server_running(&config).await;
+
+    if installed && was_running {
+        println!("🔄 Restarting server so Multiverse-Core gets loaded...");
+        stop_server(&config).await?;
+    }
+
+    let (mut client, _) = ensure_running(&config, ram).await?;
+
+    let mut command = format!("mv create {} {} -t {}", world.name, world.environment, world.world_type.to_uppercase());
+    if let Some(seed) = &world.seed {
+        command.push_str(&format!(" -s {}", seed));
+    }
+    println!("⏳ Generating '{}', this can take a while...", world.name);
+    let response = client.command(&command).await?;
+
+    // Multiverse answers in free text that differs between versions, the level.dat on disk is what counts
+    let level_dat = config.path.join(&world.name).join("level.dat");
+    if !level_dat.exists() {
+        let _ = client.command("save-all flush").await;
+    }
+    let failed = !level_dat.exists();
+
+    if !was_running {
+        crate::output::say("🛑", "server.stopping_after_setup", &[]);
+        stop_server(&config).await?;
+    }
+
+    if failed {
+        let response = response.trim();
+        let response = if response.is_empty() { "no level.dat was written" } else { response };
+        return Err(anyhow!("Multiverse could not create '{}': {}", world.name, response));
+    }
+
+    // The config repository only tracks configuration, so the new world stays out of it like the main ones
+    let gitignore = config.path.join(".gitignore");
+    if gitignore.exists() {
+        let mut contents = fs::read_to_string(&gitignore)?;
+        contents.push_str(&format!("/{}/\n", world.name));
+        fs::write(&gitignore, contents)?;
+    }
+
+    let mut config = load_server_config(server_name)?;
+    println!("✅ World '{}' created ({}, {})", world.name, world.environment, world.world_type);
+    println!("💡 Players can join it with /mvtp {}", world.name);
+    config.worlds.push(world);
+    save_server_config(&config)?;
+
+    Ok(())
+}
+
+// Sets the vanilla border and pregenerates everything inside it with Chunky, so players never wait on generation
+async fn prepare_world(server_name: &str, radius: u32, center: (i32, i32), ram: u8) -> Result<()> {
+    if radius == 0 || radius > 29_999_984 {
+        return Err(anyhow!("The radius must be between 1 and 29999984 blocks"));
+    }
+    let config = load_server_config(server_name)?;
+    require_plugin_support(&config)?;
+
+    let installed = ensure_plugin(server_name, "Chunky").await?;
+    let config = load_server_config(server_name)?;
+    let was_running = is_server_running(&config).await;
+    if installed && was_running {
+        println!("🔄 Restarting server so Chunky gets loaded...");
+        stop_server(&config).await?;
+    }
+
+    let world = level_name(&config.path);
+    let world_dir = config.path.join(&world);
+    let size_before = get_directory_size(&world_dir).unwrap_or(0);
+
+    let (mut client, _) = ensure_running(&config, ram).await?;
+    let result = pregenerate(&mut client, &world, radius, center).await;
+
+    if !was_running {
+        println!("🛑 Stopping server after pregeneration...");
+        stop_server(&config).await?;
+    }
+    result?;
+
+    let size_after = get_directory_size(&world_dir).unwrap_or(0);
+    println!(
+        "✅ '{}' is pregenerated within a {} block border around {}, {}",
+        world, radius * 2, center.0, center.1
+    );
+    println!(
+        "💾 The world grew by {} to {}",
+        format_bytes(size_after.saturating_sub(size_before)),
+        format_bytes(size_after)
+    );
+    Ok(())
+}
+
+async fn pregenerate(client: &mut RconClient, world: &str, radius: u32, (x, z): (i32, i32)) -> Result<()> {
+    // Chunky reads its area straight from the border, so both always agree
+    for command in [
+        format!("worldborder center {} {}", x, z),
+        format!("worldborder set {}", radius * 2),
+        format!("chunky world {}", world),
+        "chunky worldborder".to_string(),
+        "chunky start".to_string()
+    ] {
+        client.command(&command).await?;
+    }
+    println!("🧱 Set the world border to {} blocks around {}, {}", radius * 2, x, z);
+    println!("⛏️  Pregenerating '{}', this can take hours for large radii...", world);
+
+    let progress = async {
+        loop {
+            tokio::time::sleep(Duration::from_secs(PREPARE_POLL_SECS)).await;
+            let progress = crate::bench::strip_colors(&client.command("chunky progress").await?);
+            // "Task running for world. Processed: 1234 chunks (12.34%), ETA: 0:10:00, Rate: 95.2 cps, ..."
+            let Some((_, processed)) = progress.split_once("Processed: ") else {
+                return Ok::<_, anyhow::Error>(());
+            };
+            println!("📈 {}", processed.lines().next().unwrap_or(processed).trim());
+        }
+    };
+
+    tokio::select! {
+        result = progress => result,
+        _ = tokio::signal::ctrl_c() => {
+            client.command("chunky pause").await?;
+            Err(anyhow!("Interrupted, Chunky saved its progress; resume it with `chunky continue` while the server runs"))
+        }
+    }
+}
+
+async fn show_border(server_name: &str) -> Result<()> {
+    let config = load_server_config(server_name)?;
+
+    if is_server_running(&config).await {
+        let response = crate::rcon::send_command(&config, "worldborder get").await?;
+        println!("🧱 {}", response.trim());
+    } else {
+        let border = read_border(&config)?;
+        println!("🧱 World border of '{}':", server_name);
+        println!(" - Size: {} blocks", border.size);
+        println!(" - Center: {}, {}", border.center_x, border.center_z);
+    }
+
+    if let Some(area) = pregen_area(&config) {
+        println!(
+            " - Pregenerated: radius {} around {}, {}{}",
+            area.radius, area.center_x, area.center_z,
+            if area.circle { " (circle)" } else { "" }
+        );
+    }
+
+    Ok(())
+}
+
+async fn set_border(server_name: &str, size: f64, force: bool) -> Result<()> {
+    let config = load_server_config(server_name)?;
+
+    if !(1.0..=59_999_968.0).contains(&size) {
+        return Err(anyhow!("Border size must be between 1 and 59999968 blocks"));
+    }
+
+    let running = is_server_running(&config).await;
+    let center = read_border(&config).ok();
+    let border = Border {
+        size,
+        center_x: center.as_ref().map_or(0.0, |border| border.center_x),
+        center_z: center.as_ref().map_or(0.0, |border| border.center_z)
+    };
+
+    if let Some(area) = pregen_area(&config) {
+        let overshoot = area.overshoot(&border);
+        if overshoot > 0.0 && !force {
+            return Err(anyhow!(
+                "A {} block border reaches {:.0} blocks past the pregenerated radius of {}; \
+                 extend the pregeneration with `chunky radius` or re-run with --force",
+                size, overshoot, area.radius
+            ));
+        }
+        if overshoot > 0.0 {
+            println!("⚠️  The border reaches {:.0} blocks into chunks that were never pregenerated", overshoot);
+        }
+    }
+
+    if running {
+        crate::rcon::send_command(&config, &format!("worldborder set {}", size)).await?;
+        println!("🧱 Set the world border of '{}' to {} blocks", server_name, size);
+    } else {
+        write_border_size(&config, size)?;
+        println!("🧱 Set the world border of '{}' to {} blocks in level.dat", server_name, size);
+    }
+
+    Ok(())
+}
+
+// Refuses to launch a jar older than the format the world was last saved in, which silently corrupts it
+pub fn check_world_version(config: &ServerConfig, allow_downgrade: bool) -> Result<()> {
+    let Some(world) = world_version(config) else {
+        return Ok(());
+    };
+    let (running, saved) = match jar_version(&config.path.join(&config.jar_file)) {
+        Some(jar) if jar.data_version >= world.data_version => return Ok(()),
+        Some(jar) => (
+            format!("{} (data version {})", jar.name, jar.data_version),
+            format!("{} (data version {})", world.name, world.data_version)
+        ),
+        // Jars older than 1.14 or repackaged ones have no version.json, the configured version has to do
+        None if world.name.starts_with(|c: char| c.is_ascii_digit())
+            && compare_versions(&config.version, &world.name).is_lt() =>
+        {
+            (config.version.clone(), world.name.clone())
+        }
+        None => return Ok(())
+    };
+
+    if allow_downgrade {
+        println!("⚠️  Starting {} on a world saved by {}; chunks and items it does not know may be lost", running, saved);
+        return Ok(());
+    }
+    Err(anyhow!(
+        "The world of '{}' was last saved by Minecraft {}, but its jar runs {}. \
+         Older servers drop or corrupt what they cannot read; restore a backup from before the upgrade or run \
+         `anvil upgrade {} {}`, or pass --allow-downgrade to start anyway",
+        config.name, saved, running, config.name, world.name
+    ))
+}
+
+pub fn world_version(config: &ServerConfig) -> Option<WorldVersion> {
+    let file = NbtFile::load_gzip(&level_dat(config)).ok()?;
+    let data = file.root.get("Data")?;
+    let data_version = data.get("DataVersion")?.as_int()?;
+    let name = data
+        .get("Version")
+        .and_then(|version| version.get("Name"))
+        .and_then(Tag::as_str)
+        .map_or_else(|| format!("data version {}", data_version), str::to_string);
+    Some(WorldVersion { data_version, name })
+}
+
+// Server jars since 1.14 carry a version.json, bundlers and Paperclip may only have it in a nested jar
+pub fn jar_version(jar_path: &Path) -> Option<WorldVersion> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(jar_path).ok()?).ok()?;
+    if let Some(version) = read_version_json(&mut archive) {
+        return Some(version);
+    }
+
+    let nested: Vec<String> = archive
+        .file_names()
+        .filter(|name| name.starts_with("META-INF/versions/") && name.ends_with(".jar"))
+        .map(str::to_string)
+        .collect();
+    nested.iter().find_map(|name| {
+        let mut bytes = Vec::new();
+        archive.by_name(name).ok()?.read_to_end(&mut bytes).ok()?;
+        read_version_json(&mut zip::ZipArchive::new(std::io::Cursor::new(bytes)).ok()?)
+    })
+}
+
+fn read_version_json<R: Read + std::io::Seek>(archive: &mut zip::ZipArchive<R>) -> Option<WorldVersion> {
+    let mut contents = String::new();
+    archive.by_name("version.json").ok()?.read_to_string(&mut contents).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&contents).ok()?;
+    Some(WorldVersion {
+        data_version: json["world_version"].as_i64()? as i32,
+        name: json["name"].as_str().or(json["id"].as_str())?.to_string()
+    })
+}
+
+fn level_dat(config: &ServerConfig) -> PathBuf {
+    config.path.join(level_name(&config.path)).join("level.dat")
+}
+
+fn read_border(config: &ServerConfig) -> Result<Border> {
+    let path = level_dat(config);
+    if !path.exists() {
+        return Err(anyhow!("Server '{}' has no world yet, start it once first", config.name));
+    }
+
+    let file = NbtFile::load_gzip(&path)?;
+    let data = file.root.get("Data").ok_or_else(|| anyhow!("{} has no Data compound", path.display()))?;
+    let field = |name: &str| data.get(name).and_then(Tag::as_double);
+
+    let size = field("BorderSize").ok_or_else(|| {
+        anyhow!("{} does not store the border in a known format, start the server and retry", path.display())
+    })?;
+
+    Ok(Border {
+        size,
+        center_x: field("BorderCenterX").unwrap_or(0.0),
+        center_z: field("BorderCenterZ").unwrap_or(0.0)
+    })
+}
+
+fn write_border_size(config: &ServerConfig, size: f64) -> Result<()> {
+    // Fails early when the world is missing or uses an unknown layout
+    read_border(config)?;
+
+    let path = level_dat(config);
+    let mut file = NbtFile::load_gzip(&path)?;
+    if let Some(data) = file.root.get_mut("Data") {
+        data.set("BorderSize", Tag::Double(size));
+        data.set("BorderSizeLerpTarget", Tag::Double(size));
+        data.set("BorderSizeLerpTime", Tag::Long(0));
+    }
+
+    std::fs::copy(&path, path.with_file_name("level.dat_old"))?;
+    file.save_gzip(&path)
+}
+
+fn pregen_area(config: &ServerConfig) -> Option<PregenArea> {
+    let tasks = PropertiesFile::load(&config.path.join(CHUNKY_TASKS_FILE)).ok()?;
+    let world = level_name(&config.path);
+    let value = |key: &str| tasks.get(&format!("{}.{}", world, key));
+
+    Some(PregenArea {
+        center_x: value("centerX").and_then(|v| v.parse().ok()).unwrap_or(0.0),
+        center_z: value("centerZ").and_then(|v| v.parse().ok()).unwrap_or(0.0),
+        radius: value("radius")?.parse().ok()?,
+        circle: value("shape").is_some_and(|shape| shape == "circle")
+    })
+}
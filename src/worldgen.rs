@@ -0,0 +1,196 @@
+use crate::version::compare_versions;
+use anyhow::{anyhow, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+const LEVEL_TYPES: &[&str] = &["normal", "flat", "large_biomes", "amplified", "single_biome"];
+const NAMED_PRESETS: &[(&str, &str)] = &[
+    ("classic", "minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block;minecraft:plains"),
+    ("void", "minecraft:air;minecraft:the_void"),
+    ("redstone_ready", "minecraft:bedrock,3*minecraft:stone,52*minecraft:sandstone;minecraft:desert")
+];
+// Well known world generation datapacks by their Modrinth slug
+const DATAPACK_PRESETS: &[(&str, &str)] = &[
+    ("terralith", "overworld terrain and biomes"),
+    ("tectonic", "larger mountains, valleys and rivers"),
+    ("incendium", "nether biomes and structures"),
+    ("nullscape", "end islands and biomes"),
+    ("structory", "extra overworld structures")
+];
+
+#[derive(Default)]
+pub struct WorldGenOptions {
+    pub level_type: Option<String>,
+    pub generator_settings: Option<String>,
+    pub superflat_preset: Option<String>,
+    pub biome: Option<String>,
+    pub datapacks: Vec<String>
+}
+
+impl WorldGenOptions {
+    // Checks everything that can be checked before the server is downloaded
+    pub fn validate(&self, version: &str) -> Result<()> {
+        self.properties(version)?;
+        // World generation datapacks only exist since worldgen became data driven in 1.16
+        if !self.datapacks.is_empty() && compare_versions(version, "1.16").is_lt() {
+            return Err(anyhow!("World generation datapacks need Minecraft 1.16 or newer"));
+        }
+        Ok(())
+    }
+
+    // The server.properties entries for these options in the format the given version reads
+    pub fn properties(&self, version: &str) -> Result<Vec<(&'static str, String)>> {
+        let level_type = match (&self.level_type, &self.superflat_preset) {
+            (Some(level_type), _) => level_type.to_lowercase(),
+            (None, Some(_)) => "flat".to_string(),
+            (None, None) if self.generator_settings.is_some() || self.biome.is_some() => {
+                return Err(anyhow!("--generator-settings and --biome need a --level-type"));
+            }
+            (None, None) => return Ok(Vec::new())
+        };
+
+        if !LEVEL_TYPES.contains(&level_type.as_str()) {
+            return Err(anyhow!("Unknown level type '{}', expected one of: {}", level_type, LEVEL_TYPES.join(", ")));
+        }
+        if self.superflat_preset.is_some() && level_type != "flat" {
+            return Err(anyhow!("--superflat-preset only applies to flat worlds"));
+        }
+        if self.biome.is_some() && level_type != "single_biome" {
+            return Err(anyhow!("--biome only applies to single_biome worlds, flat presets name their biome"));
+        }
+        // Before 1.19 a single biome world could only be made with a datapack
+        if level_type == "single_biome" && compare_versions(version, "1.19").is_lt() {
+            return Err(anyhow!("single_biome worlds need Minecraft 1.19 or newer"));
+        }
+
+        let mut properties = vec![("level-type", level_type_value(&level_type, version))];
+
+        let settings = match (&self.generator_settings, &self.superflat_preset, &self.biome) {
+            (Some(settings), _, _) => Some(validate_settings(settings, version)?),
+            (None, Some(preset), _) => Some(flat_settings(preset, version)?),
+            (None, None, Some(biome)) => Some(json!({ "biome": namespaced(biome) }).to_string()),
+            (None, None, None) if level_type == "single_biome" => {
+                return Err(anyhow!("single_biome worlds need a --biome, e.g. --biome desert"));
+            }
+            (None, None, None) => None
+        };
+        if let Some(settings) = settings {
+            properties.push(("generator-settings", settings));
+        }
+        Ok(properties)
+    }
+}
+
+// Places the datapacks in the main world before its first boot, so every chunk is generated with them
+pub async fn install_datapacks(world_dir: &Path, version: &str, datapacks: &[String]) -> Result<()> {
+    if datapacks.is_empty() {
+        return Ok(());
+    }
+    let datapacks_dir = world_dir.join("datapacks");
+    fs::create_dir_all(&datapacks_dir)?;
+
+    for name in datapacks {
+        let slug = name.to_lowercase();
+        let versions = crate::api::modrinth::get_datapack_versions(&slug, version).await.map_err(|_| {
+            let presets: Vec<&str> = DATAPACK_PRESETS.iter().map(|(name, _)| *name).collect();
+            anyhow!("Modrinth has no datapack '{}', try one of: {}", name, presets.join(", "))
+        })?;
+        // Versions come newest first
+        let file = versions
+            .iter()
+            .flat_map(|release| release.files.iter())
+            .find(|file| file.primary || file.filename.ends_with(".zip"))
+            .ok_or_else(|| anyhow!("'{}' has no datapack release for Minecraft {}", name, version))?;
+
+        let pb = ProgressBar::new(0);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{spinner:.green} [{elapsed_precise}] {bar:40.cyan/blue} {bytes}/{total_bytes} {msg}")?
+                .progress_chars("█▉▊▋▌▍▎▏  "),
+        );
+        pb.set_message(format!("Downloading {}", file.filename));
+        crate::api::modrinth::download_datapack(file, &datapacks_dir, &pb).await?;
+        pb.finish_with_message("Download complete!");
+
+        let description = DATAPACK_PRESETS
+            .iter()
+            .find(|(preset, _)| *preset == slug)
+            .map(|(_, description)| format!(" ({})", description))
+            .unwrap_or_default();
+        println!("🧩 Added datapack {}{}", file.filename, description);
+    }
+    Ok(())
+}
+
+fn level_type_value(level_type: &str, version: &str) -> String {
+    if compare_versions(version, "1.19").is_ge() {
+        let id = if level_type == "single_biome" { "single_biome_surface" } else { level_type };
+        return format!("minecraft:{}", id);
+    }
+
+    // Before 1.19 the values were bare names and large biomes had no underscore
+    match level_type {
+        "normal" => "default",
+        "large_biomes" => "largebiomes",
+        other => other
+    }
+    .to_string()
+}
+
+fn validate_settings(settings: &str, version: &str) -> Result<String> {
+    // Before 1.13 flat worlds used the preset string directly, there was no JSON
+    if compare_versions(version, "1.13").is_lt() {
+        return Ok(settings.to_string());
+    }
+    let parsed: Value = serde_json::from_str(settings)
+        .map_err(|e| anyhow!("--generator-settings must be JSON on {}: {}", version, e))?;
+    Ok(parsed.to_string())
+}
+
+// Turns a superflat preset string like "minecraft:bedrock,2*minecraft:dirt;minecraft:plains" into generator settings
+fn flat_settings(preset: &str, version: &str) -> Result<String> {
+    let preset = NAMED_PRESETS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(preset))
+        .map(|(_, preset)| *preset)
+        .unwrap_or(preset);
+
+    if compare_versions(version, "1.13").is_lt() {
+        let preset = if preset.starts_with("3;") { preset.to_string() } else { format!("3;{}", preset) };
+        return Ok(preset.replace("minecraft:grass_block", "minecraft:grass"));
+    }
+
+    // Presets copied from the game may still carry the old format version in front
+    let preset = preset.strip_prefix("3;").unwrap_or(preset);
+    let mut sections = preset.split(';');
+    let layers = sections.next().unwrap_or_default();
+    let biome = sections.next().filter(|biome| !biome.is_empty()).unwrap_or("minecraft:plains");
+
+    let mut parsed = Vec::new();
+    for layer in layers.split(',').map(str::trim).filter(|layer| !layer.is_empty()) {
+        let (height, block) = match layer.split_once('*') {
+            Some((height, block)) => (
+                height.trim().parse::<u32>().map_err(|_| anyhow!("Invalid layer height in '{}'", layer))?,
+                block.trim()
+            ),
+            None => (1, layer)
+        };
+        parsed.push(json!({ "block": namespaced(block), "height": height }));
+    }
+    if parsed.is_empty() {
+        let names: Vec<&str> = NAMED_PRESETS.iter().map(|(name, _)| *name).collect();
+        return Err(anyhow!(
+            "Superflat preset '{}' has no layers, use a preset string or one of: {}",
+            preset,
+            names.join(", ")
+        ));
+    }
+
+    Ok(json!({ "layers": parsed, "biome": namespaced(biome) }).to_string())
+}
+
+fn namespaced(id: &str) -> String {
+    if id.contains(':') { id.to_string() } else { format!("minecraft:{}", id) }
+}
@@ -0,0 +1,443 @@
+use anyhow::{anyhow, Result};
+
+pub struct YamlDocument {
+    lines: Vec<String>
+}
+
+struct Entry {
+    line: usize,
+    indent: usize,
+    end: usize
+}
+
+impl YamlDocument {
+    pub fn parse(contents: &str) -> Self {
+        YamlDocument {
+            lines: contents.lines().map(str::to_string).collect()
+        }
+    }
+
+    pub fn render(&self) -> String {
+        self.lines.join("\n") + "\n"
+    }
+
+    pub fn get(&self, path: &[&str]) -> Option<String> {
+        let entry = self.find(path)?;
+        let (_, value, _) = split_line(&self.lines[entry.line]);
+        if value.is_empty() {
+            return None;
+        }
+        Some(value.to_string())
+    }
+
+    // Reads a flow list, a block list or a single scalar as a list of unquoted values
+    pub fn get_list(&self, path: &[&str]) -> Vec<String> {
+        let Some(entry) = self.find(path) else {
+            return Vec::new();
+        };
+
+        let (_, value, _) = split_line(&self.lines[entry.line]);
+        if let Some(items) = value.strip_prefix('[').and_then(|value| value.strip_suffix(']')) {
+            return items.split(',').map(unquote).filter(|item| !item.is_empty()).collect();
+        }
+        if !value.is_empty() {
+            return vec![unquote(value)];
+        }
+
+        let mut items = Vec::new();
+        for line in &self.lines[entry.line + 1..] {
+            let Some(indent) = content_indent(line) else {
+                continue;
+            };
+            let trimmed = line.trim_start();
+            if indent >= entry.indent && (trimmed == "-" || trimmed.starts_with("- ")) {
+                let item = &trimmed[1..];
+                let item = &item[..find_comment(item).unwrap_or(item.len())];
+                if !item.trim().is_empty() {
+                    items.push(unquote(item));
+                }
+            } else if indent <= entry.indent {
+                break;
+            }
+        }
+        items
+    }
+
+    // Keys directly under a section, in file order
+    pub fn keys(&self, path: &[&str]) -> Vec<String> {
+        let Some(entry) = self.find(path) else {
+            return Vec::new();
+        };
+        let Some(indent) = self.child_indent(entry.line + 1, entry.end, Some(entry.indent)) else {
+            return Vec::new();
+        };
+
+        self.lines[entry.line + 1..entry.end]
+            .iter()
+            .filter(|line| content_indent(line) == Some(indent) && !line.trim_start().starts_with('-'))
+            .map(|line| unquote(split_line(line).0))
+            .collect()
+    }
+
+    pub fn set(&mut self, path: &[&str], value: &str) -> Result<Option<String>> {
+        if let Some(entry) = self.find(path) {
+            let line = &self.lines[entry.line];
+            let (key, old_value, comment) = split_line(line);
+
+            if old_value.is_empty() && entry.end > entry.line + 1 {
+                return Err(anyhow!("'{}' is a section, not a value", path.join(".")));
+            }
+
+            let value = coerce_value(old_value, value)?;
+            let old_value = old_value.to_string();
+            self.lines[entry.line] = format!("{}{}: {}{}", " ".repeat(entry.indent), key, value, comment);
+            return Ok(Some(old_value));
+        }
+
+        self.insert(path, &coerce_value("", value)?);
+        Ok(None)
+    }
+
+    // Replaces the value at the path with a block list, creating the key when missing
+    pub fn set_list(&mut self, path: &[&str], values: &[String]) -> Result<()> {
+        if self.find(path).is_none() {
+            self.insert(path, "[]");
+        }
+        let Some(entry) = self.find(path) else {
+            return Err(anyhow!("Could not create '{}'", path.join(".")));
+        };
+
+        // List items may sit at the same indent as their key, which find() does not count as children
+        let mut end = entry.end;
+        while end < self.lines.len()
+            && content_indent(&self.lines[end]) == Some(entry.indent)
+            && self.lines[end].trim_start().starts_with('-')
+        {
+            end += 1;
+        }
+
+        let (key, _, _) = split_line(&self.lines[entry.line]);
+        let mut lines = vec![format!("{}{}:", " ".repeat(entry.indent), key)];
+        for value in values {
+            lines.push(format!("{}- {}", " ".repeat(entry.indent + 2), quote_string(value)));
+        }
+        self.lines.splice(entry.line..end, lines);
+
+        Ok(())
+    }
+
+    pub fn flatten(&self) -> Vec<(String, String)> {
+        let mut values = Vec::new();
+        let mut stack: Vec<(usize, String)> = Vec::new();
+
+        for line in &self.lines {
+            let Some(indent) = content_indent(line) else {
+                continue;
+            };
+            if line.trim_start().starts_with('-') {
+                continue;
+            }
+
+            while stack.last().is_some_and(|(parent_indent, _)| *parent_indent >= indent) {
+                stack.pop();
+            }
+
+            let (key, value, _) = split_line(line);
+            let key = unquote(key);
+
+            if value.is_empty() {
+                stack.push((indent, key));
+            } else {
+                let mut path: Vec<&str> = stack.iter().map(|(_, key)| key.as_str()).collect();
+                path.push(&key);
+                values.push((path.join("."), value.to_string()));
+            }
+        }
+
+        values
+    }
+
+    fn find(&self, path: &[&str]) -> Option<Entry> {
+        let mut start = 0;
+        let mut end = self.lines.len();
+        let mut parent_indent: Option<usize> = None;
+        let mut found = None;
+
+        for segment in path {
+            let entry = self.find_child(start, end, parent_indent, segment)?;
+            start = entry.line + 1;
+            end = entry.end;
+            parent_indent = Some(entry.indent);
+            found = Some(entry);
+        }
+
+        found
+    }
+
+    fn find_child(&self, start: usize, end: usize, parent_indent: Option<usize>, key: &str) -> Option<Entry> {
+        let child_indent = self.child_indent(start, end, parent_indent)?;
+
+        for index in start..end {
+            let line = &self.lines[index];
+            if content_indent(line) != Some(child_indent) {
+                continue;
+            }
+
+            let (line_key, _, _) = split_line(line);
+            if unquote(line_key) == key {
+                return Some(Entry {
+                    line: index,
+                    indent: child_indent,
+                    end: self.block_end(index + 1, end, child_indent)
+                });
+            }
+        }
+
+        None
+    }
+
+    fn child_indent(&self, start: usize, end: usize, parent_indent: Option<usize>) -> Option<usize> {
+        self.lines[start..end]
+            .iter()
+            .filter_map(|line| content_indent(line))
+            .find(|indent| parent_indent.is_none_or(|parent| *indent > parent))
+    }
+
+    fn block_end(&self, start: usize, end: usize, indent: usize) -> usize {
+        (start..end)
+            .find(|index| content_indent(&self.lines[*index]).is_some_and(|i| i <= indent))
+            .unwrap_or(end)
+    }
+
+    fn insert(&mut self, path: &[&str], value: &str) {
+        let mut start = 0;
+        let mut end = self.lines.len();
+        let mut parent_indent: Option<usize> = None;
+        let mut depth = 0;
+
+        while depth < path.len() {
+            match self.find_child(start, end, parent_indent, path[depth]) {
+                Some(entry) => {
+                    start = entry.line + 1;
+                    end = entry.end;
+                    parent_indent = Some(entry.indent);
+                    depth += 1;
+                }
+                None => break
+            }
+        }
+
+        let mut indent = self
+            .child_indent(start, end, parent_indent)
+            .unwrap_or_else(|| parent_indent.map_or(0, |parent| parent + 2));
+
+        let mut insert_at = end;
+        while insert_at > start && self.lines[insert_at - 1].trim().is_empty() {
+            insert_at -= 1;
+        }
+
+        let mut new_lines = Vec::new();
+        for (offset, segment) in path[depth..].iter().enumerate() {
+            if depth + offset == path.len() - 1 {
+                new_lines.push(format!("{}{}: {}", " ".repeat(indent), segment, value));
+            } else {
+                new_lines.push(format!("{}{}:", " ".repeat(indent), segment));
+                indent += 2;
+            }
+        }
+
+        self.lines.splice(insert_at..insert_at, new_lines);
+    }
+}
+
+fn content_indent(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+    Some(line.len() - trimmed.len())
+}
+
+fn split_line(line: &str) -> (&str, &str, &str) {
+    let trimmed = line.trim_start();
+    let Some((key, rest)) = split_key(trimmed) else {
+        return (trimmed, "", "");
+    };
+
+    let comment_start = find_comment(rest).unwrap_or(rest.len());
+    let value = rest[..comment_start].trim();
+    let comment = &rest[comment_start..];
+    let comment = if comment.is_empty() { "" } else { &line[line.len() - comment.len() - 1..] };
+
+    (key, value, comment)
+}
+
+fn split_key(line: &str) -> Option<(&str, &str)> {
+    let mut quote = None;
+
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, ':') => {
+                let rest = &line[index + 1..];
+                if rest.is_empty() || rest.starts_with(' ') {
+                    return Some((&line[..index], rest));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn find_comment(value: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut previous = ' ';
+
+    for (index, c) in value.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '#') if previous == ' ' => return Some(index),
+            _ => {}
+        }
+        previous = c;
+    }
+
+    None
+}
+
+pub fn unquote(value: &str) -> String {
+    let value = value.trim();
+    if value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')))
+    {
+        return value[1..value.len() - 1].to_string();
+    }
+    value.to_string()
+}
+
+fn coerce_value(existing: &str, value: &str) -> Result<String> {
+    let existing = existing.trim();
+
+    if matches!(existing, "true" | "false") {
+        return match value {
+            "true" | "false" => Ok(value.to_string()),
+            _ => Err(anyhow!("Expected true or false, got '{}'", value))
+        };
+    }
+
+    if existing.parse::<i64>().is_ok() {
+        return value
+            .parse::<i64>()
+            .map(|v| v.to_string())
+            .map_err(|_| anyhow!("Expected a whole number, got '{}'", value));
+    }
+
+    if existing.parse::<f64>().is_ok() {
+        return value
+            .parse::<f64>()
+            .map(|_| value.to_string())
+            .map_err(|_| anyhow!("Expected a number, got '{}'", value));
+    }
+
+    if existing.is_empty()
+        && (matches!(value, "true" | "false") || value.parse::<f64>().is_ok())
+    {
+        return Ok(value.to_string());
+    }
+
+    if existing.starts_with('[') || existing.starts_with('{') || value.starts_with('[') {
+        return Ok(value.to_string());
+    }
+
+    Ok(quote_string(value))
+}
+
+fn quote_string(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+        && !matches!(value, "true" | "false" | "null" | "yes" | "no" | "on" | "off")
+        && value.parse::<f64>().is_err();
+
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "\
+# Paper world defaults
+chunks:
+  auto-save-interval: default # ticks
+  delay-chunk-unloads-by: 10s
+spawn:
+  keep-loaded: true
+  radius: 10
+entities:
+  spawning:
+    monster-spawn-max-light-level: 7.0
+";
+
+    #[test]
+    fn reads_nested_values() {
+        let document = YamlDocument::parse(CONFIG);
+        assert_eq!(document.get(&["chunks", "auto-save-interval"]).as_deref(), Some("default"));
+        assert_eq!(document.get(&["entities", "spawning", "monster-spawn-max-light-level"]).as_deref(), Some("7.0"));
+        assert_eq!(document.get(&["spawn"]), None);
+        assert_eq!(document.get(&["radius"]), None);
+    }
+
+    #[test]
+    fn set_keeps_comments_and_layout() {
+        let mut document = YamlDocument::parse(CONFIG);
+        assert_eq!(document.set(&["chunks", "auto-save-interval"], "6000").unwrap().as_deref(), Some("default"));
+        assert!(document.render().contains("  auto-save-interval: '6000' # ticks\n"));
+        assert_eq!(document.render().lines().count(), CONFIG.lines().count());
+    }
+
+    #[test]
+    fn set_checks_the_existing_type() {
+        let mut document = YamlDocument::parse(CONFIG);
+        assert!(document.set(&["spawn", "keep-loaded"], "yes").is_err());
+        assert!(document.set(&["spawn", "radius"], "1.5").is_err());
+        assert!(document.set(&["spawn"], "1").is_err());
+        document.set(&["chunks", "delay-chunk-unloads-by"], "5 s").unwrap();
+        assert_eq!(document.get(&["chunks", "delay-chunk-unloads-by"]).as_deref(), Some("'5 s'"));
+    }
+
+    #[test]
+    fn set_inserts_missing_sections() {
+        let mut document = YamlDocument::parse(CONFIG);
+        document.set(&["entities", "behavior", "door-breaking"], "true").unwrap();
+        assert_eq!(document.get(&["entities", "behavior", "door-breaking"]).as_deref(), Some("true"));
+        assert!(document.render().ends_with("  behavior:\n    door-breaking: true\n"));
+    }
+
+    #[test]
+    fn reads_and_replaces_lists() {
+        let mut document = YamlDocument::parse("depend: [Vault, 'Lib']\nsoftdepend:\n- One # first\n- \"Two\"\nauthor: me\n");
+        assert_eq!(document.get_list(&["depend"]), vec!["Vault", "Lib"]);
+        assert_eq!(document.get_list(&["softdepend"]), vec!["One", "Two"]);
+        assert_eq!(document.get_list(&["author"]), vec!["me"]);
+        assert!(document.get_list(&["missing"]).is_empty());
+
+        document.set_list(&["softdepend"], &["Three".to_string()]).unwrap();
+        assert_eq!(document.render(), "depend: [Vault, 'Lib']\nsoftdepend:\n  - Three\nauthor: me\n");
+    }
+
+    #[test]
+    fn lists_section_keys_and_flattens() {
+        let document = YamlDocument::parse(CONFIG);
+        assert_eq!(document.keys(&["spawn"]), vec!["keep-loaded", "radius"]);
+        assert!(document.flatten().contains(&("entities.spawning.monster-spawn-max-light-level".to_string(), "7.0".to_string())));
+    }
+}